@@ -0,0 +1,260 @@
+//! The validation core of `product-config`: checks of single values against datatypes, bounds and
+//! allowed values that only require `alloc`, e.g. to run basic validation on embedded devices.
+//!
+//! Regular expressions (for allowed patterns) are checked via an injectable [`RegexBackend`], so
+//! the core does not depend on a regex engine. `product-config` uses the core with the `regex`
+//! crate as backend and adds the property spec and reader layers on top.
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display};
+use core::str::FromStr;
+
+/// A problem of a value found by the checks of the core
+#[derive(Clone, Debug, PartialEq)]
+pub enum CheckError {
+    /// the value cannot be parsed as the datatype (e.g. `i64`)
+    DatatypeNotMatching {
+        value: String,
+        datatype: &'static str,
+    },
+    /// the value is out of the (inclusive) bound
+    OutOfBounds { received: String, expected: String },
+    /// the value is not one of the allowed values
+    NotInAllowedValues { value: String },
+    /// the value is neither one of the allowed values nor matching an allowed pattern
+    NotMatchingAllowedPattern { value: String },
+    /// the allowed pattern is no valid regular expression for the backend
+    InvalidPattern { pattern: String, reason: String },
+}
+
+impl Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckError::DatatypeNotMatching { value, datatype } => {
+                write!(
+                    f,
+                    "value '{}' does not match datatype '{}'",
+                    value, datatype
+                )
+            }
+            CheckError::OutOfBounds { received, expected } => write!(
+                f,
+                "value '{}' is out of bounds (expected: '{}')",
+                received, expected
+            ),
+            CheckError::NotInAllowedValues { value } => {
+                write!(f, "value '{}' is not an allowed value", value)
+            }
+            CheckError::NotMatchingAllowedPattern { value } => {
+                write!(f, "value '{}' does not match an allowed pattern", value)
+            }
+            CheckError::InvalidPattern { pattern, reason } => {
+                write!(f, "invalid allowed pattern '{}': {}", pattern, reason)
+            }
+        }
+    }
+}
+
+/// A regex engine to check allowed patterns, e.g. the `regex` or `regex-lite` crate or a small
+/// engine of the target platform
+pub trait RegexBackend {
+    /// Returns true if the pattern matches the whole value or the reason if the pattern is
+    /// invalid
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - the (unanchored) pattern, e.g. `[a-z]+`
+    /// * `value` - the value to be matched
+    ///
+    fn is_full_match(&self, pattern: &str, value: &str) -> Result<bool, String>;
+}
+
+/// Parse a value to a certain datatype and return an error if parsing is not possible
+///
+/// # Arguments
+///
+/// * `to_parse` - value to be parsed into a certain T
+///
+pub fn parse<T: FromStr>(to_parse: &str) -> Result<T, CheckError> {
+    to_parse
+        .parse::<T>()
+        .map_err(|_| CheckError::DatatypeNotMatching {
+            value: to_parse.to_string(),
+            datatype: core::any::type_name::<T>(),
+        })
+}
+
+/// Check if value is out of min bound
+///
+/// # Arguments
+///
+/// * `val` - value to be validated
+/// * `min` - min border (exclusive)
+///
+pub fn min_bound<T: PartialOrd>(val: T, min: T) -> bool {
+    val < min
+}
+
+/// Check if value is out of max bound
+///
+/// # Arguments
+///
+/// * `val` - value to be validated
+/// * `max` - max border (exclusive)
+///
+pub fn max_bound<T: PartialOrd>(val: T, max: T) -> bool {
+    val > max
+}
+
+/// Check if a value is inside a certain bound
+///
+/// # Arguments
+///
+/// * `value` - value to be validated
+/// * `bound` - upper/lower bound (if any)
+/// * `check_out_of_bound` - the method to check against the bound
+///
+pub fn check_bound<T>(
+    value: T,
+    bound: Option<&str>,
+    check_out_of_bound: fn(T, T) -> bool,
+) -> Result<T, CheckError>
+where
+    T: FromStr + PartialOrd + Display + Copy,
+{
+    if let Some(bound) = bound {
+        let bound: T = parse::<T>(bound)?;
+        if check_out_of_bound(value, bound) {
+            return Err(CheckError::OutOfBounds {
+                received: value.to_string(),
+                expected: bound.to_string(),
+            });
+        }
+    }
+
+    Ok(value)
+}
+
+/// Returns the provided scalar value of type T (bool, i64, u64, f64 ...) if it can be parsed and
+/// is within the bounds
+///
+/// # Arguments
+///
+/// * `value` - the value to be validated
+/// * `min` - minimum value specified
+/// * `max` - maximum value specified
+///
+pub fn check_scalar<T>(value: &str, min: Option<&str>, max: Option<&str>) -> Result<T, CheckError>
+where
+    T: FromStr + PartialOrd + Display + Copy,
+{
+    let value: T = parse::<T>(value)?;
+    check_bound(value, min, min_bound)?;
+    check_bound(value, max, max_bound)
+}
+
+/// Check if the value is one of the allowed values or matches one of the allowed patterns. Values
+/// are allowed if neither allowed values nor patterns are provided.
+///
+/// # Arguments
+///
+/// * `value` - the value to be validated
+/// * `allowed_values` - the allowed values
+/// * `allowed_pattern` - the allowed patterns (matching the whole value)
+/// * `regex` - the regex engine for the allowed patterns
+///
+pub fn check_allowed_values(
+    value: &str,
+    allowed_values: &[String],
+    allowed_pattern: &[String],
+    regex: &dyn RegexBackend,
+) -> Result<(), CheckError> {
+    if allowed_values.iter().any(|allowed| allowed == value) {
+        return Ok(());
+    }
+
+    for pattern in allowed_pattern {
+        let matches =
+            regex
+                .is_full_match(pattern, value)
+                .map_err(|reason| CheckError::InvalidPattern {
+                    pattern: pattern.clone(),
+                    reason,
+                })?;
+        if matches {
+            return Ok(());
+        }
+    }
+
+    if !allowed_pattern.is_empty() {
+        return Err(CheckError::NotMatchingAllowedPattern {
+            value: value.to_string(),
+        });
+    }
+    if !allowed_values.is_empty() {
+        return Err(CheckError::NotInAllowedValues {
+            value: value.to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{check_allowed_values, check_scalar, CheckError, RegexBackend};
+    use rstest::*;
+
+    /// supports literal patterns and `*` as wildcard suffix only
+    struct PrefixBackend;
+
+    impl RegexBackend for PrefixBackend {
+        fn is_full_match(&self, pattern: &str, value: &str) -> Result<bool, String> {
+            match pattern.strip_suffix('*') {
+                Some(prefix) if prefix.contains('*') => Err("nested wildcard".to_string()),
+                Some(prefix) => Ok(value.starts_with(prefix)),
+                None => Ok(value == pattern),
+            }
+        }
+    }
+
+    #[rstest]
+    #[case("5", Some("1"), Some("10"), Ok(5))]
+    #[case("0", Some("1"), None, Err(CheckError::OutOfBounds { received: "0".to_string(), expected: "1".to_string() }))]
+    #[case("11", None, Some("10"), Err(CheckError::OutOfBounds { received: "11".to_string(), expected: "10".to_string() }))]
+    #[case("five", None, None, Err(CheckError::DatatypeNotMatching { value: "five".to_string(), datatype: "i64" }))]
+    #[trace]
+    fn test_check_scalar(
+        #[case] value: &str,
+        #[case] min: Option<&str>,
+        #[case] max: Option<&str>,
+        #[case] expected: Result<i64, CheckError>,
+    ) {
+        assert_eq!(check_scalar::<i64>(value, min, max), expected);
+    }
+
+    #[rstest]
+    #[case("gzip", &["gzip"], &[], Ok(()))]
+    #[case("lz4", &["gzip"], &[], Err(CheckError::NotInAllowedValues { value: "lz4".to_string() }))]
+    #[case("zstd-1", &["gzip"], &["zstd*"], Ok(()))]
+    #[case("lz4", &["gzip"], &["zstd*"], Err(CheckError::NotMatchingAllowedPattern { value: "lz4".to_string() }))]
+    #[case("lz4", &[], &["*z*"], Err(CheckError::InvalidPattern { pattern: "*z*".to_string(), reason: "nested wildcard".to_string() }))]
+    #[case("anything", &[], &[], Ok(()))]
+    #[trace]
+    fn test_check_allowed_values(
+        #[case] value: &str,
+        #[case] allowed_values: &[&str],
+        #[case] allowed_pattern: &[&str],
+        #[case] expected: Result<(), CheckError>,
+    ) {
+        let allowed_values: Vec<String> = allowed_values.iter().map(|v| v.to_string()).collect();
+        let allowed_pattern: Vec<String> = allowed_pattern.iter().map(|p| p.to_string()).collect();
+
+        assert_eq!(
+            check_allowed_values(value, &allowed_values, &allowed_pattern, &PrefixBackend),
+            expected
+        );
+    }
+}
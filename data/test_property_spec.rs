@@ -0,0 +1,161 @@
+// Generated by `product_config::codegen` from `test_property_spec.json`, do not edit.
+
+/// Property names of kind `conf` (`env.sh`)
+pub mod env_sh {
+    pub const ENV_INTEGER_PORT_MIN_MAX: &str = "ENV_INTEGER_PORT_MIN_MAX";
+    pub const ENV_FLOAT: &str = "ENV_FLOAT";
+    pub const ENV_PROPERTY_STRING_MEMORY: &str = "ENV_PROPERTY_STRING_MEMORY";
+    pub const ENV_PROPERTY_STRING_DEPRECATED: &str = "ENV_PROPERTY_STRING_DEPRECATED";
+    pub const ENV_ALLOWED_VALUES: &str = "ENV_ALLOWED_VALUES";
+    pub const ENV_SECURITY: &str = "ENV_SECURITY";
+    pub const ENV_SECURITY_PASSWORD: &str = "ENV_SECURITY_PASSWORD";
+    pub const ENV_SSL_ENABLED: &str = "ENV_SSL_ENABLED";
+    pub const ENV_SSL_CERTIFICATE_PATH: &str = "ENV_SSL_CERTIFICATE_PATH";
+}
+
+/// Typed config of kind `conf` (`env.sh`)
+#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub struct EnvShConfig {
+    #[serde(rename = "ENV_ALLOWED_VALUES")]
+    pub env_allowed_values: Option<EnvAllowedValues>,
+    #[serde(rename = "ENV_FLOAT")]
+    pub env_float: Option<f64>,
+    #[serde(rename = "ENV_INTEGER_PORT_MIN_MAX")]
+    pub env_integer_port_min_max: Option<i64>,
+    #[serde(rename = "ENV_PROPERTY_STRING_DEPRECATED")]
+    pub env_property_string_deprecated: Option<String>,
+    #[serde(rename = "ENV_PROPERTY_STRING_MEMORY")]
+    pub env_property_string_memory: Option<String>,
+    #[serde(rename = "ENV_SECURITY")]
+    pub env_security: Option<bool>,
+    #[serde(rename = "ENV_SECURITY_PASSWORD")]
+    pub env_security_password: Option<String>,
+    #[serde(rename = "ENV_SSL_CERTIFICATE_PATH")]
+    pub env_ssl_certificate_path: Option<String>,
+    #[serde(rename = "ENV_SSL_ENABLED")]
+    pub env_ssl_enabled: Option<bool>,
+}
+
+/// Property names of kind `conf` (`my.config`)
+pub mod my_config {
+    pub const CONF_INTEGER_PORT_MIN_MAX: &str = "conf.integer.port.min.max";
+    pub const CONF_FLOAT: &str = "conf.float";
+    pub const CONF_PROPERTY_STRING_MEMORY: &str = "conf.property.string.memory";
+    pub const CONF_PROPERTY_STRING_DEPRECATED: &str = "conf.property.string.deprecated";
+    pub const CONF_ALLOWED_VALUES: &str = "conf.allowed.values";
+    pub const CONF_SECURITY: &str = "conf.security";
+    pub const CONF_SECURITY_PASSWORD: &str = "conf.security.password";
+    pub const CONF_SSL_ENABLED: &str = "conf.ssl.enabled";
+    pub const CONF_SSL_CERTIFICATE_PATH: &str = "conf.ssl.certificate.path";
+}
+
+/// Typed config of kind `conf` (`my.config`)
+#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub struct MyConfigConfig {
+    #[serde(rename = "conf", default)]
+    pub conf: MyConfigConfigConf,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub struct MyConfigConfigConf {
+    #[serde(rename = "allowed", default)]
+    pub allowed: MyConfigConfigConfAllowed,
+    #[serde(rename = "float")]
+    pub float: Option<f64>,
+    #[serde(rename = "integer", default)]
+    pub integer: MyConfigConfigConfInteger,
+    #[serde(rename = "property", default)]
+    pub property: MyConfigConfigConfProperty,
+    #[serde(rename = "security")]
+    pub security: Option<bool>,
+    #[serde(rename = "security.password")]
+    pub security_password: Option<String>,
+    #[serde(rename = "ssl", default)]
+    pub ssl: MyConfigConfigConfSsl,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub struct MyConfigConfigConfAllowed {
+    #[serde(rename = "values")]
+    pub values: Option<EnvAllowedValues>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub struct MyConfigConfigConfInteger {
+    #[serde(rename = "port", default)]
+    pub port: MyConfigConfigConfIntegerPort,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub struct MyConfigConfigConfIntegerPort {
+    #[serde(rename = "min", default)]
+    pub min: MyConfigConfigConfIntegerPortMin,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub struct MyConfigConfigConfIntegerPortMin {
+    #[serde(rename = "max")]
+    pub max: Option<i64>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub struct MyConfigConfigConfProperty {
+    #[serde(rename = "string", default)]
+    pub string: MyConfigConfigConfPropertyString,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub struct MyConfigConfigConfPropertyString {
+    #[serde(rename = "deprecated")]
+    pub deprecated: Option<String>,
+    #[serde(rename = "memory")]
+    pub memory: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub struct MyConfigConfigConfSsl {
+    #[serde(rename = "certificate", default)]
+    pub certificate: MyConfigConfigConfSslCertificate,
+    #[serde(rename = "enabled")]
+    pub enabled: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub struct MyConfigConfigConfSslCertificate {
+    #[serde(rename = "path")]
+    pub path: Option<String>,
+}
+
+/// Allowed values of `ENV_ALLOWED_VALUES`
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ::product_config::__private::serde::Deserialize, ::product_config::__private::serde::Serialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub enum EnvAllowedValues {
+    #[serde(rename = "allowed_value1")]
+    AllowedValue1,
+    #[serde(rename = "allowed_value2")]
+    AllowedValue2,
+    #[serde(rename = "allowed_value3")]
+    AllowedValue3,
+}
+
+impl EnvAllowedValues {
+    /// Returns the value as specified
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EnvAllowedValues::AllowedValue1 => "allowed_value1",
+            EnvAllowedValues::AllowedValue2 => "allowed_value2",
+            EnvAllowedValues::AllowedValue3 => "allowed_value3",
+        }
+    }
+}
@@ -0,0 +1,31 @@
+// Generated by `product_config::codegen` from `test_ref_property_spec.json`, do not edit.
+
+/// Property names of kind `conf` (`server.properties`)
+pub mod server_properties {
+    pub const HEAP_SIZE: &str = "heap.size";
+    pub const DEBUG_ENABLED: &str = "debug.enabled";
+}
+
+/// Typed config of kind `conf` (`server.properties`)
+#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub struct ServerPropertiesConfig {
+    #[serde(rename = "debug", default)]
+    pub debug: ServerPropertiesConfigDebug,
+    #[serde(rename = "heap", default)]
+    pub heap: ServerPropertiesConfigHeap,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub struct ServerPropertiesConfigDebug {
+    #[serde(rename = "enabled")]
+    pub enabled: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+#[serde(crate = "::product_config::__private::serde")]
+pub struct ServerPropertiesConfigHeap {
+    #[serde(rename = "size")]
+    pub size: Option<i64>,
+}
@@ -0,0 +1,543 @@
+//! Macros of the `product-config` crate (re-exported via its `macros` feature).
+//!
+//! The `product_config` attribute reads a property spec (JSON) at compile time and turns a unit
+//! struct into a typed config of one kind with a field per property available in a product
+//! version:
+//!
+//! ```ignore
+//! #[product_config(path = "data/property_spec.json", version = "1.2.0", file = "server.properties")]
+//! pub struct ServerConfig;
+//!
+//! let config = ServerConfig::load(&spec, &user_config)?;
+//! ```
+//!
+//! The kind is provided via `kind = "env"`, `kind = "cli"` or `file = "<config file>"`, the path
+//! is relative to the manifest directory of the crate. For specs with a `products` section, the
+//! product is selected via `product = "<name>"`. Datatype references (`{"ref": "heap-size"}`) are
+//! resolved via the `types` of the config spec provided by `config_spec = "<path>"`.
+//!
+//! Property names are split at `.` into nested structs (like `ProductConfigSpec::validate_into`
+//! does), e.g. `ssl.enabled` is the field `enabled` of the struct `ServerConfigSsl` in the field
+//! `ssl`. Fields are of type `bool`, `i64`, `u64`, `f64`, `u16` (ports), `serde_json::Value`
+//! (datatype `json`), `String` (all other datatypes) or a `Vec` of these for list properties.
+//! Fields of properties without a default value for the version are optional. Missing or
+//! malformed specs are reported as compile errors.
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitStr};
+
+/// The arguments of the attribute
+#[derive(Default)]
+struct Args {
+    path: Option<LitStr>,
+    version: Option<LitStr>,
+    kind: Option<LitStr>,
+    file: Option<LitStr>,
+    product: Option<LitStr>,
+    config_spec: Option<LitStr>,
+}
+
+/// A field of a property (a leaf of the struct tree)
+struct Field {
+    ty: TokenStream2,
+    optional: bool,
+    description: Option<String>,
+}
+
+/// The fields of a struct with the (rest of the) property name as key
+enum Node {
+    Leaf(Field),
+    Branch(BTreeMap<String, Node>),
+}
+
+/// Generates a typed config of one kind from a property spec, see the crate docs
+#[proc_macro_attribute]
+pub fn product_config(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut args = Args::default();
+    let parser = syn::meta::parser(|meta| {
+        let target = if meta.path.is_ident("path") {
+            &mut args.path
+        } else if meta.path.is_ident("version") {
+            &mut args.version
+        } else if meta.path.is_ident("kind") {
+            &mut args.kind
+        } else if meta.path.is_ident("file") {
+            &mut args.file
+        } else if meta.path.is_ident("product") {
+            &mut args.product
+        } else if meta.path.is_ident("config_spec") {
+            &mut args.config_spec
+        } else {
+            return Err(meta
+                .error("expected `path`, `version`, `kind`, `file`, `product` or `config_spec`"));
+        };
+        *target = Some(meta.value()?.parse()?);
+        Ok(())
+    });
+    parse_macro_input!(attr with parser);
+    let input = parse_macro_input!(item as DeriveInput);
+
+    expand(args, input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(args: Args, input: DeriveInput) -> syn::Result<TokenStream2> {
+    if !matches!(&input.data, Data::Struct(data) if matches!(data.fields, Fields::Unit)) {
+        return Err(syn::Error::new_spanned(&input, "expected a unit struct"));
+    }
+    let path = args
+        .path
+        .clone()
+        .ok_or_else(|| syn::Error::new(Span::call_site(), "missing argument `path`"))?;
+    let version_lit = args
+        .version
+        .clone()
+        .ok_or_else(|| syn::Error::new(Span::call_site(), "missing argument `version`"))?;
+    let version = parse_version(&version_lit.value())
+        .ok_or_else(|| syn::Error::new(version_lit.span(), "invalid product version"))?;
+
+    let kind = match (&args.kind, &args.file) {
+        (Some(kind), None) if kind.value() == "env" => quote!(Env),
+        (Some(kind), None) if kind.value() == "cli" => quote!(Cli),
+        (Some(kind), None) => {
+            return Err(syn::Error::new(
+                kind.span(),
+                "expected `env` or `cli` (use `file` for config files)",
+            ))
+        }
+        (None, Some(file)) => quote!(Conf(#file.to_string())),
+        _ => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "expected either argument `kind` or `file`",
+            ))
+        }
+    };
+
+    let (full_path, spec) = read_json(&path)?;
+    let config_spec = args.config_spec.as_ref().map(read_json).transpose()?;
+    let types = config_spec
+        .as_ref()
+        .map(|(_, config_spec)| &config_spec["types"]);
+    let properties = match (&spec, &args.product) {
+        (Value::Array(properties), None) => properties,
+        (Value::Object(_), Some(product)) => spec["products"][product.value()]
+            .as_array()
+            .ok_or_else(|| syn::Error::new(product.span(), "product not found in the spec"))?,
+        (Value::Object(_), None) => {
+            return Err(syn::Error::new(
+                path.span(),
+                "the spec contains several products, select one via `product`",
+            ))
+        }
+        _ => {
+            return Err(syn::Error::new(
+                path.span(),
+                "expected a list of property specs",
+            ))
+        }
+    };
+
+    let mut fields = BTreeMap::new();
+    for (index, property) in properties.iter().enumerate() {
+        let field = property_field(property, version, &args, types).map_err(|reason| {
+            syn::Error::new(path.span(), format!("property spec {}: {}", index, reason))
+        })?;
+        if let Some((name, field)) = field {
+            fields.insert(name, field);
+        }
+    }
+    let mut tree = BTreeMap::new();
+    for (name, field) in fields {
+        insert(&mut tree, &name, field);
+    }
+
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let attrs = &input.attrs;
+    let structs = structs(ident, vis, attrs, &tree)?;
+    let config_spec_path = config_spec.map(|(path, _)| path).into_iter();
+    let version = version_lit.value();
+
+    Ok(quote! {
+        #structs
+
+        // recompile if the specs change
+        const _: &[u8] = include_bytes!(#full_path);
+        #(const _: &[u8] = include_bytes!(#config_spec_path);)*
+
+        impl #ident {
+            /// The product version the config is generated for
+            pub const VERSION: &'static str = #version;
+
+            /// Returns the kind of the properties of the config
+            pub fn kind() -> ::product_config::types::PropertyNameKind {
+                ::product_config::types::PropertyNameKind::#kind
+            }
+
+            /// Validates the user config (property name and value) for the product version and
+            /// returns the typed config, see `ProductConfigSpec::validate_into`
+            pub fn load(
+                spec: &::product_config::ProductConfigSpec,
+                user_config: &::std::collections::HashMap<String, String>,
+            ) -> ::std::result::Result<Self, ::product_config::error::Error> {
+                spec.validate_into(&Self::kind(), user_config, Self::VERSION)
+            }
+        }
+    })
+}
+
+/// Returns the property name of the kind and the field of the property, None if the property
+/// has no name of the kind or is not available in the version
+///
+/// # Arguments
+///
+/// * `property` - the property spec (JSON)
+/// * `version` - the product version
+/// * `args` - the arguments of the attribute (with the kind)
+/// * `types` - the datatype definitions of the config spec (if provided)
+///
+fn property_field(
+    property: &Value,
+    version: (u64, u64, u64),
+    args: &Args,
+    types: Option<&Value>,
+) -> Result<Option<(String, Field)>, String> {
+    let names = property["property_names"]
+        .as_array()
+        .filter(|names| !names.is_empty())
+        .ok_or("`property_names` missing")?;
+    let datatype = resolve_datatype(&property["datatype"], types)?;
+    let as_of_version =
+        spec_version(property, "as_of_version")?.ok_or("`as_of_version` missing")?;
+    spec_version(property, "deprecated_since")?;
+    let removed_in = spec_version(property, "removed_in")?;
+    if as_of_version > version || removed_in.is_some_and(|removed_in| removed_in <= version) {
+        return Ok(None);
+    }
+
+    let mut name = None;
+    for property_name in names {
+        let kind = &property_name["kind"];
+        let matches = match (kind["type"].as_str(), &args.kind, &args.file) {
+            (Some(kind), Some(expected), None) => kind == expected.value(),
+            (Some("conf"), None, Some(file)) => kind["file"].as_str() == Some(&file.value()),
+            (Some(_), _, _) => false,
+            (None, _, _) => return Err("property name without `kind`".to_string()),
+        };
+        let property_name = property_name["name"]
+            .as_str()
+            .ok_or("property name without `name`")?;
+        if matches && name.is_none() {
+            name = Some(property_name.to_string());
+        }
+    }
+    let name = match name {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+
+    let ty = match datatype["type"].as_str() {
+        Some("bool") => quote!(bool),
+        Some("integer") => quote!(i64),
+        Some("unsignedinteger") => quote!(u64),
+        Some("float") => quote!(f64),
+        Some("port") if datatype["range"] != Value::Bool(true) => quote!(u16),
+        Some("json") => quote!(::product_config::__private::serde_json::Value),
+        _ => quote!(String),
+    };
+    let ty = match property["list"] {
+        Value::Object(_) => quote!(Vec<#ty>),
+        _ => ty,
+    };
+    let description = match &property["description"] {
+        Value::String(text) => Some(text.clone()),
+        Value::Object(texts) => texts
+            .get("en")
+            .or_else(|| texts.values().next())
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        _ => None,
+    };
+
+    Ok(Some((
+        name,
+        Field {
+            ty,
+            optional: !has_default(property, version)?,
+            description,
+        },
+    )))
+}
+
+/// Returns the datatype with a reference (`{"ref": "heap-size"}` or `{"type": "ref", ...}`)
+/// replaced by its definition in the config spec (definitions must not reference other
+/// definitions, like for the reader)
+///
+/// # Arguments
+///
+/// * `datatype` - the datatype of the property spec (JSON)
+/// * `types` - the datatype definitions of the config spec (if provided)
+///
+fn resolve_datatype<'a>(
+    datatype: &'a Value,
+    types: Option<&'a Value>,
+) -> Result<&'a Value, String> {
+    let name = match (datatype["type"].as_str(), datatype["ref"].as_str()) {
+        (None | Some("ref"), Some(name)) => name,
+        (Some("ref"), None) => return Err("datatype reference without `ref`".to_string()),
+        (Some(_), _) => return Ok(datatype),
+        (None, None) => return Err("`datatype` missing".to_string()),
+    };
+    let types = types.ok_or_else(|| {
+        format!(
+            "datatype reference '{}' requires the argument `config_spec`",
+            name
+        )
+    })?;
+    match &types[name] {
+        definition if definition["type"].as_str().is_some_and(|ty| ty != "ref") => Ok(definition),
+        _ => Err(format!(
+            "datatype definition '{}' not found in the config spec",
+            name
+        )),
+    }
+}
+
+/// Returns the path (relative to the manifest directory) and the content of a JSON spec file
+fn read_json(path: &LitStr) -> syn::Result<(String, Value)> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = Path::new(&manifest_dir).join(path.value());
+    let content = std::fs::read_to_string(&full_path).map_err(|err| {
+        syn::Error::new(
+            path.span(),
+            format!("could not read '{}': {}", full_path.display(), err),
+        )
+    })?;
+    let json = serde_json::from_str(&content).map_err(|err| {
+        syn::Error::new(
+            path.span(),
+            format!("could not parse '{}': {}", full_path.display(), err),
+        )
+    })?;
+    Ok((full_path.to_string_lossy().to_string(), json))
+}
+
+/// Returns true if the property has a default value for the version that applies regardless of
+/// other properties and the platform
+fn has_default(property: &Value, version: (u64, u64, u64)) -> Result<bool, String> {
+    for value in property["default_values"].as_array().into_iter().flatten() {
+        let from_version = spec_version(value, "from_version")?;
+        let to_version = spec_version(value, "to_version")?;
+        if value["when"].is_null()
+            && value["platform"].is_null()
+            && from_version.is_none_or(|from_version| from_version <= version)
+            && to_version.is_none_or(|to_version| to_version >= version)
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns the version of the key of the spec (None if not provided)
+fn spec_version(spec: &Value, key: &str) -> Result<Option<(u64, u64, u64)>, String> {
+    match &spec[key] {
+        Value::Null => Ok(None),
+        Value::String(version) => parse_version(version)
+            .map(Some)
+            .ok_or_else(|| format!("invalid version '{}' in `{}`", version, key)),
+        _ => Err(format!("invalid version in `{}`", key)),
+    }
+}
+
+/// Returns major, minor and patch of a semantic version (pre-release and build are ignored)
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) => {
+            Some((major, minor, patch))
+        }
+        _ => None,
+    }
+}
+
+/// Inserts the field into the tree for the name split at `.`; if a parent is a field, the rest of
+/// the name is kept as key (like the values are nested for deserialization)
+fn insert(tree: &mut BTreeMap<String, Node>, name: &str, field: Field) {
+    if let Some((parent, rest)) = name.split_once('.') {
+        let child = tree
+            .entry(parent.to_string())
+            .or_insert_with(|| Node::Branch(BTreeMap::new()));
+        if let Node::Branch(child) = child {
+            insert(child, rest, field);
+            return;
+        }
+    }
+    tree.insert(name.to_string(), Node::Leaf(field));
+}
+
+/// Returns the struct (and its nested structs) for the fields of the tree
+///
+/// # Arguments
+///
+/// * `ident` - the name of the struct
+/// * `vis` - the visibility of the structs
+/// * `attrs` - the attributes of the struct (e.g. doc comments)
+/// * `tree` - the fields of the struct
+///
+fn structs(
+    ident: &Ident,
+    vis: &syn::Visibility,
+    attrs: &[syn::Attribute],
+    tree: &BTreeMap<String, Node>,
+) -> syn::Result<TokenStream2> {
+    let mut fields = vec![];
+    let mut nested = vec![];
+    let mut field_names: BTreeMap<String, &String> = BTreeMap::new();
+
+    for (key, node) in tree {
+        let field_ident = field_ident(key);
+        if let Some(other) = field_names.insert(field_ident.to_string(), key) {
+            return Err(syn::Error::new(
+                ident.span(),
+                format!("properties '{}' and '{}' map to the same field", other, key),
+            ));
+        }
+        fields.push(match node {
+            Node::Leaf(field) => {
+                let ty = &field.ty;
+                let ty = match field.optional {
+                    true => quote!(Option<#ty>),
+                    false => quote!(#ty),
+                };
+                let doc = field.description.iter();
+                quote! {
+                    #(#[doc = #doc])*
+                    #[serde(rename = #key)]
+                    pub #field_ident: #ty
+                }
+            }
+            Node::Branch(children) => {
+                let nested_ident = format_ident!("{}{}", ident, camel_case(key));
+                nested.push(structs(&nested_ident, vis, &[], children)?);
+                quote! {
+                    #[serde(rename = #key, default)]
+                    pub #field_ident: #nested_ident
+                }
+            }
+        });
+    }
+
+    Ok(quote! {
+        #(#attrs)*
+        #[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]
+        #[serde(crate = "::product_config::__private::serde")]
+        #vis struct #ident {
+            #(#fields,)*
+        }
+
+        #(#nested)*
+    })
+}
+
+/// Returns the field name for the (part of the) property name in snake case, e.g. `log_dirs`
+/// for `log-dirs` or `heap_opts` for `HEAP_OPTS`
+fn field_ident(key: &str) -> Ident {
+    let mut name: String = key
+        .trim_start_matches('-')
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_lowercase(),
+            false => '_',
+        })
+        .collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    match syn::parse_str::<Ident>(&name) {
+        Ok(ident) => ident,
+        // keywords like `type` are raw identifiers, the ones that cannot be raw get a suffix
+        Err(_) if ["crate", "self", "super", "Self", "_"].contains(&name.as_str()) => {
+            format_ident!("{}_", name)
+        }
+        Err(_) => Ident::new_raw(&name, Span::call_site()),
+    }
+}
+
+/// Returns the (part of the) property name in camel case for the name of a nested struct
+fn camel_case(key: &str) -> String {
+    key.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let part = part.to_ascii_lowercase();
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{camel_case, field_ident, parse_version, resolve_datatype};
+    use rstest::*;
+    use serde_json::{json, Value};
+
+    #[rstest]
+    #[case("log.dirs", "log_dirs")]
+    #[case("--port", "port")]
+    #[case("HEAP_OPTS", "heap_opts")]
+    #[case("1st", "_1st")]
+    #[case("type", "r#type")]
+    #[case("self", "self_")]
+    #[trace]
+    fn test_field_ident(#[case] key: &str, #[case] expected: &str) {
+        assert_eq!(field_ident(key).to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("ssl", "Ssl")]
+    #[case("client-config_2", "ClientConfig2")]
+    #[trace]
+    fn test_camel_case(#[case] key: &str, #[case] expected: &str) {
+        assert_eq!(camel_case(key), expected);
+    }
+
+    #[rstest]
+    #[case(json!({"type": "bool"}), None, Ok(json!({"type": "bool"})))]
+    #[case(json!({"ref": "heap-size"}), Some(json!({"heap-size": {"type": "integer"}})), Ok(json!({"type": "integer"})))]
+    #[case(json!({"type": "ref", "ref": "heap-size"}), Some(json!({"heap-size": {"type": "integer"}})), Ok(json!({"type": "integer"})))]
+    #[case(json!({"ref": "heap-size"}), None, Err("datatype reference 'heap-size' requires the argument `config_spec`"))]
+    #[case(json!({"ref": "heap-size"}), Some(json!({})), Err("datatype definition 'heap-size' not found in the config spec"))]
+    #[case(json!({}), None, Err("`datatype` missing"))]
+    #[trace]
+    fn test_resolve_datatype(
+        #[case] datatype: Value,
+        #[case] types: Option<Value>,
+        #[case] expected: Result<Value, &str>,
+    ) {
+        assert_eq!(
+            resolve_datatype(&datatype, types.as_ref()).cloned(),
+            expected.map_err(str::to_string)
+        );
+    }
+
+    #[rstest]
+    #[case("1.2.3", Some((1, 2, 3)))]
+    #[case("1.2.3-beta.1+build", Some((1, 2, 3)))]
+    #[case("1.2", None)]
+    #[case("1.2.x", None)]
+    #[trace]
+    fn test_parse_version(#[case] version: &str, #[case] expected: Option<(u64, u64, u64)>) {
+        assert_eq!(parse_version(version), expected);
+    }
+}
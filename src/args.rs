@@ -0,0 +1,157 @@
+//! Parsing of command line arguments (e.g. passed through by wrapper scripts) to the values of
+//! properties of kind `cli`.
+//!
+//! Options are written as `--name value` or `--name=value`. Properties of datatype bool may be
+//! used as flags (`--verbose` for `true`). Arguments not starting with `-` (like positional
+//! arguments) are ignored, as are all arguments after `--`.
+use crate::types::{Datatype, PropertyName, PropertyNameKind, PropertySpec};
+use crate::ProductConfigSpec;
+
+/// An option of the command line
+#[derive(Debug, PartialEq)]
+pub(crate) struct Argument {
+    /// the name of the property (as specified, with or without leading `--`) or the option as
+    /// provided if there is no property for it
+    pub name: String,
+    /// the option as provided, e.g. `--port`
+    pub option: String,
+    pub value: String,
+}
+
+/// Returns the options of the arguments in the order provided
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec (to find the property names and bool properties)
+/// * `args` - the arguments (without the program name)
+///
+pub(crate) fn parse<S: AsRef<str>>(spec: &ProductConfigSpec, args: &[S]) -> Vec<Argument> {
+    let mut arguments = vec![];
+    let mut args = args.iter().map(AsRef::as_ref).peekable();
+
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            continue;
+        }
+
+        let (option, value) = match arg.split_once('=') {
+            Some((option, value)) => (option, Some(value.to_string())),
+            None => (arg, None),
+        };
+        let property = find_property(spec, option);
+        let flag =
+            matches!(property, Some((_, property)) if matches!(property.datatype, Datatype::Bool));
+        let value = match value {
+            Some(value) => value,
+            // flags only take `true` or `false` as separate value
+            None if flag => match args.peek() {
+                Some(&value @ ("true" | "false")) => {
+                    args.next();
+                    value.to_string()
+                }
+                _ => "true".to_string(),
+            },
+            None => match args.peek() {
+                Some(value) if !value.starts_with("--") => args.next().unwrap().to_string(),
+                _ => String::new(),
+            },
+        };
+
+        arguments.push(Argument {
+            name: property
+                .map(|(name, _)| name.name.clone())
+                .unwrap_or_else(|| option.to_string()),
+            option: option.to_string(),
+            value,
+        });
+    }
+
+    arguments
+}
+
+/// Returns the property of kind `cli` for the option: properties may be specified with or
+/// without leading dashes (and the prefix for the kind)
+fn find_property<'a>(
+    spec: &'a ProductConfigSpec,
+    option: &str,
+) -> Option<(&'a PropertyName, &'a PropertySpec)> {
+    let prefix = spec
+        .config_spec
+        .prefix(&PropertyNameKind::Cli)
+        .unwrap_or_default();
+    let bare = option.trim_start_matches('-');
+    [option, bare, bare.strip_prefix(prefix).unwrap_or(bare)]
+        .iter()
+        .find_map(|name| {
+            spec.property_specs.get_key_value(&PropertyName {
+                name: name.to_string(),
+                kind: PropertyNameKind::Cli,
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::args::{parse, Argument};
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder};
+    use crate::types::{Datatype, PropertyName, PropertyNameKind, Role};
+
+    #[test]
+    fn test_parse() {
+        let property = |name: &str, datatype: Datatype| {
+            PropertySpecBuilder::new(datatype, "0.1.0")
+                .property_name(PropertyName {
+                    name: name.to_string(),
+                    kind: PropertyNameKind::Cli,
+                })
+                .role(Role {
+                    name: "broker".to_string(),
+                    required: false,
+                })
+                .build()
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(property(
+                "--port",
+                Datatype::Port {
+                    allow_privileged: false,
+                    range: false,
+                },
+            ))
+            .property_spec(property("verbose", Datatype::Bool))
+            .property_spec(property("debug", Datatype::Bool))
+            .build()
+            .unwrap();
+        let args = [
+            "start",
+            "--port",
+            "9092",
+            "--verbose",
+            "--debug",
+            "false",
+            "--unknown=1",
+            "-x",
+            "--",
+            "--port=1",
+        ];
+
+        let argument = |name: &str, option: &str, value: &str| Argument {
+            name: name.to_string(),
+            option: option.to_string(),
+            value: value.to_string(),
+        };
+        assert_eq!(
+            parse(&spec, &args),
+            vec![
+                argument("--port", "--port", "9092"),
+                argument("verbose", "--verbose", "true"),
+                argument("debug", "--debug", "false"),
+                argument("--unknown", "--unknown", "1"),
+                argument("-x", "-x", ""),
+            ]
+        );
+    }
+}
@@ -0,0 +1,628 @@
+//! Builders to create a product config spec programmatically instead of reading it from files.
+//!
+//! This is useful if (parts of) the spec are generated dynamically, e.g. ports per role.
+//!
+//! # Example
+//!
+//! ```
+//! use product_config::builder::{ProductConfigSpecBuilder, PropertySpecBuilder, UnitBuilder};
+//! use product_config::types::{Datatype, PropertyName, PropertyNameKind, PropertyValueSpec, Role};
+//!
+//! let spec = ProductConfigSpecBuilder::new()
+//!     .unit(UnitBuilder::new("port", "^[0-9]{1,5}$").build())
+//!     .property_spec(
+//!         PropertySpecBuilder::new(
+//!             Datatype::Integer {
+//!                 min: Some("1".to_string()),
+//!                 max: Some("65535".to_string()),
+//!                 unit: Some("port".to_string()),
+//!                 accepted_units: None,
+//!                 default_unit: None,
+//!                 multiple_of: None,
+//!             },
+//!             "0.1.0",
+//!         )
+//!         .property_name(PropertyName {
+//!             name: "http.port".to_string(),
+//!             kind: PropertyNameKind::Conf("my.config".to_string()),
+//!         })
+//!         .default_value(PropertyValueSpec {
+//!             from_version: Some("0.1.0".to_string()),
+//!             to_version: None,
+//!             when: None,
+//!             platform: None,
+//!             value: "8080".to_string(),
+//!         })
+//!         .role(Role {
+//!             name: "server".to_string(),
+//!             required: true,
+//!         })
+//!         .build(),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! ```
+use crate::error::Error;
+use crate::reader::{self, ConfigReader, JsonProductConfigSpecProperties};
+use crate::types::{
+    ChangePolicy, Datatype, Description, DocLink, Importance, ListSpec, Normalization,
+    PropertyDependency, PropertyGroup, PropertyName, PropertyNameKind, PropertyNamePrefix,
+    PropertySpec, PropertyValueSpec, RecommendedRange, RegexFlag, Role, Rule, Stability, Unit,
+};
+use crate::validation::ValidationResult;
+use crate::ProductConfigSpec;
+use std::collections::BTreeMap;
+
+/// Builder for a complete product config spec consisting of units and property specs
+#[derive(Clone, Debug, Default)]
+pub struct ProductConfigSpecBuilder {
+    pub(crate) units: Vec<Unit>,
+    pub(crate) prefixes: Vec<PropertyNamePrefix>,
+    pub(crate) rules: Vec<Rule>,
+    pub(crate) normalization: Option<Normalization>,
+    pub(crate) full_match: Option<bool>,
+    pub(crate) types: BTreeMap<String, Datatype>,
+    pub(crate) value_sets: BTreeMap<String, Vec<String>>,
+    pub(crate) groups: Vec<PropertyGroup>,
+    pub(crate) default_locale: Option<String>,
+    pub(crate) property_specs: Vec<PropertySpec>,
+    pub(crate) products: BTreeMap<String, Vec<PropertySpec>>,
+}
+
+impl ProductConfigSpecBuilder {
+    pub fn new() -> Self {
+        ProductConfigSpecBuilder::default()
+    }
+
+    /// Add a unit (name and regex pattern) to the config spec
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.units.push(unit);
+        self
+    }
+
+    /// Add a prefix of user provided property names for the given kind (or all kinds if None)
+    pub fn prefix(mut self, prefix: &str, kind: Option<PropertyNameKind>) -> Self {
+        self.prefixes.push(PropertyNamePrefix {
+            prefix: prefix.to_string(),
+            kind,
+        });
+        self
+    }
+
+    /// Add a cross property rule (see the `rules` module for the syntax)
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Set the pre-processing of user values for all properties
+    pub fn normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = Some(normalization);
+        self
+    }
+
+    /// Set if unit regexes have to match the whole value (units may override this)
+    pub fn full_match(mut self, full_match: bool) -> Self {
+        self.full_match = Some(full_match);
+        self
+    }
+
+    /// Add a datatype definition that property specs may reference via `Datatype::Ref`
+    pub fn datatype(mut self, name: &str, datatype: Datatype) -> Self {
+        self.types.insert(name.to_string(), datatype);
+        self
+    }
+
+    /// Add a set of allowed values that property specs may reference via `allowed_value_set`
+    pub fn value_set(mut self, name: &str, values: &[&str]) -> Self {
+        self.value_sets.insert(
+            name.to_string(),
+            values.iter().map(|value| value.to_string()).collect(),
+        );
+        self
+    }
+
+    /// Add a group of properties (groups are presented in the order they are added)
+    pub fn group(mut self, name: &str, description: Option<&str>) -> Self {
+        self.groups.push(PropertyGroup {
+            name: name.to_string(),
+            description: description.map(str::to_string),
+        });
+        self
+    }
+
+    /// Set the language of descriptions used if not available in the requested one
+    pub fn default_locale(mut self, locale: &str) -> Self {
+        self.default_locale = Some(locale.to_string());
+        self
+    }
+
+    /// Add a property spec to the config spec
+    pub fn property_spec(mut self, property_spec: PropertySpec) -> Self {
+        self.property_specs.push(property_spec);
+        self
+    }
+
+    /// Add a property spec to the product with the given name (all products share the units)
+    pub fn product_property_spec(mut self, product: &str, property_spec: PropertySpec) -> Self {
+        self.products
+            .entry(product.to_string())
+            .or_default()
+            .push(property_spec);
+        self
+    }
+
+    /// Create and validate the ProductConfigSpec (same checks as `ProductConfigSpec::new`)
+    pub fn build(&self) -> ValidationResult<ProductConfigSpec> {
+        ProductConfigSpec::new(self.clone())
+    }
+}
+
+impl ConfigReader for ProductConfigSpecBuilder {
+    fn read(&self) -> Result<ProductConfigSpec, Error> {
+        let config_spec = JsonProductConfigSpecProperties {
+            units: self.units.clone(),
+            prefixes: self.prefixes.clone(),
+            rules: self.rules.clone(),
+            normalization: self.normalization,
+            full_match: self.full_match,
+            types: self.types.clone(),
+            value_sets: self.value_sets.clone(),
+            groups: self.groups.clone(),
+            default_locale: self.default_locale.clone(),
+        };
+        reader::parse_config_spec(&config_spec, &self.property_specs, &self.products)
+    }
+}
+
+/// Builder for a single property spec
+#[derive(Clone, Debug)]
+pub struct PropertySpecBuilder {
+    spec: PropertySpec,
+}
+
+impl PropertySpecBuilder {
+    /// Create a new property spec builder
+    ///
+    /// # Arguments
+    ///
+    /// * `datatype` - the datatype of the property
+    /// * `as_of_version` - the product version the property is available from
+    ///
+    pub fn new(datatype: Datatype, as_of_version: &str) -> Self {
+        PropertySpecBuilder {
+            spec: PropertySpec {
+                property_names: vec![],
+                datatype,
+                default_values: None,
+                recommended_values: None,
+                recommended_range: None,
+                allowed_values: None,
+                allowed_value_set: None,
+                allowed_pattern: None,
+                forbidden_values: None,
+                validator: None,
+                as_of_version: as_of_version.to_string(),
+                deprecated_since: None,
+                removed_in: None,
+                stability: None,
+                experimental: None,
+                importance: None,
+                list: None,
+                normalization: None,
+                allow_empty: None,
+                allow_non_finite: None,
+                radix_prefixes: None,
+                immutable: None,
+                sensitive: None,
+                deprecated_for: None,
+                depends_on: None,
+                conflicts_with: None,
+                platforms: None,
+                roles: None,
+                restart_required: None,
+                change_policy: None,
+                tags: None,
+                group: None,
+                additional_doc: None,
+                comment: None,
+                description: None,
+                extra: BTreeMap::new(),
+            },
+        }
+    }
+
+    pub fn property_name(mut self, property_name: PropertyName) -> Self {
+        self.spec.property_names.push(property_name);
+        self
+    }
+
+    pub fn default_value(mut self, value: PropertyValueSpec) -> Self {
+        self.spec
+            .default_values
+            .get_or_insert_with(Vec::new)
+            .push(value);
+        self
+    }
+
+    pub fn recommended_value(mut self, value: PropertyValueSpec) -> Self {
+        self.spec
+            .recommended_values
+            .get_or_insert_with(Vec::new)
+            .push(value);
+        self
+    }
+
+    pub fn recommended_range(mut self, range: RecommendedRange) -> Self {
+        self.spec.recommended_range = Some(range);
+        self
+    }
+
+    pub fn allowed_value(mut self, value: &str) -> Self {
+        self.spec
+            .allowed_values
+            .get_or_insert_with(Vec::new)
+            .push(value.to_string());
+        self
+    }
+
+    /// Accept the values of the named value set of the config spec (in addition to the allowed values)
+    pub fn allowed_value_set(mut self, name: &str) -> Self {
+        self.spec.allowed_value_set = Some(name.to_string());
+        self
+    }
+
+    /// Accept values matching the regular expression (in addition to the allowed values)
+    pub fn allowed_pattern(mut self, pattern: &str) -> Self {
+        self.spec
+            .allowed_pattern
+            .get_or_insert_with(Vec::new)
+            .push(pattern.to_string());
+        self
+    }
+
+    pub fn forbidden_value(mut self, value: &str) -> Self {
+        self.spec
+            .forbidden_values
+            .get_or_insert_with(Vec::new)
+            .push(value.to_string());
+        self
+    }
+
+    /// Reference a custom validator (registered via `ProductConfigSpec::register_validator`)
+    pub fn validator(mut self, validator: &str) -> Self {
+        self.spec.validator = Some(validator.to_string());
+        self
+    }
+
+    pub fn deprecated_since(mut self, version: &str) -> Self {
+        self.spec.deprecated_since = Some(version.to_string());
+        self
+    }
+
+    pub fn removed_in(mut self, version: &str) -> Self {
+        self.spec.removed_in = Some(version.to_string());
+        self
+    }
+
+    pub fn stability(mut self, stability: Stability) -> Self {
+        self.spec.stability = Some(stability);
+        self
+    }
+
+    pub fn experimental(mut self, experimental: bool) -> Self {
+        self.spec.experimental = Some(experimental);
+        self
+    }
+
+    pub fn importance(mut self, importance: Importance) -> Self {
+        self.spec.importance = Some(importance);
+        self
+    }
+
+    /// Treat the value as delimited list whose items are validated one by one
+    pub fn list(mut self, list: ListSpec) -> Self {
+        self.spec.list = Some(list);
+        self
+    }
+
+    /// Pre-process user values of this property (overrides the global normalization flags)
+    pub fn normalization(mut self, normalization: Normalization) -> Self {
+        self.spec.normalization = Some(normalization);
+        self
+    }
+
+    /// Accept an empty value (e.g. meaning "disabled") without validating it any further
+    pub fn allow_empty(mut self, allow_empty: bool) -> Self {
+        self.spec.allow_empty = Some(allow_empty);
+        self
+    }
+
+    /// Accept (or reject) NaN and infinite float values regardless of the validation policy
+    pub fn allow_non_finite(mut self, allow_non_finite: bool) -> Self {
+        self.spec.allow_non_finite = Some(allow_non_finite);
+        self
+    }
+
+    /// Accept integer values as hex, octal or binary literal (e.g. 0x1F)
+    pub fn radix_prefixes(mut self, radix_prefixes: bool) -> Self {
+        self.spec.radix_prefixes = Some(radix_prefixes);
+        self
+    }
+
+    pub fn immutable(mut self, immutable: bool) -> Self {
+        self.spec.immutable = Some(immutable);
+        self
+    }
+
+    /// Replace the value by `***` in errors (e.g. for passwords)
+    pub fn sensitive(mut self, sensitive: bool) -> Self {
+        self.spec.sensitive = Some(sensitive);
+        self
+    }
+
+    /// Add a replacement for the deprecated property (its property names, one per kind)
+    pub fn deprecated_for(mut self, replacement: Vec<PropertyName>) -> Self {
+        self.spec
+            .deprecated_for
+            .get_or_insert_with(Vec::new)
+            .push(replacement);
+        self
+    }
+
+    pub fn depends_on(mut self, dependency: PropertyDependency) -> Self {
+        self.spec
+            .depends_on
+            .get_or_insert_with(Vec::new)
+            .push(dependency);
+        self
+    }
+
+    /// Add a property that must not be set together with this property (a trailing '*' matches
+    /// all property names with that prefix)
+    pub fn conflicts_with(mut self, property_name: PropertyName) -> Self {
+        self.spec
+            .conflicts_with
+            .get_or_insert_with(Vec::new)
+            .push(property_name);
+        self
+    }
+
+    /// Add a platform (`os` or `os/arch`) the property is supported on
+    pub fn platform(mut self, platform: &str) -> Self {
+        self.spec
+            .platforms
+            .get_or_insert_with(Vec::new)
+            .push(platform.to_string());
+        self
+    }
+
+    pub fn role(mut self, role: Role) -> Self {
+        self.spec.roles.get_or_insert_with(Vec::new).push(role);
+        self
+    }
+
+    pub fn restart_required(mut self, restart_required: bool) -> Self {
+        self.spec.restart_required = Some(restart_required);
+        self
+    }
+
+    pub fn change_policy(mut self, change_policy: ChangePolicy) -> Self {
+        self.spec.change_policy = Some(change_policy);
+        self
+    }
+
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.spec
+            .tags
+            .get_or_insert_with(Vec::new)
+            .push(tag.to_string());
+        self
+    }
+
+    /// Set the group (declared in the config spec) the property belongs to
+    pub fn group(mut self, group: &str) -> Self {
+        self.spec.group = Some(group.to_string());
+        self
+    }
+
+    /// Add a link to additional documentation (e.g. the upstream docs)
+    pub fn additional_doc(mut self, title: &str, url: &str) -> Self {
+        self.spec
+            .additional_doc
+            .get_or_insert_with(Vec::new)
+            .push(DocLink {
+                title: title.to_string(),
+                url: url.to_string(),
+            });
+        self
+    }
+
+    pub fn comment(mut self, comment: &str) -> Self {
+        self.spec.comment = Some(comment.to_string());
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.spec.description = Some(Description::Text(description.to_string()));
+        self
+    }
+
+    /// Add the description for a language (replaces a plain text description)
+    pub fn localized_description(mut self, locale: &str, description: &str) -> Self {
+        match &mut self.spec.description {
+            Some(Description::Localized(texts)) => {
+                texts.insert(locale.to_string(), description.to_string());
+            }
+            _ => {
+                self.spec.description = Some(Description::Localized(BTreeMap::from([(
+                    locale.to_string(),
+                    description.to_string(),
+                )])))
+            }
+        }
+        self
+    }
+
+    /// Add a field unknown to this library (e.g. a vendor annotation) that is kept when writing the spec
+    pub fn extra(mut self, key: &str, value: serde_json::Value) -> Self {
+        self.spec.extra.insert(key.to_string(), value);
+        self
+    }
+
+    pub fn build(self) -> PropertySpec {
+        self.spec
+    }
+}
+
+/// Builder for a unit (name and regex pattern)
+#[derive(Clone, Debug)]
+pub struct UnitBuilder {
+    unit: Unit,
+}
+
+impl UnitBuilder {
+    /// Create a new unit builder
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the unit (e.g. port, memory)
+    /// * `regex` - the regex pattern values of this unit have to match
+    ///
+    pub fn new(name: &str, regex: &str) -> Self {
+        UnitBuilder {
+            unit: Unit {
+                name: name.to_string(),
+                regex: Some(regex.to_string()),
+                examples: None,
+                comment: None,
+                full_match: None,
+                flags: None,
+                extra: BTreeMap::new(),
+            },
+        }
+    }
+
+    pub fn example(mut self, example: &str) -> Self {
+        self.unit
+            .examples
+            .get_or_insert_with(Vec::new)
+            .push(example.to_string());
+        self
+    }
+
+    pub fn comment(mut self, comment: &str) -> Self {
+        self.unit.comment = Some(comment.to_string());
+        self
+    }
+
+    /// Set if the regex has to match the whole value (overrides the global default)
+    pub fn full_match(mut self, full_match: bool) -> Self {
+        self.unit.full_match = Some(full_match);
+        self
+    }
+
+    /// Add a flag applied when compiling the regex (e.g. case insensitive)
+    pub fn flag(mut self, flag: RegexFlag) -> Self {
+        self.unit.flags.get_or_insert_with(Vec::new).push(flag);
+        self
+    }
+
+    /// Add a field unknown to this library (e.g. a vendor annotation) that is kept when writing the spec
+    pub fn extra(mut self, key: &str, value: serde_json::Value) -> Self {
+        self.unit.extra.insert(key.to_string(), value);
+        self
+    }
+
+    pub fn build(self) -> Unit {
+        self.unit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder, UnitBuilder};
+    use crate::error::Error;
+    use crate::types::{Datatype, PropertyName, PropertyNameKind, PropertyValueSpec, Role};
+    use crate::PropertyValidationResult;
+    use std::collections::HashMap;
+
+    const PORT: &str = "http.port";
+    const CONF_FILE: &str = "my.config";
+    const ROLE: &str = "server";
+
+    fn port_spec_builder() -> PropertySpecBuilder {
+        PropertySpecBuilder::new(
+            Datatype::Integer {
+                min: Some("1".to_string()),
+                max: Some("65535".to_string()),
+                unit: Some("port".to_string()),
+                accepted_units: None,
+                default_unit: None,
+                multiple_of: None,
+            },
+            "0.1.0",
+        )
+        .property_name(PropertyName {
+            name: PORT.to_string(),
+            kind: PropertyNameKind::Conf(CONF_FILE.to_string()),
+        })
+        .recommended_value(PropertyValueSpec {
+            from_version: Some("0.1.0".to_string()),
+            to_version: None,
+            when: None,
+            platform: None,
+            value: "8080".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_build_and_get() {
+        let spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("port", "^[0-9]{1,5}$").build())
+            .property_spec(
+                port_spec_builder()
+                    .role(Role {
+                        name: ROLE.to_string(),
+                        required: true,
+                    })
+                    .build(),
+            )
+            .build()
+            .unwrap();
+
+        let result = spec
+            .get(
+                "0.1.0",
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE),
+                &HashMap::new(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            result.get(PORT),
+            Some(&PropertyValidationResult::RecommendedDefault(
+                "8080".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_build_validates_spec() {
+        let result = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("port", "^[0-9]{1,5}$").build())
+            .property_spec(port_spec_builder().build())
+            .build();
+
+        assert_eq!(
+            result.err(),
+            Some(Error::PropertySpecRoleNotProvided {
+                name: PropertyName {
+                    name: PORT.to_string(),
+                    kind: PropertyNameKind::Conf(CONF_FILE.to_string()),
+                }
+            })
+        );
+    }
+}
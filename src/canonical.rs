@@ -0,0 +1,181 @@
+//! Canonicalization of user config values, so that semantically identical configs compare equal
+//! (e.g. `TRUE` and `true` or `1024m` and `1g`).
+use crate::types::{Datatype, PropertyName, PropertyNameKind, PropertySpec};
+use crate::units;
+use crate::util;
+use crate::validation::{self, ValidationResult};
+use crate::ProductConfigSpec;
+use semver::Version;
+use std::collections::HashMap;
+
+/// Returns the values in their canonical form: values are pre-processed (trimmed and normalized
+/// as configured), enum variants and bools are written in their specified casing, percentages
+/// and integer literals in the configured notation and values with unit in the largest unit in
+/// which the number is whole. The items of list properties are converted one by one. Values of
+/// properties unknown to the spec or not available in the version are only trimmed.
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `kind` - kind of the user config (e.g. env, conf)
+/// * `values` - map with property name and value
+/// * `product_version` - the product version
+///
+pub(crate) fn canonicalize(
+    spec: &ProductConfigSpec,
+    kind: &PropertyNameKind,
+    values: &HashMap<String, String>,
+    product_version: &Version,
+) -> ValidationResult<HashMap<String, String>> {
+    let prefix = spec.config_spec.prefix(kind).unwrap_or_default();
+    let mut canonical = HashMap::new();
+
+    for (name, value) in values {
+        let property_name = PropertyName {
+            name: name.strip_prefix(prefix).unwrap_or(name).to_string(),
+            kind: kind.clone(),
+        };
+        let value = match spec.property_specs.get(&property_name) {
+            Some(property) if util::is_available_in_version(property, product_version)? => {
+                canonical_value(spec, property, value)
+            }
+            _ => value.trim().to_string(),
+        };
+        canonical.insert(name.clone(), value);
+    }
+
+    Ok(canonical)
+}
+
+/// Returns the canonical form of the value of the property, see `canonicalize`
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `property` - the property spec
+/// * `value` - the value provided by the user
+///
+pub(crate) fn canonical_value(
+    spec: &ProductConfigSpec,
+    property: &PropertySpec,
+    value: &str,
+) -> String {
+    let normalized = validation::normalized_value(&spec.config_spec, property, value);
+    let value = normalized.as_deref().unwrap_or(value).trim();
+    let canonical = validation::canonical_value(property, value);
+    let value = canonical.as_deref().unwrap_or(value);
+
+    let items: Vec<String> = validation::list_items(property, value)
+        .into_iter()
+        .map(|item| canonical_item(property, item))
+        .collect();
+    match &property.list {
+        Some(list) => items.join(&list.delimiter),
+        None => items.concat(),
+    }
+}
+
+/// Returns the canonical form of a single value (or list item) for bools and values with unit
+///
+/// # Arguments
+///
+/// * `property` - the property spec
+/// * `item` - the (trimmed) value or list item
+///
+fn canonical_item(property: &PropertySpec, item: &str) -> String {
+    match &property.datatype {
+        Datatype::Bool
+            if item.eq_ignore_ascii_case("true") || item.eq_ignore_ascii_case("false") =>
+        {
+            item.to_ascii_lowercase()
+        }
+        _ => units::canonical_unit(property, item).unwrap_or_else(|| item.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder};
+    use crate::types::{Datatype, ListSpec, PropertyName, PropertyNameKind, Role};
+    use rstest::*;
+    use std::collections::HashMap;
+
+    #[rstest]
+    #[case("ssl.enabled", " TRUE ", "true")]
+    #[case("compression", "GZIP", "gzip")]
+    #[case("heap", "1024MB", "1g")]
+    #[case("timeouts", " 60s; 1.5m ;120m", "1m;90s;2h")]
+    #[case("unknown", " x ", "x")]
+    #[case("future", " TRUE ", "TRUE")]
+    #[trace]
+    fn test_canonicalize(#[case] name: &str, #[case] value: &str, #[case] expected: &str) {
+        let kind = PropertyNameKind::Conf("server.properties".to_string());
+        let property = |name: &str, datatype: Datatype| {
+            PropertySpecBuilder::new(datatype, "0.1.0")
+                .property_name(PropertyName {
+                    name: name.to_string(),
+                    kind: kind.clone(),
+                })
+                .role(Role {
+                    name: "broker".to_string(),
+                    required: false,
+                })
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(property("ssl.enabled", Datatype::Bool).build())
+            .property_spec(
+                property(
+                    "compression",
+                    Datatype::Enum {
+                        values: vec!["gzip".to_string(), "lz4".to_string()],
+                        case_insensitive: true,
+                    },
+                )
+                .build(),
+            )
+            .property_spec(
+                property(
+                    "heap",
+                    Datatype::MemorySize {
+                        min: None,
+                        max: None,
+                    },
+                )
+                .build(),
+            )
+            .property_spec(
+                property(
+                    "timeouts",
+                    Datatype::Duration {
+                        min: None,
+                        max: None,
+                    },
+                )
+                .list(ListSpec {
+                    delimiter: ";".to_string(),
+                    ..ListSpec::default()
+                })
+                .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "1.0.0")
+                    .property_name(PropertyName {
+                        name: "future".to_string(),
+                        kind: kind.clone(),
+                    })
+                    .role(Role {
+                        name: "broker".to_string(),
+                        required: false,
+                    })
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        let values = HashMap::from([(name.to_string(), value.to_string())]);
+
+        assert_eq!(
+            spec.canonicalize(&kind, &values, "0.1.0").unwrap()[name],
+            expected
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Generation of a `clap::Command` from the properties of kind `cli`, e.g. for wrapper CLIs that
+//! pass the options through to the product.
+//!
+//! Every property available in the product version becomes an argument with its first long name
+//! (or short name if there is none) as id. Names with a single dash and character (e.g. `-v`) are
+//! short options, all others long options (with the prefix of the kind), further names are visible
+//! aliases. Values are parsed according to the datatype (e.g. as `i64` with the bounds as range
+//! for integers), allowed values are the possible values. Properties of datatype bool may be used
+//! as flags.
+use crate::docgen;
+use crate::markdown;
+use crate::types::{Datatype, PropertyNameKind, PropertySpec};
+use crate::validation::ValidationResult;
+use crate::ProductConfigSpec;
+use clap::builder::{PossibleValuesParser, ValueParser};
+use clap::{value_parser, Arg, ArgAction, Command};
+use semver::Version;
+
+/// Returns the command with an argument per property of kind `cli` available in the version. The
+/// command has no name, it is set by the caller via `Command::name`.
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `version` - the product version
+///
+pub(crate) fn to_clap_command(
+    spec: &ProductConfigSpec,
+    version: &Version,
+) -> ValidationResult<Command> {
+    let prefix = spec
+        .config_spec
+        .prefix(&PropertyNameKind::Cli)
+        .unwrap_or_default()
+        .trim_start_matches('-');
+    let mut command = Command::new("");
+
+    for (names, property) in docgen::options(spec, version)? {
+        let names: Vec<&str> = names
+            .iter()
+            .filter(|name| name.kind == PropertyNameKind::Cli)
+            .map(|name| name.name.as_str())
+            .collect();
+        let (shorts, longs): (Vec<&str>, Vec<&str>) =
+            names.iter().partition(|name| short(name).is_some());
+        // the first long name is the id (short names only if there is none)
+        let id = match longs.first().or_else(|| shorts.first()) {
+            Some(id) => id.to_string(),
+            None => continue,
+        };
+
+        let mut arg = Arg::new(id).value_parser(value_parser_for(property));
+        for (index, name) in shorts.iter().filter_map(|name| short(name)).enumerate() {
+            arg = match index {
+                0 => arg.short(name),
+                _ => arg.visible_short_alias(name),
+            };
+        }
+        for (index, name) in longs.iter().enumerate() {
+            let long = format!("{}{}", prefix, name.trim_start_matches('-'));
+            arg = match index {
+                0 => arg.long(long),
+                _ => arg.visible_alias(long),
+            };
+        }
+
+        if let Datatype::Enum {
+            case_insensitive: true,
+            ..
+        } = property.datatype
+        {
+            arg = arg.ignore_case(true);
+        }
+        if matches!(property.datatype, Datatype::Bool) {
+            // `--verbose` for `true`, `--verbose false` to disable
+            arg = arg.num_args(0..=1).default_missing_value("true");
+        } else if let Some(delimiter) = property
+            .list
+            .as_ref()
+            .and_then(|list| single_char(&list.delimiter))
+        {
+            arg = arg.value_delimiter(delimiter).action(ArgAction::Append);
+        }
+        if let Some(description) = docgen::description(spec, property) {
+            arg = arg.help(markdown::to_plain_text(description));
+        }
+
+        command = command.arg(arg);
+    }
+
+    Ok(command)
+}
+
+/// Returns the character of a short option name like `-v`
+fn short(name: &str) -> Option<char> {
+    name.strip_prefix('-')
+        .filter(|name| !name.starts_with('-'))
+        .and_then(single_char)
+}
+
+fn single_char(text: &str) -> Option<char> {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+/// Returns the value parser for the property: allowed values (if values are not allowed via
+/// pattern as well) and enum variants are possible values, numbers are parsed within their
+/// bounds, all other values are parsed as strings and left to the validation
+fn value_parser_for(property: &PropertySpec) -> ValueParser {
+    if let (Some(values), None) = (&property.allowed_values, &property.allowed_pattern) {
+        return PossibleValuesParser::new(values.iter().cloned()).into();
+    }
+
+    match &property.datatype {
+        Datatype::Bool => value_parser!(bool),
+        Datatype::Integer { min, max, .. } => {
+            let min = min
+                .as_deref()
+                .and_then(|min| min.parse().ok())
+                .unwrap_or(i64::MIN);
+            let max = max
+                .as_deref()
+                .and_then(|max| max.parse().ok())
+                .unwrap_or(i64::MAX);
+            value_parser!(i64).range(min..=max).into()
+        }
+        Datatype::UnsignedInteger { min, max, .. } => {
+            let min = min.as_deref().and_then(|min| min.parse().ok()).unwrap_or(0);
+            let max = max
+                .as_deref()
+                .and_then(|max| max.parse().ok())
+                .unwrap_or(u64::MAX);
+            value_parser!(u64).range(min..=max).into()
+        }
+        Datatype::Float { .. } => value_parser!(f64).into(),
+        Datatype::Port { range: false, .. } => value_parser!(u16).range(1..).into(),
+        Datatype::Enum { values, .. } => PossibleValuesParser::new(values.iter().cloned()).into(),
+        _ => value_parser!(String),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder};
+    use crate::types::{Datatype, PropertyName, PropertyNameKind, Role};
+    use clap::error::ErrorKind;
+    use rstest::*;
+
+    fn property(names: &[&str], datatype: Datatype) -> PropertySpecBuilder {
+        let mut builder = PropertySpecBuilder::new(datatype, "0.1.0").role(Role {
+            name: "broker".to_string(),
+            required: false,
+        });
+        for name in names {
+            builder = builder.property_name(PropertyName {
+                name: name.to_string(),
+                kind: PropertyNameKind::Cli,
+            });
+        }
+        builder
+    }
+
+    fn command() -> clap::Command {
+        ProductConfigSpecBuilder::new()
+            .property_spec(
+                property(
+                    &["--port"],
+                    Datatype::Port {
+                        allow_privileged: false,
+                        range: false,
+                    },
+                )
+                .description("The **port** to listen on")
+                .build(),
+            )
+            .property_spec(property(&["verbose", "-v"], Datatype::Bool).build())
+            .property_spec(
+                property(
+                    &["compression"],
+                    Datatype::Enum {
+                        values: vec!["gzip".to_string(), "lz4".to_string()],
+                        case_insensitive: true,
+                    },
+                )
+                .build(),
+            )
+            .property_spec(
+                property(
+                    &["threads"],
+                    Datatype::Integer {
+                        min: Some("1".to_string()),
+                        max: Some("8".to_string()),
+                        unit: None,
+                        accepted_units: None,
+                        default_unit: None,
+                        multiple_of: None,
+                    },
+                )
+                .build(),
+            )
+            .build()
+            .unwrap()
+            .to_clap_command("0.1.0")
+            .unwrap()
+            .name("broker")
+    }
+
+    #[test]
+    fn test_to_clap_command() {
+        let matches = command()
+            .try_get_matches_from([
+                "broker",
+                "--port",
+                "9092",
+                "-v",
+                "--compression",
+                "GZIP",
+                "--threads=4",
+            ])
+            .unwrap();
+
+        assert_eq!(matches.get_one::<u16>("--port"), Some(&9092));
+        assert_eq!(matches.get_one::<bool>("verbose"), Some(&true));
+        assert_eq!(
+            matches.get_one::<String>("compression").map(String::as_str),
+            Some("GZIP")
+        );
+        assert_eq!(matches.get_one::<i64>("threads"), Some(&4));
+        assert!(command()
+            .render_help()
+            .to_string()
+            .contains("The port to listen on"));
+    }
+
+    #[rstest]
+    #[case(&["--port", "0"], ErrorKind::ValueValidation)]
+    #[case(&["--threads", "9"], ErrorKind::ValueValidation)]
+    #[case(&["--compression", "zstd"], ErrorKind::InvalidValue)]
+    #[case(&["--verbose", "yes"], ErrorKind::InvalidValue)]
+    #[trace]
+    fn test_to_clap_command_invalid(#[case] args: &[&str], #[case] expected: ErrorKind) {
+        let args = std::iter::once(&"broker").chain(args);
+
+        assert_eq!(
+            command().try_get_matches_from(args).unwrap_err().kind(),
+            expected
+        );
+    }
+}
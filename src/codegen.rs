@@ -0,0 +1,551 @@
+//! Generation of Rust types from a property spec, e.g. in build scripts of products that do not
+//! want to use the `macros` feature:
+//!
+//! ```no_run
+//! // build.rs
+//! let out_dir = std::env::var("OUT_DIR").unwrap();
+//! product_config::codegen::generate_rust_types(
+//!     "config/config_spec.json",
+//!     "config/property_spec.json",
+//!     &format!("{}/product_config.rs", out_dir),
+//! )
+//! .unwrap();
+//! ```
+//!
+//! The generated file is included via `include!(concat!(env!("OUT_DIR"), "/product_config.rs"))`
+//! and contains per kind (e.g. `env.sh`):
+//! - a module (`env_sh`) with a constant per property name (`env_sh::ENV_SSL_ENABLED`)
+//! - a typed config (`EnvShConfig`) to deserialize into via `ProductConfigSpec::validate_into`,
+//!   with property names split at `.` into nested structs
+//!
+//! and an enum per property with allowed values (or enum datatype). As the generated types are
+//! not bound to a product version, all fields are optional. Property specs with several products
+//! are generated into a module per product. Datatype references and value sets are resolved via
+//! the config spec like by the `ConfigJsonReader`.
+use crate::error::Error;
+use crate::markdown;
+use crate::reader::{self, JsonProductConfigSpecProperties, JsonPropertySpecs};
+use crate::types::{Datatype, PropertyNameKind, PropertySpec};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+const DERIVE_STRUCT: &str =
+    "#[derive(Clone, Debug, Default, PartialEq, ::product_config::__private::serde::Deserialize)]";
+const DERIVE_ENUM: &str = "#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, \
+    ::product_config::__private::serde::Deserialize, ::product_config::__private::serde::Serialize)]";
+const SERDE_CRATE: &str = "#[serde(crate = \"::product_config::__private::serde\")]";
+
+/// Reads the config spec and the property spec and writes the generated types to the output file
+///
+/// # Arguments
+///
+/// * `config_spec_path` - the path of the config spec (JSON) with the datatype definitions
+/// * `spec_path` - the path of the property spec (JSON)
+/// * `out_path` - the path of the generated Rust file
+///
+pub fn generate_rust_types(
+    config_spec_path: &str,
+    spec_path: &str,
+    out_path: &str,
+) -> Result<(), Error> {
+    let config_spec: JsonProductConfigSpecProperties = reader::read_file(config_spec_path)?;
+    let resolve = |properties: &[PropertySpec]| {
+        properties
+            .iter()
+            .map(|property| reader::resolve_references(&config_spec, property))
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    let source = match reader::read_file(spec_path)? {
+        JsonPropertySpecs::Properties(properties) => rust_types(&resolve(&properties)?),
+        JsonPropertySpecs::Products { products } => products
+            .iter()
+            .map(|(product, properties)| {
+                let types = rust_types(&resolve(properties)?)
+                    .lines()
+                    .map(|line| match line.is_empty() {
+                        true => "\n".to_string(),
+                        false => format!("    {}\n", line),
+                    })
+                    .collect::<String>();
+                Ok(format!(
+                    "/// Types of the product `{}`\npub mod {} {{\n{}}}\n",
+                    product,
+                    snake_case(product),
+                    types
+                ))
+            })
+            .collect::<Result<Vec<String>, Error>>()?
+            .join("\n"),
+    };
+
+    let header = format!(
+        "// Generated by `product_config::codegen` from `{}`, do not edit.\n\n",
+        spec_path.rsplit(['/', '\\']).next().unwrap_or(spec_path)
+    );
+    std::fs::write(out_path, header + &source).map_err(|err| Error::FileNotWritable {
+        file_name: out_path.to_string(),
+        reason: err.to_string(),
+    })
+}
+
+/// A field of a generated struct: a property or a nested struct
+enum Node<'a> {
+    Leaf(&'a PropertySpec),
+    Branch(BTreeMap<String, Node<'a>>),
+}
+
+/// Returns the generated types (constants, structs and enums) of the properties
+fn rust_types(properties: &[PropertySpec]) -> String {
+    let mut type_names = BTreeSet::new();
+    let mut names: BTreeMap<&PropertyNameKind, Vec<(&str, &PropertySpec)>> = BTreeMap::new();
+    for property in properties {
+        for property_name in &property.property_names {
+            names
+                .entry(&property_name.kind)
+                .or_default()
+                .push((&property_name.name, property));
+        }
+    }
+    let mut modules = BTreeSet::new();
+    let idents: BTreeMap<&PropertyNameKind, (String, String)> = names
+        .keys()
+        .map(|kind| {
+            let module = unique(snake_case(&kind_name(kind)), &mut modules);
+            let struct_name = format!("{}Config", camel_case(&kind_name(kind)));
+            (*kind, (module, unique(struct_name, &mut type_names)))
+        })
+        .collect();
+
+    // the enums are named after the first name of their property
+    let mut enums: Vec<(String, &PropertySpec, &[String])> = vec![];
+    for property in properties {
+        if let (Some(values), Some(name)) = (enum_values(property), property.property_names.first())
+        {
+            let enum_name = unique(camel_case(&name.name), &mut type_names);
+            enums.push((enum_name, property, values));
+        }
+    }
+
+    let mut out = String::new();
+    for (kind, names) in &names {
+        let (module, struct_name) = &idents[kind];
+        let label = match kind {
+            PropertyNameKind::Conf(file) => format!("`conf` (`{}`)", file),
+            PropertyNameKind::Env => "`env`".to_string(),
+            PropertyNameKind::Cli => "`cli`".to_string(),
+        };
+
+        let _ = writeln!(out, "/// Property names of kind {}", label);
+        let _ = writeln!(out, "pub mod {} {{", module);
+        let mut constants = BTreeSet::new();
+        for (name, property) in names {
+            write_doc(&mut out, "    ", property);
+            let constant = unique(snake_case(name).to_ascii_uppercase(), &mut constants);
+            let _ = writeln!(out, "    pub const {}: &str = {:?};", constant, name);
+        }
+        out.push_str("}\n\n");
+
+        // only the first name of a property per kind, like the effective values are typed
+        let mut tree = BTreeMap::new();
+        let mut seen = BTreeSet::new();
+        let mut sorted: Vec<&(&str, &PropertySpec)> = names
+            .iter()
+            .filter(|(_, property)| seen.insert(*property as *const PropertySpec))
+            .collect();
+        sorted.sort_by_key(|(name, _)| *name);
+        for (name, property) in sorted {
+            insert(&mut tree, name, property);
+        }
+
+        let _ = writeln!(out, "/// Typed config of kind {}", label);
+        write_struct(&mut out, struct_name, &tree, &enums, &mut type_names);
+    }
+
+    for (enum_name, property, values) in &enums {
+        write_enum(&mut out, enum_name, property, values);
+    }
+
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+/// Writes the struct and its nested structs for the fields of the tree
+///
+/// # Arguments
+///
+/// * `out` - the generated source
+/// * `struct_name` - the name of the struct
+/// * `tree` - the fields of the struct
+/// * `enums` - the generated enums with their property
+/// * `type_names` - the names of all generated types (to keep names of nested structs unique)
+///
+fn write_struct(
+    out: &mut String,
+    struct_name: &str,
+    tree: &BTreeMap<String, Node>,
+    enums: &[(String, &PropertySpec, &[String])],
+    type_names: &mut BTreeSet<String>,
+) {
+    let mut fields = String::new();
+    let mut nested = vec![];
+    let mut field_names = BTreeSet::new();
+
+    for (key, node) in tree {
+        let field = unique(field_ident(key), &mut field_names);
+        match node {
+            Node::Leaf(property) => {
+                write_doc(&mut fields, "    ", property);
+                let _ = writeln!(fields, "    #[serde(rename = {:?})]", key);
+                let _ = writeln!(
+                    fields,
+                    "    pub {}: Option<{}>,",
+                    field,
+                    rust_type(property, enums)
+                );
+            }
+            Node::Branch(children) => {
+                let nested_name = unique(format!("{}{}", struct_name, camel_case(key)), type_names);
+                let _ = writeln!(fields, "    #[serde(rename = {:?}, default)]", key);
+                let _ = writeln!(fields, "    pub {}: {},", field, nested_name);
+                nested.push((nested_name, children));
+            }
+        }
+    }
+
+    let _ = writeln!(out, "{}", DERIVE_STRUCT);
+    let _ = writeln!(out, "{}", SERDE_CRATE);
+    let _ = writeln!(out, "pub struct {} {{", struct_name);
+    out.push_str(&fields);
+    out.push_str("}\n\n");
+
+    for (nested_name, children) in nested {
+        write_struct(out, &nested_name, children, enums, type_names);
+    }
+}
+
+/// Writes the enum of the allowed values of the property
+fn write_enum(out: &mut String, enum_name: &str, property: &PropertySpec, values: &[String]) {
+    let mut variants = BTreeSet::new();
+    let variants: Vec<(String, &String)> = values
+        .iter()
+        .map(|value| (unique(variant_ident(value), &mut variants), value))
+        .collect();
+
+    if let Some(name) = property.property_names.first() {
+        let _ = writeln!(out, "/// Allowed values of `{}`", name.name);
+    }
+    let _ = writeln!(out, "{}", DERIVE_ENUM);
+    let _ = writeln!(out, "{}", SERDE_CRATE);
+    let _ = writeln!(out, "pub enum {} {{", enum_name);
+    for (variant, value) in &variants {
+        let _ = writeln!(out, "    #[serde(rename = {:?})]", value);
+        let _ = writeln!(out, "    {},", variant);
+    }
+    out.push_str("}\n\n");
+
+    let _ = writeln!(out, "impl {} {{", enum_name);
+    out.push_str("    /// Returns the value as specified\n");
+    out.push_str("    pub fn as_str(&self) -> &'static str {\n");
+    out.push_str("        match self {\n");
+    for (variant, value) in &variants {
+        let _ = writeln!(
+            out,
+            "            {}::{} => {:?},",
+            enum_name, variant, value
+        );
+    }
+    out.push_str("        }\n    }\n}\n\n");
+}
+
+/// Writes the description of the property (as plain text) as doc comment
+fn write_doc(out: &mut String, indent: &str, property: &PropertySpec) {
+    let description = property
+        .description
+        .as_ref()
+        .and_then(|description| description.text("en", "en"));
+    if let Some(description) = description {
+        for line in markdown::to_plain_text(description).lines() {
+            match line.trim_end() {
+                "" => {
+                    let _ = writeln!(out, "{}///", indent);
+                }
+                line => {
+                    let _ = writeln!(out, "{}/// {}", indent, line);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the values of the enum for the property: the allowed values (if values are not allowed
+/// via pattern as well) or the enum variants
+fn enum_values(property: &PropertySpec) -> Option<&[String]> {
+    match (&property.allowed_values, &property.allowed_pattern) {
+        (Some(values), None) if !values.is_empty() => Some(values),
+        _ => match &property.datatype {
+            Datatype::Enum { values, .. } if !values.is_empty() => Some(values),
+            _ => None,
+        },
+    }
+}
+
+/// Returns the Rust type of the property, like the effective values are typed for
+/// deserialization
+fn rust_type(property: &PropertySpec, enums: &[(String, &PropertySpec, &[String])]) -> String {
+    let enum_name = enums
+        .iter()
+        .find(|(_, other, _)| std::ptr::eq(*other, property))
+        .map(|(enum_name, _, _)| enum_name.as_str());
+    let item = match (&property.datatype, enum_name) {
+        (_, Some(enum_name)) => enum_name,
+        (Datatype::Bool, _) => "bool",
+        (Datatype::Integer { .. }, _) => "i64",
+        (Datatype::UnsignedInteger { .. }, _) => "u64",
+        (Datatype::Float { .. }, _) => "f64",
+        (Datatype::Port { range: false, .. }, _) => "u16",
+        (Datatype::Json { .. }, _) => "::product_config::__private::serde_json::Value",
+        _ => "String",
+    };
+    match property.list {
+        Some(_) => format!("Vec<{}>", item),
+        None => item.to_string(),
+    }
+}
+
+/// Inserts the property into the tree for the name split at `.`; if a parent is a property, the
+/// rest of the name is kept as key (like the values are nested for deserialization)
+fn insert<'a>(tree: &mut BTreeMap<String, Node<'a>>, name: &str, property: &'a PropertySpec) {
+    if let Some((parent, rest)) = name.split_once('.') {
+        let child = tree
+            .entry(parent.to_string())
+            .or_insert_with(|| Node::Branch(BTreeMap::new()));
+        if let Node::Branch(child) = child {
+            insert(child, rest, property);
+            return;
+        }
+    }
+    tree.insert(name.to_string(), Node::Leaf(property));
+}
+
+/// Returns the name of the kind for module and struct names, e.g. `env.sh` for `conf` kinds
+fn kind_name(kind: &PropertyNameKind) -> String {
+    match kind {
+        PropertyNameKind::Conf(file) => file.clone(),
+        PropertyNameKind::Env => "env".to_string(),
+        PropertyNameKind::Cli => "cli".to_string(),
+    }
+}
+
+/// Returns the name with a numeric suffix if it is already used
+fn unique(name: String, used: &mut BTreeSet<String>) -> String {
+    let mut unique = name.clone();
+    let mut index = 2;
+    while !used.insert(unique.clone()) {
+        unique = format!("{}{}", name, index);
+        index += 1;
+    }
+    unique
+}
+
+/// Returns the name in snake case, e.g. `log_dirs` for `--log-dirs` or `env_sh` for `env.sh`
+fn snake_case(name: &str) -> String {
+    let mut snake: String = name
+        .trim_start_matches('-')
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_lowercase(),
+            false => '_',
+        })
+        .collect();
+    if snake.is_empty() || snake.starts_with(|c: char| c.is_ascii_digit()) {
+        snake.insert(0, '_');
+    }
+    snake
+}
+
+/// Returns the field name for the (part of the) property name, keywords as raw identifiers
+fn field_ident(key: &str) -> String {
+    let name = snake_case(key);
+    match name.as_str() {
+        "crate" | "self" | "super" | "_" => format!("{}_", name),
+        _ if KEYWORDS.contains(&name.as_str()) => format!("r#{}", name),
+        _ => name,
+    }
+}
+
+/// Returns the variant name for the value, e.g. `Gzip` for `gzip` or `V1` for `1`
+fn variant_ident(value: &str) -> String {
+    let name = camel_case(value);
+    match name.as_str() {
+        "" => "Empty".to_string(),
+        "Self" => "Self_".to_string(),
+        _ if name.starts_with(|c: char| c.is_ascii_digit()) => format!("V{}", name),
+        _ => name,
+    }
+}
+
+/// Returns the name in camel case, e.g. `EnvSh` for `env.sh`
+fn camel_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let part = part.to_ascii_lowercase();
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Keywords (strict and reserved) that are only valid as raw identifiers
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{field_ident, snake_case, variant_ident};
+    use crate::reader::ConfigJsonReader;
+    use crate::types::PropertyNameKind;
+    use crate::ProductConfigSpec;
+    use rstest::*;
+    use std::collections::HashMap;
+
+    #[allow(dead_code)]
+    mod generated {
+        include!("../data/test_property_spec.rs");
+    }
+
+    #[allow(dead_code)]
+    mod generated_ref {
+        include!("../data/test_ref_property_spec.rs");
+    }
+
+    const CONFIG_SPEC_PATH: &str = "data/test_config_spec.json";
+    const SPEC_PATH: &str = "data/test_property_spec.json";
+    const REF_CONFIG_SPEC_PATH: &str = "data/test_ref_config_spec.json";
+    const REF_SPEC_PATH: &str = "data/test_ref_property_spec.json";
+
+    #[rstest]
+    #[case(CONFIG_SPEC_PATH, SPEC_PATH, include_str!("../data/test_property_spec.rs"))]
+    #[case(REF_CONFIG_SPEC_PATH, REF_SPEC_PATH, include_str!("../data/test_ref_property_spec.rs"))]
+    #[trace]
+    fn test_generate_rust_types(
+        #[case] config_spec_path: &str,
+        #[case] spec_path: &str,
+        #[case] expected: &str,
+    ) {
+        let out_path = std::env::temp_dir().join(format!(
+            "product_config_{}_generate_rust_types_{}.rs",
+            std::process::id(),
+            snake_case(spec_path)
+        ));
+        let out_path = out_path.to_str().unwrap();
+
+        super::generate_rust_types(config_spec_path, spec_path, out_path).unwrap();
+
+        assert_eq!(std::fs::read_to_string(out_path).unwrap(), expected);
+        std::fs::remove_file(out_path).unwrap();
+    }
+
+    #[test]
+    fn test_generated_types() {
+        let spec =
+            ProductConfigSpec::new(ConfigJsonReader::new(CONFIG_SPEC_PATH, SPEC_PATH)).unwrap();
+        let user_config = HashMap::from([
+            (
+                generated::env_sh::ENV_INTEGER_PORT_MIN_MAX.to_string(),
+                "12345".to_string(),
+            ),
+            (
+                generated::env_sh::ENV_ALLOWED_VALUES.to_string(),
+                "allowed_value2".to_string(),
+            ),
+        ]);
+
+        let config: generated::EnvShConfig = spec
+            .validate_into(
+                &PropertyNameKind::Conf("env.sh".to_string()),
+                &user_config,
+                "0.5.0",
+            )
+            .unwrap();
+
+        assert_eq!(config.env_integer_port_min_max, Some(12345));
+        assert_eq!(
+            config.env_allowed_values,
+            Some(generated::EnvAllowedValues::AllowedValue2)
+        );
+        assert_eq!(
+            generated::EnvAllowedValues::AllowedValue2.as_str(),
+            "allowed_value2"
+        );
+    }
+
+    #[test]
+    fn test_generated_ref_types() {
+        let spec =
+            ProductConfigSpec::new(ConfigJsonReader::new(REF_CONFIG_SPEC_PATH, REF_SPEC_PATH))
+                .unwrap();
+        let user_config = HashMap::from([
+            (
+                generated_ref::server_properties::HEAP_SIZE.to_string(),
+                "2gb".to_string(),
+            ),
+            (
+                generated_ref::server_properties::DEBUG_ENABLED.to_string(),
+                "true".to_string(),
+            ),
+        ]);
+
+        let config: generated_ref::ServerPropertiesConfig = spec
+            .validate_into(
+                &PropertyNameKind::Conf("server.properties".to_string()),
+                &user_config,
+                "0.1.0",
+            )
+            .unwrap();
+
+        assert_eq!(config.heap.size, Some(2048));
+        assert_eq!(config.debug.enabled, Some(true));
+    }
+
+    #[rstest]
+    #[case("log.dirs", "log_dirs")]
+    #[case("--log-dirs", "log_dirs")]
+    #[case("HEAP_OPTS", "heap_opts")]
+    #[case("type", "r#type")]
+    #[case("self", "self_")]
+    #[case("1st", "_1st")]
+    #[trace]
+    fn test_field_ident(#[case] key: &str, #[case] expected: &str) {
+        assert_eq!(field_ident(key), expected);
+    }
+
+    #[rstest]
+    #[case("env.sh", "env_sh")]
+    #[case("-v", "v")]
+    #[trace]
+    fn test_snake_case(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(snake_case(name), expected);
+    }
+
+    #[rstest]
+    #[case("gzip", "Gzip")]
+    #[case("allowed_value1", "AllowedValue1")]
+    #[case("1", "V1")]
+    #[case("", "Empty")]
+    #[case("self", "Self_")]
+    #[trace]
+    fn test_variant_ident(#[case] value: &str, #[case] expected: &str) {
+        assert_eq!(variant_ident(value), expected);
+    }
+}
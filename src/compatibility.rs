@@ -0,0 +1,418 @@
+//! Compatibility of a user config with a product upgrade: which of the set properties become
+//! deprecated or are removed, which values become invalid and which properties have to be set
+//! in addition, e.g. to block or annotate an upgrade before it is rolled out. Downgrades (e.g.
+//! rollbacks) are checked for set properties that do not exist in the older version and for
+//! defaults that differ.
+use crate::canonical;
+use crate::effective;
+use crate::error::{Error, REDACTED};
+use crate::types::{PropertyName, PropertyNameKind};
+use crate::util;
+use crate::validation::ValidationResult;
+use crate::{DeprecatedProperty, ProductConfigSpec};
+use semver::Version;
+use std::collections::{BTreeSet, HashMap};
+
+/// The issues of a user config when upgrading from one product version to another
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CompatibilityReport {
+    /// set properties that are deprecated in the target version, but not in the current one
+    pub deprecated: Vec<DeprecatedProperty>,
+    /// set properties that are removed in the target version
+    pub removed: Vec<PropertyName>,
+    /// values (with the name as provided) that are valid for the current version, but not for
+    /// the target version
+    pub invalid: Vec<(String, Error)>,
+    /// properties that are required in the target version, but not set (and not required in the
+    /// current version)
+    pub new_required: Vec<PropertyName>,
+}
+
+impl CompatibilityReport {
+    /// Returns true if the user config can be used as it is for the target version (deprecated
+    /// properties still work and do not prevent the upgrade)
+    pub fn is_compatible(&self) -> bool {
+        self.removed.is_empty() && self.invalid.is_empty() && self.new_required.is_empty()
+    }
+}
+
+/// The issues of a user config when downgrading (e.g. rolling back) to an older product version
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DowngradeReport {
+    /// set properties that do not exist in the older version
+    pub unavailable: Vec<PropertyName>,
+    /// properties that are not set, but whose default differs in the older version
+    pub changed_defaults: Vec<DefaultChange>,
+}
+
+impl DowngradeReport {
+    /// Returns true if the product behaves the same with the user config in the older version
+    pub fn is_safe(&self) -> bool {
+        self.unavailable.is_empty() && self.changed_defaults.is_empty()
+    }
+}
+
+/// A default value that differs between two product versions (values of sensitive properties
+/// are replaced by `***`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct DefaultChange {
+    pub property_name: PropertyName,
+    /// the default of the current version (None if there is none)
+    pub from_default: Option<String>,
+    /// the default of the older version (None if there is none)
+    pub to_default: Option<String>,
+}
+
+/// Returns the compatibility report of the user config for the upgrade, see `CompatibilityReport`
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `kind` - kind of the user config (e.g. env, conf)
+/// * `values` - map with property name and value
+/// * `from_version` - the current product version
+/// * `to_version` - the product version to upgrade to
+///
+pub(crate) fn compatibility_report(
+    spec: &ProductConfigSpec,
+    kind: &PropertyNameKind,
+    values: &HashMap<String, String>,
+    from_version: &Version,
+    to_version: &Version,
+) -> ValidationResult<CompatibilityReport> {
+    if from_version > to_version {
+        return Err(Error::InvalidVersionRange {
+            from_version: from_version.to_string(),
+            to_version: to_version.to_string(),
+        });
+    }
+
+    let prefix = spec.config_spec.prefix(kind).unwrap_or_default();
+    let mut report = CompatibilityReport::default();
+    let mut removed_names = BTreeSet::new();
+
+    let mut names: Vec<&String> = values.keys().collect();
+    names.sort();
+    for name in names {
+        let property_name = PropertyName {
+            name: name.strip_prefix(prefix).unwrap_or(name).to_string(),
+            kind: kind.clone(),
+        };
+        let property = match spec.property_specs.get(&property_name) {
+            Some(property) => property,
+            None => continue,
+        };
+
+        if let Some(removed_in) = &property.removed_in {
+            if in_range(removed_in, from_version, to_version)? {
+                removed_names.insert(name.as_str());
+                report.removed.push(property_name);
+                continue;
+            }
+        }
+        if let Some(deprecated_since) = &property.deprecated_since {
+            if in_range(deprecated_since, from_version, to_version)? {
+                report.deprecated.push(DeprecatedProperty {
+                    property_name,
+                    deprecated_since: deprecated_since.clone(),
+                    deprecated_for: property.deprecated_for.clone().unwrap_or_default(),
+                });
+            }
+        }
+    }
+
+    let from_problems = spec.problems(&from_version.to_string(), kind, values)?;
+    let to_problems = spec.problems(&to_version.to_string(), kind, values)?;
+    for (name, error) in to_problems {
+        let known = from_problems
+            .iter()
+            .any(|(from_name, from_error)| from_name == &name && same_kind(from_error, &error));
+        if known || removed_names.contains(name.as_str()) {
+            continue;
+        }
+        match error {
+            Error::RequiredPropertyMissing { property_name } => {
+                report.new_required.push(property_name)
+            }
+            error => report.invalid.push((name, error)),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Returns the downgrade report of the user config, see `DowngradeReport`
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `kind` - kind of the user config (e.g. env, conf)
+/// * `values` - map with property name and value
+/// * `from_version` - the current product version
+/// * `to_version` - the (older) product version to downgrade to
+///
+pub(crate) fn check_downgrade(
+    spec: &ProductConfigSpec,
+    kind: &PropertyNameKind,
+    values: &HashMap<String, String>,
+    from_version: &Version,
+    to_version: &Version,
+) -> ValidationResult<DowngradeReport> {
+    if to_version > from_version {
+        return Err(Error::InvalidVersionRange {
+            from_version: to_version.to_string(),
+            to_version: from_version.to_string(),
+        });
+    }
+
+    let prefix = spec.config_spec.prefix(kind).unwrap_or_default();
+    let mut report = DowngradeReport::default();
+
+    for (property_name, property) in &spec.property_specs {
+        if &property_name.kind != kind {
+            continue;
+        }
+        let set = values.contains_key(&property_name.name)
+            || values.contains_key(&format!("{}{}", prefix, property_name.name));
+
+        if set {
+            if !util::is_available_in_version(property, to_version)? {
+                report.unavailable.push(property_name.clone());
+            }
+            continue;
+        }
+        if !util::is_available_in_version(property, from_version)?
+            || !util::is_available_in_version(property, to_version)?
+        {
+            continue;
+        }
+
+        let from_default =
+            effective::default_value(spec, property_name, property, values, from_version)?;
+        let to_default =
+            effective::default_value(spec, property_name, property, values, to_version)?;
+        let canonical = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(|value| canonical::canonical_value(spec, property, value))
+        };
+        if canonical(&from_default) != canonical(&to_default) {
+            let redact = |value: Option<String>| match spec.is_sensitive(property_name) {
+                true => value.map(|_| REDACTED.to_string()),
+                false => value,
+            };
+            report.changed_defaults.push(DefaultChange {
+                property_name: property_name.clone(),
+                from_default: redact(from_default),
+                to_default: redact(to_default),
+            });
+        }
+    }
+
+    report.unavailable.sort();
+    report
+        .changed_defaults
+        .sort_by(|a, b| a.property_name.cmp(&b.property_name));
+    Ok(report)
+}
+
+/// Returns true if the version is after `from_version` up to and including `to_version`
+fn in_range(version: &str, from_version: &Version, to_version: &Version) -> ValidationResult<bool> {
+    let version = Version::parse(version)?;
+    Ok(version > *from_version && version <= *to_version)
+}
+
+/// Returns true if both errors are of the same variant: errors contain the product version,
+/// so the same problem differs between the versions
+fn same_kind(error: &Error, other: &Error) -> bool {
+    std::mem::discriminant(error) == std::mem::discriminant(other)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder};
+    use crate::compatibility::DefaultChange;
+    use crate::error::Error;
+    use crate::types::{
+        Datatype, Importance, PropertyName, PropertyNameKind, PropertyValueSpec, Role,
+    };
+    use crate::DeprecatedProperty;
+    use rstest::*;
+    use std::collections::HashMap;
+
+    const CONF_FILE: &str = "server.properties";
+
+    fn property_name(name: &str) -> PropertyName {
+        PropertyName {
+            name: name.to_string(),
+            kind: PropertyNameKind::Conf(CONF_FILE.to_string()),
+        }
+    }
+
+    fn property(name: &str, as_of_version: &str) -> PropertySpecBuilder {
+        PropertySpecBuilder::new(Datatype::Bool, as_of_version)
+            .property_name(property_name(name))
+            .role(Role {
+                name: "broker".to_string(),
+                required: false,
+            })
+    }
+
+    fn default_value(
+        from_version: &str,
+        to_version: Option<&str>,
+        value: &str,
+    ) -> PropertyValueSpec {
+        PropertyValueSpec {
+            from_version: Some(from_version.to_string()),
+            to_version: to_version.map(str::to_string),
+            when: None,
+            platform: None,
+            value: value.to_string(),
+        }
+    }
+
+    fn spec() -> crate::ProductConfigSpec {
+        ProductConfigSpecBuilder::new()
+            .property_spec(property("ssl.enabled", "1.0.0").build())
+            .property_spec(
+                property("legacy.auth", "1.0.0")
+                    .deprecated_since("2.0.0")
+                    .deprecated_for(vec![property_name("auth.enabled")])
+                    .build(),
+            )
+            .property_spec(property("old.cache", "1.0.0").removed_in("2.0.0").build())
+            .property_spec(
+                property("compression.enabled", "1.0.0")
+                    .default_value(default_value("1.0.0", Some("1.9.0"), "false"))
+                    .default_value(default_value("2.0.0", None, "true"))
+                    .build(),
+            )
+            .property_spec(
+                property("auth.enabled", "2.0.0")
+                    .importance(Importance::High)
+                    .build(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[rstest]
+    #[case("1.0.0", "1.5.0", vec![], vec![], vec![])]
+    #[case(
+        "1.0.0",
+        "2.0.0",
+        vec![DeprecatedProperty {
+            property_name: property_name("legacy.auth"),
+            deprecated_since: "2.0.0".to_string(),
+            deprecated_for: vec![vec![property_name("auth.enabled")]],
+        }],
+        vec![property_name("old.cache")],
+        vec![property_name("auth.enabled")]
+    )]
+    #[trace]
+    fn test_compatibility_report(
+        #[case] from_version: &str,
+        #[case] to_version: &str,
+        #[case] deprecated: Vec<DeprecatedProperty>,
+        #[case] removed: Vec<PropertyName>,
+        #[case] new_required: Vec<PropertyName>,
+    ) {
+        let values = HashMap::from([
+            ("ssl.enabled".to_string(), "true".to_string()),
+            ("legacy.auth".to_string(), "true".to_string()),
+            ("old.cache".to_string(), "false".to_string()),
+        ]);
+
+        let report = spec()
+            .compatibility_report(
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                &values,
+                from_version,
+                to_version,
+            )
+            .unwrap();
+
+        assert_eq!(report.deprecated, deprecated);
+        assert_eq!(report.removed, removed);
+        assert_eq!(report.new_required, new_required);
+        assert!(report.invalid.is_empty());
+        assert_eq!(report.is_compatible(), to_version == "1.5.0");
+    }
+
+    #[test]
+    fn test_compatibility_report_invalid_version_range() {
+        let result = spec().compatibility_report(
+            &PropertyNameKind::Conf(CONF_FILE.to_string()),
+            &HashMap::new(),
+            "2.0.0",
+            "1.0.0",
+        );
+
+        assert_eq!(
+            result,
+            Err(Error::InvalidVersionRange {
+                from_version: "2.0.0".to_string(),
+                to_version: "1.0.0".to_string(),
+            })
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "2.0.0",
+        "1.5.0",
+        &["ssl.enabled", "auth.enabled"],
+        vec![property_name("auth.enabled")],
+        vec![DefaultChange {
+            property_name: property_name("compression.enabled"),
+            from_default: Some("true".to_string()),
+            to_default: Some("false".to_string()),
+        }]
+    )]
+    #[case("1.5.0", "1.0.0", &["ssl.enabled"], vec![], vec![])]
+    #[trace]
+    fn test_check_downgrade(
+        #[case] from_version: &str,
+        #[case] to_version: &str,
+        #[case] names: &[&str],
+        #[case] unavailable: Vec<PropertyName>,
+        #[case] changed_defaults: Vec<DefaultChange>,
+    ) {
+        let values = names
+            .iter()
+            .map(|name| (name.to_string(), "true".to_string()))
+            .collect();
+
+        let report = spec()
+            .check_downgrade(
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                &values,
+                from_version,
+                to_version,
+            )
+            .unwrap();
+
+        assert_eq!(report.unavailable, unavailable);
+        assert_eq!(report.changed_defaults, changed_defaults);
+        assert_eq!(report.is_safe(), to_version == "1.0.0");
+    }
+
+    #[test]
+    fn test_check_downgrade_invalid_version_range() {
+        let result = spec().check_downgrade(
+            &PropertyNameKind::Conf(CONF_FILE.to_string()),
+            &HashMap::new(),
+            "1.0.0",
+            "2.0.0",
+        );
+
+        assert_eq!(
+            result,
+            Err(Error::InvalidVersionRange {
+                from_version: "2.0.0".to_string(),
+                to_version: "1.0.0".to_string(),
+            })
+        );
+    }
+}
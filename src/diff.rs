@@ -0,0 +1,417 @@
+//! Compare two product config specs, e.g. to generate a changelog of the configuration
+//! surface between two product versions, or two user configs, e.g. to show the changes (and
+//! their impact) before applying them.
+use crate::canonical;
+use crate::error::REDACTED;
+use crate::types::{
+    ChangePolicy, Datatype, PropertyName, PropertyNameKind, PropertySpec, PropertyValueSpec,
+};
+use crate::util;
+use crate::validation::ValidationResult;
+use crate::ProductConfigSpec;
+use semver::Version;
+use std::collections::{BTreeSet, HashMap};
+
+/// The differences between two product config specs
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpecDiff {
+    /// property names that only exist in the other spec
+    pub added: Vec<PropertyName>,
+    /// property names that only exist in the base spec
+    pub removed: Vec<PropertyName>,
+    /// changes of property names that exist in both specs
+    pub changed: Vec<PropertySpecChange>,
+}
+
+impl SpecDiff {
+    /// Returns true if both specs do not differ in any reported aspect
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A single change of a property that exists in both specs
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertySpecChange {
+    /// The datatype changed apart from its bounds (e.g. integer to string or a different unit)
+    Datatype {
+        property_name: PropertyName,
+        old: Datatype,
+        new: Datatype,
+    },
+    /// The min and / or max bounds of the datatype changed
+    Bounds {
+        property_name: PropertyName,
+        old_min: Option<String>,
+        old_max: Option<String>,
+        new_min: Option<String>,
+        new_max: Option<String>,
+    },
+    /// The default values changed
+    DefaultValues {
+        property_name: PropertyName,
+        old: Option<Vec<PropertyValueSpec>>,
+        new: Option<Vec<PropertyValueSpec>>,
+    },
+}
+
+/// The differences between two user configs (of the same kind)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValuesDiff {
+    /// properties that are only set in the new values
+    pub added: Vec<ValueChange>,
+    /// properties that are only set in the old values
+    pub removed: Vec<ValueChange>,
+    /// properties whose values differ (in their canonical form)
+    pub changed: Vec<ValueChange>,
+    /// the action with the highest impact of all changes
+    pub required_action: ChangePolicy,
+}
+
+impl ValuesDiff {
+    /// Returns true if both user configs do not differ
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A single added, removed or changed value
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValueChange {
+    pub name: String,
+    /// the old value (None if added); values of sensitive properties are replaced by `***`
+    pub old_value: Option<String>,
+    /// the new value (None if removed); values of sensitive properties are replaced by `***`
+    pub new_value: Option<String>,
+    /// the action required to apply the change (None for properties unknown to the spec)
+    pub change_policy: Option<ChangePolicy>,
+}
+
+/// Collect the differences between two user configs. Values are compared in their canonical
+/// form (so `TRUE` and `true` do not differ), the results are sorted by property name.
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `kind` - kind of the user configs (e.g. env, conf)
+/// * `old_values` - map with property name and the old values
+/// * `new_values` - map with property name and the new values
+/// * `product_version` - the product version
+///
+pub(crate) fn diff_values(
+    spec: &ProductConfigSpec,
+    kind: &PropertyNameKind,
+    old_values: &HashMap<String, String>,
+    new_values: &HashMap<String, String>,
+    product_version: &Version,
+) -> ValidationResult<ValuesDiff> {
+    let old_canonical = canonical::canonicalize(spec, kind, old_values, product_version)?;
+    let new_canonical = canonical::canonicalize(spec, kind, new_values, product_version)?;
+    let prefix = spec.config_spec.prefix(kind).unwrap_or_default();
+
+    let mut diff = ValuesDiff {
+        added: vec![],
+        removed: vec![],
+        changed: vec![],
+        required_action: ChangePolicy::None,
+    };
+    let names: BTreeSet<&String> = old_values.keys().chain(new_values.keys()).collect();
+    for name in names {
+        if old_canonical.get(name) == new_canonical.get(name) {
+            continue;
+        }
+
+        let property_name = PropertyName {
+            name: name.strip_prefix(prefix).unwrap_or(name).to_string(),
+            kind: kind.clone(),
+        };
+        let change_policy = spec
+            .property_specs
+            .get(&property_name)
+            .map(util::change_policy);
+        let redact = |value: Option<&String>| match spec.is_sensitive(&property_name) {
+            true => value.map(|_| REDACTED.to_string()),
+            false => value.cloned(),
+        };
+        let change = ValueChange {
+            name: name.clone(),
+            old_value: redact(old_values.get(name)),
+            new_value: redact(new_values.get(name)),
+            change_policy,
+        };
+
+        diff.required_action = diff
+            .required_action
+            .max(change_policy.unwrap_or(ChangePolicy::None));
+        match (&change.old_value, &change.new_value) {
+            (None, _) => diff.added.push(change),
+            (_, None) => diff.removed.push(change),
+            _ => diff.changed.push(change),
+        }
+    }
+
+    Ok(diff)
+}
+
+/// Collect the differences between two property specs. The result is sorted by property name.
+///
+/// # Arguments
+///
+/// * `property_specs` - map with property name as key and the corresponding property spec as value (base)
+/// * `other_property_specs` - map with property name as key and the corresponding property spec as value
+///
+pub(crate) fn diff_property_specs(
+    property_specs: &HashMap<PropertyName, PropertySpec>,
+    other_property_specs: &HashMap<PropertyName, PropertySpec>,
+) -> SpecDiff {
+    let mut diff = SpecDiff::default();
+
+    let mut names: Vec<&PropertyName> = property_specs
+        .keys()
+        .chain(other_property_specs.keys())
+        .collect();
+    names.sort();
+    names.dedup();
+
+    for name in names {
+        let (spec, other) = match (property_specs.get(name), other_property_specs.get(name)) {
+            (None, None) => continue,
+            (None, Some(_)) => {
+                diff.added.push(name.clone());
+                continue;
+            }
+            (Some(_), None) => {
+                diff.removed.push(name.clone());
+                continue;
+            }
+            (Some(spec), Some(other)) => (spec, other),
+        };
+
+        if spec.datatype.without_bounds() != other.datatype.without_bounds() {
+            diff.changed.push(PropertySpecChange::Datatype {
+                property_name: name.clone(),
+                old: spec.datatype.clone(),
+                new: other.datatype.clone(),
+            });
+        } else if spec.datatype.bounds() != other.datatype.bounds() {
+            let (old_min, old_max) = spec.datatype.bounds();
+            let (new_min, new_max) = other.datatype.bounds();
+            diff.changed.push(PropertySpecChange::Bounds {
+                property_name: name.clone(),
+                old_min: old_min.cloned(),
+                old_max: old_max.cloned(),
+                new_min: new_min.cloned(),
+                new_max: new_max.cloned(),
+            });
+        }
+
+        if spec.default_values != other.default_values {
+            diff.changed.push(PropertySpecChange::DefaultValues {
+                property_name: name.clone(),
+                old: spec.default_values.clone(),
+                new: other.default_values.clone(),
+            });
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder, UnitBuilder};
+    use crate::diff::{PropertySpecChange, ValueChange, ValuesDiff};
+    use crate::types::{
+        ChangePolicy, Datatype, PropertyName, PropertyNameKind, PropertyValueSpec, Role,
+    };
+    use crate::ProductConfigSpec;
+    use std::collections::HashMap;
+
+    const CONF_FILE: &str = "my.config";
+    const PORT: &str = "http.port";
+    const HOST: &str = "http.host";
+    const THREADS: &str = "threads";
+
+    fn name(name: &str) -> PropertyName {
+        PropertyName {
+            name: name.to_string(),
+            kind: PropertyNameKind::Conf(CONF_FILE.to_string()),
+        }
+    }
+
+    fn integer(max: &str) -> Datatype {
+        Datatype::Integer {
+            min: Some("1".to_string()),
+            max: Some(max.to_string()),
+            unit: None,
+            accepted_units: None,
+            default_unit: None,
+            multiple_of: None,
+        }
+    }
+
+    fn spec(properties: Vec<(&str, Datatype, &str)>) -> ProductConfigSpec {
+        let mut builder =
+            ProductConfigSpecBuilder::new().unit(UnitBuilder::new("text", ".*").build());
+        for (property, datatype, default) in properties {
+            builder = builder.property_spec(
+                PropertySpecBuilder::new(datatype, "0.1.0")
+                    .property_name(name(property))
+                    .default_value(PropertyValueSpec {
+                        from_version: None,
+                        to_version: None,
+                        when: None,
+                        platform: None,
+                        value: default.to_string(),
+                    })
+                    .role(Role {
+                        name: "server".to_string(),
+                        required: false,
+                    })
+                    .build(),
+            );
+        }
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn test_diff() {
+        let text = Datatype::String {
+            min: None,
+            max: None,
+            unit: Some("text".to_string()),
+            accepted_units: None,
+            default_unit: None,
+            length_unit: None,
+        };
+
+        let old = spec(vec![
+            (PORT, integer("65535"), "8080"),
+            (THREADS, integer("16"), "4"),
+            ("removed", integer("10"), "1"),
+        ]);
+        let new = spec(vec![
+            (PORT, text, "8080"),
+            (THREADS, integer("32"), "8"),
+            (HOST, integer("10"), "1"),
+        ]);
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.added, vec![name(HOST)]);
+        assert_eq!(diff.removed, vec![name("removed")]);
+        assert_eq!(diff.changed.len(), 3);
+        assert!(matches!(
+            &diff.changed[0],
+            PropertySpecChange::Datatype { property_name, .. } if property_name == &name(PORT)
+        ));
+        assert_eq!(
+            diff.changed[1],
+            PropertySpecChange::Bounds {
+                property_name: name(THREADS),
+                old_min: Some("1".to_string()),
+                old_max: Some("16".to_string()),
+                new_min: Some("1".to_string()),
+                new_max: Some("32".to_string()),
+            }
+        );
+        assert!(matches!(
+            &diff.changed[2],
+            PropertySpecChange::DefaultValues { property_name, .. } if property_name == &name(THREADS)
+        ));
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn test_diff_values() {
+        let property = |property: &str, datatype: Datatype, change_policy: ChangePolicy| {
+            PropertySpecBuilder::new(datatype, "0.1.0")
+                .property_name(name(property))
+                .change_policy(change_policy)
+                .role(Role {
+                    name: "server".to_string(),
+                    required: false,
+                })
+                .build()
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(property(PORT, integer("65535"), ChangePolicy::Restart))
+            .property_spec(property(
+                THREADS,
+                integer("32"),
+                ChangePolicy::RollingRestart,
+            ))
+            .property_spec(property("debug", Datatype::Bool, ChangePolicy::None))
+            .property_spec(property(
+                "password",
+                Datatype::Password {
+                    min_length: None,
+                    required_classes: None,
+                    min_entropy: None,
+                },
+                ChangePolicy::None,
+            ))
+            .build()
+            .unwrap();
+        let values = |values: &[(&str, &str)]| -> HashMap<String, String> {
+            values
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        };
+        let change = |name: &str, old: Option<&str>, new: Option<&str>, policy| ValueChange {
+            name: name.to_string(),
+            old_value: old.map(str::to_string),
+            new_value: new.map(str::to_string),
+            change_policy: policy,
+        };
+
+        let diff = spec
+            .diff_values(
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                &values(&[
+                    (THREADS, "8"),
+                    ("debug", "true"),
+                    ("password", "secret"),
+                    ("custom", "1"),
+                ]),
+                &values(&[
+                    (PORT, "8080"),
+                    (THREADS, "16"),
+                    ("debug", " TRUE"),
+                    ("password", "changed"),
+                ]),
+                "0.1.0",
+            )
+            .unwrap();
+
+        assert_eq!(
+            diff,
+            ValuesDiff {
+                added: vec![change(
+                    PORT,
+                    None,
+                    Some("8080"),
+                    Some(ChangePolicy::Restart)
+                )],
+                removed: vec![change("custom", Some("1"), None, None)],
+                changed: vec![
+                    change(
+                        "password",
+                        Some("***"),
+                        Some("***"),
+                        Some(ChangePolicy::None)
+                    ),
+                    change(
+                        THREADS,
+                        Some("8"),
+                        Some("16"),
+                        Some(ChangePolicy::RollingRestart)
+                    ),
+                ],
+                required_action: ChangePolicy::Restart,
+            }
+        );
+        assert!(!diff.is_empty());
+    }
+}
@@ -0,0 +1,804 @@
+//! Generation of a configuration reference from the spec, e.g. to publish it alongside a product
+//! release instead of maintaining the docs by hand.
+//!
+//! The reference covers the properties available in one product version. Every option (property
+//! spec) gets a section with its names per kind, description, datatype and bounds, the default
+//! and recommended values of the version, deprecation notes and links to additional docs. It is
+//! rendered as Markdown page or as self-contained HTML page with client-side search. The command
+//! line options (properties of kind `cli`) can be rendered as man page as well.
+//!
+//! ```
+//! use product_config::builder::{ProductConfigSpecBuilder, PropertySpecBuilder};
+//! use product_config::docgen;
+//! use product_config::types::{Datatype, PropertyName, PropertyNameKind, Role};
+//!
+//! let spec = ProductConfigSpecBuilder::new()
+//!     .property_spec(
+//!         PropertySpecBuilder::new(Datatype::Bool, "1.0.0")
+//!             .property_name(PropertyName {
+//!                 name: "DEBUG".to_string(),
+//!                 kind: PropertyNameKind::Env,
+//!             })
+//!             .description("Enables *verbose* logging")
+//!             .role(Role {
+//!                 name: "server".to_string(),
+//!                 required: false,
+//!             })
+//!             .build(),
+//!     )
+//!     .build()
+//!     .unwrap();
+//!
+//! let reference = docgen::markdown(&spec, "1.2.0").unwrap();
+//!
+//! assert!(reference.starts_with("# Configuration reference (version 1.2.0)"));
+//! assert!(reference.contains("## `DEBUG`"));
+//! assert!(reference.contains("- **Datatype:** bool"));
+//! ```
+use crate::markdown;
+use crate::types::{
+    Datatype, PropertyName, PropertyNameKind, PropertySpec, PropertyValueSpec, Stability,
+};
+use crate::util;
+use crate::validation::ValidationResult;
+use crate::ProductConfigSpec;
+use semver::Version;
+
+/// Renders the configuration reference of the product version as Markdown page
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `product_version` - the product version to document the properties for
+///
+pub fn markdown(spec: &ProductConfigSpec, product_version: &str) -> ValidationResult<String> {
+    let version = Version::parse(product_version)?;
+    let mut page = format!("# Configuration reference (version {})\n", version);
+
+    for (names, property) in options(spec, &version)? {
+        page.push_str(&format!("\n## {}\n\n", code(&names[0].name)));
+
+        page.push_str("| Kind | Name |\n| --- | --- |\n");
+        for name in &names {
+            page.push_str(&format!(
+                "| {} | {} |\n",
+                kind_label(&name.kind),
+                code(&name.name)
+            ));
+        }
+
+        if let Some(description) = description(spec, property) {
+            page.push_str(&format!("\n{}\n", description.trim()));
+        }
+
+        page.push('\n');
+        for (label, value) in facts(property, &version)? {
+            page.push_str(&format!("- **{}:** {}\n", label, value));
+        }
+    }
+
+    Ok(page)
+}
+
+/// The default template of the HTML reference: a self-contained page with embedded styles and
+/// a search field that filters the options client-side. `{{title}}` is replaced by the (escaped)
+/// title and `{{content}}` by the sections of the options.
+pub const HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{{title}}</title>
+<style>
+body { font-family: sans-serif; max-width: 60em; margin: 0 auto; padding: 1em; }
+input[type=search] { width: 100%; padding: 0.5em; font-size: 1em; }
+section.option { border-top: 1px solid #ccc; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.2em 0.5em; text-align: left; }
+code { background: #f4f4f4; padding: 0 0.2em; }
+</style>
+</head>
+<body>
+<h1>{{title}}</h1>
+<input id="search" type="search" placeholder="Search options" autofocus>
+{{content}}
+<script>
+document.getElementById("search").addEventListener("input", function (event) {
+  var query = event.target.value.toLowerCase();
+  document.querySelectorAll("section.option").forEach(function (option) {
+    option.hidden = option.textContent.toLowerCase().indexOf(query) < 0;
+  });
+});
+</script>
+</body>
+</html>
+"#;
+
+/// Renders the configuration reference of the product version as self-contained HTML page
+/// (see `HTML_TEMPLATE`), e.g. to publish it from CI artifacts without docs pipeline
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `product_version` - the product version to document the properties for
+///
+pub fn html(spec: &ProductConfigSpec, product_version: &str) -> ValidationResult<String> {
+    html_with_template(spec, product_version, HTML_TEMPLATE)
+}
+
+/// Renders the configuration reference of the product version as HTML page using the provided
+/// template, e.g. to apply a corporate design. `{{title}}` in the template is replaced by the
+/// (escaped) title and `{{content}}` by one `<section class="option">` per option.
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `product_version` - the product version to document the properties for
+/// * `template` - the HTML page with `{{title}}` and `{{content}}` placeholders
+///
+pub fn html_with_template(
+    spec: &ProductConfigSpec,
+    product_version: &str,
+    template: &str,
+) -> ValidationResult<String> {
+    let version = Version::parse(product_version)?;
+    let mut content = String::new();
+
+    for (names, property) in options(spec, &version)? {
+        content.push_str(&format!(
+            "<section class=\"option\" id=\"{}\">\n<h2><code>{}</code></h2>\n",
+            markdown::escape(&kind_qualified_name(names[0])),
+            markdown::escape(&names[0].name)
+        ));
+
+        content.push_str("<table>\n<tr><th>Kind</th><th>Name</th></tr>\n");
+        for name in &names {
+            content.push_str(&format!(
+                "<tr><td>{}</td><td><code>{}</code></td></tr>\n",
+                markdown::inline_to_html(&kind_label(&name.kind)),
+                markdown::escape(&name.name)
+            ));
+        }
+        content.push_str("</table>\n");
+
+        if let Some(description) = description(spec, property) {
+            content.push_str(&markdown::to_html(description));
+            content.push('\n');
+        }
+
+        content.push_str("<ul class=\"facts\">\n");
+        for (label, value) in facts(property, &version)? {
+            content.push_str(&format!(
+                "<li><strong>{}:</strong> {}</li>\n",
+                label,
+                markdown::inline_to_html(&value)
+            ));
+        }
+        content.push_str("</ul>\n</section>\n");
+    }
+
+    let title = markdown::escape(&format!("Configuration reference (version {})", version));
+    // the content is inserted last, so placeholders within descriptions are kept as they are
+    Ok(template
+        .replace("{{title}}", &title)
+        .replace("{{content}}", content.trim_end()))
+}
+
+/// Renders the command line options (properties of kind `cli`) of the product version as man
+/// page (roff), e.g. for a CLI wrapping the product. Every option is listed with its value
+/// syntax, description, default and the versions it was introduced or deprecated in.
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `product_version` - the product version to document the options for
+/// * `command` - the name of the command, e.g. `kafka-server`
+///
+pub fn man_page(
+    spec: &ProductConfigSpec,
+    product_version: &str,
+    command: &str,
+) -> ValidationResult<String> {
+    let version = Version::parse(product_version)?;
+    let mut page = format!(
+        ".TH {} 1 \"\" \"{} {}\" \"Configuration\"\n.SH NAME\n{} \\- command line options\n.SH OPTIONS\n",
+        roff_escape(&command.to_uppercase()),
+        roff_escape(command),
+        version,
+        roff_escape(command)
+    );
+
+    let mut cli_options: Vec<(&PropertyName, &PropertySpec)> = options(spec, &version)?
+        .into_iter()
+        .filter_map(|(names, property)| {
+            let name = names
+                .into_iter()
+                .find(|name| name.kind == PropertyNameKind::Cli)?;
+            Some((name, property))
+        })
+        .collect();
+    cli_options.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+
+    for (name, property) in cli_options {
+        let flag = if name.name.starts_with('-') {
+            name.name.clone()
+        } else {
+            format!("--{}", name.name)
+        };
+        page.push_str(&format!(".TP\n\\fB{}\\fR", roff_escape(&flag)));
+        if let Some(syntax) = value_syntax(property) {
+            page.push_str(&format!(" \\fI{}\\fR", roff_escape(&syntax)));
+        }
+        page.push('\n');
+
+        let mut lines: Vec<String> = description(spec, property)
+            .map(|description| {
+                markdown::to_plain_text(description)
+                    .lines()
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        for (label, value) in facts(property, &version)? {
+            if label != "Datatype" {
+                lines.push(format!("{}: {}", label, markdown::to_plain_text(&value)));
+            }
+        }
+        // every fact on its own line, blank lines separate paragraphs of the description
+        let mut separator = None;
+        for line in lines {
+            if line.is_empty() {
+                separator = Some(".sp\n");
+                continue;
+            }
+            if let Some(separator) = separator {
+                page.push_str(separator);
+            }
+            page.push_str(&roff_escape(&line));
+            page.push('\n');
+            separator = Some(".br\n");
+        }
+    }
+
+    Ok(page)
+}
+
+/// Returns the syntax of the option value, e.g. `integer` or `plain|gzip` (None for flags)
+fn value_syntax(property: &PropertySpec) -> Option<String> {
+    let syntax = match property
+        .allowed_values
+        .as_deref()
+        .or_else(|| property.datatype.variants())
+    {
+        Some(values) => values.join("|"),
+        None if property.datatype == Datatype::Bool => return None,
+        None => datatype_name(property),
+    };
+
+    Some(match &property.list {
+        Some(list) => format!("{}[{}{}...]", syntax, list.delimiter, syntax),
+        None => syntax,
+    })
+}
+
+/// Returns the text escaped for roff: backslashes and hyphens are escaped and lines starting
+/// with a control character are protected
+fn roff_escape(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{}", escaped)
+    } else {
+        escaped
+    }
+}
+
+/// Returns the options (property specs) available in the product version with their names
+/// (sorted by kind), sorted by the first name
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `version` - the product version
+///
+pub(crate) fn options<'a>(
+    spec: &'a ProductConfigSpec,
+    version: &Version,
+) -> ValidationResult<Vec<(Vec<&'a PropertyName>, &'a PropertySpec)>> {
+    let mut options = vec![];
+    for entry in &spec.property_spec_list {
+        let mut names: Vec<&PropertyName> = entry.property_names.iter().collect();
+        names.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.name.cmp(&b.name)));
+        // the entries are kept as provided, the map contains the resolved (and merged) specs
+        let property = match names.first().and_then(|name| spec.property_specs.get(name)) {
+            Some(property) => property,
+            None => continue,
+        };
+        if util::is_available_in_version(property, version)? {
+            options.push((names, property));
+        }
+    }
+
+    options.sort_by(|(a, _), (b, _)| a[0].name.cmp(&b[0].name).then(a[0].kind.cmp(&b[0].kind)));
+    Ok(options)
+}
+
+/// Returns the description of the property in the default locale of the spec
+pub(crate) fn description<'a>(
+    spec: &'a ProductConfigSpec,
+    property: &'a PropertySpec,
+) -> Option<&'a str> {
+    let default_locale = spec.config_spec.default_locale.as_deref().unwrap_or("en");
+    property
+        .description
+        .as_ref()?
+        .text(default_locale, default_locale)
+}
+
+/// Returns the facts about the property (label and Markdown value) in the order they are listed
+///
+/// # Arguments
+///
+/// * `property` - the property spec
+/// * `version` - the product version (to select default and recommended values)
+///
+fn facts(
+    property: &PropertySpec,
+    version: &Version,
+) -> ValidationResult<Vec<(&'static str, String)>> {
+    let mut facts = vec![("Datatype", datatype(property))];
+
+    if let Some(list) = &property.list {
+        facts.push((
+            "List",
+            format!("items separated by {}", code(&list.delimiter)),
+        ));
+    }
+
+    for (label, values) in [
+        ("Default", &property.default_values),
+        ("Recommended", &property.recommended_values),
+    ] {
+        let values = values_for_version(values.as_deref().unwrap_or_default(), version)?;
+        if !values.is_empty() {
+            facts.push((label, values.join(", ")));
+        }
+    }
+
+    if let Some(range) = &property.recommended_range {
+        let mut bounds = bounds(range.min.as_ref(), range.max.as_ref());
+        if let Some(reason) = &range.reason {
+            bounds.push_str(&format!(" ({})", reason));
+        }
+        facts.push(("Recommended range", bounds));
+    }
+
+    if let Some(allowed_values) = property
+        .allowed_values
+        .as_deref()
+        .or_else(|| property.datatype.variants())
+    {
+        facts.push(("Allowed values", codes(allowed_values.iter())));
+    }
+
+    if let Some(platforms) = &property.platforms {
+        facts.push(("Platforms", codes(platforms.iter())));
+    }
+
+    match property.stability {
+        Some(stability) if stability != Stability::Stable => {
+            facts.push(("Stability", stability.to_string()))
+        }
+        _ => {}
+    }
+    if property.experimental == Some(true) {
+        facts.push(("Experimental", "yes".to_string()));
+    }
+
+    facts.push(("Available since", property.as_of_version.clone()));
+
+    if let Some(deprecated_since) = &property.deprecated_since {
+        let mut note = format!("since {}", deprecated_since);
+        let replacements: Vec<String> = property
+            .deprecated_for
+            .iter()
+            .flatten()
+            .filter_map(|names| names.first())
+            .map(|name| code(&name.name))
+            .collect();
+        if !replacements.is_empty() {
+            note.push_str(&format!(", use {} instead", replacements.join(" or ")));
+        }
+        facts.push(("Deprecated", note));
+    }
+
+    if let Some(removed_in) = &property.removed_in {
+        facts.push(("Removed in", removed_in.clone()));
+    }
+
+    if let Some(links) = &property.additional_doc {
+        let links: Vec<String> = links
+            .iter()
+            .map(|link| format!("[{}]({})", link.title, link.url))
+            .collect();
+        facts.push(("Links", links.join(", ")));
+    }
+
+    Ok(facts)
+}
+
+/// Returns the datatype with its bounds, e.g. `integer (min: 1, max: 10)`
+pub(crate) fn datatype(property: &PropertySpec) -> String {
+    let name = datatype_name(property);
+    match property.datatype.bounds() {
+        (None, None) => name,
+        (min, max) => format!("{} ({})", name, bounds(min, max)),
+    }
+}
+
+/// Returns the name of the datatype as used in the spec, e.g. `memorysize`
+fn datatype_name(property: &PropertySpec) -> String {
+    serde_json::to_value(&property.datatype)
+        .ok()
+        .and_then(|datatype| datatype["type"].as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn bounds(min: Option<&String>, max: Option<&String>) -> String {
+    min.map(|min| format!("min: {}", code(min)))
+        .into_iter()
+        .chain(max.map(|max| format!("max: {}", code(max))))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns the values that apply to the product version (as Markdown), noting the conditions
+/// and platforms they depend on. Like for validation, the first matching value wins if several
+/// values have the same condition and platform.
+///
+/// # Arguments
+///
+/// * `values` - the default or recommended values of the property
+/// * `version` - the product version
+///
+fn values_for_version(
+    values: &[PropertyValueSpec],
+    version: &Version,
+) -> ValidationResult<Vec<String>> {
+    let mut result = vec![];
+    let mut selectors = vec![];
+    for value in values {
+        if let Some(from) = &value.from_version {
+            if Version::parse(from)? > *version {
+                continue;
+            }
+        }
+        if let Some(to) = &value.to_version {
+            if Version::parse(to)? < *version {
+                continue;
+            }
+        }
+
+        if selectors.contains(&(&value.when, &value.platform)) {
+            continue;
+        }
+        selectors.push((&value.when, &value.platform));
+
+        let mut notes = vec![];
+        if let Some(condition) = &value.when {
+            let names = codes(condition.property_names.iter().map(|name| &name.name));
+            notes.push(match &condition.value {
+                Some(required) => format!("if {} is {}", names, code(required)),
+                None => format!("if {} is set", names),
+            });
+        }
+        if let Some(platform) = &value.platform {
+            notes.push(format!("on {}", platform));
+        }
+
+        result.push(if notes.is_empty() {
+            code(&value.value)
+        } else {
+            format!("{} ({})", code(&value.value), notes.join(", "))
+        });
+    }
+    Ok(result)
+}
+
+/// Returns the name prefixed with its kind (e.g. `env.DEBUG`), unique within the reference
+fn kind_qualified_name(name: &PropertyName) -> String {
+    match &name.kind {
+        PropertyNameKind::Conf(file) => format!("{}.{}", file, name.name),
+        PropertyNameKind::Env => format!("env.{}", name.name),
+        PropertyNameKind::Cli => format!("cli.{}", name.name),
+    }
+}
+
+fn kind_label(kind: &PropertyNameKind) -> String {
+    match kind {
+        PropertyNameKind::Conf(file) => format!("conf ({})", code(file)),
+        PropertyNameKind::Env => "env".to_string(),
+        PropertyNameKind::Cli => "cli".to_string(),
+    }
+}
+
+/// Returns the text as inline code (with a longer delimiter if it contains backticks)
+fn code(text: &str) -> String {
+    if text.is_empty() {
+        "*empty*".to_string()
+    } else if text.contains('`') {
+        format!("`` {} ``", text)
+    } else {
+        format!("`{}`", text)
+    }
+}
+
+fn codes<'a>(texts: impl Iterator<Item = &'a String>) -> String {
+    texts.map(|text| code(text)).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder};
+    use crate::docgen::{code, html, html_with_template, man_page, markdown, roff_escape};
+    use crate::types::{
+        Datatype, PropertyDependency, PropertyName, PropertyNameKind, PropertyValueSpec, Role,
+        Stability,
+    };
+    use rstest::*;
+
+    fn value(
+        value: &str,
+        from_version: Option<&str>,
+        when: Option<(&str, &str)>,
+    ) -> PropertyValueSpec {
+        PropertyValueSpec {
+            from_version: from_version.map(str::to_string),
+            to_version: None,
+            when: when.map(|(name, value)| PropertyDependency {
+                property_names: vec![conf(name)],
+                value: Some(value.to_string()),
+            }),
+            platform: None,
+            value: value.to_string(),
+        }
+    }
+
+    fn conf(name: &str) -> PropertyName {
+        PropertyName {
+            name: name.to_string(),
+            kind: PropertyNameKind::Conf("server.properties".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_markdown() {
+        let role = Role {
+            name: "broker".to_string(),
+            required: false,
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Integer {
+                        min: Some("1".to_string()),
+                        max: Some("10".to_string()),
+                        unit: None,
+                        accepted_units: None,
+                        default_unit: None,
+                        multiple_of: None,
+                    },
+                    "1.0.0",
+                )
+                .property_name(conf("num.threads"))
+                .property_name(PropertyName {
+                    name: "NUM_THREADS".to_string(),
+                    kind: PropertyNameKind::Env,
+                })
+                .default_value(value("2", None, None))
+                .default_value(value("4", Some("2.0.0"), None))
+                .recommended_value(value("8", None, Some(("ssl.enabled", "true"))))
+                .description("Number of **worker** threads")
+                .additional_doc("Tuning", "https://example.org/tuning")
+                .role(role.clone())
+                .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "1.0.0")
+                    .property_name(conf("ssl.enabled"))
+                    .role(role.clone())
+                    .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "1.0.0")
+                    .property_name(conf("legacy.mode"))
+                    .stability(Stability::Deprecated)
+                    .deprecated_since("1.5.0")
+                    .deprecated_for(vec![conf("ssl.enabled")])
+                    .removed_in("3.0.0")
+                    .role(role.clone())
+                    .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "2.5.0")
+                    .property_name(conf("later"))
+                    .role(role)
+                    .build(),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            markdown(&spec, "2.0.0").unwrap(),
+            "# Configuration reference (version 2.0.0)
+
+## `legacy.mode`
+
+| Kind | Name |
+| --- | --- |
+| conf (`server.properties`) | `legacy.mode` |
+
+- **Datatype:** bool
+- **Stability:** deprecated
+- **Available since:** 1.0.0
+- **Deprecated:** since 1.5.0, use `ssl.enabled` instead
+- **Removed in:** 3.0.0
+
+## `num.threads`
+
+| Kind | Name |
+| --- | --- |
+| conf (`server.properties`) | `num.threads` |
+| env | `NUM_THREADS` |
+
+Number of **worker** threads
+
+- **Datatype:** integer (min: `1`, max: `10`)
+- **Default:** `2`
+- **Recommended:** `8` (if `ssl.enabled` is `true`)
+- **Available since:** 1.0.0
+- **Links:** [Tuning](https://example.org/tuning)
+
+## `ssl.enabled`
+
+| Kind | Name |
+| --- | --- |
+| conf (`server.properties`) | `ssl.enabled` |
+
+- **Datatype:** bool
+- **Available since:** 1.0.0
+"
+        );
+    }
+
+    #[test]
+    fn test_html() {
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "1.0.0")
+                    .property_name(conf("ssl.enabled"))
+                    .property_name(PropertyName {
+                        name: "SSL_ENABLED".to_string(),
+                        kind: PropertyNameKind::Env,
+                    })
+                    .default_value(value("false", None, None))
+                    .description("Enables <b>TLS</b> for *all* listeners, see {{title}}")
+                    .role(Role {
+                        name: "broker".to_string(),
+                        required: false,
+                    })
+                    .build(),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            html_with_template(&spec, "1.0.0", "<h1>{{title}}</h1>\n{{content}}").unwrap(),
+            r#"<h1>Configuration reference (version 1.0.0)</h1>
+<section class="option" id="server.properties.ssl.enabled">
+<h2><code>ssl.enabled</code></h2>
+<table>
+<tr><th>Kind</th><th>Name</th></tr>
+<tr><td>conf (<code>server.properties</code>)</td><td><code>ssl.enabled</code></td></tr>
+<tr><td>env</td><td><code>SSL_ENABLED</code></td></tr>
+</table>
+<p>Enables &lt;b&gt;TLS&lt;/b&gt; for <em>all</em> listeners, see {{title}}</p>
+<ul class="facts">
+<li><strong>Datatype:</strong> bool</li>
+<li><strong>Default:</strong> <code>false</code></li>
+<li><strong>Available since:</strong> 1.0.0</li>
+</ul>
+</section>"#
+        );
+
+        let page = html(&spec, "1.0.0").unwrap();
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains("<title>Configuration reference (version 1.0.0)</title>"));
+        assert!(page.contains("<input id=\"search\" type=\"search\""));
+        assert!(page.contains("<section class=\"option\" id=\"server.properties.ssl.enabled\">"));
+    }
+
+    #[test]
+    fn test_man_page() {
+        let role = Role {
+            name: "broker".to_string(),
+            required: false,
+        };
+        let cli = |name: &str| PropertyName {
+            name: name.to_string(),
+            kind: PropertyNameKind::Cli,
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "1.0.0")
+                    .property_name(cli("verbose"))
+                    .description("Logs *more*.\n\nUse with care.")
+                    .role(role.clone())
+                    .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Enum {
+                        values: vec!["plain".to_string(), "gzip".to_string()],
+                        case_insensitive: false,
+                    },
+                    "1.2.0",
+                )
+                .property_name(conf("compression"))
+                .property_name(cli("--compression"))
+                .default_value(value("plain", None, None))
+                .deprecated_since("2.0.0")
+                .role(role.clone())
+                .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "1.0.0")
+                    .property_name(conf("not.on.cli"))
+                    .role(role)
+                    .build(),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            man_page(&spec, "2.0.0", "kafka-server").unwrap(),
+            r#".TH KAFKA\-SERVER 1 "" "kafka\-server 2.0.0" "Configuration"
+.SH NAME
+kafka\-server \- command line options
+.SH OPTIONS
+.TP
+\fB\-\-compression\fR \fIplain|gzip\fR
+Default: plain
+.br
+Allowed values: plain, gzip
+.br
+Available since: 1.2.0
+.br
+Deprecated: since 2.0.0
+.TP
+\fB\-\-verbose\fR
+Logs more.
+.sp
+Use with care.
+.br
+Available since: 1.0.0
+"#
+        );
+    }
+
+    #[rstest]
+    #[case("a-b", r"a\-b")]
+    #[case(r"C:\dir", r"C:\edir")]
+    #[case(".start", r"\&.start")]
+    #[trace]
+    fn test_roff_escape(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(roff_escape(text), expected);
+    }
+
+    #[rstest]
+    #[case("9092", "`9092`")]
+    #[case("a`b", "`` a`b ``")]
+    #[case("", "*empty*")]
+    #[trace]
+    fn test_code(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(code(text), expected);
+    }
+}
@@ -0,0 +1,190 @@
+//! Flattening of nested user config documents (e.g. written in YAML or JSON) to property names
+//! and values, e.g. `{"log": {"dirs": ["/a", "/b"]}}` to `log.dirs` with the value `/a,/b`.
+//!
+//! Keys of nested objects are joined via `.`. Arrays of scalars are values of list properties
+//! (joined via the delimiter of the list), other arrays are flattened with the index as key.
+//! Objects (and arrays) set for properties of datatype `json` are values as they are.
+use crate::types::{Datatype, PropertyName, PropertyNameKind};
+use crate::ProductConfigSpec;
+use serde_json::Value;
+
+/// A value of the document with the property name and the JSON pointer of the value
+#[derive(Debug, PartialEq)]
+pub(crate) struct Leaf {
+    pub name: String,
+    pub value: String,
+    pub pointer: String,
+}
+
+/// Returns the values of the document (`null` values are left out), sorted by key per object
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec (to find list and JSON properties)
+/// * `kind` - kind of the properties in the document (e.g. conf)
+/// * `document` - the user config document
+///
+pub(crate) fn flatten(
+    spec: &ProductConfigSpec,
+    kind: &PropertyNameKind,
+    document: &Value,
+) -> Vec<Leaf> {
+    let mut leaves = vec![];
+    flatten_value(spec, kind, None, "", document, &mut leaves);
+    leaves
+}
+
+/// Adds the leaves of the value to the leaves
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `kind` - kind of the properties in the document
+/// * `name` - the property name of the value (None for the document)
+/// * `pointer` - the JSON pointer of the value
+/// * `value` - the value
+/// * `leaves` - the leaves found so far
+///
+fn flatten_value(
+    spec: &ProductConfigSpec,
+    kind: &PropertyNameKind,
+    name: Option<&str>,
+    pointer: &str,
+    value: &Value,
+    leaves: &mut Vec<Leaf>,
+) {
+    let prefix = spec.config_spec.prefix(kind).unwrap_or_default();
+    let property = name.and_then(|name| {
+        spec.property_specs.get(&PropertyName {
+            name: name.strip_prefix(prefix).unwrap_or(name).to_string(),
+            kind: kind.clone(),
+        })
+    });
+    let json = property.is_some_and(|property| matches!(property.datatype, Datatype::Json { .. }));
+    let list = property.and_then(|property| property.list.as_ref());
+    let mut leaf = |value: String| {
+        leaves.push(Leaf {
+            name: name.unwrap_or_default().to_string(),
+            value,
+            pointer: pointer.to_string(),
+        })
+    };
+
+    match value {
+        Value::Null => {}
+        Value::String(text) => leaf(text.clone()),
+        Value::Bool(_) | Value::Number(_) => leaf(value.to_string()),
+        _ if json => leaf(value.to_string()),
+        Value::Array(items) if list.is_some() => {
+            let delimiter = list.map(|list| list.delimiter.as_str()).unwrap_or_default();
+            let items: Vec<String> = items
+                .iter()
+                .map(|item| match item {
+                    Value::String(text) => text.clone(),
+                    item => item.to_string(),
+                })
+                .collect();
+            leaf(items.join(delimiter))
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                let key = index.to_string();
+                flatten_entry(spec, kind, name, pointer, &key, item, leaves);
+            }
+        }
+        Value::Object(entries) => {
+            for (key, item) in entries {
+                flatten_entry(spec, kind, name, pointer, key, item, leaves);
+            }
+        }
+    }
+}
+
+/// Adds the leaves of an entry (of an object or array) to the leaves
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `kind` - kind of the properties in the document
+/// * `name` - the property name of the object or array (None for the document)
+/// * `pointer` - the JSON pointer of the object or array
+/// * `key` - the key (or index) of the entry
+/// * `value` - the value of the entry
+/// * `leaves` - the leaves found so far
+///
+fn flatten_entry(
+    spec: &ProductConfigSpec,
+    kind: &PropertyNameKind,
+    name: Option<&str>,
+    pointer: &str,
+    key: &str,
+    value: &Value,
+    leaves: &mut Vec<Leaf>,
+) {
+    let name = match name {
+        Some(name) => format!("{}.{}", name, key),
+        None => key.to_string(),
+    };
+    // `~` and `/` are escaped in JSON pointers (RFC 6901)
+    let pointer = format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+    flatten_value(spec, kind, Some(&name), &pointer, value, leaves);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder};
+    use crate::document::{flatten, Leaf};
+    use crate::types::{Datatype, ListSpec, PropertyName, PropertyNameKind, Role};
+
+    #[test]
+    fn test_flatten() {
+        let kind = PropertyNameKind::Conf("broker.yaml".to_string());
+        let property = |name: &str, datatype: Datatype| {
+            PropertySpecBuilder::new(datatype, "0.1.0")
+                .property_name(PropertyName {
+                    name: name.to_string(),
+                    kind: kind.clone(),
+                })
+                .role(Role {
+                    name: "broker".to_string(),
+                    required: false,
+                })
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                property("log.dirs", Datatype::Bool)
+                    .list(ListSpec {
+                        delimiter: ";".to_string(),
+                        ..ListSpec::default()
+                    })
+                    .build(),
+            )
+            .property_spec(property("client.config", Datatype::Json { schema: None }).build())
+            .build()
+            .unwrap();
+        let document = serde_json::json!({
+            "log": {"dirs": ["/a", "/b"], "retention": {"hours": 168}},
+            "client.config": {"acks": "all"},
+            "listeners": [{"port": 9092}, {"port": 9093}],
+            "a/b~c": true,
+            "unset": null
+        });
+
+        let leaf = |name: &str, value: &str, pointer: &str| Leaf {
+            name: name.to_string(),
+            value: value.to_string(),
+            pointer: pointer.to_string(),
+        };
+        assert_eq!(
+            flatten(&spec, &kind, &document),
+            vec![
+                leaf("a/b~c", "true", "/a~1b~0c"),
+                leaf("client.config", r#"{"acks":"all"}"#, "/client.config"),
+                leaf("listeners.0.port", "9092", "/listeners/0/port"),
+                leaf("listeners.1.port", "9093", "/listeners/1/port"),
+                leaf("log.dirs", "/a;/b", "/log/dirs"),
+                leaf("log.retention.hours", "168", "/log/retention/hours"),
+            ]
+        );
+    }
+}
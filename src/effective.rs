@@ -0,0 +1,445 @@
+//! The effective configuration of a product version: the values the product actually runs with
+//! per kind, combined from the user values, the defaults and the replacements of deprecated
+//! properties, with the source of every value.
+use crate::canonical;
+use crate::error::Error;
+use crate::types::{PropertyName, PropertyNameKind, PropertySpec};
+use crate::util;
+use crate::validation::ValidationResult;
+use crate::ProductConfigSpec;
+use semver::Version;
+use std::collections::{BTreeMap, HashMap};
+
+/// Where an effective value comes from
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValueSource {
+    /// The value was provided by the user
+    User,
+    /// The value is the default of the property
+    Default,
+    /// The value was provided by the user for a deprecated property (with the provided name)
+    /// that is replaced by this property
+    Migrated { from: String },
+}
+
+/// An effective value and its source
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EffectiveValue {
+    pub value: String,
+    /// the value in its canonical form (see `ProductConfigSpec::canonicalize`)
+    pub canonical: String,
+    pub source: ValueSource,
+    /// if the property is sensitive (e.g. a password)
+    pub sensitive: bool,
+}
+
+/// The effective values of a product version per kind
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EffectiveConfig {
+    /// the effective values per kind with the property name as key
+    pub values: BTreeMap<PropertyNameKind, BTreeMap<String, EffectiveValue>>,
+}
+
+impl EffectiveConfig {
+    /// Returns the effective value of the property (if any)
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the property (e.g. env, conf)
+    /// * `name` - name of the property
+    ///
+    pub fn get(&self, kind: &PropertyNameKind, name: &str) -> Option<&EffectiveValue> {
+        self.values.get(kind).and_then(|values| values.get(name))
+    }
+
+    /// Returns the effective values of the kind with the property name as key (e.g. to be written
+    /// via the functions of the `writer` module)
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the properties (e.g. env, conf)
+    ///
+    pub fn values(&self, kind: &PropertyNameKind) -> HashMap<String, String> {
+        self.values
+            .get(kind)
+            .iter()
+            .flat_map(|values| values.iter())
+            .map(|(name, value)| (name.clone(), value.value.clone()))
+            .collect()
+    }
+
+    /// Returns a stable fingerprint (64 bit FNV-1a hash as hex string) of the canonical values,
+    /// e.g. to decide if a restart is required after reconciliation. Values of sensitive
+    /// properties are left out (only their names are included), as is the source of values, so a
+    /// value set by the user that equals the default does not change the fingerprint.
+    pub fn fingerprint(&self) -> String {
+        // offset basis and prime of the 64 bit FNV-1a hash
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut write = |bytes: &[u8]| {
+            for byte in bytes {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+
+        for (kind, values) in &self.values {
+            let kind = match kind {
+                PropertyNameKind::Conf(file) => format!("conf:{}", file),
+                PropertyNameKind::Env => "env".to_string(),
+                PropertyNameKind::Cli => "cli".to_string(),
+            };
+            for (name, value) in values {
+                // fields are terminated by a byte that does not occur in UTF-8 text
+                for field in [&kind, name] {
+                    write(field.as_bytes());
+                    write(&[0xff]);
+                }
+                if !value.sensitive {
+                    write(value.canonical.as_bytes());
+                }
+                write(&[0xfe]);
+            }
+        }
+
+        format!("{:016x}", hash)
+    }
+}
+
+/// Returns the effective config of the product version. Values may be keyed by any name of a
+/// property and apply to all its names (of all kinds); names unknown to the spec are left out.
+/// Values of deprecated properties with a single replacement are migrated to the replacement
+/// (unless it is set by the user), all other properties available in the version get their
+/// default (if any).
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `user_values` - map with property name (of any kind) and value
+/// * `product_version` - the product version
+///
+pub(crate) fn effective_config(
+    spec: &ProductConfigSpec,
+    user_values: &HashMap<String, String>,
+    product_version: &Version,
+) -> ValidationResult<EffectiveConfig> {
+    // values of deprecated properties via the names of their replacement
+    let mut migrated: HashMap<&PropertyName, (&String, &String)> = HashMap::new();
+    for property in spec.property_specs.values() {
+        let (from, value) = match user_value(property, None, user_values) {
+            Some(user_value) => user_value,
+            None => continue,
+        };
+        let replacement = match (
+            &property.deprecated_since,
+            property.deprecated_for.as_deref(),
+        ) {
+            (Some(deprecated_since), Some([replacement]))
+                if Version::parse(deprecated_since)? <= *product_version =>
+            {
+                replacement
+            }
+            _ => continue,
+        };
+        let replaced = replacement.iter().any(|name| {
+            spec.property_specs
+                .get(name)
+                .is_some_and(|replacement| user_value(replacement, None, user_values).is_none())
+        });
+        if replaced {
+            for name in replacement {
+                migrated.entry(name).or_insert((from, value));
+            }
+        }
+    }
+
+    let mut config = EffectiveConfig::default();
+    for (property_name, property) in &spec.property_specs {
+        if !util::is_available_in_version(property, product_version)? {
+            continue;
+        }
+
+        let effective = if let Some((from, value)) = migrated.get(property_name) {
+            Some((
+                value.to_string(),
+                ValueSource::Migrated {
+                    from: from.to_string(),
+                },
+            ))
+        } else if let Some((from, value)) = user_value(property, Some(property_name), user_values) {
+            // the value is used for the replacement if migrated
+            let replaced = migrated
+                .values()
+                .any(|(migrated_from, _)| *migrated_from == from);
+            (!replaced).then(|| (value.clone(), ValueSource::User))
+        } else {
+            default_value(spec, property_name, property, user_values, product_version)?
+                .map(|value| (value, ValueSource::Default))
+        };
+
+        if let Some((value, source)) = effective {
+            config
+                .values
+                .entry(property_name.kind.clone())
+                .or_default()
+                .insert(
+                    property_name.name.clone(),
+                    EffectiveValue {
+                        canonical: canonical::canonical_value(spec, property, &value),
+                        value,
+                        source,
+                        sensitive: spec.is_sensitive(property_name),
+                    },
+                );
+        }
+    }
+
+    Ok(config)
+}
+
+/// Returns the name and value the user provided for the property: the value for the provided
+/// name takes precedence over the values for other names of the property
+///
+/// # Arguments
+///
+/// * `property` - the property spec
+/// * `property_name` - the preferred name of the property
+/// * `user_values` - map with property name (of any kind) and value
+///
+fn user_value<'a>(
+    property: &PropertySpec,
+    property_name: Option<&PropertyName>,
+    user_values: &'a HashMap<String, String>,
+) -> Option<(&'a String, &'a String)> {
+    property_name
+        .into_iter()
+        .chain(&property.property_names)
+        .find_map(|name| user_values.get_key_value(&name.name))
+}
+
+/// Returns the default value of the property for the version and the platform of the spec;
+/// conditional defaults are selected via the user values
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `property_name` - name of the property
+/// * `property` - the property spec
+/// * `user_values` - map with property name and value
+/// * `product_version` - the product version
+///
+pub(crate) fn default_value(
+    spec: &ProductConfigSpec,
+    property_name: &PropertyName,
+    property: &PropertySpec,
+    user_values: &HashMap<String, String>,
+    product_version: &Version,
+) -> ValidationResult<Option<String>> {
+    let values = match &property.default_values {
+        Some(values) => values,
+        None => return Ok(None),
+    };
+    match util::get_property_value_for_version(
+        property_name,
+        values,
+        product_version,
+        Some(user_values),
+        Some(&spec.platform),
+    ) {
+        Ok(default) => Ok(Some(default.value)),
+        Err(Error::PropertySpecValueMissingForVersion { .. }) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder};
+    use crate::effective::{EffectiveValue, ValueSource};
+    use crate::types::{Datatype, PropertyName, PropertyNameKind, PropertyValueSpec, Role};
+    use crate::ProductConfigSpec;
+    use rstest::*;
+    use std::collections::HashMap;
+
+    fn names(conf: &str, env: &str) -> Vec<PropertyName> {
+        vec![
+            PropertyName {
+                name: conf.to_string(),
+                kind: PropertyNameKind::Conf("server.properties".to_string()),
+            },
+            PropertyName {
+                name: env.to_string(),
+                kind: PropertyNameKind::Env,
+            },
+        ]
+    }
+
+    fn spec() -> ProductConfigSpec {
+        let property = |names: Vec<PropertyName>, default: Option<&str>| {
+            let mut builder = PropertySpecBuilder::new(Datatype::Bool, "1.0.0").role(Role {
+                name: "broker".to_string(),
+                required: false,
+            });
+            for name in names {
+                builder = builder.property_name(name);
+            }
+            if let Some(default) = default {
+                builder = builder.default_value(PropertyValueSpec {
+                    from_version: None,
+                    to_version: None,
+                    when: None,
+                    platform: None,
+                    value: default.to_string(),
+                });
+            }
+            builder
+        };
+
+        ProductConfigSpecBuilder::new()
+            .property_spec(property(names("ssl.enabled", "SSL_ENABLED"), Some("false")).build())
+            .property_spec(property(names("tls.enabled", "TLS_ENABLED"), None).build())
+            .property_spec(
+                property(names("ssl.on", "SSL_ON"), None)
+                    .deprecated_since("2.0.0")
+                    .deprecated_for(names("tls.enabled", "TLS_ENABLED"))
+                    .build(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    fn user(value: &str) -> EffectiveValue {
+        EffectiveValue {
+            value: value.to_string(),
+            canonical: value.to_string(),
+            source: ValueSource::User,
+            sensitive: false,
+        }
+    }
+
+    fn default(value: &str) -> EffectiveValue {
+        EffectiveValue {
+            value: value.to_string(),
+            canonical: value.to_string(),
+            source: ValueSource::Default,
+            sensitive: false,
+        }
+    }
+
+    fn migrated(value: &str, from: &str) -> EffectiveValue {
+        EffectiveValue {
+            value: value.to_string(),
+            canonical: value.to_string(),
+            source: ValueSource::Migrated {
+                from: from.to_string(),
+            },
+            sensitive: false,
+        }
+    }
+
+    #[rstest]
+    #[case("1.0.0", &[("SSL_ON", "true")], vec![
+        ("SSL_ENABLED", default("false")),
+        ("SSL_ON", user("true")),
+    ])]
+    #[case("2.0.0", &[("SSL_ON", "true")], vec![
+        ("SSL_ENABLED", default("false")),
+        ("TLS_ENABLED", migrated("true", "SSL_ON")),
+    ])]
+    #[case("2.0.0", &[("ssl.on", "true"), ("TLS_ENABLED", "false"), ("ssl.enabled", "true")], vec![
+        ("SSL_ENABLED", user("true")),
+        ("SSL_ON", user("true")),
+        ("TLS_ENABLED", user("false")),
+    ])]
+    #[trace]
+    fn test_effective_config(
+        #[case] version: &str,
+        #[case] user_values: &[(&str, &str)],
+        #[case] expected_env: Vec<(&str, EffectiveValue)>,
+    ) {
+        let user_values: HashMap<String, String> = user_values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        let config = spec().effective_config(&user_values, version).unwrap();
+
+        assert_eq!(
+            config.values[&PropertyNameKind::Env]
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.clone()))
+                .collect::<Vec<_>>(),
+            expected_env
+        );
+        // values apply to all names of a property
+        assert_eq!(
+            config
+                .get(
+                    &PropertyNameKind::Conf("server.properties".to_string()),
+                    "ssl.enabled"
+                )
+                .map(|value| value.value.as_str()),
+            config
+                .get(&PropertyNameKind::Env, "SSL_ENABLED")
+                .map(|value| value.value.as_str()),
+        );
+    }
+
+    #[rstest]
+    #[case(&[("SSL_ENABLED", "true")], &[("ssl.enabled", " TRUE ")], true)]
+    #[case(&[("SSL_ENABLED", "false")], &[], true)]
+    #[case(&[("SSL_PASSWORD", "secret")], &[("SSL_PASSWORD", "changed")], true)]
+    #[case(&[("SSL_ENABLED", "true")], &[], false)]
+    #[case(&[], &[("SSL_PASSWORD", "secret")], false)]
+    #[trace]
+    fn test_fingerprint(
+        #[case] values: &[(&str, &str)],
+        #[case] other_values: &[(&str, &str)],
+        #[case] equal: bool,
+    ) {
+        let role = Role {
+            name: "broker".to_string(),
+            required: false,
+        };
+        let mut ssl_enabled = PropertySpecBuilder::new(Datatype::Bool, "1.0.0")
+            .default_value(PropertyValueSpec {
+                from_version: None,
+                to_version: None,
+                when: None,
+                platform: None,
+                value: "false".to_string(),
+            })
+            .role(role.clone());
+        for name in names("ssl.enabled", "SSL_ENABLED") {
+            ssl_enabled = ssl_enabled.property_name(name);
+        }
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(ssl_enabled.build())
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Password {
+                        min_length: None,
+                        required_classes: None,
+                        min_entropy: None,
+                    },
+                    "1.0.0",
+                )
+                .property_name(names("ssl.password", "SSL_PASSWORD").remove(1))
+                .role(role)
+                .build(),
+            )
+            .build()
+            .unwrap();
+        let fingerprint = |values: &[(&str, &str)]| {
+            let values: HashMap<String, String> = values
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect();
+            spec.effective_config(&values, "1.0.0")
+                .unwrap()
+                .fingerprint()
+        };
+
+        assert_eq!(fingerprint(values).len(), 16);
+        assert_eq!(fingerprint(values) == fingerprint(other_values), equal);
+    }
+}
@@ -1,5 +1,5 @@
-use crate::types::{PropertyDependency, PropertyValueSpec};
-use crate::PropertyName;
+use crate::types::{LengthUnit, PropertyDependency, PropertyValueSpec, Stability};
+use crate::{ConfigProblem, PropertyName};
 
 #[derive(thiserror::Error, Clone, Debug, PartialOrd, PartialEq)]
 pub enum Error {
@@ -9,12 +9,21 @@ pub enum Error {
     #[error("could not parse file: {file_name}: {reason}")]
     FileNotParsable { file_name: String, reason: String },
 
+    #[error("could not write file: {file_name}: {reason}")]
+    FileNotWritable { file_name: String, reason: String },
+
     #[error("Invalid sem version: {source}")]
     InvalidVersion {
         #[from]
         source: semver::SemVerError,
     },
 
+    #[error("Invalid version range: '{from_version}' is greater than '{to_version}'")]
+    InvalidVersionRange {
+        from_version: String,
+        to_version: String,
+    },
+
     #[error("[{property_name}]: current product version is '{product_version}' -> property not supported; available from version '{required_version}'")]
     VersionNotSupported {
         property_name: PropertyName,
@@ -29,12 +38,167 @@ pub enum Error {
         deprecated_version: String,
     },
 
+    #[error("[{property_name}]: current product version is '{product_version}' -> property removed in version '{removed_version}'; replaced by: {replaced_by:?}")]
+    VersionRemoved {
+        property_name: PropertyName,
+        product_version: String,
+        removed_version: String,
+        replaced_by: Vec<String>,
+    },
+
+    #[error("[{property_name}]: property stability '{stability}' is not allowed by the validation policy")]
+    StabilityNotAllowed {
+        property_name: PropertyName,
+        stability: Stability,
+    },
+
+    #[error("[{property_name}]: property is experimental and experimental properties are not allowed by the validation policy")]
+    ExperimentalPropertyNotAllowed { property_name: PropertyName },
+
+    #[error("[{property_name}]: property is not supported on platform '{platform}' (only on {platforms:?})")]
+    PropertyNotSupportedOnPlatform {
+        property_name: PropertyName,
+        platform: String,
+        platforms: Vec<String>,
+    },
+
+    #[error("[{property_name}]: property is immutable and cannot be changed from '{old_value}' to '{new_value}'")]
+    ImmutablePropertyChanged {
+        property_name: PropertyName,
+        old_value: String,
+        new_value: String,
+    },
+
+    #[error("[{property_name}]: property must not be set together with conflicting property '{conflicting_property}'")]
+    PropertyConflict {
+        property_name: PropertyName,
+        conflicting_property: String,
+    },
+
+    #[error("Invalid rule '{rule}': {reason}")]
+    InvalidRule { rule: String, reason: String },
+
+    #[error("[{property_name}]: violates rule '{rule}'")]
+    RuleViolated {
+        property_name: PropertyName,
+        rule: String,
+    },
+
+    #[error("[{property_name}]: custom validator '{validator}' is not registered")]
+    CustomValidatorNotFound {
+        property_name: PropertyName,
+        validator: String,
+    },
+
+    #[error("[{property_name}]: provided value '{value}' rejected by custom validator '{validator}': {reason}")]
+    CustomValidationFailed {
+        property_name: PropertyName,
+        value: String,
+        validator: String,
+        reason: String,
+    },
+
+    #[error("[{property_name}]: provided value '{value}' rejected by hook: {reason}")]
+    HookFailed {
+        property_name: PropertyName,
+        value: String,
+        reason: String,
+    },
+
+    #[error("[{property_name}]: provided value '{value}' reported by hook: {reason}")]
+    HookWarning {
+        property_name: PropertyName,
+        value: String,
+        reason: String,
+    },
+
+    #[error("[{property_name}]: provided value '{value}' is not a multiple of '{multiple_of}'")]
+    PropertyValueNotMultipleOf {
+        property_name: PropertyName,
+        value: String,
+        multiple_of: String,
+    },
+
+    #[error("[{property_name}]: provided value '{value}' has length {length} ({length_unit}) which violates min/max length '{expected}'")]
+    StringLengthOutOfBounds {
+        property_name: PropertyName,
+        value: String,
+        length: usize,
+        length_unit: LengthUnit,
+        expected: String,
+    },
+
+    #[error("[{property_name}]: non finite float value '{value}' is not allowed")]
+    NonFiniteFloatNotAllowed {
+        property_name: PropertyName,
+        value: String,
+    },
+
+    #[error("[{property_name}]: privileged port '{port}' (below 1024) is not allowed")]
+    PrivilegedPortNotAllowed {
+        property_name: PropertyName,
+        port: u16,
+    },
+
+    #[error("[{property_name}]: provided value '{value}' is not a valid url: {reason}")]
+    InvalidUrl {
+        property_name: PropertyName,
+        value: String,
+        reason: String,
+    },
+
+    #[error("[{property_name}]: provided value '{value}' is not a valid path: {reason}")]
+    InvalidPath {
+        property_name: PropertyName,
+        value: String,
+        reason: String,
+    },
+
+    #[error("[{property_name}]: provided value '{value}' is not a valid regex: {reason}")]
+    InvalidRegex {
+        property_name: PropertyName,
+        value: String,
+        reason: String,
+    },
+
+    #[error("[{property_name}]: provided value '{value}' is not valid json: {reason}")]
+    InvalidJson {
+        property_name: PropertyName,
+        value: String,
+        reason: String,
+    },
+
+    #[error("[{property_name}]: provided value '{value}' exceeds decimal precision: {reason}")]
+    DecimalPrecisionExceeded {
+        property_name: PropertyName,
+        value: String,
+        reason: String,
+    },
+
+    #[error(
+        "[{property_name}]: validating datatype '{datatype}' requires the '{feature}' feature"
+    )]
+    DatatypeFeatureNotEnabled {
+        property_name: PropertyName,
+        datatype: String,
+        feature: String,
+    },
+
     #[error("Required config spec property not found: '{name}'")]
     ConfigSpecPropertiesNotFound { name: String },
 
     #[error("No config property found that matches '{property_name}'")]
     PropertyNotFound { property_name: PropertyName },
 
+    #[error("[{property_name}]: required property is missing and has no default")]
+    RequiredPropertyMissing { property_name: PropertyName },
+
+    #[error("[{path}]: could not deserialize value: {reason}")]
+    DeserializationFailed { path: String, reason: String },
+
+    #[error("user config is invalid: {} problem(s)", .problems.len())]
+    UserConfigInvalid { problems: Vec<ConfigProblem> },
+
     #[error("No roles in '{name}' match the provided role: '{role}'")]
     PropertySpecRoleNotFound { name: PropertyName, role: String },
 
@@ -44,13 +208,27 @@ pub enum Error {
     #[error("No role was provided by user for '{name}' ")]
     PropertySpecRoleNotProvidedByUser { name: PropertyName },
 
-    #[error("[{0}]: provided value '{received}' violates min/max bound '{expected}'")]
+    #[error("[{property_name}]: password is too weak: {}", reasons.join("; "))]
+    PasswordTooWeak {
+        property_name: PropertyName,
+        reasons: Vec<String>,
+    },
+
+    #[error("[{property_name}]: provided value '{received}' violates min/max bound '{expected}'")]
     PropertyValueOutOfBounds {
         property_name: PropertyName,
         received: String,
         expected: String,
     },
 
+    #[error("[{property_name}]: provided value '{received}' ({received_in_unit}) violates min/max bound '{expected}'")]
+    PropertyValueOutOfBoundsInUnit {
+        property_name: PropertyName,
+        received: String,
+        received_in_unit: String,
+        expected: String,
+    },
+
     #[error("[{property_name}]: provided config value missing")]
     PropertyValueMissing { property_name: PropertyName },
 
@@ -68,6 +246,52 @@ pub enum Error {
         allowed_values: Vec<String>,
     },
 
+    #[error("[{property_name}]: value '{value}' neither in allowed values {allowed_values:?} nor matching allowed patterns {allowed_pattern:?}")]
+    PropertyValueNotMatchingAllowedPattern {
+        property_name: PropertyName,
+        value: String,
+        allowed_values: Vec<String>,
+        allowed_pattern: Vec<String>,
+    },
+
+    #[error("[{property_name}]: value '{value}' is outside of the recommended range '{recommended_range}': {reason}")]
+    PropertyValueNotRecommended {
+        property_name: PropertyName,
+        value: String,
+        recommended_range: String,
+        reason: String,
+    },
+
+    #[error(
+        "[{property_name}]: list value '{value}' has {count} items which violates '{expected}'"
+    )]
+    ListItemCountOutOfBounds {
+        property_name: PropertyName,
+        value: String,
+        count: usize,
+        expected: String,
+    },
+
+    #[error("[{property_name}]: list value '{value}' contains item '{item}' more than once")]
+    ListItemNotUnique {
+        property_name: PropertyName,
+        value: String,
+        item: String,
+    },
+
+    #[error("[{property_name}]: value '{value}' is forbidden")]
+    PropertyValueForbidden {
+        property_name: PropertyName,
+        value: String,
+    },
+
+    #[error("[{property_name}]: invalid allowed pattern '{pattern}': {reason}")]
+    InvalidAllowedPattern {
+        property_name: PropertyName,
+        pattern: String,
+        reason: String,
+    },
+
     #[error("[{property_name}]: value '{value}' not of specified type: '{datatype}'")]
     DatatypeNotMatching {
         property_name: PropertyName,
@@ -87,9 +311,81 @@ pub enum Error {
     #[error("Invalid regex pattern for unit '{unit}': '{regex}'")]
     InvalidRegexPattern { unit: String, regex: String },
 
+    #[error(
+        "Regex pattern for unit '{unit}' is not anchored and may match parts of a value: '{regex}'"
+    )]
+    UnitRegexNotAnchored { unit: String, regex: String },
+
+    #[error("[{property_name}]: unit '{unit}' of value '{value}' not in accepted units: {accepted_units:?}")]
+    UnitNotAccepted {
+        property_name: PropertyName,
+        value: String,
+        unit: String,
+        accepted_units: Vec<String>,
+    },
+
+    #[error("[{property_name}]: unit '{unit}' cannot be converted to '{target_unit}'")]
+    UnitConversionNotSupported {
+        property_name: PropertyName,
+        unit: String,
+        target_unit: String,
+    },
+
     #[error("[{property_name}]: unit not provided")]
     UnitNotProvided { property_name: PropertyName },
 
+    #[error("[{property_name}]: placeholder '${{{reference}}}' references a property that is not set or cannot be resolved")]
+    PlaceholderNotResolved {
+        property_name: PropertyName,
+        reference: String,
+    },
+
+    #[error("[{property_name}]: secret reference '{reference}' cannot be resolved: {reason}")]
+    SecretNotResolved {
+        property_name: PropertyName,
+        reference: String,
+        reason: String,
+    },
+
+    #[error("[{property_name}]: environment variable '{variable}' is not set")]
+    EnvVarNotSet {
+        property_name: PropertyName,
+        variable: String,
+    },
+
+    #[error("[{property_name}]: placeholders reference each other in a cycle: {cycle:?}")]
+    PlaceholderCycle {
+        property_name: PropertyName,
+        cycle: Vec<String>,
+    },
+
+    #[error("[{property_name}]: datatype '{name}' not found in the types of the config spec")]
+    DatatypeDefinitionNotFound {
+        property_name: PropertyName,
+        name: String,
+    },
+
+    #[error(
+        "[{property_name}]: value set '{name}' not found in the value sets of the config spec"
+    )]
+    ValueSetNotFound {
+        property_name: PropertyName,
+        name: String,
+    },
+
+    #[error("[{property_name}]: additional doc link '{url}' is not valid: {reason}")]
+    InvalidDocLink {
+        property_name: PropertyName,
+        url: String,
+        reason: String,
+    },
+
+    #[error("[{property_name}]: group '{group}' not found in the groups of the config spec")]
+    PropertyGroupNotFound {
+        property_name: PropertyName,
+        group: String,
+    },
+
     #[error("[{property_name}]: unit '{unit}' not found in settings")]
     UnitSettingNotFound {
         property_name: PropertyName,
@@ -130,9 +426,75 @@ pub enum Error {
         required_value: String,
     },
 
+    #[error("[{property_name}]: property spec differs in the specs to be merged")]
+    PropertySpecMergeConflict { property_name: PropertyName },
+
+    #[error("Unit '{unit}' differs in the specs to be merged")]
+    UnitMergeConflict { unit: String },
+
+    #[error("Datatype definition '{name}' differs in the specs to be merged")]
+    DatatypeDefinitionMergeConflict { name: String },
+
+    #[error("Value set '{name}' differs in the specs to be merged")]
+    ValueSetMergeConflict { name: String },
+
     #[error("[{property_name}]: no provided or recommended values in dependency '{dependency:?}'")]
     PropertyDependencyValueMissing {
         property_name: PropertyName,
         dependency: PropertyDependency,
     },
 }
+
+/// Replacement for the values of sensitive properties in errors
+pub const REDACTED: &str = "***";
+
+impl Error {
+    /// Returns the error with the value of the property replaced by `***`, e.g. for passwords
+    /// that must not show up in logs
+    pub fn redacted(mut self) -> Self {
+        match &mut self {
+            Error::ImmutablePropertyChanged {
+                old_value,
+                new_value,
+                ..
+            } => {
+                *old_value = REDACTED.to_string();
+                *new_value = REDACTED.to_string();
+            }
+            Error::PropertyValueOutOfBounds { received, .. } => *received = REDACTED.to_string(),
+            Error::PropertyValueOutOfBoundsInUnit {
+                received,
+                received_in_unit,
+                ..
+            } => {
+                *received = REDACTED.to_string();
+                *received_in_unit = REDACTED.to_string();
+            }
+            Error::ListItemNotUnique { value, item, .. } => {
+                *value = REDACTED.to_string();
+                *item = REDACTED.to_string();
+            }
+            Error::CustomValidationFailed { value, .. }
+            | Error::HookFailed { value, .. }
+            | Error::HookWarning { value, .. }
+            | Error::PropertyValueNotMultipleOf { value, .. }
+            | Error::StringLengthOutOfBounds { value, .. }
+            | Error::NonFiniteFloatNotAllowed { value, .. }
+            | Error::InvalidUrl { value, .. }
+            | Error::InvalidPath { value, .. }
+            | Error::InvalidRegex { value, .. }
+            | Error::InvalidJson { value, .. }
+            | Error::DecimalPrecisionExceeded { value, .. }
+            | Error::PropertyValueNotInAllowedValues { value, .. }
+            | Error::PropertyValueNotMatchingAllowedPattern { value, .. }
+            | Error::PropertyValueNotRecommended { value, .. }
+            | Error::ListItemCountOutOfBounds { value, .. }
+            | Error::PropertyValueForbidden { value, .. }
+            | Error::DatatypeNotMatching { value, .. }
+            | Error::DatatypeRegexNotMatching { value, .. }
+            | Error::UnitNotAccepted { value, .. } => *value = REDACTED.to_string(),
+            _ => {}
+        }
+        self
+    }
+}
@@ -0,0 +1,313 @@
+//! Placeholders in property values that reference the values of other properties (of the same
+//! kind), e.g. `log.dirs=${data.dir}/logs`.
+//!
+//! Placeholders are resolved recursively before the values are validated. A reference to a
+//! property that is not set or a cycle of references results in an error for the property.
+//! A literal `${` is written as `$${`.
+//!
+//! If enabled via `ValidationPolicy::env_substitution`, references to environment variables
+//! (`${env:HOSTNAME}` or `$HOSTNAME`) are expanded as well; a literal `$` is then written as `$$`.
+use crate::error::Error;
+use crate::types::{MissingEnvVar, PropertyName, PropertyNameKind};
+use std::collections::HashMap;
+use std::env;
+
+/// Returns the properties with all placeholders resolved or the error if a placeholder cannot
+/// be resolved
+///
+/// # Arguments
+///
+/// * `kind` - the kind of the properties (for error messages)
+/// * `properties` - map with property name and (unresolved) values
+/// * `env_substitution` - how missing environment variables are handled (None if environment
+///   variables are not expanded)
+///
+pub(crate) fn interpolate(
+    kind: &PropertyNameKind,
+    properties: &HashMap<String, String>,
+    env_substitution: Option<MissingEnvVar>,
+) -> HashMap<String, Result<String, Error>> {
+    let mut resolved = HashMap::new();
+    for name in properties.keys() {
+        // the result is stored in `resolved`
+        let _ = resolve(
+            kind,
+            properties,
+            env_substitution,
+            name,
+            &mut resolved,
+            &mut Vec::new(),
+        );
+    }
+    resolved
+}
+
+/// Resolves the value of the property (and all properties it references) and stores the
+/// result in `resolved`
+///
+/// # Arguments
+///
+/// * `kind` - the kind of the properties (for error messages)
+/// * `properties` - map with property name and (unresolved) values
+/// * `env_substitution` - how missing environment variables are handled (None if environment
+///   variables are not expanded)
+/// * `name` - the name of the property to be resolved
+/// * `resolved` - the already resolved properties
+/// * `stack` - the properties currently being resolved (to detect cycles)
+///
+fn resolve(
+    kind: &PropertyNameKind,
+    properties: &HashMap<String, String>,
+    env_substitution: Option<MissingEnvVar>,
+    name: &str,
+    resolved: &mut HashMap<String, Result<String, Error>>,
+    stack: &mut Vec<String>,
+) -> Result<String, Error> {
+    if let Some(result) = resolved.get(name) {
+        return result.clone();
+    }
+
+    let property_name = PropertyName {
+        name: name.to_string(),
+        kind: kind.clone(),
+    };
+
+    stack.push(name.to_string());
+    let mut result = Ok(String::new());
+    for part in parse(&properties[name], env_substitution.is_some()) {
+        let value = match part {
+            Part::Literal(literal) => Ok(literal.to_string()),
+            Part::EnvVar(variable, literal) => match (env::var(variable), env_substitution) {
+                (Ok(value), _) => Ok(value),
+                (Err(_), Some(MissingEnvVar::Empty)) => Ok(String::new()),
+                (Err(_), Some(MissingEnvVar::Keep)) => Ok(literal.to_string()),
+                (Err(_), _) => Err(Error::EnvVarNotSet {
+                    property_name: property_name.clone(),
+                    variable: variable.to_string(),
+                }),
+            },
+            Part::Placeholder(reference) if stack.iter().any(|name| name == reference) => {
+                Err(Error::PlaceholderCycle {
+                    property_name: property_name.clone(),
+                    cycle: stack
+                        .iter()
+                        .skip_while(|name| *name != reference)
+                        .cloned()
+                        .chain([reference.to_string()])
+                        .collect(),
+                })
+            }
+            Part::Placeholder(reference) if properties.contains_key(reference) => resolve(
+                kind,
+                properties,
+                env_substitution,
+                reference,
+                resolved,
+                stack,
+            )
+            .map_err(|err| match err {
+                Error::PlaceholderCycle { cycle, .. } => Error::PlaceholderCycle {
+                    property_name: property_name.clone(),
+                    cycle,
+                },
+                _ => Error::PlaceholderNotResolved {
+                    property_name: property_name.clone(),
+                    reference: reference.to_string(),
+                },
+            }),
+            Part::Placeholder(reference) => Err(Error::PlaceholderNotResolved {
+                property_name: property_name.clone(),
+                reference: reference.to_string(),
+            }),
+        };
+
+        match (&mut result, value) {
+            (Ok(result), Ok(value)) => result.push_str(&value),
+            (_, Err(err)) => {
+                result = Err(err);
+                break;
+            }
+            _ => {}
+        }
+    }
+    stack.pop();
+
+    // errors within a cycle depend on where resolving started, so only successes are kept
+    if result.is_ok() || stack.is_empty() {
+        resolved.insert(name.to_string(), result.clone());
+    }
+    result
+}
+
+#[derive(Debug, PartialEq)]
+enum Part<'a> {
+    Literal(&'a str),
+    // the (trimmed) name of the referenced property
+    Placeholder(&'a str),
+    // the name of the environment variable and the reference as written in the value
+    EnvVar(&'a str, &'a str),
+}
+
+/// Splits the value into literals, placeholders (`${name}`) and (if enabled) environment
+/// variable references (`${env:NAME}`, `$NAME`). `$${` is a literal `${` (and `$$` a literal `$`
+/// if environment variables are enabled). Unterminated placeholders are kept as literal.
+///
+/// # Arguments
+///
+/// * `value` - the property value
+/// * `env` - if environment variable references are recognized
+///
+fn parse(value: &str, env: bool) -> Vec<Part<'_>> {
+    let mut parts = Vec::new();
+    let mut literal_start = 0;
+    let mut index = 0;
+
+    while let Some(offset) = value[index..].find('$') {
+        let start = index + offset;
+        let rest = &value[start + 1..];
+
+        let (part, len) = if rest.starts_with("${") {
+            (Part::Literal("${"), 3)
+        } else if env && rest.starts_with('$') {
+            (Part::Literal("$"), 2)
+        } else if let Some(end) = rest.strip_prefix('{').and_then(|rest| rest.find('}')) {
+            let reference = rest[1..end + 1].trim();
+            let literal = &value[start..start + end + 3];
+            match reference.strip_prefix("env:") {
+                Some(variable) if env => (Part::EnvVar(variable.trim(), literal), literal.len()),
+                // environment variables are kept as they are if not enabled
+                Some(_) => (Part::Literal(literal), literal.len()),
+                None => (Part::Placeholder(reference), literal.len()),
+            }
+        } else {
+            let name_len = rest
+                .char_indices()
+                .find(|(i, c)| {
+                    !(c.is_ascii_alphabetic() || *c == '_' || (*i > 0 && c.is_ascii_digit()))
+                })
+                .map_or(rest.len(), |(i, _)| i);
+            if env && name_len > 0 {
+                (
+                    Part::EnvVar(&rest[..name_len], &value[start..start + name_len + 1]),
+                    name_len + 1,
+                )
+            } else {
+                index = start + 1;
+                continue;
+            }
+        };
+
+        parts.push(Part::Literal(&value[literal_start..start]));
+        parts.push(part);
+        index = start + len;
+        literal_start = index;
+    }
+    parts.push(Part::Literal(&value[literal_start..]));
+
+    parts.retain(|part| *part != Part::Literal(""));
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::interpolation::{interpolate, parse, Part};
+    use crate::types::{MissingEnvVar, PropertyName, PropertyNameKind};
+    use rstest::*;
+    use std::collections::HashMap;
+    use std::env;
+
+    #[rstest]
+    #[case("/data", false, vec![Part::Literal("/data")])]
+    #[case("${data.dir}/logs", false, vec![Part::Placeholder("data.dir"), Part::Literal("/logs")])]
+    #[case("$${data.dir}", false, vec![Part::Literal("${"), Part::Literal("data.dir}")])]
+    #[case("${ a }${b}", false, vec![Part::Placeholder("a"), Part::Placeholder("b")])]
+    #[case("${data.dir", false, vec![Part::Literal("${data.dir")])]
+    #[case("$HOME/x", false, vec![Part::Literal("$HOME/x")])]
+    #[case("$HOME/x", true, vec![Part::EnvVar("HOME", "$HOME"), Part::Literal("/x")])]
+    #[case("${env:HOST}:9092", false, vec![Part::Literal("${env:HOST}"), Part::Literal(":9092")])]
+    #[case("${env:HOST}:9092", true, vec![Part::EnvVar("HOST", "${env:HOST}"), Part::Literal(":9092")])]
+    #[case("pa$$word", true, vec![Part::Literal("pa"), Part::Literal("$"), Part::Literal("word")])]
+    #[case("5$", true, vec![Part::Literal("5$")])]
+    #[trace]
+    fn test_parse(#[case] value: &str, #[case] env: bool, #[case] expected: Vec<Part>) {
+        assert_eq!(parse(value, env), expected);
+    }
+
+    fn property_name(name: &str) -> PropertyName {
+        PropertyName {
+            name: name.to_string(),
+            kind: PropertyNameKind::Env,
+        }
+    }
+
+    #[rstest]
+    #[case(&[("a", "/data"), ("b", "${a}/logs"), ("c", "${b}/gc")], "c", Ok("/data/logs/gc"))]
+    #[case(&[("a", "${missing}")], "a", Err(Error::PlaceholderNotResolved {
+        property_name: property_name("a"),
+        reference: "missing".to_string(),
+    }))]
+    #[case(&[("a", "x"), ("b", "${a}${missing}")], "b", Err(Error::PlaceholderNotResolved {
+        property_name: property_name("b"),
+        reference: "missing".to_string(),
+    }))]
+    #[case(&[("a", "${a}")], "a", Err(Error::PlaceholderCycle {
+        property_name: property_name("a"),
+        cycle: vec!["a".to_string(), "a".to_string()],
+    }))]
+    #[trace]
+    fn test_interpolate(
+        #[case] properties: &[(&str, &str)],
+        #[case] name: &str,
+        #[case] expected: Result<&str, Error>,
+    ) {
+        let properties = properties
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+
+        let resolved = interpolate(&PropertyNameKind::Env, &properties, None);
+
+        assert_eq!(resolved[name], expected.map(str::to_string));
+    }
+
+    #[rstest]
+    #[case("PRODUCT_CONFIG_TEST_HOST", MissingEnvVar::Error, Ok("broker-0:9092"))]
+    #[case("PRODUCT_CONFIG_TEST_MISSING", MissingEnvVar::Error, Err(Error::EnvVarNotSet {
+        property_name: property_name("a"),
+        variable: "PRODUCT_CONFIG_TEST_MISSING".to_string(),
+    }))]
+    #[case("PRODUCT_CONFIG_TEST_MISSING", MissingEnvVar::Empty, Ok(":9092"))]
+    #[case(
+        "PRODUCT_CONFIG_TEST_MISSING",
+        MissingEnvVar::Keep,
+        Ok("${env:PRODUCT_CONFIG_TEST_MISSING}:9092")
+    )]
+    #[trace]
+    fn test_interpolate_env(
+        #[case] variable: &str,
+        #[case] missing: MissingEnvVar,
+        #[case] expected: Result<&str, Error>,
+    ) {
+        env::set_var("PRODUCT_CONFIG_TEST_HOST", "broker-0");
+        let mut properties = HashMap::new();
+        properties.insert("a".to_string(), format!("${{env:{}}}:9092", variable));
+
+        let resolved = interpolate(&PropertyNameKind::Env, &properties, Some(missing));
+
+        assert_eq!(resolved["a"], expected.map(str::to_string));
+    }
+
+    #[test]
+    fn test_interpolate_cycle() {
+        let properties = [("a", "${b}"), ("b", "${a}")]
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+
+        let resolved = interpolate(&PropertyNameKind::Env, &properties, None);
+
+        assert!(matches!(resolved["a"], Err(Error::PlaceholderCycle { .. })));
+        assert!(matches!(resolved["b"], Err(Error::PlaceholderCycle { .. })));
+    }
+}
@@ -0,0 +1,659 @@
+//! JSON Schema support: a minimal validator for schemas embedded in property specs
+//! (`Datatype::Json`) and the export of the config spec as JSON Schema (e.g. for web UIs or IDE
+//! plugins).
+//!
+//! Supported keywords of the validator: `type`, `enum`, `const`, `properties`, `required`,
+//! `additionalProperties`, `items`, `minItems`, `maxItems`, `minimum`, `maximum`, `minLength`,
+//! `maxLength` and `pattern`. Other keywords are ignored.
+use crate::types::{
+    Datatype, Importance, ProductConfigSpecProperties, PropertyName, PropertyNameKind, PropertySpec,
+};
+use crate::util;
+use crate::validation::ValidationResult;
+use regex::Regex;
+use semver::Version;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+
+const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Returns the reason (prefixed with the JSON pointer of the offending value) if the value does
+/// not conform to the schema
+///
+/// # Arguments
+///
+/// * `schema` - the JSON schema
+/// * `value` - the value to be validated
+///
+pub(crate) fn validate(schema: &Value, value: &Value) -> Result<(), String> {
+    validate_at(schema, value, "")
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let schema = match schema {
+        // `true` accepts and `false` rejects everything
+        Value::Bool(true) => return Ok(()),
+        Value::Bool(false) => return Err(format!("{}: no value allowed", pointer(path))),
+        Value::Object(schema) => schema,
+        _ => return Err(format!("{}: schema must be an object", pointer(path))),
+    };
+
+    if let Some(types) = schema.get("type") {
+        let matches = match types {
+            Value::String(expected) => type_matches(expected, value),
+            Value::Array(types) => types
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|expected| type_matches(expected, value)),
+            _ => true,
+        };
+        if !matches {
+            return Err(format!("{}: expected type {}", pointer(path), types));
+        }
+    }
+
+    if let Some(Value::Array(variants)) = schema.get("enum") {
+        if !variants.contains(value) {
+            return Err(format!(
+                "{}: value not in {}",
+                pointer(path),
+                Value::Array(variants.clone())
+            ));
+        }
+    }
+
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            return Err(format!("{}: expected {}", pointer(path), expected));
+        }
+    }
+
+    match value {
+        Value::Object(object) => validate_object(schema, object, path)?,
+        Value::Array(items) => validate_array(schema, items, path)?,
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+            if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+                if number < minimum {
+                    return Err(format!("{}: less than minimum {}", pointer(path), minimum));
+                }
+            }
+            if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+                if number > maximum {
+                    return Err(format!(
+                        "{}: greater than maximum {}",
+                        pointer(path),
+                        maximum
+                    ));
+                }
+            }
+        }
+        Value::String(string) => {
+            let length = string.chars().count() as u64;
+            if let Some(min_length) = schema.get("minLength").and_then(Value::as_u64) {
+                if length < min_length {
+                    return Err(format!(
+                        "{}: shorter than {} characters",
+                        pointer(path),
+                        min_length
+                    ));
+                }
+            }
+            if let Some(max_length) = schema.get("maxLength").and_then(Value::as_u64) {
+                if length > max_length {
+                    return Err(format!(
+                        "{}: longer than {} characters",
+                        pointer(path),
+                        max_length
+                    ));
+                }
+            }
+            if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+                let regex = Regex::new(pattern)
+                    .map_err(|err| format!("{}: invalid pattern: {}", pointer(path), err))?;
+                if !regex.is_match(string) {
+                    return Err(format!(
+                        "{}: does not match pattern '{}'",
+                        pointer(path),
+                        pattern
+                    ));
+                }
+            }
+        }
+        Value::Bool(_) | Value::Null => {}
+    }
+
+    Ok(())
+}
+
+fn validate_object(
+    schema: &Map<String, Value>,
+    object: &Map<String, Value>,
+    path: &str,
+) -> Result<(), String> {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for name in required.iter().filter_map(Value::as_str) {
+            if !object.contains_key(name) {
+                return Err(format!(
+                    "{}: missing required property '{}'",
+                    pointer(path),
+                    name
+                ));
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (name, property_value) in object {
+        let property_path = format!("{}/{}", path, name);
+        match properties.and_then(|properties| properties.get(name)) {
+            Some(property_schema) => validate_at(property_schema, property_value, &property_path)?,
+            None => match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => {
+                    return Err(format!(
+                        "{}: additional property '{}' not allowed",
+                        pointer(path),
+                        name
+                    ))
+                }
+                Some(additional) => validate_at(additional, property_value, &property_path)?,
+                None => {}
+            },
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_array(schema: &Map<String, Value>, items: &[Value], path: &str) -> Result<(), String> {
+    let length = items.len() as u64;
+    if let Some(min_items) = schema.get("minItems").and_then(Value::as_u64) {
+        if length < min_items {
+            return Err(format!("{}: fewer than {} items", pointer(path), min_items));
+        }
+    }
+    if let Some(max_items) = schema.get("maxItems").and_then(Value::as_u64) {
+        if length > max_items {
+            return Err(format!("{}: more than {} items", pointer(path), max_items));
+        }
+    }
+
+    if let Some(item_schema) = schema.get("items") {
+        for (index, item) in items.iter().enumerate() {
+            validate_at(item_schema, item, &format!("{}/{}", path, index))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        _ => false,
+    }
+}
+
+fn pointer(path: &str) -> &str {
+    if path.is_empty() {
+        "/"
+    } else {
+        path
+    }
+}
+
+/// Returns a JSON Schema document describing the configuration of the product version: an
+/// object with one object per property kind (`env`, `cli` or the name of the config file) that
+/// contains the properties available in the version.
+///
+/// # Arguments
+///
+/// * `config_spec` - the global settings of the config spec (units, default locale)
+/// * `property_specs` - map with property name as key and the corresponding property spec as value
+/// * `product_version` - the product version
+///
+pub(crate) fn config_schema(
+    config_spec: &ProductConfigSpecProperties,
+    property_specs: &HashMap<PropertyName, PropertySpec>,
+    product_version: &Version,
+) -> ValidationResult<Value> {
+    Ok(json!({
+        "$schema": SCHEMA_DIALECT,
+        "type": "object",
+        "properties": kind_schemas(config_spec, property_specs, product_version, false)?,
+    }))
+}
+
+/// Returns an OpenAPI 3 `components/schemas` fragment with the configuration of the product
+/// version as schema with the provided name (structured like the JSON Schema document).
+/// Properties of high importance are required, all others optional.
+///
+/// # Arguments
+///
+/// * `config_spec` - the global settings of the config spec (units, default locale)
+/// * `property_specs` - map with property name as key and the corresponding property spec as value
+/// * `product_version` - the product version
+/// * `schema_name` - the name of the schema in `components/schemas`
+///
+pub(crate) fn openapi_schemas(
+    config_spec: &ProductConfigSpecProperties,
+    property_specs: &HashMap<PropertyName, PropertySpec>,
+    product_version: &Version,
+    schema_name: &str,
+) -> ValidationResult<Value> {
+    let kinds = kind_schemas(config_spec, property_specs, product_version, true)?;
+    // a kind is required if it contains required properties
+    let required: Vec<&String> = kinds
+        .iter()
+        .filter(|(_, kind)| kind.get("required").is_some())
+        .map(|(name, _)| name)
+        .collect();
+    let mut schema = json!({"type": "object", "properties": kinds});
+    if !required.is_empty() {
+        schema["required"] = json!(required);
+    }
+
+    Ok(json!({
+        "components": {
+            "schemas": {
+                schema_name: schema,
+            }
+        }
+    }))
+}
+
+/// Returns the object schemas per property kind with the properties available in the product
+/// version
+///
+/// # Arguments
+///
+/// * `config_spec` - the global settings of the config spec (units, default locale)
+/// * `property_specs` - map with property name as key and the corresponding property spec as value
+/// * `product_version` - the product version
+/// * `with_required` - if properties of high importance are listed as `required` (sorted by name)
+///
+pub(crate) fn kind_schemas(
+    config_spec: &ProductConfigSpecProperties,
+    property_specs: &HashMap<PropertyName, PropertySpec>,
+    product_version: &Version,
+    with_required: bool,
+) -> ValidationResult<Map<String, Value>> {
+    let mut kinds = Map::new();
+    // sorted to be independent of the order of the property specs
+    let mut required: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for (name, spec) in property_specs {
+        if !util::is_available_in_version(spec, product_version)? {
+            continue;
+        }
+        let kind_name = kind_name(&name.kind);
+        if with_required && spec.importance == Some(Importance::High) {
+            required
+                .entry(kind_name.clone())
+                .or_default()
+                .push(&name.name);
+        }
+        let kind = kinds
+            .entry(kind_name)
+            .or_insert_with(|| json!({"type": "object", "properties": {}}));
+        kind["properties"][&name.name] = property_schema(config_spec, name, spec, product_version)?;
+    }
+
+    for (kind_name, mut names) in required {
+        names.sort_unstable();
+        kinds[&kind_name]["required"] = json!(names);
+    }
+
+    Ok(kinds)
+}
+
+/// Returns the JSON Schema of a single property: type and bounds derived from the datatype,
+/// allowed values as `enum`, the default value and description for the product version.
+/// Values of lists are described as arrays of their items.
+///
+/// # Arguments
+///
+/// * `config_spec` - the global settings of the config spec (units, default locale)
+/// * `property_name` - name of the property
+/// * `spec` - the property spec
+/// * `product_version` - the product version
+///
+pub(crate) fn property_schema(
+    config_spec: &ProductConfigSpecProperties,
+    property_name: &PropertyName,
+    spec: &PropertySpec,
+    product_version: &Version,
+) -> ValidationResult<Value> {
+    let mut item = datatype_schema(config_spec, &spec.datatype);
+    let allowed_values = spec
+        .allowed_values
+        .as_deref()
+        .or_else(|| spec.datatype.variants());
+    if let Some(allowed_values) = allowed_values {
+        item.insert(
+            "enum".to_string(),
+            allowed_values
+                .iter()
+                .map(|value| typed_value(schema_type(&item), value))
+                .collect(),
+        );
+    }
+
+    let item_type = schema_type(&item).map(str::to_string);
+    let mut schema = match &spec.list {
+        Some(list) => {
+            let mut schema = Map::new();
+            schema.insert("type".to_string(), json!("array"));
+            schema.insert("items".to_string(), Value::Object(item));
+            if let Some(min_items) = list.min_items {
+                schema.insert("minItems".to_string(), json!(min_items));
+            }
+            if let Some(max_items) = list.max_items {
+                schema.insert("maxItems".to_string(), json!(max_items));
+            }
+            if list.unique {
+                schema.insert("uniqueItems".to_string(), json!(true));
+            }
+            schema
+        }
+        None => item,
+    };
+
+    // only values that do not depend on other properties or the platform are defaults
+    if let Some(default) = util::get_unconditional_default(property_name, spec, product_version)? {
+        let default = match &spec.list {
+            Some(list) => Value::Array(
+                default
+                    .value
+                    .split(list.delimiter.as_str())
+                    .map(|item| typed_value(item_type.as_deref(), item.trim()))
+                    .collect(),
+            ),
+            None => typed_value(item_type.as_deref(), &default.value),
+        };
+        schema.insert("default".to_string(), default);
+    }
+
+    let default_locale = config_spec.default_locale.as_deref().unwrap_or("en");
+    if let Some(description) = spec
+        .description
+        .as_ref()
+        .and_then(|description| description.text(default_locale, default_locale))
+    {
+        schema.insert("description".to_string(), json!(description));
+    }
+
+    if let Some(deprecated_since) = &spec.deprecated_since {
+        if Version::parse(deprecated_since)? <= *product_version {
+            schema.insert("deprecated".to_string(), json!(true));
+        }
+    }
+
+    Ok(Value::Object(schema))
+}
+
+/// Returns the type and bounds of the datatype. Numbers with unit and values like durations are
+/// strings; bounds that are no plain numbers (e.g. references to other properties) are left out.
+///
+/// # Arguments
+///
+/// * `config_spec` - the global settings of the config spec (to look up unit regexes)
+/// * `datatype` - the datatype of the property
+///
+fn datatype_schema(
+    config_spec: &ProductConfigSpecProperties,
+    datatype: &Datatype,
+) -> Map<String, Value> {
+    let mut schema = Map::new();
+    let mut insert = |key: &str, value: Value| {
+        schema.insert(key.to_string(), value);
+    };
+    let unit_pattern = |unit: &Option<String>| {
+        unit.as_ref()
+            .and_then(|unit| config_spec.units.get(unit))
+            .map(|regex| json!(regex.as_str()))
+    };
+    // integers are kept as they are, e.g. `1` instead of `1.0`
+    let number = |bound: Option<&String>| match bound?.parse::<i64>() {
+        Ok(bound) => Some(json!(bound)),
+        Err(_) => typed_value(Some("number"), bound?)
+            .as_f64()
+            .map(|bound| json!(bound)),
+    };
+
+    match datatype {
+        Datatype::Bool => insert("type", json!("boolean")),
+        Datatype::Integer {
+            min,
+            max,
+            unit: None,
+            accepted_units: None,
+            multiple_of,
+            ..
+        } => {
+            insert("type", json!("integer"));
+            if let Some(min) = number(min.as_ref()) {
+                insert("minimum", min);
+            }
+            if let Some(max) = number(max.as_ref()) {
+                insert("maximum", max);
+            }
+            if let Some(multiple_of) = number(multiple_of.as_ref()) {
+                insert("multipleOf", multiple_of);
+            }
+        }
+        Datatype::UnsignedInteger {
+            min,
+            max,
+            unit: None,
+            accepted_units: None,
+            ..
+        } => {
+            insert("type", json!("integer"));
+            insert("minimum", number(min.as_ref()).unwrap_or(json!(0)));
+            if let Some(max) = number(max.as_ref()) {
+                insert("maximum", max);
+            }
+        }
+        Datatype::Float {
+            min,
+            max,
+            unit: None,
+            accepted_units: None,
+            ..
+        }
+        | Datatype::Decimal { min, max, .. } => {
+            insert("type", json!("number"));
+            if let Some(min) = number(min.as_ref()) {
+                insert("minimum", min);
+            }
+            if let Some(max) = number(max.as_ref()) {
+                insert("maximum", max);
+            }
+        }
+        Datatype::Integer { unit, .. }
+        | Datatype::UnsignedInteger { unit, .. }
+        | Datatype::Float { unit, .. }
+        | Datatype::Array { unit, .. } => {
+            insert("type", json!("string"));
+            if let Some(pattern) = unit_pattern(unit) {
+                insert("pattern", pattern);
+            }
+        }
+        Datatype::String { min, max, unit, .. } => {
+            insert("type", json!("string"));
+            if let Some(min) = min.as_ref().and_then(|min| min.parse::<u64>().ok()) {
+                insert("minLength", json!(min));
+            }
+            if let Some(max) = max.as_ref().and_then(|max| max.parse::<u64>().ok()) {
+                insert("maxLength", json!(max));
+            }
+            if let Some(pattern) = unit_pattern(unit) {
+                insert("pattern", pattern);
+            }
+        }
+        Datatype::Port {
+            allow_privileged,
+            range: false,
+        } => {
+            insert("type", json!("integer"));
+            insert("minimum", json!(if *allow_privileged { 0 } else { 1024 }));
+            insert("maximum", json!(65535));
+        }
+        Datatype::Url { .. } => {
+            insert("type", json!("string"));
+            insert("format", json!("uri"));
+        }
+        Datatype::IpAddress {
+            v4_only,
+            v6_only,
+            allow_cidr: false,
+        } if *v4_only != *v6_only => {
+            insert("type", json!("string"));
+            insert("format", json!(if *v4_only { "ipv4" } else { "ipv6" }));
+        }
+        Datatype::DateTime { format: None, .. } => {
+            insert("type", json!("string"));
+            insert("format", json!("date-time"));
+        }
+        Datatype::Regex => {
+            insert("type", json!("string"));
+            insert("format", json!("regex"));
+        }
+        Datatype::Password { min_length, .. } => {
+            insert("type", json!("string"));
+            insert("format", json!("password"));
+            if let Some(min_length) = min_length {
+                insert("minLength", json!(min_length));
+            }
+        }
+        Datatype::Json {
+            schema: Some(schema),
+        } => {
+            if let Value::Object(schema) = &schema.0 {
+                return schema.clone();
+            }
+        }
+        Datatype::Json { schema: None } => {}
+        Datatype::Duration { .. }
+        | Datatype::MemorySize { .. }
+        | Datatype::Port { .. }
+        | Datatype::IpAddress { .. }
+        | Datatype::Path { .. }
+        | Datatype::DateTime { .. }
+        | Datatype::Percent { .. }
+        | Datatype::Enum { .. }
+        | Datatype::Ref { .. } => insert("type", json!("string")),
+    }
+
+    schema
+}
+
+/// Returns the value converted to the type of the schema (e.g. `8080` as number) or as string
+/// if it cannot be converted
+///
+/// # Arguments
+///
+/// * `schema_type` - the type of the schema (e.g. `integer`), None if not restricted
+/// * `value` - the value as written in the config
+///
+fn typed_value(schema_type: Option<&str>, value: &str) -> Value {
+    let converted = match schema_type {
+        Some("boolean") => value.parse::<bool>().ok().map(Value::Bool),
+        Some("integer") => value
+            .parse::<i64>()
+            .map(Value::from)
+            .or_else(|_| value.parse::<u64>().map(Value::from))
+            .ok(),
+        Some("number") => value
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number),
+        Some("string") => None,
+        // e.g. JSON documents
+        _ => serde_json::from_str(value).ok(),
+    };
+    converted.unwrap_or_else(|| json!(value))
+}
+
+fn schema_type(schema: &Map<String, Value>) -> Option<&str> {
+    schema.get("type").and_then(Value::as_str)
+}
+
+/// Returns the name of the object containing the properties of the kind in exported schemas
+fn kind_name(kind: &PropertyNameKind) -> String {
+    match kind {
+        PropertyNameKind::Conf(file) => file.clone(),
+        PropertyNameKind::Env => "env".to_string(),
+        PropertyNameKind::Cli => "cli".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::json_schema::{typed_value, validate};
+    use rstest::*;
+    use serde_json::json;
+
+    #[rstest]
+    #[case(json!({"type": "object"}), json!({"a": 1}), Ok(()))]
+    #[case(json!({"type": "object"}), json!([1]), Err("/: expected type \"object\""))]
+    #[case(json!({"type": ["string", "null"]}), json!(null), Ok(()))]
+    #[case(
+        json!({"type": "object", "required": ["host"], "properties": {"host": {"type": "string"}}}),
+        json!({"port": 1}),
+        Err("/: missing required property 'host'")
+    )]
+    #[case(
+        json!({"properties": {"port": {"type": "integer", "minimum": 1, "maximum": 65535}}}),
+        json!({"port": 70000}),
+        Err("/port: greater than maximum 65535")
+    )]
+    #[case(
+        json!({"properties": {"a": {}}, "additionalProperties": false}),
+        json!({"a": 1, "b": 2}),
+        Err("/: additional property 'b' not allowed")
+    )]
+    #[case(
+        json!({"type": "array", "items": {"enum": ["read", "write"]}, "maxItems": 2}),
+        json!(["read", "delete"]),
+        Err("/1: value not in [\"read\",\"write\"]")
+    )]
+    #[case(json!({"type": "string", "pattern": "^[a-z]+$"}), json!("abc"), Ok(()))]
+    #[case(json!({"type": "string", "minLength": 4}), json!("abc"), Err("/: shorter than 4 characters"))]
+    #[trace]
+    fn test_validate(
+        #[case] schema: serde_json::Value,
+        #[case] value: serde_json::Value,
+        #[case] expected: Result<(), &str>,
+    ) {
+        assert_eq!(
+            validate(&schema, &value),
+            expected.map_err(|reason| reason.to_string())
+        );
+    }
+
+    #[rstest]
+    #[case(Some("boolean"), "true", json!(true))]
+    #[case(Some("integer"), "-1", json!(-1))]
+    #[case(Some("integer"), "18446744073709551615", json!(u64::MAX))]
+    #[case(Some("integer"), "1g", json!("1g"))]
+    #[case(Some("number"), "0.5", json!(0.5))]
+    #[case(Some("string"), "8080", json!("8080"))]
+    #[case(None, r#"{"a": 1}"#, json!({"a": 1}))]
+    #[case(None, "not json", json!("not json"))]
+    #[trace]
+    fn test_typed_value(
+        #[case] schema_type: Option<&str>,
+        #[case] value: &str,
+        #[case] expected: serde_json::Value,
+    ) {
+        assert_eq!(typed_value(schema_type, value), expected);
+    }
+}
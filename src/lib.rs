@@ -7,26 +7,114 @@
 //! - version and deprecated checks
 //! - support for default and recommended values depending on version
 //! - dependency checks for values that require other values to be set to a certain value
+//! - placeholders in values that reference other properties (e.g. `${data.dir}/logs`)
 //! - properties can be assigned to certain rules (server, client ...)
 //! - apply mode for config changes (e.g. restart)
-//! - additional information like web links or descriptions
+//! - additional information like web links or descriptions (Markdown rendered to plain text or
+//!   HTML via the `markdown` module)
+//! - validation of existing config files (e.g. Java `.properties` files), environments,
+//!   nested documents (e.g. YAML) and command line arguments
+//! - deserialization of validated user configs into typed configs (e.g. structs of the product)
+//! - computation of the effective config (user values, defaults and migrated values with their
+//!   source) and of the compatibility of user configs with product upgrades and downgrades
+//! - export of the properties of a product version as JSON Schema, OpenAPI schema, Terraform
+//!   variables or TypeScript type definitions
+//! - generation of a `clap::Command` from the command line options (`clap` feature)
+//! - bindings for JavaScript to validate user configs in browsers via WebAssembly (`wasm`
+//!   feature); the file based readers and writers are not available on `wasm32-unknown-unknown`
+//! - typed configs generated from property specs at compile time (`macros` feature) or in build
+//!   scripts via the `codegen` module
+//! - generation of a configuration reference per product version via the `docgen` module and
+//!   of sample configs
 //!
+//! The checks of single values (datatypes, bounds and allowed values) are provided by the
+//! `product-config-core` crate, which builds with `alloc` only and takes the regex engine as
+//! parameter (e.g. for embedded devices).
+//!
+//! Regular expressions (units, patterns) use the `regex` crate (default feature). CLI tools that
+//! only need simple patterns may use `regex-lite` instead (`default-features = false`, feature
+//! `regex-lite`) to reduce compile time and binary size; it does not support Unicode classes like
+//! `\p{N}` and matches `\d` or `\w` in ASCII only.
+//!
+// `regex-lite` is used as `regex` if the `regex` feature is disabled
+#[cfg(all(feature = "regex-lite", not(feature = "regex")))]
+extern crate regex_lite as regex;
+#[cfg(not(any(feature = "regex", feature = "regex-lite")))]
+compile_error!("either the `regex` (default) or the `regex-lite` feature is required");
+
+mod args;
+pub mod builder;
+mod canonical;
+#[cfg(feature = "clap")]
+mod cli;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub mod codegen;
+pub mod compatibility;
+pub mod diff;
+pub mod docgen;
+mod document;
+pub mod effective;
 pub mod error;
+mod interpolation;
+mod json_schema;
+pub mod markdown;
+mod merge;
+pub mod migration;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod properties;
 pub mod reader;
+mod rules;
+mod sample;
 pub mod ser;
+mod terraform;
+mod typed;
 pub mod types;
+mod typescript;
+pub mod units;
 mod util;
 mod validation;
+pub mod validator;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod writer;
+
+// the code generated by the macros and the `codegen` module refers to this crate by name (also
+// within its own tests)
+extern crate self as product_config;
+#[cfg(feature = "macros")]
+pub use product_config_macros::product_config;
 
-use std::collections::HashMap;
+// dependencies of the code generated by the macros and the `codegen` module
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+    pub use serde_json;
+}
+
+use std::collections::{BTreeMap, HashMap};
 use std::str;
 use std::string::String;
+use std::sync::Arc;
 
-use crate::error::Error;
+use crate::builder::ProductConfigSpecBuilder;
+use crate::compatibility::{CompatibilityReport, DowngradeReport};
+use crate::diff::{SpecDiff, ValuesDiff};
+use crate::effective::EffectiveConfig;
+use crate::error::{Error, REDACTED};
+use crate::migration::MigrationResult;
 use crate::reader::ConfigReader;
-use crate::types::{ProductConfigSpecProperties, PropertyName, PropertyNameKind, PropertySpec};
+use crate::types::{
+    ChangePolicy, Datatype, DocLink, Importance, MergeStrategy, Platform,
+    ProductConfigSpecProperties, PropertyGroup, PropertyName, PropertyNameKind, PropertySpec,
+    SampleFormat, SampleStyle, UnitCaptures, ValidationPolicy,
+};
 use crate::validation::ValidationResult;
+use crate::validator::{
+    CustomValidator, CustomValidators, HookContext, HookResult, Hooks, SecretResolver,
+    SecretResolvers, SecretValue,
+};
 use semver::Version;
+use serde::de::DeserializeOwned;
 
 /// This will be returned for every validated configuration value (including user values
 /// and automatically added values from e.g. dependency, recommended etc.).
@@ -47,6 +135,56 @@ pub enum PropertyValidationResult {
     Error(Error),
 }
 
+impl PropertyValidationResult {
+    /// Returns the result with the value replaced by `***` in the error (if any). The value
+    /// itself is kept, it is required to configure the product.
+    pub fn redacted(self) -> Self {
+        match self {
+            PropertyValidationResult::Warn(value, error) => {
+                PropertyValidationResult::Warn(value, error.redacted())
+            }
+            PropertyValidationResult::Error(error) => {
+                PropertyValidationResult::Error(error.redacted())
+            }
+            result => result,
+        }
+    }
+}
+
+/// A property that is deprecated for a certain product version
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeprecatedProperty {
+    pub property_name: PropertyName,
+    /// the product version from which on the property is deprecated
+    pub deprecated_since: String,
+    /// the properties that replace the deprecated property (if any), each with its property
+    /// names for the different kinds
+    pub deprecated_for: Vec<Vec<PropertyName>>,
+}
+
+/// The action required to apply a set of changed properties
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeImpact {
+    /// the action with the highest impact of all changed properties
+    pub required_action: ChangePolicy,
+    /// the changed properties with their respective change policy (sorted by property name)
+    pub properties: Vec<(PropertyName, ChangePolicy)>,
+}
+
+/// A problem found when validating a user config (e.g. a config file): an unknown property, an
+/// invalid value or a missing required property
+#[derive(Clone, Debug, PartialOrd, PartialEq)]
+pub struct ConfigProblem {
+    /// the name of the property as provided (as specified for missing properties)
+    pub name: String,
+    /// the line of the property in the config file (None if not read from a file or missing)
+    pub line: Option<usize>,
+    /// the JSON pointer of the value in the user config document (None if not validated as
+    /// document or missing)
+    pub pointer: Option<String>,
+    pub error: Error,
+}
+
 /// This is the main struct to hold all our knowledge about a certain product's configuration.
 ///
 /// A product configuration consists of a list of properties and their specification
@@ -57,6 +195,20 @@ pub struct ProductConfigSpec {
     config_spec: ProductConfigSpecProperties,
     // property names as key and the corresponding property spec as value
     property_specs: HashMap<PropertyName, PropertySpec>,
+    // property specs in the order provided by the config reader (one entry per spec, not per name)
+    property_spec_list: Vec<PropertySpec>,
+    // which properties may be set by the user when validating user configs
+    validation_policy: ValidationPolicy,
+    // specs of several related products (sharing the units) via product name
+    products: BTreeMap<String, ProductConfigSpec>,
+    // custom validators referenced by property specs via name
+    validators: CustomValidators,
+    // validation hooks via property name
+    hooks: Hooks,
+    // resolvers for values that reference secrets stored outside the config
+    secret_resolvers: SecretResolvers,
+    // the platform the config is validated for (selects platform specific values)
+    platform: Platform,
 }
 
 impl ProductConfigSpec {
@@ -74,13 +226,701 @@ impl ProductConfigSpec {
             &product_config_spec.property_specs,
         )?;
 
+        for product in product_config_spec.products.values() {
+            validation::validate_property_spec(&product.config_spec, &product.property_specs)?;
+        }
+
         Ok(product_config_spec)
     }
 
+    /// Register a custom validator that property specs can reference via name (`validator`).
+    /// A validator with the same name is replaced. The validator is registered for all products.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name property specs use to reference the validator
+    /// * `validator` - the validator implementation
+    ///
+    pub fn register_validator(&mut self, name: &str, validator: Box<dyn CustomValidator>) {
+        let validator: Arc<dyn CustomValidator> = Arc::from(validator);
+        for product in self.products.values_mut() {
+            product.validators.register(name, validator.clone());
+        }
+        self.validators.register(name, validator);
+    }
+
+    /// Attach a hook to a property name (without prefix, for all kinds). Hooks run in `get` after
+    /// the built-in checks for values that passed them and may add errors or warnings. Several
+    /// hooks per property run in the order they were added. The hook is added for all products.
+    ///
+    /// # Arguments
+    ///
+    /// * `property_name` - the name of the property the hook is attached to
+    /// * `hook` - closure receiving the property value and context
+    ///
+    pub fn add_hook<F>(&mut self, property_name: &str, hook: F)
+    where
+        F: Fn(&str, &HookContext) -> HookResult + Send + Sync + 'static,
+    {
+        let hook = Arc::new(hook);
+        for product in self.products.values_mut() {
+            product.hooks.add(property_name, hook.clone());
+        }
+        self.hooks.add(property_name, hook);
+    }
+
+    /// Register a resolver for values that reference secrets stored outside the config (e.g.
+    /// `vault://path#key`). Resolvers are asked in the order they were registered. The resolver
+    /// is registered for all products.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - the resolver implementation
+    ///
+    pub fn register_secret_resolver(&mut self, resolver: Box<dyn SecretResolver>) {
+        let resolver: Arc<dyn SecretResolver> = Arc::from(resolver);
+        for product in self.products.values_mut() {
+            product.secret_resolvers.register(resolver.clone());
+        }
+        self.secret_resolvers.register(resolver);
+    }
+
+    /// Retrieve the spec of a single product if the spec describes several related products
+    /// (e.g. server, client, tools) via a `products` section. The returned spec shares the units
+    /// and the validation policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the product
+    ///
+    pub fn product(&self, name: &str) -> Option<&ProductConfigSpec> {
+        self.products.get(name)
+    }
+
+    /// Retrieve the names of all products described by the spec (sorted)
+    pub fn product_names(&self) -> Vec<&str> {
+        self.products.keys().map(String::as_str).collect()
+    }
+
+    /// Merge another ProductConfigSpec into this one, e.g. to layer product specific additions
+    /// on top of a common spec. Units are matched via name, property specs via their property
+    /// names. The merged spec is validated again.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the spec to be merged into this one (takes precedence)
+    /// * `strategy` - how to handle units and property specs that exist in both specs
+    ///
+    pub fn merge(
+        self,
+        other: ProductConfigSpec,
+        strategy: MergeStrategy,
+    ) -> ValidationResult<Self> {
+        let builder = ProductConfigSpecBuilder {
+            units: merge::merge_units(
+                &self.config_spec.unit_specs,
+                &other.config_spec.unit_specs,
+                strategy,
+            )?,
+            property_specs: merge::merge_property_specs(
+                &self.property_spec_list,
+                &other.property_spec_list,
+                strategy,
+            )?,
+            prefixes: merge::merge_prefixes(
+                &self.config_spec.prefixes,
+                &other.config_spec.prefixes,
+            ),
+            rules: merge::merge_rules(&self.config_spec.rules, &other.config_spec.rules),
+            normalization: other
+                .config_spec
+                .normalization
+                .or(self.config_spec.normalization),
+            full_match: other.config_spec.full_match.or(self.config_spec.full_match),
+            types: merge::merge_definitions(
+                &self.config_spec.types,
+                &other.config_spec.types,
+                strategy,
+                |name| Error::DatatypeDefinitionMergeConflict { name },
+            )?,
+            value_sets: merge::merge_definitions(
+                &self.config_spec.value_sets,
+                &other.config_spec.value_sets,
+                strategy,
+                |name| Error::ValueSetMergeConflict { name },
+            )?,
+            groups: merge::merge_groups(&self.config_spec.groups, &other.config_spec.groups),
+            default_locale: other
+                .config_spec
+                .default_locale
+                .or(self.config_spec.default_locale),
+            products: merge::merge_products(&self.products, &other.products, strategy)?,
+        };
+
+        let mut merged = builder
+            .build()?
+            .with_validation_policy(self.validation_policy)
+            .with_platform(self.platform);
+        merged.validators = self.validators;
+        merged.validators.extend(other.validators);
+        merged.hooks = self.hooks;
+        merged.hooks.extend(other.hooks);
+        merged.secret_resolvers = self.secret_resolvers;
+        merged.secret_resolvers.extend(other.secret_resolvers);
+        for product in merged.products.values_mut() {
+            product.validators = merged.validators.clone();
+            product.hooks = merged.hooks.clone();
+            product.secret_resolvers = merged.secret_resolvers.clone();
+        }
+        Ok(merged)
+    }
+
+    /// Set the policy that decides which properties may be set by the user, e.g. to reject
+    /// alpha properties in production.
+    ///
+    /// # Arguments
+    ///
+    /// * `validation_policy` - the policy to apply in `get`
+    ///
+    pub fn with_validation_policy(mut self, validation_policy: ValidationPolicy) -> Self {
+        for product in self.products.values_mut() {
+            product.validation_policy = validation_policy.clone();
+        }
+        self.validation_policy = validation_policy;
+        self
+    }
+
+    /// Set the platform user configs are validated for (the current platform if not set), e.g.
+    /// to select the default values for Windows when generating a config on Linux.
+    ///
+    /// # Arguments
+    ///
+    /// * `platform` - the target platform
+    ///
+    pub fn with_platform(mut self, platform: Platform) -> Self {
+        for product in self.products.values_mut() {
+            product.platform = platform.clone();
+        }
+        self.platform = platform;
+        self
+    }
+
+    /// Compare this spec with another one and report added and removed properties as well as
+    /// changed datatypes, bounds and default values.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the spec to compare with (e.g. the spec of a newer product version)
+    ///
+    pub fn diff(&self, other: &ProductConfigSpec) -> SpecDiff {
+        diff::diff_property_specs(&self.property_specs, &other.property_specs)
+    }
+
+    /// Compare two user configs and report added, removed and changed values with the action
+    /// required to apply each change, e.g. to show the changes before applying them. Values are
+    /// compared in their canonical form; values of sensitive properties are replaced by `***`.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the user configs (e.g. env, conf)
+    /// * `old_values` - map with property name and the current values
+    /// * `new_values` - map with property name and the values to be applied
+    /// * `product_version` - the current product version
+    ///
+    pub fn diff_values(
+        &self,
+        kind: &PropertyNameKind,
+        old_values: &HashMap<String, String>,
+        new_values: &HashMap<String, String>,
+        product_version: &str,
+    ) -> ValidationResult<ValuesDiff> {
+        diff::diff_values(
+            self,
+            kind,
+            old_values,
+            new_values,
+            &Version::parse(product_version)?,
+        )
+    }
+
+    /// Retrieve all property names of the provided kind with at least the provided importance
+    /// (properties without importance are of low importance), sorted by importance (highest
+    /// first) and property name, e.g. to document the most important properties first.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the properties (e.g. env, conf)
+    /// * `min_importance` - the minimum importance of the properties
+    ///
+    pub fn properties_with_importance(
+        &self,
+        kind: &PropertyNameKind,
+        min_importance: Importance,
+    ) -> Vec<(PropertyName, Importance)> {
+        let mut properties: Vec<(PropertyName, Importance)> = self
+            .property_specs
+            .iter()
+            .filter(|(name, _)| &name.kind == kind)
+            .map(|(name, spec)| (name.clone(), spec.importance.unwrap_or(Importance::Low)))
+            .filter(|(_, importance)| *importance >= min_importance)
+            .collect();
+        properties.sort_by(|(name, importance), (other_name, other_importance)| {
+            other_importance
+                .cmp(importance)
+                .then_with(|| name.name.cmp(&other_name.name))
+        });
+        properties
+    }
+
+    /// Returns the description of a property in the requested language or, if not available, in
+    /// the language of the locale (`de` for `de-CH`) or the default locale of the spec (`en` if
+    /// not provided)
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the property (e.g. env, conf)
+    /// * `name` - name of the property
+    /// * `locale` - the requested language code, e.g. `de-CH`
+    ///
+    pub fn description(&self, kind: &PropertyNameKind, name: &str, locale: &str) -> Option<&str> {
+        let property_name = PropertyName {
+            name: name.to_string(),
+            kind: kind.clone(),
+        };
+        let default_locale = self.config_spec.default_locale.as_deref().unwrap_or("en");
+        self.property_specs
+            .get(&property_name)?
+            .description
+            .as_ref()?
+            .text(locale, default_locale)
+    }
+
+    /// Retrieve the links to additional documentation of a property (empty if the property is
+    /// unknown or has none), e.g. to render them in generated docs.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the property (e.g. env, conf)
+    /// * `name` - name of the property
+    ///
+    pub fn doc_links(&self, kind: &PropertyNameKind, name: &str) -> &[DocLink] {
+        let property_name = PropertyName {
+            name: name.to_string(),
+            kind: kind.clone(),
+        };
+        self.property_specs
+            .get(&property_name)
+            .and_then(|spec| spec.additional_doc.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Retrieve the groups in the declared order together with the names of their properties of
+    /// the provided kind (sorted by name), e.g. to present the properties in sections. Groups
+    /// without properties of this kind and properties without group are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the properties (e.g. env, conf)
+    ///
+    pub fn groups(&self, kind: &PropertyNameKind) -> Vec<(&PropertyGroup, Vec<PropertyName>)> {
+        self.config_spec
+            .groups
+            .iter()
+            .filter_map(|group| {
+                let mut properties: Vec<PropertyName> = self
+                    .property_specs
+                    .iter()
+                    .filter(|(name, spec)| {
+                        &name.kind == kind && spec.group.as_ref() == Some(&group.name)
+                    })
+                    .map(|(name, _)| name.clone())
+                    .collect();
+                properties.sort();
+                (!properties.is_empty()).then_some((group, properties))
+            })
+            .collect()
+    }
+
+    /// Export the properties available in the product version as JSON Schema document, e.g. to
+    /// provide validation and autocompletion in web UIs or IDE plugins. The document describes an
+    /// object with one object per property kind (`env`, `cli` or the name of the config file);
+    /// every property has its type, bounds, allowed values, default and description.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_version` - the product version to export the properties for
+    ///
+    pub fn to_json_schema(&self, product_version: &str) -> ValidationResult<serde_json::Value> {
+        json_schema::config_schema(
+            &self.config_spec,
+            &self.property_specs,
+            &Version::parse(product_version)?,
+        )
+    }
+
+    /// Export the properties available in the product version as OpenAPI 3 `components/schemas`
+    /// fragment, e.g. to document the config endpoint of a service. The schema is structured
+    /// like the JSON Schema document (see `to_json_schema`); properties of high importance are
+    /// required, all others optional.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_version` - the product version to export the properties for
+    /// * `schema_name` - the name of the schema in `components/schemas`, e.g. `KafkaConfig`
+    ///
+    pub fn to_openapi_schemas(
+        &self,
+        product_version: &str,
+        schema_name: &str,
+    ) -> ValidationResult<serde_json::Value> {
+        json_schema::openapi_schemas(
+            &self.config_spec,
+            &self.property_specs,
+            &Version::parse(product_version)?,
+            schema_name,
+        )
+    }
+
+    /// Generate a sample config (e.g. a starter file for new users) that shows every property of
+    /// the product version with description, datatype and default. Properties of high importance
+    /// without default are highlighted as required.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_version` - the product version to generate the sample for
+    /// * `format` - the format of the sample: properties or YAML (properties of kind `conf`) or
+    ///   env (properties of kind `env`)
+    /// * `style` - if properties with default are commented out or set to the default
+    ///
+    pub fn generate_sample(
+        &self,
+        product_version: &str,
+        format: SampleFormat,
+        style: SampleStyle,
+    ) -> ValidationResult<String> {
+        sample::generate(self, &Version::parse(product_version)?, format, style)
+    }
+
+    /// Export the properties available in the product version as Terraform variable definitions
+    /// (`variables.tf`) with type, description, default and validation blocks derived from the
+    /// bounds and allowed values, e.g. for modules wrapping the product configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_version` - the product version to export the properties for
+    ///
+    pub fn to_terraform_variables(&self, product_version: &str) -> ValidationResult<String> {
+        terraform::variables(self, &Version::parse(product_version)?)
+    }
+
+    /// Export the properties available in the product version as TypeScript type definitions,
+    /// e.g. for configuration editors in web frontends: an interface per property kind with
+    /// allowed values as literal unions, bounds and defaults as JSDoc tags and a constant with the
+    /// defaults. The interface containing the kinds is structured like the OpenAPI schema (see
+    /// `to_openapi_schemas`).
+    ///
+    /// # Arguments
+    ///
+    /// * `product_version` - the product version to export the properties for
+    /// * `type_name` - the name of the interface containing the kinds, e.g. `KafkaConfig`
+    ///
+    pub fn to_typescript(
+        &self,
+        product_version: &str,
+        type_name: &str,
+    ) -> ValidationResult<String> {
+        typescript::definitions(self, &Version::parse(product_version)?, type_name)
+    }
+
+    /// Retrieve all property names that were introduced (via `as_of_version`) after `from_version`
+    /// up to and including `to_version`, e.g. to generate upgrade notes.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_version` - the product version to upgrade from (exclusive)
+    /// * `to_version` - the product version to upgrade to (inclusive)
+    ///
+    pub fn properties_added_between(
+        &self,
+        from_version: &str,
+        to_version: &str,
+    ) -> ValidationResult<Vec<PropertyName>> {
+        util::get_properties_in_version_range(
+            &self.property_specs,
+            &Version::parse(from_version)?,
+            &Version::parse(to_version)?,
+            |spec| Some(&spec.as_of_version),
+        )
+    }
+
+    /// Retrieve all property names that were deprecated (via `deprecated_since`) after
+    /// `from_version` up to and including `to_version`.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_version` - the product version to upgrade from (exclusive)
+    /// * `to_version` - the product version to upgrade to (inclusive)
+    ///
+    pub fn properties_deprecated_between(
+        &self,
+        from_version: &str,
+        to_version: &str,
+    ) -> ValidationResult<Vec<PropertyName>> {
+        util::get_properties_in_version_range(
+            &self.property_specs,
+            &Version::parse(from_version)?,
+            &Version::parse(to_version)?,
+            |spec| spec.deprecated_since.as_ref(),
+        )
+    }
+
+    /// Retrieve the lowest product version in which all properties of the user config exist and
+    /// are neither deprecated nor removed, e.g. to check if a config runs on a product version.
+    /// Returns None if there is no such version; properties unknown to the spec are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the user config (e.g. env, conf)
+    /// * `values` - map with property name and value
+    ///
+    pub fn minimum_version(
+        &self,
+        kind: &PropertyNameKind,
+        values: &HashMap<String, String>,
+    ) -> ValidationResult<Option<String>> {
+        let prefix = self.config_spec.prefix(kind).unwrap_or_default();
+        Ok(
+            util::get_minimum_version(&self.property_specs, kind, prefix, values)?
+                .map(|version| version.to_string()),
+        )
+    }
+
+    /// Retrieve all properties that are deprecated for the provided product version together
+    /// with their replacements, e.g. to warn users proactively.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    ///
+    pub fn deprecated_properties(
+        &self,
+        version: &str,
+    ) -> ValidationResult<Vec<DeprecatedProperty>> {
+        util::get_deprecated_properties(&self.property_specs, &Version::parse(version)?)
+    }
+
+    /// Check if the user config is compatible with an upgrade of the product: report set
+    /// properties that become deprecated or are removed, values that are valid for the current
+    /// version but not for the target version and properties that become required.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the user config (e.g. env, conf)
+    /// * `values` - map with property name and value
+    /// * `from_version` - the current product version
+    /// * `to_version` - the product version to upgrade to
+    ///
+    pub fn compatibility_report(
+        &self,
+        kind: &PropertyNameKind,
+        values: &HashMap<String, String>,
+        from_version: &str,
+        to_version: &str,
+    ) -> ValidationResult<CompatibilityReport> {
+        compatibility::compatibility_report(
+            self,
+            kind,
+            values,
+            &Version::parse(from_version)?,
+            &Version::parse(to_version)?,
+        )
+    }
+
+    /// Check if the user config can be used after a downgrade (e.g. the rollback of an upgrade):
+    /// report set properties that do not exist in the older version and properties that are not
+    /// set, but whose default value differs in the older version.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the user config (e.g. env, conf)
+    /// * `values` - map with property name and value
+    /// * `from_version` - the current product version
+    /// * `to_version` - the (older) product version to downgrade to
+    ///
+    pub fn check_downgrade(
+        &self,
+        kind: &PropertyNameKind,
+        values: &HashMap<String, String>,
+        from_version: &str,
+        to_version: &str,
+    ) -> ValidationResult<DowngradeReport> {
+        compatibility::check_downgrade(
+            self,
+            kind,
+            values,
+            &Version::parse(from_version)?,
+            &Version::parse(to_version)?,
+        )
+    }
+
+    /// Summarize the action (e.g. rolling restart) required to apply the changed properties.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the changed properties (e.g. env, conf)
+    /// * `changed_properties` - names of the properties whose values changed
+    ///
+    pub fn change_impact(
+        &self,
+        kind: &PropertyNameKind,
+        changed_properties: &[&str],
+    ) -> ValidationResult<ChangeImpact> {
+        util::get_change_impact(&self.property_specs, kind, changed_properties)
+    }
+
+    /// Migrate a user config to a newer product version: properties that are deprecated in
+    /// `to_version` are renamed to their replacement (`deprecated_for`) while preserving the value.
+    /// Properties that cannot be migrated automatically are kept and reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the user config (e.g. env, conf)
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    /// * `from_version` - the product version the user config was written for
+    /// * `to_version` - the product version to migrate to
+    ///
+    pub fn migrate(
+        &self,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, String>,
+        from_version: &str,
+        to_version: &str,
+    ) -> ValidationResult<MigrationResult> {
+        migration::migrate(
+            &self.property_specs,
+            &self.config_spec,
+            kind,
+            user_config,
+            &Version::parse(from_version)?,
+            &Version::parse(to_version)?,
+        )
+    }
+
+    /// Returns a `clap::Command` with an argument per property of kind `cli` available in the
+    /// product version, e.g. for wrapper CLIs that pass the options through to the product. Values
+    /// are parsed according to the datatypes (with their bounds), allowed values are the possible
+    /// values and descriptions are the help texts. The command has no name (see `Command::name`).
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the product version
+    ///
+    #[cfg(feature = "clap")]
+    pub fn to_clap_command(&self, version: &str) -> ValidationResult<clap::Command> {
+        cli::to_clap_command(self, &Version::parse(version)?)
+    }
+
+    /// Validate the user config and deserialize its effective values (user values, defaults and
+    /// migrated values) into a typed config, e.g. a struct of the product. Property names are
+    /// split at `.` into nested fields and values are typed via the datatypes of the properties.
+    /// The problems of the user config and the deserialization error (with the path of the field)
+    /// are returned together as `Error::UserConfigInvalid`.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the user config (e.g. env, conf)
+    /// * `values` - map with property name and value
+    /// * `product_version` - the current product version
+    ///
+    pub fn validate_into<T: DeserializeOwned>(
+        &self,
+        kind: &PropertyNameKind,
+        values: &HashMap<String, String>,
+        product_version: &str,
+    ) -> ValidationResult<T> {
+        typed::validate_into(self, kind, values, &Version::parse(product_version)?)
+    }
+
+    /// Deserialize a typed config (e.g. a struct of the product) from environment variables like
+    /// `validate_into`: the variables with the provided prefix (stripped from their names) that
+    /// match a property of kind `env` are validated, completed with defaults and deserialized
+    /// with the property names as field names (e.g. via `#[serde(rename_all =
+    /// "SCREAMING_SNAKE_CASE")]`). All other variables (like `PATH`) are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `vars` - the environment variables with name and value
+    /// * `prefix` - the prefix of the variables of the product (e.g. `MYAPP_`), may be empty
+    /// * `product_version` - the current product version
+    ///
+    pub fn from_vars<T: DeserializeOwned>(
+        &self,
+        vars: impl Iterator<Item = (String, String)>,
+        prefix: &str,
+        product_version: &str,
+    ) -> ValidationResult<T> {
+        let spec_prefix = self
+            .config_spec
+            .prefix(&PropertyNameKind::Env)
+            .unwrap_or_default();
+        let values: HashMap<String, String> = vars
+            .filter_map(|(name, value)| Some((name.strip_prefix(prefix)?.to_string(), value)))
+            .filter(|(name, _)| {
+                self.property_specs.contains_key(&PropertyName {
+                    name: name.strip_prefix(spec_prefix).unwrap_or(name).to_string(),
+                    kind: PropertyNameKind::Env,
+                })
+            })
+            .collect();
+
+        self.validate_into(&PropertyNameKind::Env, &values, product_version)
+    }
+
+    /// Deserialize a typed config from the environment of the current process like `from_vars`.
+    /// Variables with a name or value that is not valid unicode are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - the prefix of the variables of the product (e.g. `MYAPP_`), may be empty
+    /// * `product_version` - the current product version
+    ///
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn from_env<T: DeserializeOwned>(
+        &self,
+        prefix: &str,
+        product_version: &str,
+    ) -> ValidationResult<T> {
+        let vars = std::env::vars_os().filter_map(|(name, value)| {
+            Some((name.into_string().ok()?, value.into_string().ok()?))
+        });
+        self.from_vars(vars, prefix, product_version)
+    }
+
+    /// Returns the effective config of the product version, i.e. the values per kind the product
+    /// runs with and where they come from: values provided by the user (keyed by any name of a
+    /// property, they apply to all its names), values of deprecated properties migrated to their
+    /// replacement and the defaults of all other properties.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_values` - map with property name (of any kind) and value
+    /// * `product_version` - the current product version
+    ///
+    pub fn effective_config(
+        &self,
+        user_values: &HashMap<String, String>,
+        product_version: &str,
+    ) -> ValidationResult<EffectiveConfig> {
+        effective::effective_config(self, user_values, &Version::parse(product_version)?)
+    }
+
     /// Retrieve and check config properties depending on the kind (e.g. env, conf),
     /// the required config file (e.g. environment variables or config properties).
     /// Add other provided properties that match the config kind, config file and config role.
     /// Automatically add and correct missing or wrong config properties and dependencies.
+    /// If a prefix is configured for the kind (e.g. `MYAPP_`), it is stripped from the user
+    /// property names before validation and added to all returned property names.
+    /// Violations of cross property rules are reported as error for the affected properties.
     ///
     /// # Arguments
     ///
@@ -126,9 +966,33 @@ impl ProductConfigSpec {
 
         let product_version = Version::parse(version)?;
 
+        let prefix = self.config_spec.prefix(kind).unwrap_or_default();
+        let user_config = user_config
+            .iter()
+            .map(|(name, value)| {
+                let name = name.strip_prefix(prefix).unwrap_or(name);
+                (name.to_string(), value.clone())
+            })
+            .collect();
+
         // merge provided user properties with extracted property spec via role / kind and
         // dependencies to be validated later.
-        let merged_properties = self.merge_properties(user_config, &product_version, kind, role);
+        let merged_properties = self.merge_properties(&user_config, &product_version, kind, role);
+
+        // placeholders like `${data.dir}/logs` (and environment variables if enabled) are
+        // resolved before validation; values that cannot be resolved are reported as error and
+        // kept as they are for other properties
+        let interpolated = interpolation::interpolate(
+            kind,
+            &merged_properties,
+            self.validation_policy.env_substitution,
+        );
+        let mut merged_properties = merged_properties;
+        for (name, value) in &interpolated {
+            if let Ok(value) = value {
+                merged_properties.insert(name.clone(), value.clone());
+            }
+        }
 
         for (name, value) in &merged_properties {
             let property_name = &PropertyName {
@@ -136,21 +1000,545 @@ impl ProductConfigSpec {
                 kind: kind.clone(),
             };
 
-            result_config.insert(
-                property_name.name.clone(),
-                validation::validate(
-                    &self.property_specs,
-                    &self.config_spec,
+            if let Some(Err(error)) = interpolated.get(name) {
+                result_config.insert(
+                    format!("{}{}", prefix, property_name.name),
+                    PropertyValidationResult::Error(error.clone()),
+                );
+                continue;
+            }
+
+            // secret references are validated via the secret they point to (if resolved), but
+            // the result only contains the reference
+            let secret = self.secret_resolvers.resolve(property_name, value);
+            // resolved secrets are always redacted in errors
+            let sensitive = self.is_sensitive(property_name)
+                || matches!(secret, Some(Ok(SecretValue::Resolved(_))));
+            let result = match secret {
+                None => validation::validate(
+                    self,
                     &merged_properties,
                     &product_version,
                     role,
                     property_name,
                     value,
                 ),
-            );
-        }
-
-        Ok(result_config)
+                Some(Ok(SecretValue::Resolved(secret))) => match validation::validate(
+                    self,
+                    &merged_properties,
+                    &product_version,
+                    role,
+                    property_name,
+                    &secret,
+                ) {
+                    PropertyValidationResult::Default(_) => {
+                        PropertyValidationResult::Default(value.clone())
+                    }
+                    PropertyValidationResult::RecommendedDefault(_) => {
+                        PropertyValidationResult::RecommendedDefault(value.clone())
+                    }
+                    PropertyValidationResult::Valid(_) => {
+                        PropertyValidationResult::Valid(value.clone())
+                    }
+                    PropertyValidationResult::Warn(_, error) => {
+                        PropertyValidationResult::Warn(value.clone(), error)
+                    }
+                    error => error,
+                },
+                Some(Ok(SecretValue::Opaque)) => PropertyValidationResult::Valid(value.clone()),
+                Some(Err(error)) => PropertyValidationResult::Error(error),
+            };
+
+            let context = HookContext {
+                property_name,
+                product_version: version,
+                role,
+                properties: &merged_properties,
+            };
+
+            let result = self.hooks.run(value, &context, result);
+            result_config.insert(
+                format!("{}{}", prefix, property_name.name),
+                if sensitive { result.redacted() } else { result },
+            );
+        }
+
+        // rules may report properties that are not set (e.g. required by another property)
+        for (name, error) in rules::check_rules(&self.config_spec.rules, kind, &merged_properties) {
+            let name = format!("{}{}", prefix, name);
+            if !matches!(
+                result_config.get(&name),
+                Some(PropertyValidationResult::Error(_))
+            ) {
+                result_config.insert(name, PropertyValidationResult::Error(error));
+            }
+        }
+
+        Ok(result_config)
+    }
+
+    /// Validate the user config for a specific role like `get`, but properties that are not
+    /// specified for this role (e.g. a broker only property set for a worker) result in an
+    /// error instead of a warning.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `kind` - kind of the user config (e.g. env, conf)
+    /// * `role` - the role (process) the user config is meant for
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    ///
+    pub fn validate_for_role(
+        &self,
+        version: &str,
+        kind: &PropertyNameKind,
+        role: &str,
+        user_config: &HashMap<String, String>,
+    ) -> ValidationResult<HashMap<String, PropertyValidationResult>> {
+        let mut result_config = self.get(version, kind, Some(role), user_config)?;
+
+        for result in result_config.values_mut() {
+            if let PropertyValidationResult::Warn(
+                _,
+                error @ Error::PropertySpecRoleNotFound { .. },
+            ) = result
+            {
+                *result = PropertyValidationResult::Error(error.clone());
+            }
+        }
+
+        Ok(result_config)
+    }
+
+    /// Validate an existing Java `.properties` config file (comments, escapes and continued
+    /// lines are supported) and return its problems: unknown properties, invalid values (for
+    /// the line the property starts in) and missing required properties. Properties of high
+    /// importance without default are required. The config file is selected via the file name,
+    /// e.g. `server.properties` for `/etc/kafka/server.properties`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path of the `.properties` file
+    /// * `product_version` - the current product version
+    ///
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn validate_properties_file(
+        &self,
+        path: &str,
+        product_version: &str,
+    ) -> ValidationResult<Vec<ConfigProblem>> {
+        let content = std::fs::read_to_string(path).map_err(|_| Error::FileNotFound {
+            file_name: path.to_string(),
+        })?;
+        let entries = properties::parse(&content).map_err(|reason| Error::FileNotParsable {
+            file_name: path.to_string(),
+            reason,
+        })?;
+        let file_name = std::path::Path::new(path)
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        // like for `java.util.Properties`, the last value of a property wins
+        let kind = PropertyNameKind::Conf(file_name);
+        // problems are reported with the prefix of the kind, which may be left out in the file
+        let prefix = self.config_spec.prefix(&kind).unwrap_or_default();
+        let mut values = HashMap::new();
+        let mut lines = HashMap::new();
+        for entry in entries {
+            let name = entry.key.strip_prefix(prefix).unwrap_or(&entry.key);
+            lines.insert(format!("{}{}", prefix, name), entry.line);
+            values.insert(entry.key, entry.value);
+        }
+
+        let mut problems: Vec<ConfigProblem> = self
+            .problems(product_version, &kind, &values)?
+            .into_iter()
+            .map(|(name, error)| ConfigProblem {
+                line: lines.get(&name).copied(),
+                pointer: None,
+                name,
+                error,
+            })
+            .collect();
+        // problems are sorted by line, problems without line (e.g. missing properties) last
+        problems.sort_by_key(|problem| (problem.line.is_none(), problem.line));
+        Ok(problems)
+    }
+
+    /// Validate environment variables (e.g. as pre-flight check of an entrypoint script) and
+    /// return the problems per variable: invalid values and missing required properties of kind
+    /// `env`. Only variables matching a property of kind `env` are validated, all other variables
+    /// (like `PATH`) are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `vars` - the environment variables with name and value
+    /// * `product_version` - the current product version
+    ///
+    pub fn validate_env(
+        &self,
+        vars: impl Iterator<Item = (String, String)>,
+        product_version: &str,
+    ) -> ValidationResult<Vec<ConfigProblem>> {
+        let prefix = self
+            .config_spec
+            .prefix(&PropertyNameKind::Env)
+            .unwrap_or_default();
+        let values: HashMap<String, String> = vars
+            .filter(|(name, _)| {
+                self.property_specs.contains_key(&PropertyName {
+                    name: name.strip_prefix(prefix).unwrap_or(name).to_string(),
+                    kind: PropertyNameKind::Env,
+                })
+            })
+            .collect();
+
+        Ok(self
+            .problems(product_version, &PropertyNameKind::Env, &values)?
+            .into_iter()
+            .map(|(name, error)| ConfigProblem {
+                name,
+                line: None,
+                pointer: None,
+                error,
+            })
+            .collect())
+    }
+
+    /// Validate the environment of the current process like `validate_env`. Variables with a
+    /// name or value that is not valid unicode are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_version` - the current product version
+    ///
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn validate_process_env(
+        &self,
+        product_version: &str,
+    ) -> ValidationResult<Vec<ConfigProblem>> {
+        let vars = std::env::vars_os().filter_map(|(name, value)| {
+            Some((name.into_string().ok()?, value.into_string().ok()?))
+        });
+        self.validate_env(vars, product_version)
+    }
+
+    /// Validate a nested user config document (e.g. read from YAML or JSON) and return the
+    /// problems with the JSON pointer of the invalid value: unknown properties, invalid values
+    /// and missing required properties. Keys of nested objects are joined to property names,
+    /// e.g. `{"log": {"dirs": ["/a", "/b"]}}` is validated as `log.dirs` with value `/a,/b`.
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - the user config document
+    /// * `kind` - kind of the user config (e.g. conf)
+    /// * `product_version` - the current product version
+    ///
+    pub fn validate_document(
+        &self,
+        document: &serde_json::Value,
+        kind: &PropertyNameKind,
+        product_version: &str,
+    ) -> ValidationResult<Vec<ConfigProblem>> {
+        let mut values = HashMap::new();
+        let mut pointers = HashMap::new();
+        for leaf in document::flatten(self, kind, document) {
+            pointers.insert(leaf.name.clone(), leaf.pointer);
+            values.insert(leaf.name, leaf.value);
+        }
+
+        Ok(self
+            .problems(product_version, kind, &values)?
+            .into_iter()
+            .map(|(name, error)| ConfigProblem {
+                line: None,
+                pointer: pointers.get(&name).cloned(),
+                name,
+                error,
+            })
+            .collect())
+    }
+
+    /// Validate command line arguments (e.g. passed through by a wrapper before exec) written as
+    /// `--name value` or `--name=value` and return the problems per option: unknown options,
+    /// invalid values and missing required properties of kind `cli`. Properties of kind `cli` may
+    /// be specified with or without leading `--`; bool properties may be used as flags.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - the arguments (without the program name)
+    /// * `product_version` - the current product version
+    ///
+    pub fn validate_args<S: AsRef<str>>(
+        &self,
+        args: &[S],
+        product_version: &str,
+    ) -> ValidationResult<Vec<ConfigProblem>> {
+        let mut values = HashMap::new();
+        let mut options = HashMap::new();
+        for argument in args::parse(self, args) {
+            options.insert(argument.name.clone(), argument.option);
+            values.insert(argument.name, argument.value);
+        }
+
+        Ok(self
+            .problems(product_version, &PropertyNameKind::Cli, &values)?
+            .into_iter()
+            .map(|(name, error)| ConfigProblem {
+                // options are reported as provided
+                name: options.get(&name).cloned().unwrap_or(name),
+                line: None,
+                pointer: None,
+                error,
+            })
+            .collect())
+    }
+
+    /// Check if the value of a single property may be changed, e.g. when updating a running
+    /// deployment. Immutable properties (e.g. data directories) may only be set once.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `kind` - kind of the property (e.g. env, conf)
+    /// * `name` - name of the property
+    /// * `old_value` - the current value of the property (None if not set yet)
+    /// * `new_value` - the value the property should be changed to
+    ///
+    pub fn validate_change(
+        &self,
+        version: &str,
+        kind: &PropertyNameKind,
+        name: &str,
+        old_value: Option<&str>,
+        new_value: &str,
+    ) -> ValidationResult<()> {
+        let property_name = PropertyName {
+            name: name.to_string(),
+            kind: kind.clone(),
+        };
+        validation::validate_change(
+            self,
+            &Version::parse(version)?,
+            &property_name,
+            old_value,
+            new_value,
+        )
+        .map_err(|err| {
+            if self.is_sensitive(&property_name) {
+                err.redacted()
+            } else {
+                err
+            }
+        })
+    }
+
+    /// Convert the value of a duration, memory size or numeric property (with accepted units)
+    /// to the provided unit, e.g. to emit `2g` as `2048m` for a product that expects megabytes.
+    /// Values without unit suffix are given in the default unit of the property.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the property (e.g. env, conf)
+    /// * `name` - name of the property
+    /// * `value` - the value to be converted
+    /// * `target_unit` - the unit to convert the value to
+    ///
+    pub fn normalize(
+        &self,
+        kind: &PropertyNameKind,
+        name: &str,
+        value: &str,
+        target_unit: &str,
+    ) -> ValidationResult<String> {
+        let property_name = PropertyName {
+            name: name.to_string(),
+            kind: kind.clone(),
+        };
+        let property =
+            self.property_specs
+                .get(&property_name)
+                .ok_or_else(|| Error::PropertyNotFound {
+                    property_name: property_name.clone(),
+                })?;
+        units::normalize(&property_name, property, value, target_unit)
+    }
+
+    /// Returns the user config with every value in its canonical form, so that semantically
+    /// identical configs compare equal: whitespace is trimmed, bools and enum variants are
+    /// written in their specified casing and values with unit in the largest unit in which the
+    /// number is whole (e.g. `1g` for `1024m`).
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the user config (e.g. env, conf)
+    /// * `values` - map with property name and values
+    /// * `product_version` - the current product version
+    ///
+    pub fn canonicalize(
+        &self,
+        kind: &PropertyNameKind,
+        values: &HashMap<String, String>,
+        product_version: &str,
+    ) -> ValidationResult<HashMap<String, String>> {
+        canonical::canonicalize(self, kind, values, &Version::parse(product_version)?)
+    }
+
+    /// Returns the values with the values of sensitive properties replaced by `***`, e.g. to log
+    /// the effective configuration of the product.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the properties (e.g. env, conf)
+    /// * `values` - map with property name and values
+    /// * `redact_unknown` - if values of properties without property spec are replaced as well
+    ///
+    pub fn redact(
+        &self,
+        kind: &PropertyNameKind,
+        values: &HashMap<String, String>,
+        redact_unknown: bool,
+    ) -> HashMap<String, String> {
+        values
+            .iter()
+            .map(|(name, value)| {
+                let property_name = PropertyName {
+                    name: name.clone(),
+                    kind: kind.clone(),
+                };
+                let redact = if self.property_specs.contains_key(&property_name) {
+                    self.is_sensitive(&property_name)
+                } else {
+                    redact_unknown
+                };
+                let value = if redact { REDACTED } else { value };
+                (name.clone(), value.to_string())
+            })
+            .collect()
+    }
+
+    /// Returns warnings about the spec that do not prevent using it, e.g. unit regexes that
+    /// are not anchored and therefore accept values like `12abc` for `[0-9]+`.
+    pub fn lint(&self) -> Vec<Error> {
+        validation::lint_config_spec(&self.config_spec)
+    }
+
+    /// Validate the value of a string property with unit and return the parts captured by the
+    /// named groups of the unit regex, e.g. the magnitude and suffix of `512mb` for the regex
+    /// `(?P<value>\d+)(?P<unit>mb|gb)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the property (e.g. env, conf)
+    /// * `name` - name of the property
+    /// * `value` - the value to be validated and captured
+    ///
+    pub fn unit_captures(
+        &self,
+        kind: &PropertyNameKind,
+        name: &str,
+        value: &str,
+    ) -> ValidationResult<UnitCaptures> {
+        let property_name = PropertyName {
+            name: name.to_string(),
+            kind: kind.clone(),
+        };
+        let property =
+            self.property_specs
+                .get(&property_name)
+                .ok_or_else(|| Error::PropertyNotFound {
+                    property_name: property_name.clone(),
+                })?;
+        validation::unit_captures(&self.config_spec.units, &property_name, property, value)
+    }
+
+    /// Returns true if the property is marked as sensitive (passwords are sensitive unless marked
+    /// otherwise), i.e. its value must not show up in errors
+    ///
+    /// # Arguments
+    ///
+    /// * `property_name` - name of the property
+    ///
+    fn is_sensitive(&self, property_name: &PropertyName) -> bool {
+        self.property_specs
+            .get(property_name)
+            .map(|property| {
+                property
+                    .sensitive
+                    .unwrap_or(matches!(property.datatype, Datatype::Password { .. }))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Validate the user config like `get` (without role) and return the errors (sorted by
+    /// property name) followed by the missing required properties of the kind, i.e. properties
+    /// of high importance that are available in the version, but have no default value
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `kind` - kind of the user config (e.g. env, conf)
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    ///
+    fn problems(
+        &self,
+        version: &str,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, String>,
+    ) -> ValidationResult<Vec<(String, Error)>> {
+        let mut problems: Vec<(String, Error)> = self
+            .get(version, kind, None, user_config)?
+            .into_iter()
+            .filter_map(|(name, result)| match result {
+                PropertyValidationResult::Error(error) => Some((name, error)),
+                _ => None,
+            })
+            .collect();
+        problems.sort_by(|(name, _), (other_name, _)| name.cmp(other_name));
+
+        let product_version = Version::parse(version)?;
+        let prefix = self.config_spec.prefix(kind).unwrap_or_default();
+        let user_values: HashMap<String, String> = user_config
+            .iter()
+            .map(|(name, value)| {
+                let name = name.strip_prefix(prefix).unwrap_or(name);
+                (name.to_string(), value.clone())
+            })
+            .collect();
+        let mut missing = vec![];
+        for (property_name, property) in &self.property_specs {
+            if &property_name.kind != kind
+                || property.importance != Some(Importance::High)
+                || user_config.contains_key(&property_name.name)
+                || user_config.contains_key(&format!("{}{}", prefix, property_name.name))
+                || !util::is_available_in_version(property, &product_version)?
+                || validation::check_platform(property_name, property, &self.platform).is_err()
+            {
+                continue;
+            }
+            // conditional and platform specific defaults only count if they apply
+            let default = effective::default_value(
+                self,
+                property_name,
+                property,
+                &user_values,
+                &product_version,
+            )?
+            .is_some();
+            if !default {
+                missing.push((
+                    format!("{}{}", prefix, property_name.name),
+                    Error::RequiredPropertyMissing {
+                        property_name: property_name.clone(),
+                    },
+                ));
+            }
+        }
+        missing.sort_by(|(name, _), (other_name, _)| name.cmp(other_name));
+
+        problems.extend(missing);
+        Ok(problems)
     }
 
     /// Merge provided user config properties and available property spec (from JSON, YAML...)
@@ -172,18 +1560,41 @@ impl ProductConfigSpec {
     ) -> HashMap<String, String> {
         let mut merged_properties = HashMap::new();
 
-        if let Ok(properties) =
-            util::get_matching_properties(&self.property_specs, kind, role, version)
-        {
+        if let Ok(properties) = util::get_matching_properties(
+            &self.property_specs,
+            kind,
+            role,
+            version,
+            user_config,
+            &self.platform,
+        ) {
             merged_properties.extend(properties)
         }
 
-        if let Ok(dependencies) =
-            util::get_matching_dependencies(&self.property_specs, user_config, version, kind)
-        {
+        if let Ok(dependencies) = util::get_matching_dependencies(
+            &self.property_specs,
+            user_config,
+            version,
+            kind,
+            &self.platform,
+        ) {
             merged_properties.extend(dependencies);
         }
 
+        // do not add values for properties the user is not allowed to set or that are not
+        // supported on the target platform
+        merged_properties.retain(|name, _| {
+            let property_name = PropertyName {
+                name: name.clone(),
+                kind: kind.clone(),
+            };
+            self.property_specs.get(&property_name).is_none_or(|spec| {
+                validation::check_validation_policy(&property_name, spec, &self.validation_policy)
+                    .is_ok()
+                    && validation::check_platform(&property_name, spec, &self.platform).is_ok()
+            })
+        });
+
         merged_properties.extend(user_config.clone());
 
         merged_properties
@@ -192,18 +1603,24 @@ impl ProductConfigSpec {
 
 #[cfg(test)]
 mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder, UnitBuilder};
     use crate::error::Error;
     use crate::reader::ConfigJsonReader;
-    use crate::types::{PropertyName, PropertyNameKind};
-    use crate::{ProductConfigSpec, PropertyValidationResult};
+    use crate::types::{
+        ChangePolicy, Datatype, Importance, LengthUnit, ListSpec, Normalization, Platform,
+        PropertyDependency, PropertyName, PropertyNameKind, PropertyValueSpec, Role, Rule,
+        Stability, ValidationPolicy,
+    };
+    use crate::validator::{CustomValidator, HookResult, SecretResolver, SecretValue};
+    use crate::{DeprecatedProperty, ProductConfigSpec, PropertyValidationResult};
     use rstest::*;
     use std::collections::HashMap;
 
     const ENV_INTEGER_PORT_MIN_MAX: &str = "ENV_INTEGER_PORT_MIN_MAX";
 
     const ENV_FLOAT: &str = "ENV_FLOAT";
-    //const ENV_PROPERTY_STRING_MEMORY: &str = "ENV_PROPERTY_STRING_MEMORY";
-    //const ENV_PROPERTY_STRING_DEPRECATED: &str = "ENV_PROPERTY_STRING_DEPRECATED";
+    const ENV_PROPERTY_STRING_MEMORY: &str = "ENV_PROPERTY_STRING_MEMORY";
+    const ENV_PROPERTY_STRING_DEPRECATED: &str = "ENV_PROPERTY_STRING_DEPRECATED";
     //const ENV_ALLOWED_VALUES: &str = "ENV_ALLOWED_VALUES";
     //const ENV_SECURITY: &str = "ENV_SECURITY";
     //const ENV_SECURITY_PASSWORD: &str = "ENV_SECURITY_PASSWORD";
@@ -211,8 +1628,19 @@ mod tests {
     const ENV_SSL_CERTIFICATE_PATH: &str = "ENV_SSL_CERTIFICATE_PATH";
 
     const ROLE_1: &str = "role_1";
+    const ROLE_2: &str = "role_2";
     const VERSION_0_5_0: &str = "0.5.0";
     const CONF_FILE: &str = "env.sh";
+    const CONF_FILE_2: &str = "my.config";
+    const ALPHA_FEATURE: &str = "alpha.feature.enabled";
+    const DATA_DIR: &str = "data.dir";
+    const PRINCIPAL: &str = "kerberos.principal";
+    const KERBEROS_PRINCIPAL: &str = "kerberos-principal";
+    const LOG_DIRS: &str = "log.dirs";
+    const COMPRESSION: &str = "compression.codec";
+    const RATIO: &str = "ratio";
+    const SSL_ENABLED: &str = "ssl.enabled";
+    const KEYSTORE_PASSWORD: &str = "ssl.keystore.password";
 
     fn create_empty_data_and_expected() -> (
         HashMap<String, String>,
@@ -313,6 +1741,2729 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    fn get_conf_property_names(names: &[(&str, &str)]) -> Vec<PropertyName> {
+        names
+            .iter()
+            .map(|(name, file)| PropertyName {
+                name: name.to_string(),
+                kind: PropertyNameKind::Conf(file.to_string()),
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case("de", None, Some("Größe des Heaps"))]
+    #[case("de-CH", None, Some("Grösse des Heaps"))]
+    #[case("de-AT", None, Some("Größe des Heaps"))]
+    #[case("fr", None, Some("Heap size"))]
+    #[case("fr", Some("de"), Some("Größe des Heaps"))]
+    #[case("fr", Some("ja"), None)]
+    #[trace]
+    fn test_description(
+        #[case] locale: &str,
+        #[case] default_locale: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let mut builder = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                    .property_name(get_conf_property_names(&[("heap", CONF_FILE)]).remove(0))
+                    .localized_description("en", "Heap size")
+                    .localized_description("de", "Größe des Heaps")
+                    .localized_description("de-CH", "Grösse des Heaps")
+                    .role(Role {
+                        name: ROLE_1.to_string(),
+                        required: false,
+                    })
+                    .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                    .property_name(get_conf_property_names(&[("debug", CONF_FILE)]).remove(0))
+                    .description("Debug mode")
+                    .role(Role {
+                        name: ROLE_1.to_string(),
+                        required: false,
+                    })
+                    .build(),
+            );
+        if let Some(default_locale) = default_locale {
+            builder = builder.default_locale(default_locale);
+        }
+        let spec = builder.build().unwrap();
+        let kind = PropertyNameKind::Conf(CONF_FILE.to_string());
+
+        assert_eq!(spec.description(&kind, "heap", locale), expected);
+        assert_eq!(spec.description(&kind, "debug", locale), Some("Debug mode"));
+        assert_eq!(spec.description(&kind, "unknown", locale), None);
+    }
+
+    #[rstest]
+    #[case("https://kafka.apache.org/documentation", None)]
+    #[case("HTTP://localhost:8080?page=1", None)]
+    #[case("kafka.apache.org", Some("url is not absolute"))]
+    #[case("ftp://kafka.apache.org", Some("scheme is not http or https"))]
+    #[case("https:///documentation", Some("host is missing"))]
+    #[case("https://kafka.apache.org/a b", Some("url contains whitespace"))]
+    #[trace]
+    fn test_doc_links(#[case] url: &str, #[case] expected_error: Option<&str>) {
+        let result = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                    .property_name(get_conf_property_names(&[("debug", CONF_FILE)]).remove(0))
+                    .additional_doc("Docs", url)
+                    .role(Role {
+                        name: ROLE_1.to_string(),
+                        required: false,
+                    })
+                    .build(),
+            )
+            .build();
+        let kind = PropertyNameKind::Conf(CONF_FILE.to_string());
+
+        match (result, expected_error) {
+            (Ok(spec), None) => {
+                assert_eq!(spec.doc_links(&kind, "debug")[0].url, url);
+                assert!(spec.doc_links(&kind, "unknown").is_empty());
+            }
+            (Err(err), Some(reason)) => assert_eq!(
+                err,
+                Error::InvalidDocLink {
+                    property_name: get_conf_property_names(&[("debug", CONF_FILE)]).remove(0),
+                    url: url.to_string(),
+                    reason: reason.to_string(),
+                }
+            ),
+            (result, _) => panic!("unexpected result: {:?}", result.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_to_json_schema() {
+        let role = Role {
+            name: ROLE_1.to_string(),
+            required: false,
+        };
+        let default = |value: &str| PropertyValueSpec {
+            from_version: None,
+            to_version: None,
+            when: None,
+            platform: None,
+            value: value.to_string(),
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Port {
+                        allow_privileged: false,
+                        range: false,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[("port", CONF_FILE)]).remove(0))
+                .default_value(default("9092"))
+                .description("The **listener** port")
+                .role(role.clone())
+                .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                    .property_name(PropertyName {
+                        name: "DEBUG".to_string(),
+                        kind: PropertyNameKind::Env,
+                    })
+                    // conditional defaults are no defaults of the schema
+                    .default_value(PropertyValueSpec {
+                        when: Some(PropertyDependency {
+                            property_names: vec![PropertyName {
+                                name: "TRACE".to_string(),
+                                kind: PropertyNameKind::Env,
+                            }],
+                            value: None,
+                        }),
+                        ..default("true")
+                    })
+                    .default_value(default("false"))
+                    .deprecated_since("0.2.0")
+                    .role(role.clone())
+                    .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Integer {
+                        min: Some("1".to_string()),
+                        max: Some("10".to_string()),
+                        unit: None,
+                        accepted_units: None,
+                        default_unit: None,
+                        multiple_of: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[("levels", CONF_FILE)]).remove(0))
+                .allowed_value("1")
+                .allowed_value("5")
+                .list(ListSpec {
+                    max_items: Some(2),
+                    ..ListSpec::default()
+                })
+                .default_value(default("1,5"))
+                .role(role.clone())
+                .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, VERSION_0_5_0)
+                    .property_name(get_conf_property_names(&[("later", CONF_FILE)]).remove(0))
+                    .role(role.clone())
+                    .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                    .property_name(get_conf_property_names(&[("removed", CONF_FILE)]).remove(0))
+                    .removed_in("0.2.0")
+                    .role(role)
+                    .build(),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            spec.to_json_schema("0.3.0").unwrap(),
+            serde_json::json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {
+                    "env": {
+                        "type": "object",
+                        "properties": {
+                            "DEBUG": {"type": "boolean", "default": false, "deprecated": true}
+                        }
+                    },
+                    CONF_FILE: {
+                        "type": "object",
+                        "properties": {
+                            "port": {
+                                "type": "integer",
+                                "minimum": 1024,
+                                "maximum": 65535,
+                                "default": 9092,
+                                "description": "The **listener** port"
+                            },
+                            "levels": {
+                                "type": "array",
+                                "items": {
+                                    "type": "integer",
+                                    "minimum": 1,
+                                    "maximum": 10,
+                                    "enum": [1, 5]
+                                },
+                                "maxItems": 2,
+                                "default": [1, 5]
+                            }
+                        }
+                    }
+                }
+            })
+        );
+        assert!(spec.to_json_schema("invalid").is_err());
+    }
+
+    #[test]
+    fn test_to_openapi_schemas() {
+        let property = |name: &str, kind: PropertyNameKind, importance: Option<Importance>| {
+            let mut builder = PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                .property_name(PropertyName {
+                    name: name.to_string(),
+                    kind,
+                })
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                });
+            if let Some(importance) = importance {
+                builder = builder.importance(importance);
+            }
+            builder.build()
+        };
+        let conf = PropertyNameKind::Conf(CONF_FILE.to_string());
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(property("ssl", conf.clone(), Some(Importance::High)))
+            .property_spec(property("debug", conf.clone(), Some(Importance::Medium)))
+            .property_spec(property("auth", conf, Some(Importance::High)))
+            .property_spec(property("TRACE", PropertyNameKind::Env, None))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            spec.to_openapi_schemas("0.1.0", "KafkaConfig").unwrap(),
+            serde_json::json!({
+                "components": {
+                    "schemas": {
+                        "KafkaConfig": {
+                            "type": "object",
+                            "properties": {
+                                "env": {
+                                    "type": "object",
+                                    "properties": {"TRACE": {"type": "boolean"}}
+                                },
+                                CONF_FILE: {
+                                    "type": "object",
+                                    "properties": {
+                                        "auth": {"type": "boolean"},
+                                        "debug": {"type": "boolean"},
+                                        "ssl": {"type": "boolean"}
+                                    },
+                                    "required": ["auth", "ssl"]
+                                }
+                            },
+                            "required": [CONF_FILE]
+                        }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_groups() {
+        let property = |name: &str| {
+            PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                .property_name(get_conf_property_names(&[(name, CONF_FILE)]).remove(0))
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .group("Storage", None)
+            .group("Networking", Some("Listeners and ports"))
+            .group("Security", None)
+            .property_spec(property("tcp.nodelay").group("Networking").build())
+            .property_spec(property("log.flush").group("Storage").build())
+            .property_spec(property("http.enabled").group("Networking").build())
+            .property_spec(property("debug").build())
+            .build()
+            .unwrap();
+
+        let groups: Vec<(&str, Vec<PropertyName>)> = spec
+            .groups(&PropertyNameKind::Conf(CONF_FILE.to_string()))
+            .into_iter()
+            .map(|(group, properties)| (group.name.as_str(), properties))
+            .collect();
+
+        assert_eq!(
+            groups,
+            vec![
+                (
+                    "Storage",
+                    get_conf_property_names(&[("log.flush", CONF_FILE)])
+                ),
+                (
+                    "Networking",
+                    get_conf_property_names(&[
+                        ("http.enabled", CONF_FILE),
+                        ("tcp.nodelay", CONF_FILE)
+                    ])
+                ),
+            ]
+        );
+
+        let result = ProductConfigSpecBuilder::new()
+            .property_spec(property("debug").group("Debugging").build())
+            .build();
+
+        assert_eq!(
+            result.err(),
+            Some(Error::PropertyGroupNotFound {
+                property_name: get_conf_property_names(&[("debug", CONF_FILE)]).remove(0),
+                group: "Debugging".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_properties_file() {
+        let property = |name: &str, datatype: Datatype| {
+            PropertySpecBuilder::new(datatype, "0.1.0")
+                .property_name(get_conf_property_names(&[(name, "server.properties")]).remove(0))
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(property("ssl.enabled", Datatype::Bool).build())
+            .property_spec(
+                property(
+                    "log.dirs",
+                    Datatype::String {
+                        min: None,
+                        max: None,
+                        unit: None,
+                        accepted_units: None,
+                        default_unit: None,
+                        length_unit: None,
+                    },
+                )
+                .importance(Importance::High)
+                .build(),
+            )
+            .build()
+            .unwrap();
+        let dir = std::env::temp_dir().join(format!(
+            "product_config_{}_validate_properties_file",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("server.properties");
+        std::fs::write(
+            &path,
+            "# broker config\nssl.enabled = \\\n    maybe\nunknown=1\nssl.enabled=yes\n",
+        )
+        .unwrap();
+
+        let problems = spec
+            .validate_properties_file(path.to_str().unwrap(), "0.1.0")
+            .unwrap();
+
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (problem.name.as_str(), problem.line))
+                .collect::<Vec<_>>(),
+            vec![
+                ("unknown", Some(4)),
+                ("ssl.enabled", Some(5)),
+                ("log.dirs", None)
+            ]
+        );
+        assert!(matches!(problems[0].error, Error::PropertyNotFound { .. }));
+        assert!(matches!(
+            problems[1].error,
+            Error::DatatypeNotMatching { .. }
+        ));
+        assert_eq!(
+            problems[2].error,
+            Error::RequiredPropertyMissing {
+                property_name: get_conf_property_names(&[("log.dirs", "server.properties")])
+                    .remove(0)
+            }
+        );
+        assert_eq!(
+            spec.validate_properties_file(
+                dir.join("missing.properties").to_str().unwrap(),
+                "0.1.0"
+            )
+            .err(),
+            Some(Error::FileNotFound {
+                file_name: dir.join("missing.properties").to_str().unwrap().to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_properties_file_with_prefix() {
+        let property = |name: &str| {
+            PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                .property_name(get_conf_property_names(&[(name, "server.properties")]).remove(0))
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+                .build()
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .prefix(
+                "kafka.",
+                Some(PropertyNameKind::Conf("server.properties".to_string())),
+            )
+            .property_spec(property("ssl.enabled"))
+            .property_spec(property("debug"))
+            .build()
+            .unwrap();
+        let dir = std::env::temp_dir().join(format!(
+            "product_config_{}_validate_properties_file_with_prefix",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("server.properties");
+        std::fs::write(&path, "ssl.enabled=maybe\nkafka.debug=maybe\n").unwrap();
+
+        let problems = spec
+            .validate_properties_file(path.to_str().unwrap(), "0.1.0")
+            .unwrap();
+
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (problem.name.as_str(), problem.line))
+                .collect::<Vec<_>>(),
+            vec![("kafka.ssl.enabled", Some(1)), ("kafka.debug", Some(2))]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_validate_env() {
+        let property = |name: &str| {
+            PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                .property_name(PropertyName {
+                    name: name.to_string(),
+                    kind: PropertyNameKind::Env,
+                })
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+        };
+        // defaults that only apply if DEBUG is set (to the value)
+        let debug_default = |value: Option<&str>| PropertyValueSpec {
+            from_version: None,
+            to_version: None,
+            when: Some(PropertyDependency {
+                property_names: vec![PropertyName {
+                    name: "DEBUG".to_string(),
+                    kind: PropertyNameKind::Env,
+                }],
+                value: value.map(str::to_string),
+            }),
+            platform: None,
+            value: "false".to_string(),
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(property("SSL_ENABLED").build())
+            .property_spec(property("DEBUG").build())
+            .property_spec(property("ACCEPT_EULA").importance(Importance::High).build())
+            .property_spec(
+                property("DEBUG_SUSPEND")
+                    .importance(Importance::High)
+                    .default_value(debug_default(None))
+                    .build(),
+            )
+            .property_spec(
+                property("TRACE")
+                    .importance(Importance::High)
+                    .default_value(debug_default(Some("false")))
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        let vars = [
+            ("PATH", "/usr/bin"),
+            ("SSL_ENABLED", "yes"),
+            ("DEBUG", "true"),
+        ]
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+
+        let problems = spec.validate_env(vars, "0.1.0").unwrap();
+
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| problem.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["SSL_ENABLED", "ACCEPT_EULA", "TRACE"]
+        );
+        assert!(matches!(
+            problems[0].error,
+            Error::DatatypeNotMatching { .. }
+        ));
+        assert!(matches!(
+            problems[1].error,
+            Error::RequiredPropertyMissing { .. }
+        ));
+        assert!(spec
+            .validate_process_env("0.1.0")
+            .unwrap()
+            .iter()
+            .all(|problem| problem.name != "PATH"));
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_product_config_macro() {
+        #[crate::product_config(
+            path = "data/test_property_spec.json",
+            version = "0.5.0",
+            file = "env.sh"
+        )]
+        struct EnvConfig;
+
+        let spec = ProductConfigSpec::new(ConfigJsonReader::new(
+            "data/test_config_spec.json",
+            "data/test_property_spec.json",
+        ))
+        .unwrap();
+        let user_config = HashMap::from([
+            ("ENV_INTEGER_PORT_MIN_MAX".to_string(), "12345".to_string()),
+            ("ENV_SSL_ENABLED".to_string(), "true".to_string()),
+            (
+                "ENV_SSL_CERTIFICATE_PATH".to_string(),
+                "/tmp/certs".to_string(),
+            ),
+        ]);
+
+        let config = EnvConfig::load(&spec, &user_config).unwrap();
+
+        assert_eq!(
+            config,
+            EnvConfig {
+                env_allowed_values: "allowed_value1".to_string(),
+                env_float: 40.123,
+                env_integer_port_min_max: 12345,
+                env_property_string_deprecated: None,
+                env_property_string_memory: "256m".to_string(),
+                env_security: false,
+                env_security_password: None,
+                env_ssl_certificate_path: Some("/tmp/certs".to_string()),
+                env_ssl_enabled: true,
+            }
+        );
+        assert_eq!(
+            EnvConfig::kind(),
+            PropertyNameKind::Conf("env.sh".to_string())
+        );
+        assert!(EnvConfig::load(
+            &spec,
+            &HashMap::from([("ENV_INTEGER_PORT_MIN_MAX".to_string(), "0".to_string())])
+        )
+        .is_err());
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_product_config_macro_with_datatype_ref() {
+        #[crate::product_config(
+            path = "data/test_ref_property_spec.json",
+            config_spec = "data/test_ref_config_spec.json",
+            version = "0.1.0",
+            file = "server.properties"
+        )]
+        struct ServerConfig;
+
+        let spec = ProductConfigSpec::new(ConfigJsonReader::new(
+            "data/test_ref_config_spec.json",
+            "data/test_ref_property_spec.json",
+        ))
+        .unwrap();
+        let user_config = HashMap::from([
+            ("heap.size".to_string(), "2048".to_string()),
+            ("debug.enabled".to_string(), "true".to_string()),
+        ]);
+
+        assert_eq!(
+            ServerConfig::load(&spec, &user_config).unwrap(),
+            ServerConfig {
+                debug: ServerConfigDebug {
+                    enabled: Some(true)
+                },
+                heap: ServerConfigHeap { size: 2048 },
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_vars() {
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+        struct Env {
+            ssl_enabled: bool,
+            debug: bool,
+        }
+
+        let property = |name: &str| {
+            PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                .property_name(PropertyName {
+                    name: name.to_string(),
+                    kind: PropertyNameKind::Env,
+                })
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(property("SSL_ENABLED").build())
+            .property_spec(
+                property("DEBUG")
+                    .default_value(PropertyValueSpec {
+                        from_version: None,
+                        to_version: None,
+                        when: None,
+                        platform: None,
+                        value: "false".to_string(),
+                    })
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        let vars = [
+            ("PATH", "/usr/bin"),
+            ("SSL_ENABLED", "yes"),
+            ("MYAPP_SSL_ENABLED", "true"),
+        ]
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+
+        assert_eq!(
+            spec.from_vars::<Env>(vars, "MYAPP_", "0.1.0").unwrap(),
+            Env {
+                ssl_enabled: true,
+                debug: false
+            }
+        );
+        assert!(matches!(
+            spec.from_env::<Env>("PRODUCT_CONFIG_TEST_", "0.1.0"),
+            Err(Error::UserConfigInvalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_document() {
+        let kind = PropertyNameKind::Conf("broker.yaml".to_string());
+        let property = |name: &str| {
+            PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                .property_name(PropertyName {
+                    name: name.to_string(),
+                    kind: kind.clone(),
+                })
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(property("ssl.enabled").build())
+            .property_spec(property("ssl.client.auth").build())
+            .property_spec(property("accept.eula").importance(Importance::High).build())
+            .build()
+            .unwrap();
+        let document = serde_json::json!({
+            "ssl": {"enabled": true, "client": {"auth": "maybe"}},
+            "unknown": 1
+        });
+
+        let problems = spec.validate_document(&document, &kind, "0.1.0").unwrap();
+
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (problem.name.as_str(), problem.pointer.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("ssl.client.auth", Some("/ssl/client/auth")),
+                ("unknown", Some("/unknown")),
+                ("accept.eula", None)
+            ]
+        );
+        assert!(matches!(
+            problems[0].error,
+            Error::DatatypeNotMatching { .. }
+        ));
+        assert!(matches!(problems[1].error, Error::PropertyNotFound { .. }));
+        assert!(matches!(
+            problems[2].error,
+            Error::RequiredPropertyMissing { .. }
+        ));
+    }
+
+    #[test]
+    fn test_validate_args() {
+        let property = |name: &str| {
+            PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                .property_name(PropertyName {
+                    name: name.to_string(),
+                    kind: PropertyNameKind::Cli,
+                })
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(property("verbose").build())
+            .property_spec(property("--ssl").build())
+            .property_spec(property("accept-eula").importance(Importance::High).build())
+            .build()
+            .unwrap();
+
+        let problems = spec
+            .validate_args(&["--verbose", "--ssl=maybe", "--unknown", "x"], "0.1.0")
+            .unwrap();
+
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| problem.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["--ssl", "--unknown", "accept-eula"]
+        );
+        assert!(matches!(
+            problems[0].error,
+            Error::DatatypeNotMatching { .. }
+        ));
+        assert!(matches!(problems[1].error, Error::PropertyNotFound { .. }));
+        assert!(matches!(
+            problems[2].error,
+            Error::RequiredPropertyMissing { .. }
+        ));
+    }
+
+    #[rstest]
+    #[case(Importance::Low, vec![("c", Importance::High), ("b", Importance::Medium), ("a", Importance::Low), ("d", Importance::Low)])]
+    #[case(Importance::Medium, vec![("c", Importance::High), ("b", Importance::Medium)])]
+    #[case(Importance::High, vec![("c", Importance::High)])]
+    #[trace]
+    fn test_properties_with_importance(
+        #[case] min_importance: Importance,
+        #[case] expected: Vec<(&str, Importance)>,
+    ) {
+        let property = |name: &str| {
+            PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                .property_name(get_conf_property_names(&[(name, CONF_FILE)]).remove(0))
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(property("a").importance(Importance::Low).build())
+            .property_spec(property("b").importance(Importance::Medium).build())
+            .property_spec(property("c").importance(Importance::High).build())
+            .property_spec(property("d").build())
+            .build()
+            .unwrap();
+
+        let result = spec.properties_with_importance(
+            &PropertyNameKind::Conf(CONF_FILE.to_string()),
+            min_importance,
+        );
+
+        let expected: Vec<(PropertyName, Importance)> = expected
+            .into_iter()
+            .map(|(name, importance)| {
+                (
+                    get_conf_property_names(&[(name, CONF_FILE)]).remove(0),
+                    importance,
+                )
+            })
+            .collect();
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("0.0.0", "0.1.0", get_conf_property_names(&[
+        ("ENV_ALLOWED_VALUES", CONF_FILE),
+        (ENV_PROPERTY_STRING_DEPRECATED, CONF_FILE),
+        ("conf.allowed.values", CONF_FILE_2),
+        ("conf.property.string.deprecated", CONF_FILE_2),
+    ]))]
+    #[case("0.5.0", "1.0.0", vec![])]
+    #[trace]
+    fn test_properties_added_between(
+        #[case] from_version: &str,
+        #[case] to_version: &str,
+        #[case] expected: Vec<PropertyName>,
+    ) {
+        let config = ProductConfigSpec::new(ConfigJsonReader::new(
+            "data/test_config_spec.json",
+            "data/test_property_spec.json",
+        ))
+        .unwrap();
+
+        let result = config
+            .properties_added_between(from_version, to_version)
+            .unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(&[], Some("0.0.0"))]
+    #[case(&["ENV_ALLOWED_VALUES", "UNKNOWN"], Some("0.1.0"))]
+    #[case(&["ENV_ALLOWED_VALUES", "ENV_FLOAT"], Some("0.5.0"))]
+    #[case(&[ENV_PROPERTY_STRING_DEPRECATED, "ENV_ALLOWED_VALUES"], Some("0.1.0"))]
+    #[case(&[ENV_PROPERTY_STRING_DEPRECATED, "ENV_FLOAT"], None)]
+    #[trace]
+    fn test_minimum_version(#[case] names: &[&str], #[case] expected: Option<&str>) {
+        let config = ProductConfigSpec::new(ConfigJsonReader::new(
+            "data/test_config_spec.json",
+            "data/test_property_spec.json",
+        ))
+        .unwrap();
+        let values = names
+            .iter()
+            .map(|name| (name.to_string(), "1".to_string()))
+            .collect();
+
+        let result = config
+            .minimum_version(&PropertyNameKind::Conf(CONF_FILE.to_string()), &values)
+            .unwrap();
+
+        assert_eq!(result.as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case("0.3.0", "0.4.0", get_conf_property_names(&[
+        (ENV_PROPERTY_STRING_DEPRECATED, CONF_FILE),
+        ("conf.property.string.deprecated", CONF_FILE_2),
+    ]))]
+    #[case("0.4.0", "0.5.0", vec![])]
+    #[trace]
+    fn test_properties_deprecated_between(
+        #[case] from_version: &str,
+        #[case] to_version: &str,
+        #[case] expected: Vec<PropertyName>,
+    ) {
+        let config = ProductConfigSpec::new(ConfigJsonReader::new(
+            "data/test_config_spec.json",
+            "data/test_property_spec.json",
+        ))
+        .unwrap();
+
+        let result = config
+            .properties_deprecated_between(from_version, to_version)
+            .unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("0.3.0", vec![])]
+    #[case("0.4.0", vec![
+        DeprecatedProperty {
+            property_name: get_conf_property_names(&[(ENV_PROPERTY_STRING_DEPRECATED, CONF_FILE)]).remove(0),
+            deprecated_since: "0.4.0".to_string(),
+            deprecated_for: vec![get_conf_property_names(&[
+                (ENV_PROPERTY_STRING_MEMORY, CONF_FILE),
+                ("conf.property.string.memory", CONF_FILE_2),
+            ])],
+        },
+        DeprecatedProperty {
+            property_name: get_conf_property_names(&[("conf.property.string.deprecated", CONF_FILE_2)]).remove(0),
+            deprecated_since: "0.4.0".to_string(),
+            deprecated_for: vec![get_conf_property_names(&[
+                (ENV_PROPERTY_STRING_MEMORY, CONF_FILE),
+                ("conf.property.string.memory", CONF_FILE_2),
+            ])],
+        },
+    ])]
+    #[trace]
+    fn test_deprecated_properties(
+        #[case] version: &str,
+        #[case] expected: Vec<DeprecatedProperty>,
+    ) {
+        let config = ProductConfigSpec::new(ConfigJsonReader::new(
+            "data/test_config_spec.json",
+            "data/test_property_spec.json",
+        ))
+        .unwrap();
+
+        let result = config.deprecated_properties(version).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("0.5.0", PropertyValidationResult::Warn("1g".to_string(), Error::VersionDeprecated {
+        property_name: get_conf_property_names(&[(ENV_PROPERTY_STRING_DEPRECATED, CONF_FILE)])[0].clone(),
+        product_version: "0.5.0".to_string(),
+        deprecated_version: "0.4.0".to_string(),
+    }))]
+    #[case("1.0.0", PropertyValidationResult::Error(Error::VersionRemoved {
+        property_name: get_conf_property_names(&[(ENV_PROPERTY_STRING_DEPRECATED, CONF_FILE)])[0].clone(),
+        product_version: "1.0.0".to_string(),
+        removed_version: "1.0.0".to_string(),
+        replaced_by: vec![ENV_PROPERTY_STRING_MEMORY.to_string()],
+    }))]
+    #[trace]
+    fn test_get_deprecated_and_removed(
+        #[case] version: &str,
+        #[case] expected: PropertyValidationResult,
+    ) {
+        let config = ProductConfigSpec::new(ConfigJsonReader::new(
+            "data/test_config_spec.json",
+            "data/test_property_spec.json",
+        ))
+        .unwrap();
+
+        let mut user_data = HashMap::new();
+        user_data.insert(ENV_PROPERTY_STRING_DEPRECATED.to_string(), "1g".to_string());
+
+        let result = config
+            .get(
+                version,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get(ENV_PROPERTY_STRING_DEPRECATED), Some(&expected));
+    }
+
+    fn alpha_spec() -> ProductConfigSpec {
+        ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                    .property_name(get_conf_property_names(&[(ALPHA_FEATURE, CONF_FILE)]).remove(0))
+                    .recommended_value(PropertyValueSpec {
+                        from_version: None,
+                        to_version: None,
+                        when: None,
+                        platform: None,
+                        value: "true".to_string(),
+                    })
+                    .stability(Stability::Alpha)
+                    .role(Role {
+                        name: ROLE_1.to_string(),
+                        required: true,
+                    })
+                    .build(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[rstest]
+    #[case(ValidationPolicy::default(), vec![], Some(PropertyValidationResult::RecommendedDefault("true".to_string())))]
+    #[case(ValidationPolicy { allowed_stability: vec![Stability::Stable], allow_experimental: false, allow_non_finite_floats: false, env_substitution: None, allow_other_platforms: false }, vec![], None)]
+    #[case(ValidationPolicy { allowed_stability: vec![Stability::Stable], allow_experimental: false, allow_non_finite_floats: false, env_substitution: None, allow_other_platforms: false }, vec![(ALPHA_FEATURE, "false")],
+        Some(PropertyValidationResult::Error(Error::StabilityNotAllowed {
+            property_name: get_conf_property_names(&[(ALPHA_FEATURE, CONF_FILE)]).remove(0),
+            stability: Stability::Alpha,
+        })))]
+    #[trace]
+    fn test_get_with_validation_policy(
+        #[case] validation_policy: ValidationPolicy,
+        #[case] user_data: Vec<(&str, &str)>,
+        #[case] expected: Option<PropertyValidationResult>,
+    ) {
+        let user_data = user_data
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        let result = alpha_spec()
+            .with_validation_policy(validation_policy)
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get(ALPHA_FEATURE), expected.as_ref());
+    }
+
+    #[rstest]
+    #[case(false, vec![], None)]
+    #[case(false, vec![(ALPHA_FEATURE, "true")],
+        Some(PropertyValidationResult::Error(Error::ExperimentalPropertyNotAllowed {
+            property_name: get_conf_property_names(&[(ALPHA_FEATURE, CONF_FILE)]).remove(0),
+        })))]
+    #[case(true, vec![(ALPHA_FEATURE, "false")], Some(PropertyValidationResult::Valid("false".to_string())))]
+    #[trace]
+    fn test_get_experimental(
+        #[case] allow_experimental: bool,
+        #[case] user_data: Vec<(&str, &str)>,
+        #[case] expected: Option<PropertyValidationResult>,
+    ) {
+        let user_data = user_data
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                    .property_name(get_conf_property_names(&[(ALPHA_FEATURE, CONF_FILE)]).remove(0))
+                    .recommended_value(PropertyValueSpec {
+                        from_version: None,
+                        to_version: None,
+                        when: None,
+                        platform: None,
+                        value: "true".to_string(),
+                    })
+                    .experimental(true)
+                    .role(Role {
+                        name: ROLE_1.to_string(),
+                        required: true,
+                    })
+                    .build(),
+            )
+            .build()
+            .unwrap();
+
+        let result = spec
+            .with_validation_policy(ValidationPolicy {
+                allow_experimental,
+                ..ValidationPolicy::default()
+            })
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get(ALPHA_FEATURE), expected.as_ref());
+    }
+
+    #[rstest]
+    #[case(vec![], ChangePolicy::None)]
+    #[case(vec!["ENV_FLOAT"], ChangePolicy::None)]
+    #[case(vec!["ENV_FLOAT", "ENV_SSL_ENABLED"], ChangePolicy::RollingRestart)]
+    #[case(vec!["ENV_SSL_ENABLED", "ENV_SECURITY"], ChangePolicy::Restart)]
+    #[trace]
+    fn test_change_impact(#[case] changed: Vec<&str>, #[case] expected: ChangePolicy) {
+        let config = ProductConfigSpec::new(ConfigJsonReader::new(
+            "data/test_config_spec.json",
+            "data/test_property_spec.json",
+        ))
+        .unwrap();
+
+        let result = config
+            .change_impact(&PropertyNameKind::Conf(CONF_FILE.to_string()), &changed)
+            .unwrap();
+
+        assert_eq!(result.required_action, expected);
+        assert_eq!(result.properties.len(), changed.len());
+    }
+
+    #[rstest]
+    #[case(None, "/data", Ok(()))]
+    #[case(Some("/data"), "/data", Ok(()))]
+    #[case(Some("/data"), "/other", Err(Error::ImmutablePropertyChanged {
+        property_name: get_conf_property_names(&[(DATA_DIR, CONF_FILE)]).remove(0),
+        old_value: "/data".to_string(),
+        new_value: "/other".to_string(),
+    }))]
+    #[case(None, "data", Err(Error::DatatypeRegexNotMatching {
+        property_name: get_conf_property_names(&[(DATA_DIR, CONF_FILE)]).remove(0),
+        value: "data".to_string(),
+    }))]
+    #[trace]
+    fn test_validate_change(
+        #[case] old_value: Option<&str>,
+        #[case] new_value: &str,
+        #[case] expected: Result<(), Error>,
+    ) {
+        let spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("path", "^/.*$").build())
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::String {
+                        min: None,
+                        max: None,
+                        unit: Some("path".to_string()),
+                        accepted_units: None,
+                        default_unit: None,
+                        length_unit: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[(DATA_DIR, CONF_FILE)]).remove(0))
+                .immutable(true)
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+                .build(),
+            )
+            .build()
+            .unwrap();
+
+        let result = spec.validate_change(
+            VERSION_0_5_0,
+            &PropertyNameKind::Conf(CONF_FILE.to_string()),
+            DATA_DIR,
+            old_value,
+            new_value,
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_validate_for_role() {
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                    .property_name(get_conf_property_names(&[(ALPHA_FEATURE, CONF_FILE)]).remove(0))
+                    .role(Role {
+                        name: "broker".to_string(),
+                        required: false,
+                    })
+                    .build(),
+            )
+            .build()
+            .unwrap();
+
+        let kind = PropertyNameKind::Conf(CONF_FILE.to_string());
+        let mut user_data = HashMap::new();
+        user_data.insert(ALPHA_FEATURE.to_string(), "true".to_string());
+        let role_not_found = Error::PropertySpecRoleNotFound {
+            name: get_conf_property_names(&[(ALPHA_FEATURE, CONF_FILE)]).remove(0),
+            role: "worker".to_string(),
+        };
+
+        let lenient = spec
+            .get(VERSION_0_5_0, &kind, Some("worker"), &user_data)
+            .unwrap();
+        assert_eq!(
+            lenient.get(ALPHA_FEATURE),
+            Some(&PropertyValidationResult::Warn(
+                "true".to_string(),
+                role_not_found.clone()
+            ))
+        );
+
+        let strict = spec
+            .validate_for_role(VERSION_0_5_0, &kind, "worker", &user_data)
+            .unwrap();
+        assert_eq!(
+            strict.get(ALPHA_FEATURE),
+            Some(&PropertyValidationResult::Error(role_not_found))
+        );
+
+        let broker = spec
+            .validate_for_role(VERSION_0_5_0, &kind, "broker", &user_data)
+            .unwrap();
+        assert_eq!(
+            broker.get(ALPHA_FEATURE),
+            Some(&PropertyValidationResult::Valid("true".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_products() {
+        let config = ProductConfigSpec::new(ConfigJsonReader::new(
+            "data/test_config_spec.json",
+            "data/test_products_property_spec.json",
+        ))
+        .unwrap();
+
+        assert_eq!(config.product_names(), vec!["client", "server"]);
+        assert!(config.product("tools").is_none());
+
+        let server = config
+            .product("server")
+            .unwrap()
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf("server.properties".to_string()),
+                Some("server"),
+                &HashMap::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            server.get("server.port"),
+            Some(&PropertyValidationResult::RecommendedDefault(
+                "8080".to_string()
+            ))
+        );
+
+        // units are shared between the products
+        let mut user_data = HashMap::new();
+        user_data.insert(
+            "client.server.url".to_string(),
+            "https://localhost:8080".to_string(),
+        );
+        let client = config
+            .product("client")
+            .unwrap()
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf("client.properties".to_string()),
+                Some("client"),
+                &user_data,
+            )
+            .unwrap();
+        assert_eq!(
+            client.get("client.server.url"),
+            Some(&PropertyValidationResult::Valid(
+                "https://localhost:8080".to_string()
+            ))
+        );
+    }
+
+    #[rstest]
+    #[case(None, "MYAPP_", "MYAPP_")]
+    #[case(Some(PropertyNameKind::Conf(CONF_FILE.to_string())), "MYAPP_", "MYAPP_")]
+    #[case(Some(PropertyNameKind::Env), "", "")]
+    #[case(None, "", "MYAPP_")]
+    #[trace]
+    fn test_get_with_prefix(
+        #[case] prefix_kind: Option<PropertyNameKind>,
+        #[case] user_prefix: &str,
+        #[case] expected_prefix: &str,
+    ) {
+        let spec = ProductConfigSpecBuilder::new()
+            .prefix("MYAPP_", prefix_kind)
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                    .property_name(get_conf_property_names(&[(ALPHA_FEATURE, CONF_FILE)]).remove(0))
+                    .role(Role {
+                        name: ROLE_1.to_string(),
+                        required: false,
+                    })
+                    .build(),
+            )
+            .build()
+            .unwrap();
+
+        let mut user_data = HashMap::new();
+        user_data.insert(
+            format!("{}{}", user_prefix, ALPHA_FEATURE),
+            "true".to_string(),
+        );
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(
+            result.get(&format!("{}{}", expected_prefix, ALPHA_FEATURE)),
+            Some(&PropertyValidationResult::Valid("true".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_get_with_rules() {
+        let kind = PropertyNameKind::Conf(CONF_FILE.to_string());
+        let spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("path", "^/.*$").build())
+            .rule(Rule {
+                expression: format!(
+                    "if ${{{}}} == 'true' then required(${{{}}})",
+                    ALPHA_FEATURE, DATA_DIR
+                ),
+                kind: Some(kind.clone()),
+                comment: None,
+            })
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                    .property_name(get_conf_property_names(&[(ALPHA_FEATURE, CONF_FILE)]).remove(0))
+                    .role(Role {
+                        name: ROLE_1.to_string(),
+                        required: false,
+                    })
+                    .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::String {
+                        min: None,
+                        max: None,
+                        unit: Some("path".to_string()),
+                        accepted_units: None,
+                        default_unit: None,
+                        length_unit: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[(DATA_DIR, CONF_FILE)]).remove(0))
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+                .build(),
+            )
+            .build()
+            .unwrap();
+
+        let mut user_data = HashMap::new();
+        user_data.insert(ALPHA_FEATURE.to_string(), "true".to_string());
+
+        let result = spec
+            .get(VERSION_0_5_0, &kind, Some(ROLE_1), &user_data)
+            .unwrap();
+        assert_eq!(
+            result.get(DATA_DIR),
+            Some(&PropertyValidationResult::Error(Error::RuleViolated {
+                property_name: get_conf_property_names(&[(DATA_DIR, CONF_FILE)]).remove(0),
+                rule: format!(
+                    "if ${{{}}} == 'true' then required(${{{}}})",
+                    ALPHA_FEATURE, DATA_DIR
+                ),
+            }))
+        );
+
+        user_data.insert(DATA_DIR.to_string(), "/data".to_string());
+        let result = spec
+            .get(VERSION_0_5_0, &kind, Some(ROLE_1), &user_data)
+            .unwrap();
+        assert_eq!(
+            result.get(DATA_DIR),
+            Some(&PropertyValidationResult::Valid("/data".to_string()))
+        );
+    }
+
+    #[rstest]
+    #[case("512", "1024", PropertyValidationResult::Valid("512".to_string()))]
+    #[case("2048", "1024", PropertyValidationResult::Error(Error::PropertyValueOutOfBounds {
+        property_name: get_conf_property_names(&[("heap.initial", CONF_FILE)]).remove(0),
+        received: "2048".to_string(),
+        expected: "1024".to_string(),
+    }))]
+    #[trace]
+    fn test_get_with_bound_reference(
+        #[case] initial: &str,
+        #[case] max: &str,
+        #[case] expected: PropertyValidationResult,
+    ) {
+        let heap = |name: &str, max: Option<&str>| {
+            PropertySpecBuilder::new(
+                Datatype::Integer {
+                    min: Some("1".to_string()),
+                    max: max.map(str::to_string),
+                    unit: None,
+                    accepted_units: None,
+                    default_unit: None,
+                    multiple_of: None,
+                },
+                "0.1.0",
+            )
+            .property_name(get_conf_property_names(&[(name, CONF_FILE)]).remove(0))
+            .role(Role {
+                name: ROLE_1.to_string(),
+                required: false,
+            })
+            .build()
+        };
+
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(heap("heap.initial", Some("${heap.max}")))
+            .property_spec(heap("heap.max", None))
+            .build()
+            .unwrap();
+
+        let mut user_data = HashMap::new();
+        user_data.insert("heap.initial".to_string(), initial.to_string());
+        user_data.insert("heap.max".to_string(), max.to_string());
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get("heap.initial"), Some(&expected));
+    }
+
+    #[rstest]
+    #[case(None, PropertyValidationResult::RecommendedDefault("8".to_string()))]
+    #[case(Some("standalone"), PropertyValidationResult::RecommendedDefault("2".to_string()))]
+    #[case(Some("cluster"), PropertyValidationResult::RecommendedDefault("16".to_string()))]
+    #[trace]
+    fn test_get_with_conditional_values(
+        #[case] mode: Option<&str>,
+        #[case] expected: PropertyValidationResult,
+    ) {
+        let mode_name = get_conf_property_names(&[("mode", CONF_FILE)]).remove(0);
+        let when_mode = |value: &str| {
+            Some(PropertyDependency {
+                property_names: vec![mode_name.clone()],
+                value: Some(value.to_string()),
+            })
+        };
+        let threads = |value: &str, when: Option<PropertyDependency>| PropertyValueSpec {
+            from_version: None,
+            to_version: None,
+            when,
+            platform: None,
+            value: value.to_string(),
+        };
+
+        let spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("text", ".*").build())
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::String {
+                        min: None,
+                        max: None,
+                        unit: Some("text".to_string()),
+                        accepted_units: None,
+                        default_unit: None,
+                        length_unit: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(mode_name.clone())
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+                .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Integer {
+                        min: Some("1".to_string()),
+                        max: None,
+                        unit: None,
+                        accepted_units: None,
+                        default_unit: None,
+                        multiple_of: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[("num.io.threads", CONF_FILE)]).remove(0))
+                .recommended_value(threads("2", when_mode("standalone")))
+                .recommended_value(threads("8", None))
+                .recommended_value(threads("16", when_mode("cluster")))
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: true,
+                })
+                .build(),
+            )
+            .build()
+            .unwrap();
+
+        let mut user_data = HashMap::new();
+        if let Some(mode) = mode {
+            user_data.insert("mode".to_string(), mode.to_string());
+        }
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get("num.io.threads"), Some(&expected));
+    }
+
+    #[rstest]
+    #[case(Platform::new("linux", "x86_64"), false, Some(PropertyValidationResult::Valid("true".to_string())), true)]
+    #[case(Platform::new("linux", "aarch64"), false, Some(PropertyValidationResult::Valid("true".to_string())), true)]
+    #[case(Platform::new("windows", "x86_64"), false, Some(PropertyValidationResult::Error(Error::PropertyNotSupportedOnPlatform {
+        property_name: get_conf_property_names(&[("epoll.enabled", CONF_FILE)]).remove(0),
+        platform: "windows/x86_64".to_string(),
+        platforms: vec!["linux".to_string(), "macos/aarch64".to_string()],
+    })), false)]
+    #[case(Platform::new("windows", "x86_64"), true, Some(PropertyValidationResult::Warn("true".to_string(), Error::PropertyNotSupportedOnPlatform {
+        property_name: get_conf_property_names(&[("epoll.enabled", CONF_FILE)]).remove(0),
+        platform: "windows/x86_64".to_string(),
+        platforms: vec!["linux".to_string(), "macos/aarch64".to_string()],
+    })), false)]
+    #[trace]
+    fn test_get_with_platforms(
+        #[case] platform: Platform,
+        #[case] allow_other_platforms: bool,
+        #[case] expected: Option<PropertyValidationResult>,
+        #[case] expected_recommended: bool,
+    ) {
+        let property = |name: &str| {
+            PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                .property_name(get_conf_property_names(&[(name, CONF_FILE)]).remove(0))
+                .platform("linux")
+                .platform("macos/aarch64")
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: true,
+                })
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(property("epoll.enabled").build())
+            .property_spec(
+                property("cgroup.enabled")
+                    .recommended_value(PropertyValueSpec {
+                        from_version: None,
+                        to_version: None,
+                        when: None,
+                        platform: None,
+                        value: "true".to_string(),
+                    })
+                    .build(),
+            )
+            .build()
+            .unwrap()
+            .with_platform(platform)
+            .with_validation_policy(ValidationPolicy {
+                allow_other_platforms,
+                ..ValidationPolicy::default()
+            });
+
+        let mut user_data = HashMap::new();
+        user_data.insert("epoll.enabled".to_string(), "true".to_string());
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get("epoll.enabled"), expected.as_ref());
+        assert_eq!(result.contains_key("cgroup.enabled"), expected_recommended);
+    }
+
+    #[rstest]
+    #[case(Platform::new("linux", "x86_64"), "/var/lib/app")]
+    #[case(Platform::new("linux", "aarch64"), "/opt/arm/app")]
+    #[case(Platform::new("windows", "x86_64"), "C:\\ProgramData\\app")]
+    #[case(Platform::new("windows", "aarch64"), "C:\\ProgramData\\app")]
+    #[trace]
+    fn test_get_with_platform_values(#[case] platform: Platform, #[case] expected: &str) {
+        let data_dir = |value: &str, platform: Option<Platform>| PropertyValueSpec {
+            from_version: None,
+            to_version: None,
+            when: None,
+            platform,
+            value: value.to_string(),
+        };
+
+        let spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("path", ".*").build())
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::String {
+                        min: None,
+                        max: None,
+                        unit: Some("path".to_string()),
+                        accepted_units: None,
+                        default_unit: None,
+                        length_unit: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[(DATA_DIR, CONF_FILE)]).remove(0))
+                .recommended_value(data_dir("/var/lib/app", None))
+                .recommended_value(data_dir(
+                    "C:\\ProgramData\\app",
+                    Some(Platform {
+                        os: Some("windows".to_string()),
+                        arch: None,
+                    }),
+                ))
+                .recommended_value(data_dir(
+                    "/opt/arm/app",
+                    Some(Platform::new("linux", "aarch64")),
+                ))
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: true,
+                })
+                .build(),
+            )
+            .build()
+            .unwrap()
+            .with_platform(platform);
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &HashMap::new(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            result.get(DATA_DIR),
+            Some(&PropertyValidationResult::RecommendedDefault(
+                expected.to_string()
+            ))
+        );
+    }
+
+    struct KerberosPrincipal;
+
+    impl CustomValidator for KerberosPrincipal {
+        fn validate(&self, _property_name: &PropertyName, value: &str) -> Result<(), String> {
+            match value.split_once('@') {
+                Some((primary, realm)) if !primary.is_empty() && !realm.is_empty() => Ok(()),
+                _ => Err("expected <primary>@<REALM>".to_string()),
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(true, "hdfs/host@EXAMPLE.COM", PropertyValidationResult::Valid("hdfs/host@EXAMPLE.COM".to_string()))]
+    #[case(true, "hdfs", PropertyValidationResult::Error(Error::CustomValidationFailed {
+        property_name: get_conf_property_names(&[(PRINCIPAL, CONF_FILE)]).remove(0),
+        value: "hdfs".to_string(),
+        validator: KERBEROS_PRINCIPAL.to_string(),
+        reason: "expected <primary>@<REALM>".to_string(),
+    }))]
+    #[case(false, "hdfs/host@EXAMPLE.COM", PropertyValidationResult::Error(Error::CustomValidatorNotFound {
+        property_name: get_conf_property_names(&[(PRINCIPAL, CONF_FILE)]).remove(0),
+        validator: KERBEROS_PRINCIPAL.to_string(),
+    }))]
+    #[trace]
+    fn test_get_with_custom_validator(
+        #[case] register: bool,
+        #[case] value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) {
+        let mut spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("text", "^.+$").build())
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::String {
+                        min: None,
+                        max: None,
+                        unit: Some("text".to_string()),
+                        accepted_units: None,
+                        default_unit: None,
+                        length_unit: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[(PRINCIPAL, CONF_FILE)]).remove(0))
+                .validator(KERBEROS_PRINCIPAL)
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+                .build(),
+            )
+            .build()
+            .unwrap();
+
+        if register {
+            spec.register_validator(KERBEROS_PRINCIPAL, Box::new(KerberosPrincipal));
+        }
+
+        let mut user_data = HashMap::new();
+        user_data.insert(PRINCIPAL.to_string(), value.to_string());
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get(PRINCIPAL), Some(&expected));
+    }
+
+    #[rstest]
+    // a missing dependency is added with its recommended value
+    #[case(None, PropertyValidationResult::Valid("secret".to_string()))]
+    #[case(Some("false"), PropertyValidationResult::Error(Error::PropertyDependencyValueInvalid {
+        property_name: get_conf_property_names(&[(KEYSTORE_PASSWORD, CONF_FILE)]).remove(0),
+        dependency: SSL_ENABLED.to_string(),
+        user_value: "false".to_string(),
+        required_value: "true".to_string(),
+    }))]
+    #[case(Some("true"), PropertyValidationResult::Valid("secret".to_string()))]
+    #[trace]
+    fn test_get_with_dependencies(
+        #[case] ssl_enabled: Option<&str>,
+        #[case] expected: PropertyValidationResult,
+    ) {
+        let role = Role {
+            name: ROLE_1.to_string(),
+            required: false,
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("text", "^.+$").build())
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "0.1.0")
+                    .property_name(get_conf_property_names(&[(SSL_ENABLED, CONF_FILE)]).remove(0))
+                    .recommended_value(PropertyValueSpec {
+                        from_version: None,
+                        to_version: None,
+                        when: None,
+                        platform: None,
+                        value: "true".to_string(),
+                    })
+                    .role(role.clone())
+                    .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::String {
+                        min: None,
+                        max: None,
+                        unit: Some("text".to_string()),
+                        accepted_units: None,
+                        default_unit: None,
+                        length_unit: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[(KEYSTORE_PASSWORD, CONF_FILE)]).remove(0))
+                .depends_on(PropertyDependency {
+                    property_names: get_conf_property_names(&[(SSL_ENABLED, CONF_FILE)]),
+                    value: Some("true".to_string()),
+                })
+                .role(role)
+                .build(),
+            )
+            .build()
+            .unwrap();
+
+        let mut user_data = HashMap::new();
+        user_data.insert(KEYSTORE_PASSWORD.to_string(), "secret".to_string());
+        if let Some(ssl_enabled) = ssl_enabled {
+            user_data.insert(SSL_ENABLED.to_string(), ssl_enabled.to_string());
+        }
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get(KEYSTORE_PASSWORD), Some(&expected));
+    }
+
+    #[rstest]
+    #[case("/var/log/a,/var/log/b", PropertyValidationResult::Valid("/var/log/a,/var/log/b".to_string()))]
+    #[case("/var/log/a", PropertyValidationResult::Warn("/var/log/a".to_string(), Error::HookWarning {
+        property_name: get_conf_property_names(&[(LOG_DIRS, CONF_FILE)]).remove(0),
+        value: "/var/log/a".to_string(),
+        reason: "a single log directory for role role_1".to_string(),
+    }))]
+    #[case("/var/log/a,log", PropertyValidationResult::Error(Error::HookFailed {
+        property_name: get_conf_property_names(&[(LOG_DIRS, CONF_FILE)]).remove(0),
+        value: "/var/log/a,log".to_string(),
+        reason: "log directories must be absolute".to_string(),
+    }))]
+    #[case("", PropertyValidationResult::Error(Error::HookFailed {
+        property_name: get_conf_property_names(&[(LOG_DIRS, CONF_FILE)]).remove(0),
+        value: "".to_string(),
+        reason: "log directories must be absolute".to_string(),
+    }))]
+    #[trace]
+    fn test_get_with_hooks(#[case] value: &str, #[case] expected: PropertyValidationResult) {
+        let mut spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("text", "^.+$").build())
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::String {
+                        min: None,
+                        max: None,
+                        unit: Some("text".to_string()),
+                        accepted_units: None,
+                        default_unit: None,
+                        length_unit: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[(LOG_DIRS, CONF_FILE)]).remove(0))
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+                .build(),
+            )
+            .build()
+            .unwrap();
+
+        spec.add_hook(LOG_DIRS, |value, _ctx| {
+            if value.split(',').any(|dir| !dir.starts_with('/')) {
+                HookResult::Error("log directories must be absolute".to_string())
+            } else {
+                HookResult::Ok
+            }
+        });
+        spec.add_hook(LOG_DIRS, |value, ctx| {
+            if value.contains(',') {
+                HookResult::Ok
+            } else {
+                HookResult::Warn(format!(
+                    "a single log directory for role {}",
+                    ctx.role.unwrap_or_default()
+                ))
+            }
+        });
+
+        let mut user_data = HashMap::new();
+        user_data.insert(LOG_DIRS.to_string(), value.to_string());
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get(LOG_DIRS), Some(&expected));
+    }
+
+    #[rstest]
+    #[case("snappy", PropertyValidationResult::Valid("SNAPPY".to_string()))]
+    #[case("GZIP", PropertyValidationResult::RecommendedDefault("GZIP".to_string()))]
+    #[case("lz4", PropertyValidationResult::Error(Error::PropertyValueNotInAllowedValues {
+        property_name: get_conf_property_names(&[(COMPRESSION, CONF_FILE)]).remove(0),
+        value: "lz4".to_string(),
+        allowed_values: vec!["GZIP".to_string(), "SNAPPY".to_string()],
+    }))]
+    #[trace]
+    fn test_get_with_enum(#[case] value: &str, #[case] expected: PropertyValidationResult) {
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Enum {
+                        values: vec!["GZIP".to_string(), "SNAPPY".to_string()],
+                        case_insensitive: true,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[(COMPRESSION, CONF_FILE)]).remove(0))
+                .recommended_value(PropertyValueSpec {
+                    from_version: Some("0.1.0".to_string()),
+                    to_version: None,
+                    value: "GZIP".to_string(),
+                    when: None,
+                    platform: None,
+                })
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+                .build(),
+            )
+            .build()
+            .unwrap();
+
+        let mut user_data = HashMap::new();
+        user_data.insert(COMPRESSION.to_string(), value.to_string());
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get(COMPRESSION), Some(&expected));
+    }
+
+    #[rstest]
+    #[case(false, None, "1e2", PropertyValidationResult::Valid("1e2".to_string()))]
+    #[case(false, None, "NaN", PropertyValidationResult::Error(Error::NonFiniteFloatNotAllowed {
+        property_name: get_conf_property_names(&[(RATIO, CONF_FILE)]).remove(0),
+        value: "NaN".to_string(),
+    }))]
+    #[case(false, None, "inf", PropertyValidationResult::Error(Error::NonFiniteFloatNotAllowed {
+        property_name: get_conf_property_names(&[(RATIO, CONF_FILE)]).remove(0),
+        value: "inf".to_string(),
+    }))]
+    #[case(true, None, "NaN", PropertyValidationResult::Valid("NaN".to_string()))]
+    #[case(false, Some(true), "NaN", PropertyValidationResult::Valid("NaN".to_string()))]
+    #[case(true, Some(false), "-inf", PropertyValidationResult::Error(Error::NonFiniteFloatNotAllowed {
+        property_name: get_conf_property_names(&[(RATIO, CONF_FILE)]).remove(0),
+        value: "-inf".to_string(),
+    }))]
+    #[trace]
+    fn test_get_with_non_finite_floats(
+        #[case] allow_non_finite_floats: bool,
+        #[case] allow_non_finite: Option<bool>,
+        #[case] value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) {
+        let mut property_spec = PropertySpecBuilder::new(
+            Datatype::Float {
+                min: None,
+                max: Some("1000.0".to_string()),
+                unit: None,
+                accepted_units: None,
+                default_unit: None,
+            },
+            "0.1.0",
+        )
+        .property_name(get_conf_property_names(&[(RATIO, CONF_FILE)]).remove(0))
+        .role(Role {
+            name: ROLE_1.to_string(),
+            required: false,
+        });
+        if let Some(allow_non_finite) = allow_non_finite {
+            property_spec = property_spec.allow_non_finite(allow_non_finite);
+        }
+
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(property_spec.build())
+            .build()
+            .unwrap()
+            .with_validation_policy(ValidationPolicy {
+                allow_non_finite_floats,
+                ..ValidationPolicy::default()
+            });
+
+        let mut user_data = HashMap::new();
+        user_data.insert(RATIO.to_string(), value.to_string());
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get(RATIO), Some(&expected));
+    }
+
+    #[rstest]
+    #[case(None, "", PropertyValidationResult::Warn("".to_string(), Error::PropertySpecRoleNotFound {
+        name: get_conf_property_names(&[(RATIO, CONF_FILE)]).remove(0),
+        role: ROLE_2.to_string(),
+    }))]
+    #[case(Some(false), "", PropertyValidationResult::Warn("".to_string(), Error::PropertySpecRoleNotFound {
+        name: get_conf_property_names(&[(RATIO, CONF_FILE)]).remove(0),
+        role: ROLE_2.to_string(),
+    }))]
+    #[case(Some(true), "", PropertyValidationResult::Valid("".to_string()))]
+    #[case(Some(true), "2000.0", PropertyValidationResult::Error(Error::PropertyValueOutOfBounds {
+        property_name: get_conf_property_names(&[(RATIO, CONF_FILE)]).remove(0),
+        received: "2000".to_string(),
+        expected: "1000".to_string(),
+    }))]
+    #[trace]
+    fn test_get_with_allow_empty(
+        #[case] allow_empty: Option<bool>,
+        #[case] value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) {
+        let mut property_spec = PropertySpecBuilder::new(
+            Datatype::Float {
+                min: None,
+                max: Some("1000.0".to_string()),
+                unit: None,
+                accepted_units: None,
+                default_unit: None,
+            },
+            "0.1.0",
+        )
+        .property_name(get_conf_property_names(&[(RATIO, CONF_FILE)]).remove(0))
+        .role(Role {
+            name: ROLE_1.to_string(),
+            required: false,
+        });
+        if let Some(allow_empty) = allow_empty {
+            property_spec = property_spec.allow_empty(allow_empty);
+        }
+
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(property_spec.build())
+            .build()
+            .unwrap();
+
+        let mut user_data = HashMap::new();
+        user_data.insert(RATIO.to_string(), value.to_string());
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_2),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get(RATIO), Some(&expected));
+    }
+
+    #[rstest]
+    #[case(None, " 'snappy' ", PropertyValidationResult::Valid("SNAPPY".to_string()))]
+    #[case(
+        Some(Normalization { strip_quotes: Some(false), ..Normalization::default() }),
+        " 'snappy' ",
+        PropertyValidationResult::Error(Error::PropertyValueNotInAllowedValues {
+            property_name: get_conf_property_names(&[(COMPRESSION, CONF_FILE)]).remove(0),
+            value: "'snappy'".to_string(),
+            allowed_values: vec!["GZIP".to_string(), "SNAPPY".to_string()],
+        })
+    )]
+    #[trace]
+    fn test_get_with_normalization(
+        #[case] normalization: Option<Normalization>,
+        #[case] value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) {
+        let mut property_spec = PropertySpecBuilder::new(
+            Datatype::Enum {
+                values: vec!["GZIP".to_string(), "SNAPPY".to_string()],
+                case_insensitive: true,
+            },
+            "0.1.0",
+        )
+        .property_name(get_conf_property_names(&[(COMPRESSION, CONF_FILE)]).remove(0))
+        .role(Role {
+            name: ROLE_1.to_string(),
+            required: false,
+        });
+        if let Some(normalization) = normalization {
+            property_spec = property_spec.normalization(normalization);
+        }
+
+        let spec = ProductConfigSpecBuilder::new()
+            .normalization(Normalization {
+                trim: Some(true),
+                strip_quotes: Some(true),
+                collapse_whitespace: None,
+            })
+            .property_spec(property_spec.build())
+            .build()
+            .unwrap();
+
+        let mut user_data = HashMap::new();
+        user_data.insert(COMPRESSION.to_string(), value.to_string());
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get(COMPRESSION), Some(&expected));
+    }
+
+    #[rstest]
+    #[case("gzip, snappy", PropertyValidationResult::Valid("GZIP,SNAPPY".to_string()))]
+    #[case("GZIP", PropertyValidationResult::Valid("GZIP".to_string()))]
+    #[case("gzip,lz4", PropertyValidationResult::Error(Error::PropertyValueNotInAllowedValues {
+        property_name: get_conf_property_names(&[(COMPRESSION, CONF_FILE)]).remove(0),
+        value: "lz4".to_string(),
+        allowed_values: vec!["GZIP".to_string(), "SNAPPY".to_string()],
+    }))]
+    #[case("gzip,GZIP", PropertyValidationResult::Error(Error::ListItemNotUnique {
+        property_name: get_conf_property_names(&[(COMPRESSION, CONF_FILE)]).remove(0),
+        value: "GZIP,GZIP".to_string(),
+        item: "GZIP".to_string(),
+    }))]
+    #[case("gzip,snappy,gzip", PropertyValidationResult::Error(Error::ListItemCountOutOfBounds {
+        property_name: get_conf_property_names(&[(COMPRESSION, CONF_FILE)]).remove(0),
+        value: "GZIP,SNAPPY,GZIP".to_string(),
+        count: 3,
+        expected: "1..2 items".to_string(),
+    }))]
+    #[trace]
+    fn test_get_with_list(#[case] value: &str, #[case] expected: PropertyValidationResult) {
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Enum {
+                        values: vec!["GZIP".to_string(), "SNAPPY".to_string()],
+                        case_insensitive: true,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[(COMPRESSION, CONF_FILE)]).remove(0))
+                .list(ListSpec {
+                    min_items: Some(1),
+                    max_items: Some(2),
+                    unique: true,
+                    ..ListSpec::default()
+                })
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+                .build(),
+            )
+            .build()
+            .unwrap();
+
+        let mut user_data = HashMap::new();
+        user_data.insert(COMPRESSION.to_string(), value.to_string());
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get(COMPRESSION), Some(&expected));
+    }
+
+    const HEAP_SIZE: &str = "heap.size";
+
+    #[rstest]
+    #[case("512mb", Ok(vec![("unit", "mb"), ("value", "512")]))]
+    #[case("2gb", Ok(vec![("unit", "gb"), ("value", "2")]))]
+    #[case("512", Err(Error::DatatypeRegexNotMatching {
+        property_name: get_conf_property_names(&[(HEAP_SIZE, CONF_FILE)]).remove(0),
+        value: "512".to_string(),
+    }))]
+    #[trace]
+    fn test_unit_captures(#[case] value: &str, #[case] expected: Result<Vec<(&str, &str)>, Error>) {
+        let spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("memory", r"^(?P<value>\d+)(?P<unit>mb|gb)$").build())
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::String {
+                        min: None,
+                        max: None,
+                        unit: Some("memory".to_string()),
+                        accepted_units: None,
+                        default_unit: None,
+                        length_unit: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[(HEAP_SIZE, CONF_FILE)]).remove(0))
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+                .build(),
+            )
+            .build()
+            .unwrap();
+
+        let result = spec.unit_captures(
+            &PropertyNameKind::Conf(CONF_FILE.to_string()),
+            HEAP_SIZE,
+            value,
+        );
+
+        match expected {
+            Ok(groups) => {
+                let captures = result.unwrap();
+                assert_eq!(captures.unit, "memory");
+                assert_eq!(
+                    captures
+                        .groups
+                        .iter()
+                        .map(|(name, group)| (name.as_str(), group.as_str()))
+                        .collect::<Vec<_>>(),
+                    groups
+                );
+            }
+            Err(err) => assert_eq!(result, Err(err)),
+        }
+    }
+
+    #[rstest]
+    #[case(None, None, "12abc", true, 1)]
+    #[case(Some(true), None, "12abc", false, 0)]
+    #[case(Some(true), Some(false), "12abc", true, 1)]
+    #[case(None, Some(true), "12abc", false, 0)]
+    #[case(None, Some(true), "12", true, 0)]
+    #[trace]
+    fn test_full_match(
+        #[case] full_match: Option<bool>,
+        #[case] unit_full_match: Option<bool>,
+        #[case] value: &str,
+        #[case] valid: bool,
+        #[case] lint_warnings: usize,
+    ) {
+        let mut unit = UnitBuilder::new("number", "[0-9]+");
+        if let Some(unit_full_match) = unit_full_match {
+            unit = unit.full_match(unit_full_match);
+        }
+        let mut builder = ProductConfigSpecBuilder::new()
+            .unit(unit.build())
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::String {
+                        min: None,
+                        max: None,
+                        unit: Some("number".to_string()),
+                        accepted_units: None,
+                        default_unit: None,
+                        length_unit: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[(DATA_DIR, CONF_FILE)]).remove(0))
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+                .build(),
+            );
+        if let Some(full_match) = full_match {
+            builder = builder.full_match(full_match);
+        }
+        let spec = builder.build().unwrap();
+
+        let mut user_data = HashMap::new();
+        user_data.insert(DATA_DIR.to_string(), value.to_string());
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(
+            matches!(
+                result.get(DATA_DIR),
+                Some(PropertyValidationResult::Valid(_))
+            ),
+            valid
+        );
+        assert_eq!(spec.lint().len(), lint_warnings);
+    }
+
+    #[rstest]
+    #[case("heap-size", "512mb", Ok(PropertyValidationResult::Valid("512mb".to_string())))]
+    #[case("heap-size", "5120mb", Ok(PropertyValidationResult::Error(Error::StringLengthOutOfBounds {
+        property_name: get_conf_property_names(&[(HEAP_SIZE, CONF_FILE)]).remove(0),
+        value: "5120mb".to_string(),
+        length: 6,
+        length_unit: LengthUnit::Bytes,
+        expected: "5".to_string(),
+    })))]
+    #[case("unknown", "512mb", Err(Error::DatatypeDefinitionNotFound {
+        property_name: get_conf_property_names(&[(HEAP_SIZE, CONF_FILE)]).remove(0),
+        name: "unknown".to_string(),
+    }))]
+    #[trace]
+    fn test_get_with_datatype_ref(
+        #[case] datatype_ref: &str,
+        #[case] value: &str,
+        #[case] expected: Result<PropertyValidationResult, Error>,
+    ) {
+        let property_spec = format!(
+            r#"{{
+                "property_names": [{{"name": "{}", "kind": {{"type": "conf", "file": "{}"}}}}],
+                "datatype": {{"ref": "{}"}},
+                "roles": [{{"name": "{}", "required": false}}],
+                "as_of_version": "0.1.0"
+            }}"#,
+            HEAP_SIZE, CONF_FILE, datatype_ref, ROLE_1
+        );
+
+        let spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("memory", r"^\d+(mb|gb)$").build())
+            .datatype(
+                "heap-size",
+                Datatype::String {
+                    min: None,
+                    max: Some("5".to_string()),
+                    unit: Some("memory".to_string()),
+                    accepted_units: None,
+                    default_unit: None,
+                    length_unit: None,
+                },
+            )
+            .property_spec(serde_json::from_str(&property_spec).unwrap())
+            .build();
+
+        let mut user_data = HashMap::new();
+        user_data.insert(HEAP_SIZE.to_string(), value.to_string());
+
+        let result = spec.map(|spec| {
+            spec.get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap()
+            .remove(HEAP_SIZE)
+            .unwrap()
+        });
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("compression-codecs", "zstd", Ok(PropertyValidationResult::Valid("zstd".to_string())))]
+    #[case("compression-codecs", "none", Ok(PropertyValidationResult::Valid("none".to_string())))]
+    #[case("compression-codecs", "brotli", Ok(PropertyValidationResult::Error(Error::PropertyValueNotInAllowedValues {
+        property_name: get_conf_property_names(&[(COMPRESSION, CONF_FILE)]).remove(0),
+        value: "brotli".to_string(),
+        allowed_values: vec!["none".to_string(), "gzip".to_string(), "snappy".to_string(), "lz4".to_string(), "zstd".to_string()],
+    })))]
+    #[case("unknown", "zstd", Err(Error::ValueSetNotFound {
+        property_name: get_conf_property_names(&[(COMPRESSION, CONF_FILE)]).remove(0),
+        name: "unknown".to_string(),
+    }))]
+    #[trace]
+    fn test_get_with_value_set(
+        #[case] value_set: &str,
+        #[case] value: &str,
+        #[case] expected: Result<PropertyValidationResult, Error>,
+    ) {
+        let spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("codec", "^[a-z0-9]+$").build())
+            .value_set("compression-codecs", &["gzip", "snappy", "lz4", "zstd"])
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::String {
+                        min: None,
+                        max: None,
+                        unit: Some("codec".to_string()),
+                        accepted_units: None,
+                        default_unit: None,
+                        length_unit: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[(COMPRESSION, CONF_FILE)]).remove(0))
+                .allowed_value("none")
+                .allowed_value_set(value_set)
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+                .build(),
+            )
+            .build();
+
+        let mut user_data = HashMap::new();
+        user_data.insert(COMPRESSION.to_string(), value.to_string());
+
+        let result = spec.map(|spec| {
+            spec.get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap()
+            .remove(COMPRESSION)
+            .unwrap()
+        });
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(None, "${data.dir}/logs", PropertyValidationResult::Valid("/data/logs".to_string()))]
+    #[case(Some("/var/lib"), "${data.dir}/logs", PropertyValidationResult::Valid("/var/lib/logs".to_string()))]
+    #[case(None, "${log.dirs}", PropertyValidationResult::Error(Error::PlaceholderCycle {
+        property_name: get_conf_property_names(&[(LOG_DIRS, CONF_FILE)]).remove(0),
+        cycle: vec![LOG_DIRS.to_string(), LOG_DIRS.to_string()],
+    }))]
+    #[case(None, "${missing}/logs", PropertyValidationResult::Error(Error::PlaceholderNotResolved {
+        property_name: get_conf_property_names(&[(LOG_DIRS, CONF_FILE)]).remove(0),
+        reference: "missing".to_string(),
+    }))]
+    #[trace]
+    fn test_get_with_placeholders(
+        #[case] data_dir: Option<&str>,
+        #[case] log_dirs: &str,
+        #[case] expected: PropertyValidationResult,
+    ) {
+        let datatype = Datatype::String {
+            min: None,
+            max: None,
+            unit: Some("path".to_string()),
+            accepted_units: None,
+            default_unit: None,
+            length_unit: None,
+        };
+        let role = Role {
+            name: ROLE_1.to_string(),
+            required: false,
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("path", "^/.*$").build())
+            .property_spec(
+                PropertySpecBuilder::new(datatype.clone(), "0.1.0")
+                    .property_name(get_conf_property_names(&[(DATA_DIR, CONF_FILE)]).remove(0))
+                    .recommended_value(PropertyValueSpec {
+                        from_version: None,
+                        to_version: None,
+                        when: None,
+                        platform: None,
+                        value: "/data".to_string(),
+                    })
+                    .role(Role {
+                        name: ROLE_1.to_string(),
+                        required: true,
+                    })
+                    .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(datatype, "0.1.0")
+                    .property_name(get_conf_property_names(&[(LOG_DIRS, CONF_FILE)]).remove(0))
+                    .role(role)
+                    .build(),
+            )
+            .build()
+            .unwrap();
+
+        let mut user_data = HashMap::new();
+        user_data.insert(LOG_DIRS.to_string(), log_dirs.to_string());
+        if let Some(data_dir) = data_dir {
+            user_data.insert(DATA_DIR.to_string(), data_dir.to_string());
+        }
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get(LOG_DIRS), Some(&expected));
+    }
+
+    struct TestSecrets;
+
+    impl SecretResolver for TestSecrets {
+        fn resolve(
+            &self,
+            _property_name: &PropertyName,
+            value: &str,
+        ) -> Option<Result<SecretValue, String>> {
+            if value.starts_with("vault://") {
+                return Some(Ok(SecretValue::Opaque));
+            }
+            match value.strip_prefix("secretref:")? {
+                "db" => Some(Ok(SecretValue::Resolved("s3cr3t".to_string()))),
+                "legacy" => Some(Ok(SecretValue::Resolved("S3CR3T!".to_string()))),
+                _ => Some(Err("secret not found".to_string())),
+            }
+        }
+    }
+
+    const PASSWORD: &str = "db.password";
+
+    #[rstest]
+    #[case("s3cr3t", PropertyValidationResult::Valid("s3cr3t".to_string()))]
+    #[case("secretref:db", PropertyValidationResult::Valid("secretref:db".to_string()))]
+    #[case("vault://db#password", PropertyValidationResult::Valid("vault://db#password".to_string()))]
+    #[case("secretref:legacy", PropertyValidationResult::Error(Error::DatatypeRegexNotMatching {
+        property_name: get_conf_property_names(&[(PASSWORD, CONF_FILE)]).remove(0),
+        value: "***".to_string(),
+    }))]
+    #[case("secretref:other", PropertyValidationResult::Error(Error::SecretNotResolved {
+        property_name: get_conf_property_names(&[(PASSWORD, CONF_FILE)]).remove(0),
+        reference: "secretref:other".to_string(),
+        reason: "secret not found".to_string(),
+    }))]
+    #[trace]
+    fn test_get_with_secret_resolver(
+        #[case] value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) {
+        let mut spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("password", "^[a-z0-9]+$").build())
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::String {
+                        min: None,
+                        max: None,
+                        unit: Some("password".to_string()),
+                        accepted_units: None,
+                        default_unit: None,
+                        length_unit: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[(PASSWORD, CONF_FILE)]).remove(0))
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+                .build(),
+            )
+            .build()
+            .unwrap();
+        spec.register_secret_resolver(Box::new(TestSecrets));
+
+        let mut user_data = HashMap::new();
+        user_data.insert(PASSWORD.to_string(), value.to_string());
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get(PASSWORD), Some(&expected));
+    }
+
+    #[rstest]
+    #[case(false, "s3cr3t", PropertyValidationResult::Valid("s3cr3t".to_string()))]
+    #[case(true, "s3cr3t", PropertyValidationResult::Valid("s3cr3t".to_string()))]
+    #[case(false, "S3CR3T!", PropertyValidationResult::Error(Error::DatatypeRegexNotMatching {
+        property_name: get_conf_property_names(&[(PASSWORD, CONF_FILE)]).remove(0),
+        value: "S3CR3T!".to_string(),
+    }))]
+    #[case(true, "S3CR3T!", PropertyValidationResult::Error(Error::DatatypeRegexNotMatching {
+        property_name: get_conf_property_names(&[(PASSWORD, CONF_FILE)]).remove(0),
+        value: "***".to_string(),
+    }))]
+    #[trace]
+    fn test_get_sensitive(
+        #[case] sensitive: bool,
+        #[case] value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) {
+        let spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("password", "^[a-z0-9]+$").build())
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::String {
+                        min: None,
+                        max: None,
+                        unit: Some("password".to_string()),
+                        accepted_units: None,
+                        default_unit: None,
+                        length_unit: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[(PASSWORD, CONF_FILE)]).remove(0))
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+                .immutable(true)
+                .sensitive(sensitive)
+                .build(),
+            )
+            .build()
+            .unwrap();
+
+        let mut user_data = HashMap::new();
+        user_data.insert(PASSWORD.to_string(), value.to_string());
+
+        let result = spec
+            .get(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                Some(ROLE_1),
+                &user_data,
+            )
+            .unwrap();
+
+        assert_eq!(result.get(PASSWORD), Some(&expected));
+
+        let change = spec
+            .validate_change(
+                VERSION_0_5_0,
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                PASSWORD,
+                Some("old"),
+                "new",
+            )
+            .unwrap_err()
+            .to_string();
+
+        assert_eq!(change.contains("old"), !sensitive);
+        assert_eq!(change.contains("***"), sensitive);
+    }
+
+    #[rstest]
+    #[case(false, "x")]
+    #[case(true, "***")]
+    #[trace]
+    fn test_redact(#[case] redact_unknown: bool, #[case] expected_unknown: &str) {
+        let property = |name: &str| {
+            PropertySpecBuilder::new(
+                Datatype::String {
+                    min: None,
+                    max: None,
+                    unit: Some("text".to_string()),
+                    accepted_units: None,
+                    default_unit: None,
+                    length_unit: None,
+                },
+                "0.1.0",
+            )
+            .property_name(get_conf_property_names(&[(name, CONF_FILE)]).remove(0))
+            .role(Role {
+                name: ROLE_1.to_string(),
+                required: false,
+            })
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("text", "^.*$").build())
+            .property_spec(property(PASSWORD).sensitive(true).build())
+            .property_spec(property(DATA_DIR).sensitive(false).build())
+            .property_spec(property("codec").build())
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Password {
+                        min_length: Some(12),
+                        required_classes: None,
+                        min_entropy: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(get_conf_property_names(&[("admin", CONF_FILE)]).remove(0))
+                .role(Role {
+                    name: ROLE_1.to_string(),
+                    required: false,
+                })
+                .build(),
+            )
+            .build()
+            .unwrap();
+
+        let values = [
+            (PASSWORD, "s3cr3t"),
+            ("admin", "admin"),
+            ("codec", "tls"),
+            (DATA_DIR, "/data"),
+            ("unknown", "x"),
+        ]
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect::<HashMap<_, _>>();
+
+        let result = spec.redact(
+            &PropertyNameKind::Conf(CONF_FILE.to_string()),
+            &values,
+            redact_unknown,
+        );
+
+        assert_eq!(result[PASSWORD], "***");
+        assert_eq!(result["admin"], "***");
+        assert_eq!(result["codec"], "tls");
+        assert_eq!(result[DATA_DIR], "/data");
+        assert_eq!(result["unknown"], expected_unknown);
+    }
+
+    #[test]
+    fn test_invalid_rule() {
+        let result = ProductConfigSpecBuilder::new()
+            .rule(Rule {
+                expression: "required(".to_string(),
+                kind: None,
+                comment: None,
+            })
+            .build();
+
+        assert!(matches!(result, Err(Error::InvalidRule { .. })));
+    }
+
     #[test]
     fn test_product_config_result_order() {
         let valid = PropertyValidationResult::Valid("valid".to_string());
@@ -0,0 +1,342 @@
+//! Rendering of Markdown in descriptions to plain text (e.g. for the help output of CLIs) or
+//! HTML (e.g. for web UIs).
+//!
+//! The supported subset covers what descriptions usually need: paragraphs, headings (`#`),
+//! bullet lists (`-` or `*`), emphasis (`*text*`, `_text_`), strong emphasis (`**text**`),
+//! inline code (`` `code` ``) and links (`[text](url)`). A backslash escapes the next character,
+//! everything else is kept as text.
+//!
+//! ```
+//! use product_config::markdown;
+//!
+//! let description = "Use **at least** `3` brokers, see [docs](https://kafka.apache.org)";
+//!
+//! assert_eq!(
+//!     markdown::to_plain_text(description),
+//!     "Use at least 3 brokers, see docs (https://kafka.apache.org)"
+//! );
+//! assert_eq!(
+//!     markdown::to_html(description),
+//!     "<p>Use <strong>at least</strong> <code>3</code> brokers, see <a href=\"https://kafka.apache.org\">docs</a></p>"
+//! );
+//! ```
+
+#[derive(Debug, PartialEq)]
+enum Block<'a> {
+    // the level (1 - 6) and the text
+    Heading(usize, String),
+    Paragraph(String),
+    // the text of every item
+    List(Vec<&'a str>),
+}
+
+/// Renders the Markdown to plain text: formatting is removed, links are written as
+/// `text (url)`, list items start with `- ` and blocks are separated by an empty line.
+///
+/// # Arguments
+///
+/// * `markdown` - the Markdown text, e.g. a description
+///
+pub fn to_plain_text(markdown: &str) -> String {
+    parse_blocks(markdown)
+        .iter()
+        .map(|block| match block {
+            Block::Heading(_, text) | Block::Paragraph(text) => render_inline(text, false),
+            Block::List(items) => items
+                .iter()
+                .map(|item| format!("- {}", render_inline(item, false)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Renders the Markdown to HTML (one element per line). Text is escaped, so the result can be
+/// embedded into web pages as it is.
+///
+/// # Arguments
+///
+/// * `markdown` - the Markdown text, e.g. a description
+///
+pub fn to_html(markdown: &str) -> String {
+    parse_blocks(markdown)
+        .iter()
+        .map(|block| match block {
+            Block::Heading(level, text) => {
+                format!("<h{}>{}</h{}>", level, render_inline(text, true), level)
+            }
+            Block::Paragraph(text) => format!("<p>{}</p>", render_inline(text, true)),
+            Block::List(items) => format!(
+                "<ul>{}</ul>",
+                items
+                    .iter()
+                    .map(|item| format!("<li>{}</li>", render_inline(item, true)))
+                    .collect::<String>()
+            ),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders the inline formatting of a single line as HTML (without enclosing paragraph), e.g.
+/// for table cells
+///
+/// # Arguments
+///
+/// * `markdown` - the Markdown text of a single line
+///
+pub(crate) fn inline_to_html(markdown: &str) -> String {
+    render_inline(markdown, true)
+}
+
+/// Splits the Markdown into headings, paragraphs (consecutive lines joined by a space) and
+/// lists (consecutive list items)
+///
+/// # Arguments
+///
+/// * `markdown` - the Markdown text
+///
+fn parse_blocks(markdown: &str) -> Vec<Block<'_>> {
+    let mut blocks = Vec::new();
+
+    for line in markdown.lines().map(str::trim) {
+        let heading_level = line.chars().take_while(|c| *c == '#').count();
+        let list_item = line
+            .strip_prefix("- ")
+            .or_else(|| line.strip_prefix("* "))
+            .or_else(|| line.strip_prefix("+ "));
+
+        if line.is_empty() {
+            // an empty line ends the current paragraph or list
+            blocks.push(None);
+        } else if (1..=6).contains(&heading_level) && line[heading_level..].starts_with(' ') {
+            blocks.push(Some(Block::Heading(
+                heading_level,
+                line[heading_level..].trim().to_string(),
+            )));
+            blocks.push(None);
+        } else if let Some(item) = list_item {
+            match blocks.last_mut() {
+                Some(Some(Block::List(items))) => items.push(item.trim()),
+                _ => blocks.push(Some(Block::List(vec![item.trim()]))),
+            }
+        } else {
+            match blocks.last_mut() {
+                Some(Some(Block::Paragraph(text))) => {
+                    text.push(' ');
+                    text.push_str(line);
+                }
+                _ => blocks.push(Some(Block::Paragraph(line.to_string()))),
+            }
+        }
+    }
+
+    blocks.into_iter().flatten().collect()
+}
+
+/// Renders emphasis, inline code and links of a text either as HTML (escaping the text) or as
+/// plain text (removing the formatting). Delimiters without closing counterpart are kept as
+/// text.
+///
+/// # Arguments
+///
+/// * `text` - the text of a heading, paragraph or list item
+/// * `html` - if HTML or plain text is rendered
+///
+fn render_inline(text: &str, html: bool) -> String {
+    let mut rendered = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+
+        // (opening delimiter, closing delimiter, html tag)
+        let delimited = match c {
+            '\\' => {
+                if let Some(escaped) = after.chars().next() {
+                    push_text(&mut rendered, &escaped.to_string(), html);
+                    rest = &after[escaped.len_utf8()..];
+                    continue;
+                }
+                None
+            }
+            '`' => Some(("`", "`", "code")),
+            '*' if after.starts_with('*') => Some(("**", "**", "strong")),
+            '*' => Some(("*", "*", "em")),
+            // underscores within words (e.g. `log_dirs`) are no emphasis
+            '_' if !rendered.ends_with(|c: char| c.is_alphanumeric()) => Some(("_", "_", "em")),
+            '[' => {
+                if let Some((label, url, remaining)) = parse_link(after) {
+                    let label = render_inline(label, html);
+                    if html {
+                        rendered.push_str(&format!("<a href=\"{}\">{}</a>", escape(url), label));
+                    } else {
+                        rendered.push_str(&format!("{} ({})", label, url));
+                    }
+                    rest = remaining;
+                    continue;
+                }
+                None
+            }
+            _ => None,
+        };
+
+        if let Some((open, close, tag)) = delimited {
+            let inner_start = &rest[open.len()..];
+            if let Some(end) = find_closing(inner_start, close, tag != "code") {
+                let inner = &inner_start[..end];
+                // code is rendered as it is, emphasis may contain further formatting
+                let inner = if tag == "code" {
+                    let mut code = String::new();
+                    push_text(&mut code, inner, html);
+                    code
+                } else {
+                    render_inline(inner, html)
+                };
+                if html {
+                    rendered.push_str(&format!("<{}>{}</{}>", tag, inner, tag));
+                } else {
+                    rendered.push_str(&inner);
+                }
+                rest = &inner_start[end + close.len()..];
+                continue;
+            }
+        }
+
+        push_text(&mut rendered, &c.to_string(), html);
+        rest = after;
+    }
+
+    rendered
+}
+
+/// Returns the position of the closing delimiter in the text after the opening delimiter or None
+/// if the text is not delimited. Emphasis has to start and end with a non whitespace character
+/// (so `5 * 3` is no emphasis) and ends at the last delimiter of a run (e.g. `**a *b***`).
+///
+/// # Arguments
+///
+/// * `text` - the text after the opening delimiter
+/// * `close` - the closing delimiter
+/// * `emphasis` - if the delimiter is emphasis (or inline code)
+///
+fn find_closing(text: &str, close: &str, emphasis: bool) -> Option<usize> {
+    if emphasis && text.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let (end, _) = text
+        .match_indices(close)
+        .find(|(end, _)| *end > 0 && !(emphasis && text[..*end].ends_with(char::is_whitespace)))?;
+
+    if !emphasis {
+        return Some(end);
+    }
+    // the first delimiters of a run close the inner emphasis
+    let extra = text[end + close.len()..]
+        .chars()
+        .take_while(|c| close.starts_with(*c))
+        .count();
+    Some(end + extra)
+}
+
+/// Returns label, url and the remaining text of a link like `[label](url)` (without the opening
+/// bracket) or None if the text does not start with a link
+///
+/// # Arguments
+///
+/// * `text` - the text after the opening bracket
+///
+fn parse_link(text: &str) -> Option<(&str, &str, &str)> {
+    let label_end = text.find("](")?;
+    let url_start = &text[label_end + 2..];
+    let url_end = url_start.find(')')?;
+    Some((
+        &text[..label_end],
+        url_start[..url_end].trim(),
+        &url_start[url_end + 1..],
+    ))
+}
+
+/// Appends the text, escaped if HTML is rendered
+fn push_text(rendered: &mut String, text: &str, html: bool) {
+    if html {
+        rendered.push_str(&escape(text));
+    } else {
+        rendered.push_str(text);
+    }
+}
+
+/// Returns the text with the characters escaped that have a meaning in HTML
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::markdown::{parse_blocks, render_inline, to_html, to_plain_text, Block};
+    use rstest::*;
+
+    #[rstest]
+    #[case("plain text", "plain text", "plain text")]
+    #[case(
+        "**strong** and *em*",
+        "strong and em",
+        "<strong>strong</strong> and <em>em</em>"
+    )]
+    #[case(
+        "_em_ but log_dirs_x",
+        "em but log_dirs_x",
+        "<em>em</em> but log_dirs_x"
+    )]
+    #[case("`a < b`", "a < b", "<code>a &lt; b</code>")]
+    #[case("`**raw**`", "**raw**", "<code>**raw**</code>")]
+    #[case("**nested *em***", "nested em", "<strong>nested <em>em</em></strong>")]
+    #[case(
+        "[docs](https://x.org)",
+        "docs (https://x.org)",
+        "<a href=\"https://x.org\">docs</a>"
+    )]
+    #[case("5 * 3 and a * b", "5 * 3 and a * b", "5 * 3 and a * b")]
+    #[case("unclosed **strong", "unclosed **strong", "unclosed **strong")]
+    #[case(r"\*not em\*", "*not em*", "*not em*")]
+    #[case("[no link]", "[no link]", "[no link]")]
+    #[trace]
+    fn test_render_inline(#[case] text: &str, #[case] plain: &str, #[case] html: &str) {
+        assert_eq!(render_inline(text, false), plain);
+        assert_eq!(render_inline(text, true), html);
+    }
+
+    #[test]
+    fn test_parse_blocks() {
+        let markdown = "# Heap\nThe heap\nsize.\n\n- min: 1g\n* max: 31g\n\nNot #1 choice";
+
+        assert_eq!(
+            parse_blocks(markdown),
+            vec![
+                Block::Heading(1, "Heap".to_string()),
+                Block::Paragraph("The heap size.".to_string()),
+                Block::List(vec!["min: 1g", "max: 31g"]),
+                Block::Paragraph("Not #1 choice".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_blocks() {
+        let markdown = "## Heap\nThe **heap** size:\n- min: `1g`\n- max: `31g`";
+
+        assert_eq!(
+            to_plain_text(markdown),
+            "Heap\n\nThe heap size:\n\n- min: 1g\n- max: 31g"
+        );
+        assert_eq!(
+            to_html(markdown),
+            "<h2>Heap</h2>\n<p>The <strong>heap</strong> size:</p>\n<ul><li>min: <code>1g</code></li><li>max: <code>31g</code></li></ul>"
+        );
+    }
+}
@@ -0,0 +1,394 @@
+use crate::error::Error;
+use crate::rules::CompiledRule;
+use crate::types::{
+    MergeStrategy, PropertyGroup, PropertyNamePrefix, PropertySpec, Role, Rule, Unit,
+};
+use crate::validation::ValidationResult;
+use crate::ProductConfigSpec;
+use std::collections::BTreeMap;
+
+/// Merge the units of two specs. Units are identified via their name.
+///
+/// # Arguments
+///
+/// * `units` - the units of the base spec
+/// * `other_units` - the units of the spec to be merged into the base spec
+/// * `strategy` - how to handle units that exist in both specs
+///
+pub(crate) fn merge_units(
+    units: &[Unit],
+    other_units: &[Unit],
+    strategy: MergeStrategy,
+) -> ValidationResult<Vec<Unit>> {
+    let mut merged = units.to_vec();
+
+    for other in other_units {
+        match merged.iter_mut().find(|unit| unit.name == other.name) {
+            None => merged.push(other.clone()),
+            Some(unit) => {
+                if strategy == MergeStrategy::Error && unit != other {
+                    return Err(Error::UnitMergeConflict {
+                        unit: other.name.clone(),
+                    });
+                }
+                // a unit consists of a single regex pattern, so extend behaves like replace
+                *unit = other.clone();
+            }
+        }
+    }
+
+    Ok(merged)
+}
+
+/// Merge the named definitions (e.g. datatypes, value sets) of two specs. Definitions are
+/// identified via their name.
+///
+/// # Arguments
+///
+/// * `definitions` - the definitions of the base spec
+/// * `other_definitions` - the definitions of the spec to be merged into the base spec
+/// * `strategy` - how to handle definitions that exist in both specs
+/// * `conflict` - the error for differing definitions with the same name
+///
+pub(crate) fn merge_definitions<T: Clone + PartialEq>(
+    definitions: &BTreeMap<String, T>,
+    other_definitions: &BTreeMap<String, T>,
+    strategy: MergeStrategy,
+    conflict: fn(String) -> Error,
+) -> ValidationResult<BTreeMap<String, T>> {
+    let mut merged = definitions.clone();
+
+    for (name, other) in other_definitions {
+        if strategy == MergeStrategy::Error
+            && merged
+                .get(name)
+                .is_some_and(|definition| definition != other)
+        {
+            return Err(conflict(name.clone()));
+        }
+        // a definition is replaced as a whole, so extend behaves like replace
+        merged.insert(name.clone(), other.clone());
+    }
+
+    Ok(merged)
+}
+
+/// Merge the prefixes of two specs. A prefix of the other spec replaces a prefix with the same kind.
+///
+/// # Arguments
+///
+/// * `prefixes` - the prefixes of the base spec
+/// * `other_prefixes` - the prefixes of the spec to be merged into the base spec
+///
+pub(crate) fn merge_prefixes(
+    prefixes: &[PropertyNamePrefix],
+    other_prefixes: &[PropertyNamePrefix],
+) -> Vec<PropertyNamePrefix> {
+    let mut merged = prefixes.to_vec();
+
+    for other in other_prefixes {
+        match merged.iter_mut().find(|prefix| prefix.kind == other.kind) {
+            None => merged.push(other.clone()),
+            Some(prefix) => *prefix = other.clone(),
+        }
+    }
+
+    merged
+}
+
+/// Merge the groups of two specs. A group of the other spec replaces the group with the same name
+/// (keeping its position), new groups are appended.
+///
+/// # Arguments
+///
+/// * `groups` - the groups of the base spec
+/// * `other_groups` - the groups of the spec to be merged into the base spec
+///
+pub(crate) fn merge_groups(
+    groups: &[PropertyGroup],
+    other_groups: &[PropertyGroup],
+) -> Vec<PropertyGroup> {
+    let mut merged = groups.to_vec();
+
+    for other in other_groups {
+        match merged.iter_mut().find(|group| group.name == other.name) {
+            None => merged.push(other.clone()),
+            Some(group) => *group = other.clone(),
+        }
+    }
+
+    merged
+}
+
+/// Merge the rules of two specs (identical rules are only kept once).
+///
+/// # Arguments
+///
+/// * `rules` - the rules of the base spec
+/// * `other_rules` - the rules of the spec to be merged into the base spec
+///
+pub(crate) fn merge_rules(rules: &[CompiledRule], other_rules: &[CompiledRule]) -> Vec<Rule> {
+    let rules: Vec<Rule> = rules.iter().map(|rule| rule.rule.clone()).collect();
+    let other_rules: Vec<Rule> = other_rules.iter().map(|rule| rule.rule.clone()).collect();
+    join(&rules, &other_rules)
+}
+
+/// Merge the property specs of two specs. Two property specs are considered the same if
+/// they share at least one property name.
+///
+/// # Arguments
+///
+/// * `property_specs` - the property specs of the base spec
+/// * `other_property_specs` - the property specs to be merged into the base spec
+/// * `strategy` - how to handle property specs that exist in both specs
+///
+pub(crate) fn merge_property_specs(
+    property_specs: &[PropertySpec],
+    other_property_specs: &[PropertySpec],
+    strategy: MergeStrategy,
+) -> ValidationResult<Vec<PropertySpec>> {
+    let mut merged = property_specs.to_vec();
+
+    for other in other_property_specs {
+        let existing = merged.iter_mut().find(|spec| {
+            spec.property_names
+                .iter()
+                .any(|name| other.property_names.contains(name))
+        });
+
+        match existing {
+            None => merged.push(other.clone()),
+            Some(spec) => match strategy {
+                MergeStrategy::Replace => *spec = other.clone(),
+                MergeStrategy::Extend => *spec = extend_property_spec(spec, other),
+                MergeStrategy::Error => {
+                    if spec != other {
+                        return Err(Error::PropertySpecMergeConflict {
+                            property_name: other.property_names[0].clone(),
+                        });
+                    }
+                }
+            },
+        }
+    }
+
+    Ok(merged)
+}
+
+/// Merge the products of two specs. Products are identified via their name, the property specs
+/// of products that exist in both specs are merged like `merge_property_specs`.
+///
+/// # Arguments
+///
+/// * `products` - the products of the base spec
+/// * `other_products` - the products to be merged into the base spec
+/// * `strategy` - how to handle property specs that exist in both products
+///
+pub(crate) fn merge_products(
+    products: &BTreeMap<String, ProductConfigSpec>,
+    other_products: &BTreeMap<String, ProductConfigSpec>,
+    strategy: MergeStrategy,
+) -> ValidationResult<BTreeMap<String, Vec<PropertySpec>>> {
+    let mut merged: BTreeMap<String, Vec<PropertySpec>> = products
+        .iter()
+        .map(|(product, spec)| (product.clone(), spec.property_spec_list.clone()))
+        .collect();
+
+    for (product, other) in other_products {
+        let property_specs = match merged.get(product) {
+            None => other.property_spec_list.clone(),
+            Some(property_specs) => {
+                merge_property_specs(property_specs, &other.property_spec_list, strategy)?
+            }
+        };
+        merged.insert(product.clone(), property_specs);
+    }
+
+    Ok(merged)
+}
+
+/// Combine two property specs: lists are joined (without duplicates), single values are
+/// taken from the other spec if provided there.
+///
+/// # Arguments
+///
+/// * `spec` - the base property spec
+/// * `other` - the property spec to extend the base property spec with
+///
+fn extend_property_spec(spec: &PropertySpec, other: &PropertySpec) -> PropertySpec {
+    let mut extra = spec.extra.clone();
+    extra.extend(other.extra.clone());
+
+    PropertySpec {
+        property_names: join(&spec.property_names, &other.property_names),
+        datatype: other.datatype.clone(),
+        default_values: join_optional(&spec.default_values, &other.default_values),
+        recommended_values: join_optional(&spec.recommended_values, &other.recommended_values),
+        recommended_range: other
+            .recommended_range
+            .clone()
+            .or_else(|| spec.recommended_range.clone()),
+        allowed_values: join_optional(&spec.allowed_values, &other.allowed_values),
+        allowed_value_set: other
+            .allowed_value_set
+            .clone()
+            .or_else(|| spec.allowed_value_set.clone()),
+        allowed_pattern: join_optional(&spec.allowed_pattern, &other.allowed_pattern),
+        forbidden_values: join_optional(&spec.forbidden_values, &other.forbidden_values),
+        validator: other.validator.clone().or_else(|| spec.validator.clone()),
+        as_of_version: other.as_of_version.clone(),
+        deprecated_since: other
+            .deprecated_since
+            .clone()
+            .or_else(|| spec.deprecated_since.clone()),
+        removed_in: other.removed_in.clone().or_else(|| spec.removed_in.clone()),
+        stability: other.stability.or(spec.stability),
+        experimental: other.experimental.or(spec.experimental),
+        importance: other.importance.or(spec.importance),
+        list: other.list.clone().or_else(|| spec.list.clone()),
+        normalization: other.normalization.or(spec.normalization),
+        allow_empty: other.allow_empty.or(spec.allow_empty),
+        allow_non_finite: other.allow_non_finite.or(spec.allow_non_finite),
+        radix_prefixes: other.radix_prefixes.or(spec.radix_prefixes),
+        immutable: other.immutable.or(spec.immutable),
+        sensitive: other.sensitive.or(spec.sensitive),
+        deprecated_for: join_optional(&spec.deprecated_for, &other.deprecated_for),
+        depends_on: join_optional(&spec.depends_on, &other.depends_on),
+        conflicts_with: join_optional(&spec.conflicts_with, &other.conflicts_with),
+        platforms: join_optional(&spec.platforms, &other.platforms),
+        roles: join_roles(&spec.roles, &other.roles),
+        restart_required: other.restart_required.or(spec.restart_required),
+        change_policy: other.change_policy.or(spec.change_policy),
+        tags: join_optional(&spec.tags, &other.tags),
+        group: other.group.clone().or_else(|| spec.group.clone()),
+        additional_doc: join_optional(&spec.additional_doc, &other.additional_doc),
+        comment: other.comment.clone().or_else(|| spec.comment.clone()),
+        description: other
+            .description
+            .clone()
+            .or_else(|| spec.description.clone()),
+        extra,
+    }
+}
+
+fn join<T: Clone + PartialEq>(values: &[T], other_values: &[T]) -> Vec<T> {
+    let mut joined = values.to_vec();
+    for value in other_values {
+        if !joined.contains(value) {
+            joined.push(value.clone());
+        }
+    }
+    joined
+}
+
+fn join_optional<T: Clone + PartialEq>(
+    values: &Option<Vec<T>>,
+    other_values: &Option<Vec<T>>,
+) -> Option<Vec<T>> {
+    match (values, other_values) {
+        (None, None) => None,
+        (Some(values), None) => Some(values.clone()),
+        (None, Some(other_values)) => Some(other_values.clone()),
+        (Some(values), Some(other_values)) => Some(join(values, other_values)),
+    }
+}
+
+/// Roles are identified via name: a role of the other spec replaces a role with the same name
+fn join_roles(roles: &Option<Vec<Role>>, other_roles: &Option<Vec<Role>>) -> Option<Vec<Role>> {
+    let mut joined = match roles {
+        None => return other_roles.clone(),
+        Some(roles) => roles.clone(),
+    };
+
+    for other in other_roles.iter().flatten() {
+        match joined.iter_mut().find(|role| role.name == other.name) {
+            None => joined.push(other.clone()),
+            Some(role) => *role = other.clone(),
+        }
+    }
+
+    Some(joined)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder, UnitBuilder};
+    use crate::error::Error;
+    use crate::types::{Datatype, MergeStrategy, PropertyName, PropertyNameKind, Role};
+    use crate::ProductConfigSpec;
+    use rstest::*;
+
+    const COMPRESSION: &str = "compression";
+    const CONF_FILE: &str = "my.config";
+
+    fn compression_name() -> PropertyName {
+        PropertyName {
+            name: COMPRESSION.to_string(),
+            kind: PropertyNameKind::Conf(CONF_FILE.to_string()),
+        }
+    }
+
+    fn spec_with_allowed_values(allowed_values: &[&str]) -> ProductConfigSpec {
+        let mut property_spec = PropertySpecBuilder::new(
+            Datatype::String {
+                min: None,
+                max: None,
+                unit: Some("text".to_string()),
+                accepted_units: None,
+                default_unit: None,
+                length_unit: None,
+            },
+            "0.1.0",
+        )
+        .property_name(compression_name())
+        .role(Role {
+            name: "server".to_string(),
+            required: false,
+        });
+
+        for value in allowed_values {
+            property_spec = property_spec.allowed_value(value);
+        }
+
+        ProductConfigSpecBuilder::new()
+            .unit(UnitBuilder::new("text", ".*").build())
+            .property_spec(property_spec.build())
+            .build()
+            .unwrap()
+    }
+
+    #[rstest]
+    #[case(MergeStrategy::Replace, Ok(vec!["lz4".to_string()]))]
+    #[case(MergeStrategy::Extend, Ok(vec!["gzip".to_string(), "snappy".to_string(), "lz4".to_string()]))]
+    #[case(MergeStrategy::Error, Err(Error::PropertySpecMergeConflict { property_name: compression_name() }))]
+    #[trace]
+    fn test_merge_allowed_values(
+        #[case] strategy: MergeStrategy,
+        #[case] expected: Result<Vec<String>, Error>,
+    ) {
+        let common = spec_with_allowed_values(&["gzip", "snappy"]);
+        let product = spec_with_allowed_values(&["lz4"]);
+
+        let result = common.merge(product, strategy).map(|merged| {
+            merged
+                .property_specs
+                .get(&compression_name())
+                .unwrap()
+                .allowed_values
+                .clone()
+                .unwrap()
+        });
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_merge_identical_specs_without_conflict() {
+        let common = spec_with_allowed_values(&["gzip"]);
+        let product = spec_with_allowed_values(&["gzip"]);
+
+        let merged = common.merge(product, MergeStrategy::Error).unwrap();
+
+        assert_eq!(merged.property_spec_list.len(), 1);
+    }
+}
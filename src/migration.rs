@@ -0,0 +1,280 @@
+//! Migrate user config properties from deprecated properties to their replacements
+//! (specified via `deprecated_for`) when upgrading to a newer product version.
+use crate::error::Error;
+use crate::types::{ProductConfigSpecProperties, PropertyName, PropertyNameKind, PropertySpec};
+use crate::validation::{self, ValidationResult};
+use semver::Version;
+use std::collections::HashMap;
+
+/// The migrated user config and everything that could not be migrated automatically
+#[derive(Clone, Debug, PartialEq)]
+pub struct MigrationResult {
+    /// the user config with deprecated properties renamed to their replacements
+    pub config: HashMap<String, String>,
+    /// deprecated properties that were kept as they are and need manual attention
+    pub issues: Vec<MigrationIssue>,
+}
+
+/// Reasons why a deprecated property could not be migrated automatically
+#[derive(Clone, Debug, PartialEq)]
+pub enum MigrationIssue {
+    /// The deprecated property has no replacement
+    NoReplacement { property_name: PropertyName },
+    /// The deprecated property has multiple replacements and we cannot decide which one to use
+    AmbiguousReplacement {
+        property_name: PropertyName,
+        replacements: Vec<Vec<PropertyName>>,
+    },
+    /// The replacement has no property name of the user config kind in the property spec
+    ReplacementNotFound {
+        property_name: PropertyName,
+        replacement: Vec<PropertyName>,
+    },
+    /// The user already provided a value for the replacement
+    ReplacementAlreadySet {
+        property_name: PropertyName,
+        replacement: PropertyName,
+    },
+    /// The value of the deprecated property is not valid for the replacement
+    InvalidValue {
+        property_name: PropertyName,
+        replacement: PropertyName,
+        error: Error,
+    },
+}
+
+/// Rename all user properties that are deprecated in `to_version` to their replacements.
+///
+/// # Arguments
+///
+/// * `property_spec` - map with property name as key and the corresponding property spec as value
+/// * `config_spec` - config spec that contains customizable data like e.g. unit and regex
+/// * `kind` - property name kind of the user config
+/// * `user_config` - map with property name and values (the explicit user config properties)
+/// * `from_version` - the product version the user config was written for
+/// * `to_version` - the product version to migrate to
+///
+pub(crate) fn migrate(
+    property_spec: &HashMap<PropertyName, PropertySpec>,
+    config_spec: &ProductConfigSpecProperties,
+    kind: &PropertyNameKind,
+    user_config: &HashMap<String, String>,
+    from_version: &Version,
+    to_version: &Version,
+) -> ValidationResult<MigrationResult> {
+    if from_version > to_version {
+        return Err(Error::InvalidVersionRange {
+            from_version: from_version.to_string(),
+            to_version: to_version.to_string(),
+        });
+    }
+
+    let mut config = user_config.clone();
+    let mut issues = vec![];
+
+    let mut names: Vec<&String> = user_config.keys().collect();
+    names.sort();
+
+    for name in names {
+        let property_name = PropertyName {
+            name: name.clone(),
+            kind: kind.clone(),
+        };
+
+        let spec = match property_spec.get(&property_name) {
+            Some(spec) => spec,
+            None => continue,
+        };
+
+        match &spec.deprecated_since {
+            Some(deprecated_since) if Version::parse(deprecated_since)? <= *to_version => {}
+            _ => continue,
+        }
+
+        let replacement = match spec.deprecated_for.as_deref() {
+            None | Some([]) => {
+                issues.push(MigrationIssue::NoReplacement { property_name });
+                continue;
+            }
+            Some([replacement]) => replacement,
+            Some(replacements) => {
+                issues.push(MigrationIssue::AmbiguousReplacement {
+                    property_name,
+                    replacements: replacements.to_vec(),
+                });
+                continue;
+            }
+        };
+
+        // the replacement property name has to match the kind of the user config
+        let found = replacement.iter().find_map(|replacement_name| {
+            if &replacement_name.kind != kind {
+                return None;
+            }
+            property_spec
+                .get(replacement_name)
+                .map(|replacement_spec| (replacement_name, replacement_spec))
+        });
+
+        let (replacement_name, replacement_spec) = match found {
+            Some(found) => found,
+            None => {
+                issues.push(MigrationIssue::ReplacementNotFound {
+                    property_name,
+                    replacement: replacement.clone(),
+                });
+                continue;
+            }
+        };
+
+        if user_config.contains_key(&replacement_name.name) {
+            issues.push(MigrationIssue::ReplacementAlreadySet {
+                property_name,
+                replacement: replacement_name.clone(),
+            });
+            continue;
+        }
+
+        let value = &user_config[name];
+        if let Err(error) = validation::check_datatype(
+            &config_spec.units,
+            replacement_name,
+            value,
+            &validation::resolve_bounds(&replacement_spec.datatype, user_config),
+        )
+        .and_then(|_| {
+            validation::check_allowed_values(
+                replacement_name,
+                value,
+                &replacement_spec.allowed_values,
+                &replacement_spec.allowed_pattern,
+            )
+        })
+        .and_then(|_| {
+            validation::check_forbidden_values(
+                replacement_name,
+                value,
+                &replacement_spec.forbidden_values,
+            )
+        }) {
+            issues.push(MigrationIssue::InvalidValue {
+                property_name,
+                replacement: replacement_name.clone(),
+                error,
+            });
+            continue;
+        }
+
+        config.remove(name);
+        config.insert(replacement_name.name.clone(), value.clone());
+    }
+
+    Ok(MigrationResult { config, issues })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::migration::MigrationIssue;
+    use crate::reader::ConfigJsonReader;
+    use crate::types::{PropertyName, PropertyNameKind};
+    use crate::ProductConfigSpec;
+    use rstest::*;
+    use std::collections::HashMap;
+
+    const ENV_PROPERTY_STRING_DEPRECATED: &str = "ENV_PROPERTY_STRING_DEPRECATED";
+    const ENV_PROPERTY_STRING_MEMORY: &str = "ENV_PROPERTY_STRING_MEMORY";
+    const CONF_FILE: &str = "env.sh";
+
+    fn get_product_config() -> ProductConfigSpec {
+        ProductConfigSpec::new(ConfigJsonReader::new(
+            "data/test_config_spec.json",
+            "data/test_property_spec.json",
+        ))
+        .unwrap()
+    }
+
+    fn deprecated_property_name() -> PropertyName {
+        PropertyName {
+            name: ENV_PROPERTY_STRING_DEPRECATED.to_string(),
+            kind: PropertyNameKind::Conf(CONF_FILE.to_string()),
+        }
+    }
+
+    fn replacement_property_name() -> PropertyName {
+        PropertyName {
+            name: ENV_PROPERTY_STRING_MEMORY.to_string(),
+            kind: PropertyNameKind::Conf(CONF_FILE.to_string()),
+        }
+    }
+
+    #[rstest]
+    #[case(
+        vec![(ENV_PROPERTY_STRING_DEPRECATED, "1g")],
+        vec![(ENV_PROPERTY_STRING_MEMORY, "1g")],
+        vec![]
+    )]
+    #[case(
+        vec![(ENV_PROPERTY_STRING_DEPRECATED, "1g"), (ENV_PROPERTY_STRING_MEMORY, "2g")],
+        vec![(ENV_PROPERTY_STRING_DEPRECATED, "1g"), (ENV_PROPERTY_STRING_MEMORY, "2g")],
+        vec![MigrationIssue::ReplacementAlreadySet {
+            property_name: deprecated_property_name(),
+            replacement: replacement_property_name(),
+        }]
+    )]
+    #[case(
+        vec![(ENV_PROPERTY_STRING_DEPRECATED, "lots")],
+        vec![(ENV_PROPERTY_STRING_DEPRECATED, "lots")],
+        vec![MigrationIssue::InvalidValue {
+            property_name: deprecated_property_name(),
+            replacement: replacement_property_name(),
+            error: Error::DatatypeRegexNotMatching {
+                property_name: replacement_property_name(),
+                value: "lots".to_string(),
+            },
+        }]
+    )]
+    #[trace]
+    fn test_migrate(
+        #[case] user_config: Vec<(&str, &str)>,
+        #[case] expected_config: Vec<(&str, &str)>,
+        #[case] expected_issues: Vec<MigrationIssue>,
+    ) {
+        let to_map = |values: Vec<(&str, &str)>| -> HashMap<String, String> {
+            values
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        };
+
+        let result = get_product_config()
+            .migrate(
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                &to_map(user_config),
+                "0.3.0",
+                "0.5.0",
+            )
+            .unwrap();
+
+        assert_eq!(result.config, to_map(expected_config));
+        assert_eq!(result.issues, expected_issues);
+    }
+
+    #[test]
+    fn test_migrate_invalid_version_range() {
+        let result = get_product_config().migrate(
+            &PropertyNameKind::Conf(CONF_FILE.to_string()),
+            &HashMap::new(),
+            "0.5.0",
+            "0.3.0",
+        );
+
+        assert_eq!(
+            result,
+            Err(Error::InvalidVersionRange {
+                from_version: "0.5.0".to_string(),
+                to_version: "0.3.0".to_string(),
+            })
+        );
+    }
+}
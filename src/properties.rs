@@ -0,0 +1,189 @@
+//! Parsing of Java `.properties` files like `java.util.Properties::load` does, e.g. to validate
+//! existing config files against the spec.
+//!
+//! Lines starting with `#` or `!` are comments, a line ending with an odd number of backslashes
+//! is continued on the next line (without its leading whitespace). Keys end at the first
+//! unescaped `=`, `:` or whitespace and escapes (`\t`, `\n`, `\r`, `\f`, `\uXXXX` and `\` followed
+//! by any other character for the character itself) are resolved in keys and values.
+
+/// Whitespace separating keys and values (and ignored at the start of lines)
+const WHITESPACE: [char; 3] = [' ', '\t', '\x0c'];
+
+/// A key and value of a `.properties` file
+#[derive(Debug, PartialEq)]
+pub(crate) struct Entry {
+    pub key: String,
+    pub value: String,
+    /// the line (starting at 1) the entry starts in
+    pub line: usize,
+}
+
+/// Returns the entries of the `.properties` file in the order of their lines (duplicate keys are
+/// kept) or the reason (with line) why the content is not parsable
+///
+/// # Arguments
+///
+/// * `content` - the content of the `.properties` file
+///
+pub(crate) fn parse(content: &str) -> Result<Vec<Entry>, String> {
+    let mut entries = vec![];
+    let mut lines = content.lines().enumerate();
+
+    while let Some((index, line)) = lines.next() {
+        let line = line.trim_start_matches(WHITESPACE);
+        if line.is_empty() || line.starts_with(['#', '!']) {
+            continue;
+        }
+
+        let mut logical_line = line.to_string();
+        while is_continued(&logical_line) {
+            logical_line.pop();
+            match lines.next() {
+                Some((_, next)) => logical_line.push_str(next.trim_start_matches(WHITESPACE)),
+                None => break,
+            }
+        }
+
+        let (key, value) = split(&logical_line);
+        let unescape =
+            |text| unescape(text).map_err(|reason| format!("line {}: {}", index + 1, reason));
+        entries.push(Entry {
+            key: unescape(key)?,
+            value: unescape(value)?,
+            line: index + 1,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Returns if the line ends with an odd number of backslashes, i.e. is continued on the next line
+fn is_continued(line: &str) -> bool {
+    line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1
+}
+
+/// Returns the (escaped) key and value of the logical line: the key ends at the first unescaped
+/// separator, the value starts after the separator and surrounding whitespace
+fn split(line: &str) -> (&str, &str) {
+    let mut escaped = false;
+    let key_end = line
+        .char_indices()
+        .find(|(_, c)| {
+            let separator = !escaped && (*c == '=' || *c == ':' || WHITESPACE.contains(c));
+            escaped = !escaped && *c == '\\';
+            separator
+        })
+        .map(|(index, _)| index)
+        .unwrap_or(line.len());
+
+    let rest = line[key_end..].trim_start_matches(WHITESPACE);
+    let rest = rest
+        .strip_prefix(['=', ':'])
+        .map(|rest| rest.trim_start_matches(WHITESPACE))
+        .unwrap_or(rest);
+    (&line[..key_end], rest)
+}
+
+/// Returns the text with escapes resolved; `\uXXXX` escapes are UTF-16 code units, so characters
+/// beyond the basic multilingual plane are written as surrogate pairs
+fn unescape(text: &str) -> Result<String, String> {
+    let mut units: Vec<u16> = vec![];
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        let unescaped = match (c, c == '\\') {
+            (_, false) => c,
+            (_, true) => match chars.next() {
+                Some('t') => '\t',
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('f') => '\x0c',
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    match u16::from_str_radix(&hex, 16) {
+                        Ok(unit) if hex.len() == 4 => {
+                            units.push(unit);
+                            continue;
+                        }
+                        _ => return Err(format!("malformed \\uxxxx escape '\\u{}'", hex)),
+                    }
+                }
+                Some(other) => other,
+                None => break,
+            },
+        };
+        units.extend(unescaped.encode_utf16(&mut [0; 2]).iter());
+    }
+
+    // unpaired surrogates are replaced, Java would keep them
+    Ok(String::from_utf16_lossy(&units))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::properties::{parse, unescape, Entry};
+    use rstest::*;
+
+    #[test]
+    fn test_parse() {
+        let content = "# comment\n\
+                       ! another comment\n\
+                       \n\
+                       log.dirs=/data/1,\\\n    /data/2\n\
+                       \u{20}\u{20}ssl.enabled : true\n\
+                       greeting Gr\\u00FC\\u00DFe \\uD83D\\uDE00\n\
+                       key\\ with\\=separators=a\\\\\n\
+                       empty\n\
+                       path=C:\\\\temp";
+
+        assert_eq!(
+            parse(content).unwrap(),
+            vec![
+                Entry {
+                    key: "log.dirs".to_string(),
+                    value: "/data/1,/data/2".to_string(),
+                    line: 4
+                },
+                Entry {
+                    key: "ssl.enabled".to_string(),
+                    value: "true".to_string(),
+                    line: 6
+                },
+                Entry {
+                    key: "greeting".to_string(),
+                    value: "Grüße 😀".to_string(),
+                    line: 7
+                },
+                Entry {
+                    key: "key with=separators".to_string(),
+                    value: "a\\".to_string(),
+                    line: 8
+                },
+                Entry {
+                    key: "empty".to_string(),
+                    value: "".to_string(),
+                    line: 9
+                },
+                Entry {
+                    key: "path".to_string(),
+                    value: "C:\\temp".to_string(),
+                    line: 10
+                },
+            ]
+        );
+        assert_eq!(
+            parse("a=1\nb=\\u00g1").unwrap_err(),
+            "line 2: malformed \\uxxxx escape '\\u00g1'"
+        );
+    }
+
+    #[rstest]
+    #[case(r"plain", "plain")]
+    #[case(r"a\tb\nc", "a\tb\nc")]
+    #[case(r"\#\!\=\:\ ", "#!=: ")]
+    #[case(r"\u00e4", "ä")]
+    #[trace]
+    fn test_unescape(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(unescape(text).unwrap(), expected);
+    }
+}
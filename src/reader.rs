@@ -1,16 +1,23 @@
 //! A config reader implementation to abstract the source of config data
 //!
-//! For now only JSON as source is supported.
+//! For now only JSON as source is supported, either from files or from strings (e.g. fetched
+//! by a web frontend; the file readers are not available on `wasm32-unknown-unknown`).
 //!
 //! Possible extensions: YAML, CSV, database ...
 use crate::error::Error;
-use crate::types::{ProductConfigSpecProperties, PropertySpec, Unit};
+use crate::rules::CompiledRule;
+use crate::types::{
+    Datatype, Normalization, Platform, ProductConfigSpecProperties, PropertyGroup,
+    PropertyNamePrefix, PropertySpec, Rule, Unit, ValidationPolicy,
+};
+use crate::validator::{CustomValidators, Hooks, SecretResolvers};
 use crate::ProductConfigSpec;
-use regex::Regex;
 use serde::de::DeserializeOwned;
-use serde::Deserialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 use std::fs::File;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 use std::io::BufReader;
 
 /// trait for different config readers for json or yaml
@@ -19,16 +26,51 @@ pub trait ConfigReader {
 }
 
 /// specific json config reader struct
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 pub struct ConfigJsonReader {
     config_spec_path: String,
     property_spec_path: String,
 }
 
-#[derive(Deserialize, Debug)]
-struct JsonProductConfigSpecProperties {
+/// json config reader for specs that are already loaded, e.g. fetched by a web frontend
+pub struct ConfigJsonStrReader {
+    config_spec: String,
+    property_spec: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub(crate) struct JsonProductConfigSpecProperties {
     pub units: Vec<Unit>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prefixes: Vec<PropertyNamePrefix>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<Rule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalization: Option<Normalization>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_match: Option<bool>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub types: BTreeMap<String, Datatype>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub value_sets: BTreeMap<String, Vec<String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<PropertyGroup>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_locale: Option<String>,
+}
+
+/// The property spec file contains either the property specs of a single product or a
+/// `products` section with the property specs of several related products (sharing the units)
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(untagged)]
+pub(crate) enum JsonPropertySpecs {
+    Properties(Vec<PropertySpec>),
+    Products {
+        products: BTreeMap<String, Vec<PropertySpec>>,
+    },
 }
 
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 impl ConfigJsonReader {
     pub fn new(config_spec_path: &str, property_spec_path: &str) -> Self {
         ConfigJsonReader {
@@ -38,15 +80,54 @@ impl ConfigJsonReader {
     }
 }
 
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 impl ConfigReader for ConfigJsonReader {
     fn read(&self) -> Result<ProductConfigSpec, Error> {
-        let config_spec: JsonProductConfigSpecProperties = read_file(&self.config_spec_path)?;
-        let property_spec: Vec<PropertySpec> = read_file(&self.property_spec_path)?;
-        parse_json_config_spec(&config_spec, &property_spec)
+        let config_spec = read_file(&self.config_spec_path)?;
+        read_specs(&config_spec, read_file(&self.property_spec_path)?)
+    }
+}
+
+impl ConfigJsonStrReader {
+    pub fn new(config_spec: &str, property_spec: &str) -> Self {
+        ConfigJsonStrReader {
+            config_spec: config_spec.to_string(),
+            property_spec: property_spec.to_string(),
+        }
+    }
+}
+
+impl ConfigReader for ConfigJsonStrReader {
+    fn read(&self) -> Result<ProductConfigSpec, Error> {
+        let config_spec = read_str("config spec", &self.config_spec)?;
+        read_specs(
+            &config_spec,
+            read_str("property spec", &self.property_spec)?,
+        )
+    }
+}
+
+fn read_specs(
+    config_spec: &JsonProductConfigSpecProperties,
+    property_specs: JsonPropertySpecs,
+) -> Result<ProductConfigSpec, Error> {
+    match property_specs {
+        JsonPropertySpecs::Properties(property_spec) => {
+            parse_config_spec(config_spec, &property_spec, &BTreeMap::new())
+        }
+        JsonPropertySpecs::Products { products } => parse_config_spec(config_spec, &[], &products),
     }
 }
 
-fn read_file<T: DeserializeOwned>(path: &str) -> Result<T, Error> {
+fn read_str<T: DeserializeOwned>(name: &str, json: &str) -> Result<T, Error> {
+    serde_json::from_str(json).map_err(|err| Error::FileNotParsable {
+        file_name: name.to_string(),
+        reason: err.to_string(),
+    })
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub(crate) fn read_file<T: DeserializeOwned>(path: &str) -> Result<T, Error> {
     let file = match File::open(path) {
         Ok(file) => file,
         Err(_) => {
@@ -71,12 +152,14 @@ fn read_file<T: DeserializeOwned>(path: &str) -> Result<T, Error> {
 ///
 /// # Arguments
 ///
-/// * `config_spec` - the config spec provided by the JsonConfigReader
-/// * `property_spec` - the property spec provided by the JsonConfigReader
+/// * `config_spec` - the units, prefixes and rules provided by the config reader
+/// * `property_spec` - the property spec provided by the config reader
+/// * `products` - the property specs of several products (sharing the units) via product name
 ///
-fn parse_json_config_spec(
+pub(crate) fn parse_config_spec(
     config_spec: &JsonProductConfigSpecProperties,
     property_spec: &[PropertySpec],
+    products: &BTreeMap<String, Vec<PropertySpec>>,
 ) -> Result<ProductConfigSpec, Error> {
     // pack unit name and compiled regex pattern into map
     let mut config_spec_units = HashMap::new();
@@ -90,20 +173,21 @@ fn parse_json_config_spec(
         };
 
         // no regex or empty regex provided
-        let unit_regex = if unit.regex.is_none() || unit.regex == Some("".to_string()) {
-            return Err(Error::EmptyRegexPattern {
-                unit: unit.name.clone(),
-            });
-        } else {
-            unit.regex.clone().unwrap()
+        let compiled = match unit.compile(config_spec.full_match.unwrap_or(false)) {
+            Some(compiled) if unit.regex != Some("".to_string()) => compiled,
+            _ => {
+                return Err(Error::EmptyRegexPattern {
+                    unit: unit.name.clone(),
+                })
+            }
         };
 
-        let regex = match Regex::new(unit_regex.as_str()) {
+        let regex = match compiled {
             Ok(regex) => regex,
             Err(_) => {
                 return Err(Error::InvalidRegexPattern {
                     unit: unit_name,
-                    regex: unit_regex,
+                    regex: unit.regex.clone().unwrap_or_default(),
                 });
             }
         };
@@ -114,16 +198,116 @@ fn parse_json_config_spec(
     // pack properties via name into hashmap for easier access
     let mut parsed_property_spec = HashMap::new();
     for property in property_spec {
+        let property = resolve_references(config_spec, property)?;
         // for every provided property name, write property name and spec into map
         for property_name in &property.property_names {
             parsed_property_spec.insert(property_name.clone(), property.clone());
         }
     }
 
+    let rules = config_spec
+        .rules
+        .iter()
+        .map(CompiledRule::new)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut parsed_products = BTreeMap::new();
+    for (product, product_property_spec) in products {
+        parsed_products.insert(
+            product.clone(),
+            parse_config_spec(config_spec, product_property_spec, &BTreeMap::new())?,
+        );
+    }
+
     Ok(ProductConfigSpec {
         config_spec: ProductConfigSpecProperties {
             units: config_spec_units,
+            unit_specs: config_spec.units.clone(),
+            prefixes: config_spec.prefixes.clone(),
+            rules,
+            normalization: config_spec.normalization,
+            full_match: config_spec.full_match,
+            types: config_spec.types.clone(),
+            value_sets: config_spec.value_sets.clone(),
+            groups: config_spec.groups.clone(),
+            default_locale: config_spec.default_locale.clone(),
         },
         property_specs: parsed_property_spec,
+        property_spec_list: property_spec.to_vec(),
+        validation_policy: ValidationPolicy::default(),
+        products: parsed_products,
+        validators: CustomValidators::default(),
+        hooks: Hooks::default(),
+        secret_resolvers: SecretResolvers::default(),
+        platform: Platform::current(),
     })
 }
+
+/// Returns the property spec with a datatype reference replaced by its definition and the
+/// values of a referenced value set added to the allowed values. A referenced group has to be
+/// declared.
+///
+/// # Arguments
+///
+/// * `config_spec` - the config spec with the datatype definitions and value sets via name
+/// * `property` - the property spec (possibly with references)
+///
+pub(crate) fn resolve_references(
+    config_spec: &JsonProductConfigSpecProperties,
+    property: &PropertySpec,
+) -> Result<PropertySpec, Error> {
+    let mut property = property.clone();
+    let property_name = property.property_names.first().cloned().ok_or_else(|| {
+        Error::ConfigSpecPropertiesNotFound {
+            name: "property_names".to_string(),
+        }
+    });
+
+    if let Datatype::Ref { name } = &property.datatype {
+        match config_spec.types.get(name) {
+            // definitions must not reference other definitions
+            Some(datatype) if !matches!(datatype, Datatype::Ref { .. }) => {
+                property.datatype = datatype.clone()
+            }
+            _ => {
+                return Err(Error::DatatypeDefinitionNotFound {
+                    property_name: property_name?,
+                    name: name.clone(),
+                })
+            }
+        }
+    }
+
+    if let Some(name) = &property.allowed_value_set {
+        let values = match config_spec.value_sets.get(name) {
+            Some(values) => values,
+            None => {
+                return Err(Error::ValueSetNotFound {
+                    property_name: property_name?,
+                    name: name.clone(),
+                })
+            }
+        };
+        let allowed_values = property.allowed_values.get_or_insert_with(Vec::new);
+        for value in values {
+            if !allowed_values.contains(value) {
+                allowed_values.push(value.clone());
+            }
+        }
+    }
+
+    if let Some(group) = &property.group {
+        if !config_spec
+            .groups
+            .iter()
+            .any(|declared| &declared.name == group)
+        {
+            return Err(Error::PropertyGroupNotFound {
+                property_name: property_name?,
+                group: group.clone(),
+            });
+        }
+    }
+
+    Ok(property)
+}
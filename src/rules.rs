@@ -0,0 +1,569 @@
+//! Cross property rules declared in the config spec and evaluated against a whole user config.
+//!
+//! A rule consists of an optional condition and an action:
+//!
+//! ```text
+//! if ${ssl.enabled} == 'true' then required(${ssl.keystore.path}, ${ssl.keystore.password})
+//! if set(${kerberos.realm}) then forbidden(${sasl.plain.username})
+//! ${heap.initial} <= ${heap.max}
+//! ```
+//!
+//! Conditions support comparisons (`==`, `!=`, `<`, `<=`, `>`, `>=`), `set(...)`, `&&` / `and`,
+//! `||` / `or`, `!` / `not` and parentheses. Property values are compared as numbers if both
+//! sides are numbers, otherwise as strings. A property that is not set is not equal to any value.
+//! Actions are `required(...)`, `forbidden(...)` or a condition that has to hold.
+use crate::error::Error;
+use crate::types::{PropertyName, PropertyNameKind, Rule};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// A rule of the config spec with its parsed expression
+#[derive(Clone, Debug)]
+pub(crate) struct CompiledRule {
+    pub rule: Rule,
+    condition: Option<Condition>,
+    action: Action,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Condition {
+    Or(Box<Condition>, Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+    Compare(Operand, CompareOp, Operand),
+    Set(Vec<String>),
+    // a single operand is true if its value is 'true'
+    Truthy(Operand),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Operand {
+    Property(String),
+    Literal(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Action {
+    Required(Vec<String>),
+    Forbidden(Vec<String>),
+    Assert(Condition),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Property(String),
+    Literal(String),
+    Ident(String),
+    Op(CompareOp),
+    And,
+    Or,
+    Not,
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+impl CompiledRule {
+    /// Parse the expression of the provided rule
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - the rule as provided by the config spec
+    ///
+    pub fn new(rule: &Rule) -> Result<Self, Error> {
+        let invalid = |reason: String| Error::InvalidRule {
+            rule: rule.expression.clone(),
+            reason,
+        };
+
+        let tokens = tokenize(&rule.expression).map_err(invalid)?;
+        let (condition, action) = Parser { tokens, pos: 0 }.parse().map_err(invalid)?;
+
+        Ok(CompiledRule {
+            rule: rule.clone(),
+            condition,
+            action,
+        })
+    }
+
+    /// Returns the names of the properties violating the rule (sorted)
+    ///
+    /// # Arguments
+    ///
+    /// * `properties` - map with property name and values
+    ///
+    fn violations(&self, properties: &HashMap<String, String>) -> Vec<String> {
+        if let Some(condition) = &self.condition {
+            if !condition.evaluate(properties) {
+                return vec![];
+            }
+        }
+
+        let mut violations: Vec<String> = match &self.action {
+            Action::Required(names) => names
+                .iter()
+                .filter(|name| !properties.contains_key(*name))
+                .cloned()
+                .collect(),
+            Action::Forbidden(names) => names
+                .iter()
+                .filter(|name| properties.contains_key(*name))
+                .cloned()
+                .collect(),
+            Action::Assert(assertion) => {
+                if assertion.evaluate(properties) {
+                    vec![]
+                } else {
+                    assertion.properties().into_iter().take(1).collect()
+                }
+            }
+        };
+
+        violations.sort();
+        violations
+    }
+}
+
+/// Check all rules matching the kind against the provided properties. Returns the property
+/// name and error for every violation.
+///
+/// # Arguments
+///
+/// * `rules` - the rules of the config spec
+/// * `kind` - property name kind of the properties
+/// * `properties` - map with property name and values
+///
+pub(crate) fn check_rules(
+    rules: &[CompiledRule],
+    kind: &PropertyNameKind,
+    properties: &HashMap<String, String>,
+) -> Vec<(String, Error)> {
+    let mut violations = vec![];
+
+    for rule in rules {
+        if let Some(rule_kind) = &rule.rule.kind {
+            if rule_kind != kind {
+                continue;
+            }
+        }
+
+        for name in rule.violations(properties) {
+            let error = Error::RuleViolated {
+                property_name: PropertyName {
+                    name: name.clone(),
+                    kind: kind.clone(),
+                },
+                rule: rule.rule.expression.clone(),
+            };
+            violations.push((name, error));
+        }
+    }
+
+    violations
+}
+
+impl Condition {
+    fn evaluate(&self, properties: &HashMap<String, String>) -> bool {
+        match self {
+            Condition::Or(left, right) => left.evaluate(properties) || right.evaluate(properties),
+            Condition::And(left, right) => left.evaluate(properties) && right.evaluate(properties),
+            Condition::Not(condition) => !condition.evaluate(properties),
+            Condition::Set(names) => names.iter().all(|name| properties.contains_key(name)),
+            Condition::Truthy(operand) => operand
+                .value(properties)
+                .is_some_and(|value| value.eq_ignore_ascii_case("true")),
+            Condition::Compare(left, op, right) => {
+                match (left.value(properties), right.value(properties)) {
+                    (Some(left), Some(right)) => {
+                        let ordering = match (left.parse::<f64>(), right.parse::<f64>()) {
+                            (Ok(left), Ok(right)) => left.partial_cmp(&right),
+                            _ => Some(left.cmp(right)),
+                        };
+                        match ordering {
+                            Some(ordering) => op.matches(ordering),
+                            None => *op == CompareOp::Ne,
+                        }
+                    }
+                    (None, None) => false,
+                    // a property that is not set differs from every value
+                    _ => *op == CompareOp::Ne,
+                }
+            }
+        }
+    }
+
+    /// The property names referenced in the condition (in order of appearance)
+    fn properties(&self) -> Vec<String> {
+        match self {
+            Condition::Or(left, right) | Condition::And(left, right) => {
+                let mut properties = left.properties();
+                properties.extend(right.properties());
+                properties
+            }
+            Condition::Not(condition) => condition.properties(),
+            Condition::Set(names) => names.clone(),
+            Condition::Truthy(operand) => operand.properties(),
+            Condition::Compare(left, _, right) => {
+                let mut properties = left.properties();
+                properties.extend(right.properties());
+                properties
+            }
+        }
+    }
+}
+
+impl Operand {
+    fn value<'a>(&'a self, properties: &'a HashMap<String, String>) -> Option<&'a str> {
+        match self {
+            Operand::Property(name) => properties.get(name).map(String::as_str),
+            Operand::Literal(value) => Some(value.as_str()),
+        }
+    }
+
+    fn properties(&self) -> Vec<String> {
+        match self {
+            Operand::Property(name) => vec![name.clone()],
+            Operand::Literal(_) => vec![],
+        }
+    }
+}
+
+impl CompareOp {
+    fn matches(self, ordering: Ordering) -> bool {
+        match self {
+            CompareOp::Eq => ordering == Ordering::Equal,
+            CompareOp::Ne => ordering != Ordering::Equal,
+            CompareOp::Lt => ordering == Ordering::Less,
+            CompareOp::Le => ordering != Ordering::Greater,
+            CompareOp::Gt => ordering == Ordering::Greater,
+            CompareOp::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+/// Split the rule expression into tokens
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = expression.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '$' => {
+                chars.next();
+                if chars.next() != Some('{') {
+                    return Err("expected '{' after '$'".to_string());
+                }
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("unterminated property '${{{}'", name)),
+                    }
+                }
+                let name = name.trim();
+                if name.is_empty() {
+                    return Err("empty property name".to_string());
+                }
+                tokens.push(Token::Property(name.to_string()));
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => match chars.next() {
+                            Some(escaped) => value.push(escaped),
+                            None => return Err(format!("unterminated string '{}'", value)),
+                        },
+                        Some(quote) if quote == c => break,
+                        Some(other) => value.push(other),
+                        None => return Err(format!("unterminated string '{}'", value)),
+                    }
+                }
+                tokens.push(Token::Literal(value));
+            }
+            '(' | ')' | ',' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::LeftParen,
+                    ')' => Token::RightParen,
+                    _ => Token::Comma,
+                });
+            }
+            '=' | '!' | '<' | '>' | '&' | '|' => {
+                chars.next();
+                let followed_by_eq = chars.peek() == Some(&'=');
+                let token = match (c, followed_by_eq) {
+                    ('=', true) => Token::Op(CompareOp::Eq),
+                    ('!', true) => Token::Op(CompareOp::Ne),
+                    ('<', true) => Token::Op(CompareOp::Le),
+                    ('>', true) => Token::Op(CompareOp::Ge),
+                    ('!', false) => Token::Not,
+                    ('<', false) => Token::Op(CompareOp::Lt),
+                    ('>', false) => Token::Op(CompareOp::Gt),
+                    ('&', _) | ('|', _) => {
+                        if chars.next() != Some(c) {
+                            return Err(format!("expected '{}{}'", c, c));
+                        }
+                        tokens.push(if c == '&' { Token::And } else { Token::Or });
+                        continue;
+                    }
+                    _ => return Err(format!("unexpected character '{}'", c)),
+                };
+                if followed_by_eq {
+                    chars.next();
+                }
+                tokens.push(token);
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '.' => {
+                let mut value = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                        value.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Literal(value));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' {
+                        ident.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(match ident.to_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Ident(ident),
+                });
+            }
+            _ => return Err(format!("unexpected character '{}'", c)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser for the tokenized rule expression
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn parse(mut self) -> Result<(Option<Condition>, Action), String> {
+        let condition = if self.next_is_keyword("if") {
+            self.pos += 1;
+            let condition = self.parse_or()?;
+            if !self.next_is_keyword("then") {
+                return Err("expected 'then' after condition".to_string());
+            }
+            self.pos += 1;
+            Some(condition)
+        } else {
+            None
+        };
+
+        let action = self.parse_action()?;
+
+        if let Some(token) = self.tokens.get(self.pos) {
+            return Err(format!("unexpected token {:?}", token));
+        }
+
+        Ok((condition, action))
+    }
+
+    fn parse_action(&mut self) -> Result<Action, String> {
+        if self.next_is_keyword("required") {
+            self.pos += 1;
+            return Ok(Action::Required(self.parse_property_list()?));
+        }
+
+        if self.next_is_keyword("forbidden") {
+            self.pos += 1;
+            return Ok(Action::Forbidden(self.parse_property_list()?));
+        }
+
+        let assertion = self.parse_or()?;
+        if assertion.properties().is_empty() {
+            return Err("the rule has to reference at least one property".to_string());
+        }
+        Ok(Action::Assert(assertion))
+    }
+
+    fn parse_or(&mut self) -> Result<Condition, String> {
+        let mut condition = self.parse_and()?;
+        while self.tokens.get(self.pos) == Some(&Token::Or) {
+            self.pos += 1;
+            condition = Condition::Or(Box::new(condition), Box::new(self.parse_and()?));
+        }
+        Ok(condition)
+    }
+
+    fn parse_and(&mut self) -> Result<Condition, String> {
+        let mut condition = self.parse_not()?;
+        while self.tokens.get(self.pos) == Some(&Token::And) {
+            self.pos += 1;
+            condition = Condition::And(Box::new(condition), Box::new(self.parse_not()?));
+        }
+        Ok(condition)
+    }
+
+    fn parse_not(&mut self) -> Result<Condition, String> {
+        if self.tokens.get(self.pos) == Some(&Token::Not) {
+            self.pos += 1;
+            return Ok(Condition::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Condition, String> {
+        if self.tokens.get(self.pos) == Some(&Token::LeftParen) {
+            self.pos += 1;
+            let condition = self.parse_or()?;
+            self.expect(Token::RightParen)?;
+            return Ok(condition);
+        }
+
+        if self.next_is_keyword("set") {
+            self.pos += 1;
+            return Ok(Condition::Set(self.parse_property_list()?));
+        }
+
+        let left = self.parse_operand()?;
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) => {
+                let op = *op;
+                self.pos += 1;
+                let right = self.parse_operand()?;
+                Ok(Condition::Compare(left, op, right))
+            }
+            _ => Ok(Condition::Truthy(left)),
+        }
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, String> {
+        let operand = match self.tokens.get(self.pos) {
+            Some(Token::Property(name)) => Operand::Property(name.clone()),
+            Some(Token::Literal(value)) => Operand::Literal(value.clone()),
+            Some(token) => return Err(format!("expected property or value, found {:?}", token)),
+            None => return Err("unexpected end of rule".to_string()),
+        };
+        self.pos += 1;
+        Ok(operand)
+    }
+
+    fn parse_property_list(&mut self) -> Result<Vec<String>, String> {
+        self.expect(Token::LeftParen)?;
+        let mut names = vec![];
+        loop {
+            match self.tokens.get(self.pos) {
+                Some(Token::Property(name)) => names.push(name.clone()),
+                _ => return Err("expected property".to_string()),
+            }
+            self.pos += 1;
+            if self.tokens.get(self.pos) == Some(&Token::Comma) {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        self.expect(Token::RightParen)?;
+        Ok(names)
+    }
+
+    fn next_is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.tokens.get(self.pos), Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case(keyword))
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), String> {
+        if self.tokens.get(self.pos) != Some(&token) {
+            return Err(format!("expected {:?}", token));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rules::CompiledRule;
+    use crate::types::Rule;
+    use rstest::*;
+    use std::collections::HashMap;
+
+    fn rule(expression: &str) -> CompiledRule {
+        CompiledRule::new(&Rule {
+            expression: expression.to_string(),
+            kind: None,
+            comment: None,
+        })
+        .unwrap()
+    }
+
+    fn properties(values: &[(&str, &str)]) -> HashMap<String, String> {
+        values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    const SSL_RULE: &str = "if ${ssl.enabled} == 'true' then required(${ssl.keystore.path})";
+
+    #[rstest]
+    #[case(SSL_RULE, &[], vec![])]
+    #[case(SSL_RULE, &[("ssl.enabled", "false")], vec![])]
+    #[case(SSL_RULE, &[("ssl.enabled", "true")], vec!["ssl.keystore.path"])]
+    #[case(SSL_RULE, &[("ssl.enabled", "true"), ("ssl.keystore.path", "/tmp")], vec![])]
+    #[case("if set(${kerberos.realm}) then forbidden(${sasl.user}, ${sasl.password})",
+        &[("kerberos.realm", "X"), ("sasl.user", "u")], vec!["sasl.user"])]
+    #[case("${heap.initial} <= ${heap.max}", &[("heap.initial", "512"), ("heap.max", "1024")], vec![])]
+    #[case("${heap.initial} <= ${heap.max}", &[("heap.initial", "2048"), ("heap.max", "1024")], vec!["heap.initial"])]
+    #[case("if ${a} && !(${b} or ${c} != 'x') then ${d} > 1.5", &[("a", "true"), ("c", "x"), ("d", "1")], vec!["d"])]
+    #[trace]
+    fn test_violations(
+        #[case] expression: &str,
+        #[case] values: &[(&str, &str)],
+        #[case] expected: Vec<&str>,
+    ) {
+        assert_eq!(rule(expression).violations(&properties(values)), expected);
+    }
+
+    #[rstest]
+    #[case("if ${a} == 'true' required(${b})")]
+    #[case("required(${b}")]
+    #[case("${a} == 'unterminated")]
+    #[case("'a' == 'b'")]
+    #[case("${a} = 'b'")]
+    #[trace]
+    fn test_invalid_rule(#[case] expression: &str) {
+        let result = CompiledRule::new(&Rule {
+            expression: expression.to_string(),
+            kind: None,
+            comment: None,
+        });
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,335 @@
+//! Generation of sample configs (e.g. starter files for new users) that show every property of
+//! a product version with its description, datatype and default.
+//!
+//! Properties with default are commented out or set to the default (see `SampleStyle`).
+//! Properties of high importance without default are set to an empty value and highlighted as
+//! required, all other properties without default are commented out.
+use crate::docgen;
+use crate::markdown;
+use crate::types::{
+    Importance, PropertyName, PropertyNameKind, PropertySpec, SampleFormat, SampleStyle,
+};
+use crate::util;
+use crate::validation::ValidationResult;
+use crate::writer::{escape_env_file_value, escape_properties};
+use crate::ProductConfigSpec;
+use semver::Version;
+use std::collections::BTreeMap;
+
+/// Returns the sample config of the product version in the provided format
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `product_version` - the product version
+/// * `format` - the format of the sample (selects the kind of the properties)
+/// * `style` - if properties with default are commented out or set
+///
+pub(crate) fn generate(
+    spec: &ProductConfigSpec,
+    product_version: &Version,
+    format: SampleFormat,
+    style: SampleStyle,
+) -> ValidationResult<String> {
+    // properties per section (config file for conf properties)
+    let mut sections: BTreeMap<Option<&str>, Vec<(&PropertyName, &PropertySpec)>> = BTreeMap::new();
+    for (names, property) in docgen::options(spec, product_version)? {
+        for name in names {
+            match (&name.kind, format) {
+                (PropertyNameKind::Conf(file), SampleFormat::Properties | SampleFormat::Yaml) => {
+                    sections
+                        .entry(Some(file))
+                        .or_default()
+                        .push((name, property))
+                }
+                (PropertyNameKind::Env, SampleFormat::Env) => {
+                    sections.entry(None).or_default().push((name, property))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut sample = format!("# Sample configuration (version {})\n", product_version);
+    for (file, mut properties) in sections {
+        if let Some(file) = file {
+            sample.push_str(&format!("\n# --- {} ---\n", file));
+        }
+        properties.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+        for (name, property) in properties {
+            sample.push('\n');
+            sample.push_str(&property_entry(
+                spec,
+                name,
+                property,
+                product_version,
+                format,
+                style,
+            )?);
+        }
+    }
+
+    Ok(sample)
+}
+
+/// Returns the comments and the (possibly commented out) line of the property
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `name` - the name of the property
+/// * `property` - the property spec
+/// * `product_version` - the product version (to select the default value)
+/// * `format` - the format of the sample
+/// * `style` - if properties with default are commented out or set
+///
+fn property_entry(
+    spec: &ProductConfigSpec,
+    name: &PropertyName,
+    property: &PropertySpec,
+    product_version: &Version,
+    format: SampleFormat,
+    style: SampleStyle,
+) -> ValidationResult<String> {
+    let mut comments: Vec<String> = docgen::description(spec, property)
+        .map(|description| {
+            markdown::to_plain_text(description)
+                .lines()
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    comments.push(format!(
+        "Datatype: {}",
+        markdown::to_plain_text(&docgen::datatype(property))
+    ));
+    if let Some(allowed_values) = property
+        .allowed_values
+        .as_deref()
+        .or_else(|| property.datatype.variants())
+    {
+        comments.push(format!("Allowed values: {}", allowed_values.join(", ")));
+    }
+
+    // only values that do not depend on other properties or the platform are defaults
+    let default = util::get_unconditional_default(name, property, product_version)?
+        .map(|default| default.value);
+    let required = property.importance == Some(Importance::High);
+
+    let commented = match &default {
+        Some(_) => style == SampleStyle::CommentedDefaults,
+        None if required => {
+            comments.push("REQUIRED: no default, a value has to be provided".to_string());
+            false
+        }
+        None => true,
+    };
+
+    let mut entry: String = comments
+        .iter()
+        .map(|comment| format!("{}\n", format!("# {}", comment).trim_end()))
+        .collect();
+    if commented {
+        entry.push_str("# ");
+    }
+    entry.push_str(&line(
+        &name.name,
+        default.as_deref().unwrap_or_default(),
+        format,
+    ));
+    entry.push('\n');
+    Ok(entry)
+}
+
+/// Returns the line setting the property to the value in the format
+fn line(key: &str, value: &str, format: SampleFormat) -> String {
+    match format {
+        SampleFormat::Properties => format!(
+            "{}={}",
+            escape_properties(key, true),
+            escape_properties(value, false)
+        ),
+        SampleFormat::Yaml => format!("{}: {}", quote(key), quote(value)),
+        SampleFormat::Env => format!("{}={}", key, escape_env_file_value(value)),
+    }
+}
+
+/// Returns the text as it is if it is safe to be written unquoted in YAML or as double quoted
+/// string otherwise
+fn quote(text: &str) -> String {
+    // words YAML would read as null or (YAML 1.1) booleans are quoted
+    let plain = !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "._-/,@+".contains(c))
+        && !text.starts_with(['-', '@'])
+        && !matches!(text, "null" | "yes" | "no" | "on" | "off");
+    if plain {
+        text.to_string()
+    } else {
+        // JSON strings are valid double quoted YAML strings as well
+        serde_json::Value::String(text.to_string()).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder};
+    use crate::sample::quote;
+    use crate::types::{
+        Datatype, Importance, PropertyDependency, PropertyName, PropertyNameKind,
+        PropertyValueSpec, Role, SampleFormat, SampleStyle,
+    };
+    use crate::ProductConfigSpec;
+    use rstest::*;
+
+    fn spec() -> ProductConfigSpec {
+        let role = Role {
+            name: "broker".to_string(),
+            required: false,
+        };
+        let names = |name: &str, env: &str| {
+            vec![
+                PropertyName {
+                    name: name.to_string(),
+                    kind: PropertyNameKind::Conf("server.properties".to_string()),
+                },
+                PropertyName {
+                    name: env.to_string(),
+                    kind: PropertyNameKind::Env,
+                },
+            ]
+        };
+        let mut port = PropertySpecBuilder::new(
+            Datatype::Port {
+                allow_privileged: false,
+                range: false,
+            },
+            "1.0.0",
+        )
+        .default_value(PropertyValueSpec {
+            from_version: None,
+            to_version: None,
+            when: None,
+            platform: None,
+            value: "9092".to_string(),
+        })
+        .description("The listener port")
+        .role(role.clone());
+        for name in names("port", "PORT") {
+            port = port.property_name(name);
+        }
+        let mut log_dirs = PropertySpecBuilder::new(
+            Datatype::Path {
+                must_be_absolute: true,
+                must_exist: false,
+                kind: None,
+            },
+            "1.0.0",
+        )
+        .importance(Importance::High)
+        .role(role.clone());
+        for name in names("log.dirs", "LOG_DIRS") {
+            log_dirs = log_dirs.property_name(name);
+        }
+
+        ProductConfigSpecBuilder::new()
+            .property_spec(port.build())
+            .property_spec(log_dirs.build())
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "1.0.0")
+                    .property_name(names("ssl.enabled", "SSL_ENABLED").remove(0))
+                    // a conditional value is no default of the sample
+                    .default_value(PropertyValueSpec {
+                        from_version: None,
+                        to_version: None,
+                        when: Some(PropertyDependency {
+                            property_names: names("port", "PORT"),
+                            value: Some("443".to_string()),
+                        }),
+                        platform: None,
+                        value: "true".to_string(),
+                    })
+                    .role(role)
+                    .build(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[rstest]
+    #[case(
+        SampleFormat::Properties,
+        SampleStyle::CommentedDefaults,
+        "# Sample configuration (version 1.0.0)
+
+# --- server.properties ---
+
+# Datatype: path
+# REQUIRED: no default, a value has to be provided
+log.dirs=
+
+# The listener port
+# Datatype: port
+# port=9092
+
+# Datatype: bool
+# ssl.enabled=
+"
+    )]
+    #[case(
+        SampleFormat::Yaml,
+        SampleStyle::ExplicitDefaults,
+        "# Sample configuration (version 1.0.0)
+
+# --- server.properties ---
+
+# Datatype: path
+# REQUIRED: no default, a value has to be provided
+log.dirs: \"\"
+
+# The listener port
+# Datatype: port
+port: 9092
+
+# Datatype: bool
+# ssl.enabled: \"\"
+"
+    )]
+    #[case(
+        SampleFormat::Env,
+        SampleStyle::ExplicitDefaults,
+        "# Sample configuration (version 1.0.0)
+
+# Datatype: path
+# REQUIRED: no default, a value has to be provided
+LOG_DIRS=
+
+# The listener port
+# Datatype: port
+PORT=9092
+"
+    )]
+    #[trace]
+    fn test_generate_sample(
+        #[case] format: SampleFormat,
+        #[case] style: SampleStyle,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            spec().generate_sample("1.0.0", format, style).unwrap(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("/var/lib/kafka", "/var/lib/kafka")]
+    #[case("a b", "\"a b\"")]
+    #[case("no", "\"no\"")]
+    #[case("-1", "\"-1\"")]
+    #[case("say \"hi\"", r#""say \"hi\"""#)]
+    #[trace]
+    fn test_quote(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(quote(text), expected);
+    }
+}
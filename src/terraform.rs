@@ -0,0 +1,388 @@
+//! Export of the properties of a product version as Terraform variable definitions
+//! (`variables.tf`), e.g. for modules wrapping the product configuration.
+//!
+//! Every option (property spec) becomes a variable named after its first name (e.g.
+//! `log_dirs` for `log.dirs`) with type, description, default and validation blocks derived
+//! from the bounds and allowed values. Options without default are optional (`default = null`)
+//! unless they are of high importance.
+use crate::docgen;
+use crate::markdown;
+use crate::types::{Datatype, Importance, PropertySpec};
+use crate::util;
+use crate::validation::ValidationResult;
+use crate::ProductConfigSpec;
+use semver::Version;
+
+/// Returns the variable definitions of the options available in the product version
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `product_version` - the product version
+///
+pub(crate) fn variables(
+    spec: &ProductConfigSpec,
+    product_version: &Version,
+) -> ValidationResult<String> {
+    let mut variables = vec![];
+
+    for (names, property) in docgen::options(spec, product_version)? {
+        let name = variable_name(&names[0].name);
+        let item_type = item_type(&property.datatype);
+        let mut attributes = vec![(
+            "type",
+            match &property.list {
+                Some(_) => format!("list({})", item_type),
+                None => item_type.to_string(),
+            },
+        )];
+
+        if let Some(description) = docgen::description(spec, property) {
+            attributes.push(("description", string(&markdown::to_plain_text(description))));
+        }
+
+        // only values that do not depend on other properties or the platform are defaults
+        let default = util::get_unconditional_default(names[0], property, product_version)?
+            .map(|default| default.value);
+        let optional = default.is_some() || property.importance != Some(Importance::High);
+        match (&default, &property.list) {
+            (Some(default), Some(list)) => attributes.push((
+                "default",
+                format!(
+                    "[{}]",
+                    default
+                        .split(list.delimiter.as_str())
+                        .map(|item| literal(item.trim(), item_type))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            )),
+            (Some(default), None) => attributes.push(("default", literal(default, item_type))),
+            (None, _) if optional => attributes.push(("default", "null".to_string())),
+            (None, _) => {}
+        }
+
+        if spec.is_sensitive(names[0]) {
+            attributes.push(("sensitive", "true".to_string()));
+        }
+
+        let mut variable = format!(
+            "variable {} {{\n{}",
+            string(&name),
+            block(&attributes, "  ")
+        );
+        for (condition, error_message) in validations(&name, property, item_type) {
+            // conditional expressions are evaluated lazily, so null values are never compared
+            let condition = match optional {
+                true => format!("var.{} == null ? true : {}", name, condition),
+                false => condition,
+            };
+            variable.push_str(&format!(
+                "\n  validation {{\n{}  }}\n",
+                block(
+                    &[
+                        ("condition", condition),
+                        ("error_message", string(&error_message))
+                    ],
+                    "    "
+                )
+            ));
+        }
+        variable.push_str("}\n");
+        variables.push(variable);
+    }
+
+    Ok(variables.join("\n"))
+}
+
+/// Returns the conditions (and error messages) derived from the bounds and allowed values
+///
+/// # Arguments
+///
+/// * `name` - the name of the variable
+/// * `property` - the property spec
+/// * `item_type` - the Terraform type of the value (of the items for lists)
+///
+fn validations(name: &str, property: &PropertySpec, item_type: &str) -> Vec<(String, String)> {
+    // lists are checked item by item
+    let (value, subject) = match property.list {
+        Some(_) => ("v".to_string(), format!("All items of {}", name)),
+        None => (format!("var.{}", name), format!("The {}", name)),
+    };
+    let for_values = |condition: String| match property.list {
+        Some(_) => format!("alltrue([for v in var.{} : {}])", name, condition),
+        None => condition,
+    };
+    let mut validations = vec![];
+
+    let (min, max) = match &property.datatype {
+        Datatype::Port {
+            allow_privileged,
+            range: false,
+        } => (
+            Some(if *allow_privileged { "0" } else { "1024" }.to_string()),
+            Some("65535".to_string()),
+        ),
+        datatype => {
+            let (min, max) = datatype.bounds();
+            (min.cloned(), max.cloned())
+        }
+    };
+    // bounds referencing other properties or with unit cannot be checked
+    let number = |bound: Option<String>| bound.filter(|bound| bound.parse::<f64>().is_ok());
+    let bounded = match (&property.datatype, item_type) {
+        (_, "number") => Some((value.clone(), "")),
+        (Datatype::String { .. }, _) => Some((format!("length({})", value), " characters")),
+        _ => None,
+    };
+    if let Some((bounded, unit)) = bounded {
+        let (condition, message) = match (number(min), number(max)) {
+            (Some(min), Some(max)) => (
+                format!("{} >= {} && {} <= {}", bounded, min, bounded, max),
+                format!("between {} and {}{}", min, max, unit),
+            ),
+            (Some(min), None) => (
+                format!("{} >= {}", bounded, min),
+                format!("at least {}{}", min, unit),
+            ),
+            (None, Some(max)) => (
+                format!("{} <= {}", bounded, max),
+                format!("at most {}{}", max, unit),
+            ),
+            (None, None) => (String::new(), String::new()),
+        };
+        if !condition.is_empty() {
+            validations.push((
+                for_values(condition),
+                format!("{} must be {}.", subject, message),
+            ));
+        }
+    }
+
+    if let Some(allowed_values) = property
+        .allowed_values
+        .as_deref()
+        .or_else(|| property.datatype.variants())
+    {
+        let allowed_values = allowed_values
+            .iter()
+            .map(|allowed| literal(allowed, item_type))
+            .collect::<Vec<_>>()
+            .join(", ");
+        validations.push((
+            for_values(format!("contains([{}], {})", allowed_values, value)),
+            format!("{} must be one of {}.", subject, allowed_values),
+        ));
+    }
+
+    validations
+}
+
+/// Returns the Terraform type of values of the datatype
+fn item_type(datatype: &Datatype) -> &'static str {
+    match datatype {
+        Datatype::Bool => "bool",
+        Datatype::Integer {
+            unit: None,
+            accepted_units: None,
+            ..
+        }
+        | Datatype::UnsignedInteger {
+            unit: None,
+            accepted_units: None,
+            ..
+        }
+        | Datatype::Float {
+            unit: None,
+            accepted_units: None,
+            ..
+        }
+        | Datatype::Decimal { .. }
+        | Datatype::Port { range: false, .. } => "number",
+        _ => "string",
+    }
+}
+
+/// Returns the value as literal of the type (strings for values not matching the type)
+fn literal(value: &str, item_type: &str) -> String {
+    match item_type {
+        "bool" if value == "true" || value == "false" => value.to_string(),
+        "number" if value.parse::<f64>().is_ok_and(f64::is_finite) => value.to_string(),
+        _ => string(value),
+    }
+}
+
+/// Returns the text as quoted string; templates (`${` and `%{`) are escaped
+fn string(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t")
+        .replace("${", "$${")
+        .replace("%{", "%%{");
+    format!("\"{}\"", escaped)
+}
+
+/// Returns the attributes with aligned `=` (like `terraform fmt`)
+fn block(attributes: &[(&str, String)], indent: &str) -> String {
+    let width = attributes
+        .iter()
+        .map(|(key, _)| key.len())
+        .max()
+        .unwrap_or_default();
+    attributes
+        .iter()
+        .map(|(key, value)| format!("{}{:width$} = {}\n", indent, key, value, width = width))
+        .collect()
+}
+
+/// Returns the property name as Terraform identifier, e.g. `log_dirs` for `log.dirs`
+fn variable_name(name: &str) -> String {
+    let name: String = name
+        .trim_start_matches('-')
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => c.to_ascii_lowercase(),
+            _ => '_',
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder};
+    use crate::terraform::{string, variable_name};
+    use crate::types::{
+        Datatype, Importance, ListSpec, Platform, PropertyName, PropertyNameKind,
+        PropertyValueSpec, Role,
+    };
+    use rstest::*;
+
+    #[test]
+    fn test_to_terraform_variables() {
+        let role = Role {
+            name: "broker".to_string(),
+            required: false,
+        };
+        let conf = |name: &str| PropertyName {
+            name: name.to_string(),
+            kind: PropertyNameKind::Conf("server.properties".to_string()),
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Port {
+                        allow_privileged: false,
+                        range: false,
+                    },
+                    "1.0.0",
+                )
+                .property_name(conf("port"))
+                .default_value(PropertyValueSpec {
+                    from_version: None,
+                    to_version: None,
+                    when: None,
+                    platform: None,
+                    value: "9092".to_string(),
+                })
+                .description("The listener port")
+                .role(role.clone())
+                .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Enum {
+                        values: vec!["plain".to_string(), "gzip".to_string()],
+                        case_insensitive: false,
+                    },
+                    "1.0.0",
+                )
+                .property_name(conf("compression.types"))
+                .list(ListSpec::default())
+                .role(role.clone())
+                .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Password {
+                        min_length: None,
+                        required_classes: None,
+                        min_entropy: None,
+                    },
+                    "1.0.0",
+                )
+                .property_name(conf("ssl.password"))
+                .importance(Importance::High)
+                // a platform specific value is no default of the variable
+                .default_value(PropertyValueSpec {
+                    from_version: None,
+                    to_version: None,
+                    when: None,
+                    platform: Some(Platform {
+                        os: Some("windows".to_string()),
+                        arch: None,
+                    }),
+                    value: "changeit".to_string(),
+                })
+                .role(role)
+                .build(),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            spec.to_terraform_variables("1.0.0").unwrap(),
+            r#"variable "compression_types" {
+  type    = list(string)
+  default = null
+
+  validation {
+    condition     = var.compression_types == null ? true : alltrue([for v in var.compression_types : contains(["plain", "gzip"], v)])
+    error_message = "All items of compression_types must be one of \"plain\", \"gzip\"."
+  }
+}
+
+variable "port" {
+  type        = number
+  description = "The listener port"
+  default     = 9092
+
+  validation {
+    condition     = var.port == null ? true : var.port >= 1024 && var.port <= 65535
+    error_message = "The port must be between 1024 and 65535."
+  }
+}
+
+variable "ssl_password" {
+  type      = string
+  sensitive = true
+}
+"#
+        );
+    }
+
+    #[rstest]
+    #[case("log.dirs", "log_dirs")]
+    #[case("--Max-Heap", "max_heap")]
+    #[case("9lives", "_9lives")]
+    #[trace]
+    fn test_variable_name(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(variable_name(name), expected);
+    }
+
+    #[rstest]
+    #[case("plain", r#""plain""#)]
+    #[case("${HOME} and %{x}", r#""$${HOME} and %%{x}""#)]
+    #[case("a \"b\"\n", r#""a \"b\"\n""#)]
+    #[trace]
+    fn test_string(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(string(text), expected);
+    }
+}
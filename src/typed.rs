@@ -0,0 +1,387 @@
+//! Deserialization of typed configs (structs of the product using this library) from user
+//! configs: the effective values (user values, defaults and migrated values) of a kind are typed
+//! via the datatypes of their properties and deserialized via serde, the inverse of
+//! `ser::to_hash_map`.
+//!
+//! Property names are split at `.` into nested objects (`ssl.enabled` is the field `enabled` of
+//! the field `ssl`). Bools and numbers are deserialized as such (numbers with unit as number of
+//! the default unit of their datatype, e.g. `2048` for `2gb` with default unit `mb`), list
+//! properties as sequences, values of datatype `json` as the JSON they contain and all other
+//! values as strings.
+use crate::canonical;
+use crate::effective;
+use crate::error::Error;
+use crate::types::{Datatype, PropertyName, PropertyNameKind, PropertySpec};
+use crate::units;
+use crate::validation::{self, ValidationResult};
+use crate::{ConfigProblem, ProductConfigSpec};
+use semver::Version;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+
+/// Returns the typed config deserialized from the effective values of the user config or an
+/// `Error::UserConfigInvalid` with the problems of the user config (see `ProductConfigSpec::get`)
+/// and the deserialization error (if any)
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `kind` - kind of the user config (e.g. env, conf)
+/// * `values` - map with property name and value
+/// * `product_version` - the product version
+///
+pub(crate) fn validate_into<T: DeserializeOwned>(
+    spec: &ProductConfigSpec,
+    kind: &PropertyNameKind,
+    values: &HashMap<String, String>,
+    product_version: &Version,
+) -> ValidationResult<T> {
+    let mut problems: Vec<ConfigProblem> = spec
+        .problems(&product_version.to_string(), kind, values)?
+        .into_iter()
+        .map(|(name, error)| ConfigProblem {
+            name,
+            line: None,
+            pointer: None,
+            error,
+        })
+        .collect();
+
+    let prefix = spec.config_spec.prefix(kind).unwrap_or_default();
+    let user_values: HashMap<String, String> = values
+        .iter()
+        .map(|(name, value)| {
+            let name = name.strip_prefix(prefix).unwrap_or(name);
+            (name.to_string(), value.clone())
+        })
+        .collect();
+    let config = effective::effective_config(spec, &user_values, product_version)?;
+
+    let mut document = Map::new();
+    for (name, value) in config.values.get(kind).into_iter().flatten() {
+        let property_name = PropertyName {
+            name: name.clone(),
+            kind: kind.clone(),
+        };
+        let value = match spec.property_specs.get(&property_name) {
+            Some(property) => typed_value(spec, property, &value.value),
+            None => Value::String(value.value.clone()),
+        };
+        insert(&mut document, name, value);
+    }
+
+    match serde_path_to_error::deserialize(Value::Object(document)) {
+        Ok(typed) if problems.is_empty() => Ok(typed),
+        Ok(_) => Err(Error::UserConfigInvalid { problems }),
+        Err(err) => {
+            let path = err.path().to_string();
+            problems.push(ConfigProblem {
+                name: path.clone(),
+                line: None,
+                pointer: None,
+                error: Error::DeserializationFailed {
+                    path,
+                    reason: err.into_inner().to_string(),
+                },
+            });
+            Err(Error::UserConfigInvalid { problems })
+        }
+    }
+}
+
+/// Returns the value typed via the datatype of the property: items of list properties are typed
+/// one by one, values that do not match the datatype are kept as strings
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `property` - the property spec
+/// * `value` - the effective value
+///
+fn typed_value(spec: &ProductConfigSpec, property: &PropertySpec, value: &str) -> Value {
+    let canonical = canonical::canonical_value(spec, property, value);
+    match &property.list {
+        Some(_) => Value::Array(
+            validation::list_items(property, &canonical)
+                .into_iter()
+                .map(|item| typed_item(property, item))
+                .collect(),
+        ),
+        None => typed_item(property, &canonical),
+    }
+}
+
+/// Returns the value (or list item) typed via the datatype of the property
+fn typed_item(property: &PropertySpec, item: &str) -> Value {
+    let number = in_default_unit(property, item);
+    let item = number.as_deref().unwrap_or(item);
+    let typed = match &property.datatype {
+        Datatype::Bool => item.parse().ok().map(Value::Bool),
+        Datatype::Integer { .. } => item.parse::<i64>().ok().map(Value::from),
+        Datatype::UnsignedInteger { .. } | Datatype::Port { range: false, .. } => {
+            item.parse::<u64>().ok().map(Value::from)
+        }
+        Datatype::Float { .. } => item
+            .parse()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(Value::Number),
+        Datatype::Json { .. } => serde_json::from_str(item).ok(),
+        _ => None,
+    };
+    typed.unwrap_or_else(|| Value::String(item.to_string()))
+}
+
+/// Returns the number of a value of a numeric datatype with units converted to the default unit
+/// (the first accepted unit if there is no default unit) without unit suffix, or None for other
+/// datatypes and values that cannot be converted
+///
+/// # Arguments
+///
+/// * `property` - the property spec
+/// * `item` - the (canonical) value or list item, e.g. `2gb`
+///
+fn in_default_unit(property: &PropertySpec, item: &str) -> Option<String> {
+    let (accepted_units, default_unit) = match &property.datatype {
+        Datatype::Integer {
+            accepted_units,
+            default_unit,
+            ..
+        }
+        | Datatype::UnsignedInteger {
+            accepted_units,
+            default_unit,
+            ..
+        }
+        | Datatype::Float {
+            accepted_units,
+            default_unit,
+            ..
+        } => (accepted_units, default_unit),
+        _ => return None,
+    };
+    let target_unit = default_unit
+        .as_ref()
+        .or_else(|| accepted_units.iter().flatten().next())?;
+    let property_name = property.property_names.first()?;
+
+    let normalized = units::normalize(property_name, property, item, target_unit).ok()?;
+    Some(units::split_unit(&normalized).0.to_string())
+}
+
+/// Inserts the value into the (nested) object for the name split at `.`; if a parent is not an
+/// object (e.g. `a` and `a.b` are both set), the rest of the name is kept as key
+///
+/// # Arguments
+///
+/// * `object` - the object to insert the value into
+/// * `name` - the (remaining) property name
+/// * `value` - the typed value
+///
+fn insert(object: &mut Map<String, Value>, name: &str, value: Value) {
+    if let Some((parent, rest)) = name.split_once('.') {
+        let child = object
+            .entry(parent)
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Value::Object(child) = child {
+            insert(child, rest, value);
+            return;
+        }
+    }
+    object.insert(name.to_string(), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder};
+    use crate::error::Error;
+    use crate::types::{
+        Datatype, ListSpec, PropertyName, PropertyNameKind, PropertyValueSpec, Role,
+    };
+    use crate::ProductConfigSpec;
+    use rstest::*;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    const CONF_FILE: &str = "server.properties";
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct ServerConfig {
+        port: u16,
+        ssl: Ssl,
+        log: Log,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Ssl {
+        enabled: bool,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Log {
+        dirs: Vec<String>,
+    }
+
+    fn spec() -> ProductConfigSpec {
+        let property = |name: &str, datatype: Datatype| {
+            PropertySpecBuilder::new(datatype, "0.1.0")
+                .property_name(PropertyName {
+                    name: name.to_string(),
+                    kind: PropertyNameKind::Conf(CONF_FILE.to_string()),
+                })
+                .role(Role {
+                    name: "broker".to_string(),
+                    required: false,
+                })
+        };
+        ProductConfigSpecBuilder::new()
+            .property_spec(
+                property(
+                    "port",
+                    Datatype::Port {
+                        allow_privileged: false,
+                        range: false,
+                    },
+                )
+                .default_value(PropertyValueSpec {
+                    from_version: None,
+                    to_version: None,
+                    when: None,
+                    platform: None,
+                    value: "9092".to_string(),
+                })
+                .build(),
+            )
+            .property_spec(property("ssl.enabled", Datatype::Bool).build())
+            .property_spec(
+                property(
+                    "log.dirs",
+                    Datatype::Path {
+                        must_be_absolute: true,
+                        must_exist: false,
+                        kind: None,
+                    },
+                )
+                .list(ListSpec::default())
+                .build(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_validate_into() {
+        let values = HashMap::from([
+            ("ssl.enabled".to_string(), "true".to_string()),
+            ("log.dirs".to_string(), "/a, /b".to_string()),
+        ]);
+
+        let config: ServerConfig = spec()
+            .validate_into(
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                &values,
+                "0.1.0",
+            )
+            .unwrap();
+
+        assert_eq!(
+            config,
+            ServerConfig {
+                port: 9092,
+                ssl: Ssl { enabled: true },
+                log: Log {
+                    dirs: vec!["/a".to_string(), "/b".to_string()]
+                },
+            }
+        );
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct HeapConfig {
+        heap: Heap,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Heap {
+        size: i64,
+    }
+
+    #[rstest]
+    #[case("2048", 2048)]
+    #[case("2gb", 2048)]
+    #[case("1.5gb", 1536)]
+    #[case("512", 512)]
+    #[trace]
+    fn test_validate_into_with_unit(#[case] value: &str, #[case] expected: i64) {
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Integer {
+                        min: None,
+                        max: None,
+                        unit: None,
+                        accepted_units: Some(vec!["mb".to_string(), "gb".to_string()]),
+                        default_unit: Some("mb".to_string()),
+                        multiple_of: None,
+                    },
+                    "0.1.0",
+                )
+                .property_name(PropertyName {
+                    name: "heap.size".to_string(),
+                    kind: PropertyNameKind::Conf(CONF_FILE.to_string()),
+                })
+                .role(Role {
+                    name: "broker".to_string(),
+                    required: false,
+                })
+                .build(),
+            )
+            .build()
+            .unwrap();
+        let values = HashMap::from([("heap.size".to_string(), value.to_string())]);
+
+        let config: HeapConfig = spec
+            .validate_into(
+                &PropertyNameKind::Conf(CONF_FILE.to_string()),
+                &values,
+                "0.1.0",
+            )
+            .unwrap();
+
+        assert_eq!(
+            config,
+            HeapConfig {
+                heap: Heap { size: expected }
+            }
+        );
+    }
+
+    #[test]
+    fn test_validate_into_problems() {
+        let values = HashMap::from([
+            ("port".to_string(), "80".to_string()),
+            ("ssl.enabled".to_string(), "yes".to_string()),
+        ]);
+
+        let result = spec().validate_into::<ServerConfig>(
+            &PropertyNameKind::Conf(CONF_FILE.to_string()),
+            &values,
+            "0.1.0",
+        );
+
+        let problems = match result {
+            Err(Error::UserConfigInvalid { problems }) => problems,
+            other => panic!("expected invalid user config, got {:?}", other),
+        };
+        let names: Vec<&str> = problems
+            .iter()
+            .map(|problem| problem.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["port", "ssl.enabled", "ssl.enabled"]);
+        assert!(matches!(
+            problems[2].error,
+            Error::DeserializationFailed { .. }
+        ));
+    }
+}
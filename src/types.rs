@@ -1,36 +1,230 @@
-use regex::Regex;
-use serde::Deserialize;
-use std::collections::HashMap;
+use crate::rules::CompiledRule;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 /// Represents config spec like unit and regex specification
 #[derive(Clone, Debug)]
 pub(crate) struct ProductConfigSpecProperties {
     pub units: HashMap<String, Regex>,
+    // the units as provided by the config reader (kept to write the spec back unchanged)
+    pub unit_specs: Vec<Unit>,
+    // prefixes of user provided property names (e.g. namespaced environment variables)
+    pub prefixes: Vec<PropertyNamePrefix>,
+    // cross property rules with their parsed expressions
+    pub rules: Vec<CompiledRule>,
+    // pre-processing of user values for all properties (may be overridden per property)
+    pub normalization: Option<Normalization>,
+    // if unit regexes have to match the whole value (may be overridden per unit)
+    pub full_match: Option<bool>,
+    // datatype definitions referenced by property specs via name (kept to write the spec back)
+    pub types: BTreeMap<String, Datatype>,
+    // allowed values shared by several property specs via set name (kept to write the spec back)
+    pub value_sets: BTreeMap<String, Vec<String>>,
+    // groups of properties in the order they are presented in docs and UIs
+    pub groups: Vec<PropertyGroup>,
+    // the language of descriptions used if not available in the requested one (`en` if not
+    // provided)
+    pub default_locale: Option<String>,
+}
+
+impl ProductConfigSpecProperties {
+    /// Returns the prefix for the provided kind: a prefix for this kind takes precedence over
+    /// a global prefix (without kind)
+    pub fn prefix(&self, kind: &PropertyNameKind) -> Option<&str> {
+        self.prefixes
+            .iter()
+            .find(|prefix| prefix.kind.as_ref() == Some(kind))
+            .or_else(|| self.prefixes.iter().find(|prefix| prefix.kind.is_none()))
+            .map(|prefix| prefix.prefix.as_str())
+    }
 }
 
 /// Represents one property spec entry for a given property
-#[derive(Deserialize, Clone, Debug)]
-pub(crate) struct PropertySpec {
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(remote = "Self")]
+pub struct PropertySpec {
     pub property_names: Vec<PropertyName>,
     pub datatype: Datatype,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub default_values: Option<Vec<PropertyValueSpec>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub recommended_values: Option<Vec<PropertyValueSpec>>,
+    // legal values outside of this range are accepted with a warning (e.g. heap above 32g)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recommended_range: Option<RecommendedRange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub allowed_values: Option<Vec<String>>,
+    // name of a value set in the config spec whose values are added to the allowed values
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_value_set: Option<String>,
+    // regular expressions (matching the whole value) accepted in addition to the allowed values;
+    // a single pattern may be provided as plain string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_pattern: Option<Vec<String>>,
+    // values that are rejected even if they match the datatype and allowed values (e.g. 0.0.0.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forbidden_values: Option<Vec<String>>,
+    // name of a custom validator registered via `ProductConfigSpec::register_validator`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validator: Option<String>,
     pub as_of_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated_since: Option<String>,
-    pub deprecated_for: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed_in: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stability: Option<Stability>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub experimental: Option<bool>,
+    // how important it is to consider the property when configuring the product (low if not
+    // provided)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub importance: Option<Importance>,
+    // an empty value ("") is accepted (e.g. meaning "disabled") and not validated any further
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_empty: Option<bool>,
+    // the value is a delimited list: every item is validated on its own
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list: Option<ListSpec>,
+    // pre-processing of user values (flags provided here override the global normalization)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub normalization: Option<Normalization>,
+    // float values may be NaN or infinite (overrides the validation policy)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_non_finite: Option<bool>,
+    // integer values may be provided as hex, octal or binary literal (0x1F, 0o17, 0b1010)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub radix_prefixes: Option<bool>,
+    // the value may only be set once (e.g. data directories, cluster ids)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub immutable: Option<bool>,
+    // the value must not show up in errors (e.g. passwords), it is replaced by `***`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensitive: Option<bool>,
+    // every entry is one replacement consisting of its property names (one per kind)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated_for: Option<Vec<Vec<PropertyName>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub depends_on: Option<Vec<PropertyDependency>>,
+    // property names that must not be set together with this property (a trailing '*' matches
+    // all property names with that prefix)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflicts_with: Option<Vec<PropertyName>>,
+    // the platforms (`os` or `os/arch`, e.g. `linux`) the property is supported on; all
+    // platforms if not provided
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platforms: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub roles: Option<Vec<Role>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub restart_required: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_policy: Option<ChangePolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
-    pub additional_doc: Option<Vec<String>>,
+    // name of a group declared in the config spec (e.g. `Networking`) to present related
+    // properties together in docs and UIs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    // links to further documentation; plain urls (as used before) are accepted as well
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_doc: Option<Vec<DocLink>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
-    pub description: Option<String>,
+    // a plain text or texts via language code (e.g. `{"en": "...", "de": "..."}`); may contain
+    // Markdown (see the `markdown` module)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Description>,
+    // any fields not known to this library (e.g. vendor annotations) are kept as they are
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl Serialize for PropertySpec {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PropertySpec::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PropertySpec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut spec = Value::deserialize(deserializer)?;
+        resolve_legacy_deprecated_for(&mut spec);
+        resolve_single_allowed_pattern(&mut spec);
+        resolve_datatype_ref(&mut spec);
+        PropertySpec::deserialize(spec).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Older specs provide `deprecated_for` as a list of plain property names (without kind).
+/// These are converted into a replacement with the same kinds as the deprecated property.
+/// A single property name object is converted into a replacement with only that name.
+///
+/// # Arguments
+///
+/// * `spec` - the raw property spec
+///
+fn resolve_legacy_deprecated_for(spec: &mut Value) {
+    let kinds: Vec<Value> = spec
+        .get("property_names")
+        .and_then(Value::as_array)
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|name| name.get("kind"))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if let Some(Value::Array(replacements)) = spec.get_mut("deprecated_for") {
+        for replacement in replacements.iter_mut() {
+            match replacement {
+                Value::String(name) => {
+                    let names = kinds
+                        .iter()
+                        .map(|kind| serde_json::json!({ "name": name, "kind": kind }))
+                        .collect();
+                    *replacement = Value::Array(names);
+                }
+                Value::Object(_) => *replacement = Value::Array(vec![replacement.take()]),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A single `allowed_pattern` may be provided as plain string instead of a list of patterns.
+///
+/// # Arguments
+///
+/// * `spec` - the raw property spec
+///
+fn resolve_single_allowed_pattern(spec: &mut Value) {
+    if let Some(pattern @ Value::String(_)) = spec.get_mut("allowed_pattern") {
+        *pattern = Value::Array(vec![pattern.take()]);
+    }
+}
+
+/// A reference to a datatype definition may be provided without type (`{"ref": "heap-size"}`).
+///
+/// # Arguments
+///
+/// * `spec` - the raw property spec
+///
+fn resolve_datatype_ref(spec: &mut Value) {
+    if let Some(Value::Object(datatype)) = spec.get_mut("datatype") {
+        if datatype.contains_key("ref") && !datatype.contains_key("type") {
+            datatype.insert("type".to_string(), Value::String("ref".to_string()));
+        }
+    }
 }
 
 /// Represents (one of multiple) unique identifier for a property name depending on the type
-#[derive(Deserialize, Clone, Debug, Hash, Eq, PartialOrd, PartialEq)]
+#[derive(Deserialize, Serialize, Clone, Debug, Hash, Eq, Ord, PartialOrd, PartialEq)]
 pub struct PropertyName {
     pub name: String,
     pub kind: PropertyNameKind,
@@ -43,7 +237,7 @@ impl fmt::Display for PropertyName {
 }
 
 /// Represents different config identifier types like config file, environment variable, command line parameter etc.
-#[derive(Deserialize, Clone, Debug, Hash, Eq, PartialOrd, PartialEq)]
+#[derive(Deserialize, Serialize, Clone, Debug, Hash, Eq, Ord, PartialOrd, PartialEq)]
 #[serde(tag = "type", content = "file", rename_all = "lowercase")]
 pub enum PropertyNameKind {
     Conf(String),
@@ -61,67 +255,1184 @@ impl PropertyNameKind {
 }
 
 /// Represents the config unit (name corresponds to the unit type like password and a given regex)
-#[derive(Deserialize, Debug)]
-pub(crate) struct Unit {
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct Unit {
     pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub regex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub examples: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    // anchor the regex to match the whole value (overrides the global `full_match` default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_match: Option<bool>,
+    // flags applied when compiling the regex (instead of embedding e.g. `(?i)` in the regex)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<Vec<RegexFlag>>,
+    // any fields not known to this library (e.g. vendor annotations) are kept as they are
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl Unit {
+    /// Returns the regex pattern of the unit, anchored to match the whole value if full match
+    /// applies to this unit
+    ///
+    /// # Arguments
+    ///
+    /// * `full_match` - the global default if the unit does not set full match itself
+    ///
+    pub fn pattern(&self, full_match: bool) -> Option<String> {
+        let regex = self.regex.as_ref()?;
+        if self.full_match.unwrap_or(full_match) {
+            // a trailing comment of a verbose regex must not swallow the closing anchor
+            let separator = if self.has_flag(RegexFlag::IgnoreWhitespace) {
+                "\n"
+            } else {
+                ""
+            };
+            Some(format!("^(?:{}{})$", regex, separator))
+        } else {
+            Some(regex.clone())
+        }
+    }
+
+    /// Returns the regex of the unit compiled with its flags
+    ///
+    /// # Arguments
+    ///
+    /// * `full_match` - the global default if the unit does not set full match itself
+    ///
+    pub fn compile(&self, full_match: bool) -> Option<Result<Regex, regex::Error>> {
+        let pattern = self.pattern(full_match)?;
+        Some(
+            RegexBuilder::new(&pattern)
+                .case_insensitive(self.has_flag(RegexFlag::CaseInsensitive))
+                .multi_line(self.has_flag(RegexFlag::MultiLine))
+                .dot_matches_new_line(self.has_flag(RegexFlag::DotMatchesNewLine))
+                .ignore_whitespace(self.has_flag(RegexFlag::IgnoreWhitespace))
+                .build(),
+        )
+    }
+
+    fn has_flag(&self, flag: RegexFlag) -> bool {
+        self.flags.iter().flatten().any(|f| *f == flag)
+    }
+}
+
+/// Represents a flag of a unit regex (named like the respective inline flag)
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, Hash, Ord, PartialOrd, PartialEq)]
+pub enum RegexFlag {
+    /// `i`: letters match both upper and lower case
+    #[serde(rename = "i")]
+    CaseInsensitive,
+    /// `m`: `^` and `$` match at the beginning and end of lines
+    #[serde(rename = "m")]
+    MultiLine,
+    /// `s`: `.` matches new lines
+    #[serde(rename = "s")]
+    DotMatchesNewLine,
+    /// `x`: whitespace is ignored and `#` starts a comment
+    #[serde(rename = "x")]
+    IgnoreWhitespace,
+}
+
+/// The parts of a valid property value captured by the named groups of its unit regex, e.g.
+/// `value` and `unit` for `(?P<value>\d+)(?P<unit>mb|gb)`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnitCaptures {
+    /// name of the unit the value was matched against
+    pub unit: String,
+    /// the captured text via group name (groups that did not participate in the match are left out)
+    pub groups: BTreeMap<String, String>,
+}
+
+impl UnitCaptures {
+    /// Returns the text captured by the named group (if it participated in the match)
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - the name of the capture group
+    ///
+    pub fn get(&self, group: &str) -> Option<&str> {
+        self.groups.get(group).map(String::as_str)
+    }
+}
+
+/// Represents a prefix of user provided property names that is not part of the property spec,
+/// e.g. a user passes `MYAPP_HTTP_PORT` while the spec defines `HTTP_PORT`. Without kind the
+/// prefix applies to all kinds.
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct PropertyNamePrefix {
+    pub prefix: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<PropertyNameKind>,
+}
+
+/// Represents the description of a property: a plain text (for all languages) or texts via
+/// language code like `en`, `de` or `de-CH`
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum Description {
+    Text(String),
+    Localized(BTreeMap<String, String>),
+}
+
+impl Description {
+    /// Returns the text for the locale or, if not available, for the language of the locale
+    /// (`de` for `de-CH`) or the default locale. A plain text is returned for every locale.
+    ///
+    /// # Arguments
+    ///
+    /// * `locale` - the requested language code, e.g. `de-CH`
+    /// * `default_locale` - the language code used if the requested one is not available
+    ///
+    pub fn text(&self, locale: &str, default_locale: &str) -> Option<&str> {
+        match self {
+            Description::Text(text) => Some(text),
+            Description::Localized(texts) => {
+                let language = locale.split(['-', '_']).next().unwrap_or(locale);
+                texts
+                    .get(locale)
+                    .or_else(|| texts.get(language))
+                    .or_else(|| texts.get(default_locale))
+                    .map(String::as_str)
+            }
+        }
+    }
+}
+
+/// Represents a link to additional documentation of a property (e.g. the upstream docs). The url
+/// must be an absolute `http` or `https` url, which is checked when the config spec is read.
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
+#[serde(from = "DocLinkSpec")]
+pub struct DocLink {
+    pub title: String,
+    pub url: String,
+}
+
+// additional docs were plain urls before, these are used as title as well
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DocLinkSpec {
+    Url(String),
+    Link { title: String, url: String },
+}
+
+impl From<DocLinkSpec> for DocLink {
+    fn from(spec: DocLinkSpec) -> Self {
+        match spec {
+            DocLinkSpec::Url(url) => DocLink {
+                title: url.clone(),
+                url,
+            },
+            DocLinkSpec::Link { title, url } => DocLink { title, url },
+        }
+    }
+}
+
+/// Represents a group of related properties (e.g. `Networking`) that property specs reference via
+/// `group`. Docs and UIs present the groups in the order they are declared.
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct PropertyGroup {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Represents a cross property rule like `if ${ssl.enabled} == 'true' then required(${ssl.keystore.path})`
+/// (see the `rules` module for the syntax). Without kind the rule applies to all kinds.
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct Rule {
+    pub expression: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<PropertyNameKind>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
 }
 
 /// Represents the default or recommended values a property may have: since default values
 /// may change with different releases, optional from and to version parameters can be provided
-#[derive(Deserialize, Clone, Debug, Eq, PartialOrd, PartialEq)]
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialOrd, PartialEq)]
 pub struct PropertyValueSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub from_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub to_version: Option<String>,
+    // the value only applies if another property is set (to the provided value); values with a
+    // matching condition take precedence over values without condition
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when: Option<PropertyDependency>,
+    // the value only applies to the target platform (e.g. a Windows specific path); values for
+    // the target platform take precedence over values without platform
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<Platform>,
     pub value: String,
 }
 
+/// Represents a platform via operating system and architecture as named in `std::env::consts`
+/// (e.g. `linux` and `x86_64`). As selector, a missing os or arch matches every os or arch.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, Eq, Hash, PartialOrd, PartialEq)]
+pub struct Platform {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arch: Option<String>,
+}
+
+impl Platform {
+    /// Create a platform from operating system and architecture
+    ///
+    /// # Arguments
+    ///
+    /// * `os` - the operating system, e.g. `linux`
+    /// * `arch` - the architecture, e.g. `x86_64`
+    ///
+    pub fn new(os: &str, arch: &str) -> Self {
+        Platform {
+            os: Some(os.to_string()),
+            arch: Some(arch.to_string()),
+        }
+    }
+
+    /// Returns the platform this library is running on
+    pub fn current() -> Self {
+        Platform::new(std::env::consts::OS, std::env::consts::ARCH)
+    }
+
+    /// Returns true if the platform (used as selector) matches the provided platform
+    ///
+    /// # Arguments
+    ///
+    /// * `platform` - the (target) platform
+    ///
+    pub fn matches(&self, platform: &Platform) -> bool {
+        (self.os.is_none() || self.os == platform.os)
+            && (self.arch.is_none() || self.arch == platform.arch)
+    }
+}
+
+impl From<&str> for Platform {
+    /// Parses a platform like `linux` or `linux/x86_64` (`*` matches every os or arch)
+    fn from(platform: &str) -> Self {
+        let mut parts = platform.splitn(2, '/').map(str::trim);
+        let part = |part: Option<&str>| {
+            part.filter(|part| !part.is_empty() && *part != "*")
+                .map(str::to_string)
+        };
+        Platform {
+            os: part(parts.next()),
+            arch: part(parts.next()),
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}",
+            self.os.as_deref().unwrap_or("*"),
+            self.arch.as_deref().unwrap_or("*")
+        )
+    }
+}
+
 /// Represents all supported data types
-#[derive(Deserialize, Clone, Debug, Eq, PartialOrd, PartialEq)]
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialOrd, PartialEq)]
 #[serde(rename_all = "lowercase", tag = "type")]
-pub(crate) enum Datatype {
+pub enum Datatype {
     Bool,
     Integer {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        unit: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        accepted_units: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        default_unit: Option<String>,
+        // the value has to be a multiple of this (e.g. 1024 for buffer sizes)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        multiple_of: Option<String>,
+    },
+    // a non negative integer (u64), e.g. for large byte counts
+    UnsignedInteger {
+        #[serde(skip_serializing_if = "Option::is_none")]
         min: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         max: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         unit: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         accepted_units: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         default_unit: Option<String>,
     },
     Float {
+        #[serde(skip_serializing_if = "Option::is_none")]
         min: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         max: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         unit: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         accepted_units: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         default_unit: Option<String>,
     },
     String {
+        #[serde(skip_serializing_if = "Option::is_none")]
         min: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         max: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         unit: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         accepted_units: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         default_unit: Option<String>,
+        // how the length is measured for min / max (bytes if not provided)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        length_unit: Option<LengthUnit>,
     },
     Array {
+        #[serde(skip_serializing_if = "Option::is_none")]
         unit: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         accepted_units: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         default_unit: Option<String>,
     },
+    // an exact decimal number with at most `precision` digits of which `scale` are after the
+    // decimal point (like SQL DECIMAL); requires the `rust_decimal` feature
+    Decimal {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        precision: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        scale: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max: Option<String>,
+    },
+    // a number with time unit (ns, mus, ms, s, m, h, d), e.g. 500ms or 2h; bounds are compared after
+    // conversion to a common unit
+    Duration {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max: Option<String>,
+    },
+    // a number with (binary) memory unit (b, k(b), m(b), g(b), t(b), p(b)), e.g. 512mb or 2G;
+    // bounds are compared after conversion to bytes
+    MemorySize {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max: Option<String>,
+    },
+    // a port (0-65535) or a port range (e.g. 9000-9010) if `range` is set; privileged ports
+    // (below 1024) are rejected unless `allow_privileged` is set
+    Port {
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        allow_privileged: bool,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        range: bool,
+    },
+    // an absolute URL with one of the allowed schemes (if provided) and a host (if required);
+    // requires the `url` feature
+    Url {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        allowed_schemes: Option<Vec<String>>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        require_host: bool,
+    },
+    // an IPv4 or IPv6 address (restricted via `v4_only` / `v6_only`) or a CIDR block (e.g.
+    // 10.0.0.0/8) if `allow_cidr` is set
+    IpAddress {
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        v4_only: bool,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        v6_only: bool,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        allow_cidr: bool,
+    },
+    // a filesystem path; existence (of the provided kind) is only checked if `must_exist` is set
+    Path {
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        must_be_absolute: bool,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        must_exist: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        kind: Option<PathKind>,
+    },
+    // a date / time in the provided chrono format (RFC 3339 if not provided); requires the
+    // `chrono` feature
+    DateTime {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        format: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max: Option<String>,
+    },
+    // a percentage: `80%` or (if `as_fraction` is set) 0.0 - 1.0, otherwise 0 - 100; values
+    // are normalized to the configured notation
+    Percent {
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        as_fraction: bool,
+    },
+    // a regular expression that has to compile
+    Regex,
+    // a JSON document that conforms to the schema (if provided)
+    Json {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        schema: Option<JsonSchema>,
+    },
+    // one of the listed variants; values are canonicalized to the variant if case insensitive
+    Enum {
+        values: Vec<String>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        case_insensitive: bool,
+    },
+    // a password with at least `min_length` characters (chars), at least one character of every
+    // required class and (if provided) an estimated entropy of at least `min_entropy` bits; the
+    // value itself never shows up in errors
+    Password {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min_length: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        required_classes: Option<Vec<CharacterClass>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min_entropy: Option<u32>,
+    },
+    // a datatype defined once in the `types` of the config spec (e.g. `{"ref": "heap-size"}`);
+    // replaced by the definition when the spec is read
+    Ref {
+        #[serde(rename = "ref")]
+        name: String,
+    },
+}
+
+impl Datatype {
+    /// Returns the (min, max) bounds of the datatype if available
+    pub fn bounds(&self) -> (Option<&String>, Option<&String>) {
+        match self {
+            Datatype::Integer { min, max, .. }
+            | Datatype::UnsignedInteger { min, max, .. }
+            | Datatype::Float { min, max, .. }
+            | Datatype::String { min, max, .. }
+            | Datatype::Duration { min, max }
+            | Datatype::MemorySize { min, max }
+            | Datatype::DateTime { min, max, .. }
+            | Datatype::Decimal { min, max, .. } => (min.as_ref(), max.as_ref()),
+            Datatype::Bool
+            | Datatype::Regex
+            | Datatype::Percent { .. }
+            | Datatype::Json { .. }
+            | Datatype::Array { .. }
+            | Datatype::Port { .. }
+            | Datatype::Url { .. }
+            | Datatype::IpAddress { .. }
+            | Datatype::Path { .. }
+            | Datatype::Enum { .. }
+            | Datatype::Password { .. }
+            | Datatype::Ref { .. } => (None, None),
+        }
+    }
+
+    /// Returns the variants of an enum datatype (e.g. for doc or UI generation)
+    pub fn variants(&self) -> Option<&[String]> {
+        match self {
+            Datatype::Enum { values, .. } => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the enum variant matching the provided value (respecting `case_insensitive`) or
+    /// None if no variant matches or the datatype is no enum
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the value to be matched against the variants
+    ///
+    pub fn enum_variant(&self, value: &str) -> Option<&str> {
+        match self {
+            Datatype::Enum {
+                values,
+                case_insensitive,
+            } => values
+                .iter()
+                .find(|variant| {
+                    if *case_insensitive {
+                        variant.eq_ignore_ascii_case(value)
+                    } else {
+                        variant.as_str() == value
+                    }
+                })
+                .map(String::as_str),
+            _ => None,
+        }
+    }
+
+    /// Returns a copy of the datatype with min and max bounds removed
+    pub(crate) fn without_bounds(&self) -> Datatype {
+        self.with_bounds(None, None)
+    }
+
+    /// Returns the datatype with the provided min / max bounds (datatypes without bounds are
+    /// returned unchanged)
+    ///
+    /// # Arguments
+    ///
+    /// * `new_min` - the new min bound
+    /// * `new_max` - the new max bound
+    ///
+    pub(crate) fn with_bounds(&self, new_min: Option<String>, new_max: Option<String>) -> Datatype {
+        let mut datatype = self.clone();
+        match &mut datatype {
+            Datatype::Integer { min, max, .. }
+            | Datatype::UnsignedInteger { min, max, .. }
+            | Datatype::Float { min, max, .. }
+            | Datatype::String { min, max, .. }
+            | Datatype::Duration { min, max }
+            | Datatype::MemorySize { min, max }
+            | Datatype::DateTime { min, max, .. }
+            | Datatype::Decimal { min, max, .. } => {
+                *min = new_min;
+                *max = new_max;
+            }
+            Datatype::Bool
+            | Datatype::Regex
+            | Datatype::Percent { .. }
+            | Datatype::Json { .. }
+            | Datatype::Array { .. }
+            | Datatype::Port { .. }
+            | Datatype::Url { .. }
+            | Datatype::IpAddress { .. }
+            | Datatype::Path { .. }
+            | Datatype::Enum { .. }
+            | Datatype::Password { .. }
+            | Datatype::Ref { .. } => {}
+        }
+        datatype
+    }
 }
 
 /// Represents a dependency on another config property and (if available) a required value
 /// e.g. to set ssl certificates one has to set some property use_ssl to true
-#[derive(Deserialize, Clone, Debug, Eq, PartialOrd, PartialEq)]
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialOrd, PartialEq)]
 pub struct PropertyDependency {
     pub property_names: Vec<PropertyName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<String>,
 }
 
 /// Represents a role in the cluster, e.g. Server / Client and if the property is required
-#[derive(Deserialize, Clone, Debug, Eq, PartialOrd, PartialEq)]
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialOrd, PartialEq)]
 pub struct Role {
     pub name: String,
     pub required: bool,
 }
+
+/// Represents the lifecycle state of a property (a property without stability is considered stable)
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, Hash, Ord, PartialOrd, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Stability {
+    Alpha,
+    Beta,
+    Stable,
+    Deprecated,
+}
+
+impl fmt::Display for Stability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let stability = match self {
+            Stability::Alpha => "alpha",
+            Stability::Beta => "beta",
+            Stability::Stable => "stable",
+            Stability::Deprecated => "deprecated",
+        };
+        write!(f, "{}", stability)
+    }
+}
+
+/// Represents how important it is to consider a property when configuring the product (like
+/// Kafka's `ConfigDef.Importance`), ordered from low to high. The former levels `optional` and
+/// `required` are read as `low` and `high`.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, Hash, Ord, PartialOrd, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Importance {
+    #[serde(alias = "optional")]
+    Low,
+    Medium,
+    #[serde(alias = "required")]
+    High,
+}
+
+impl fmt::Display for Importance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let importance = match self {
+            Importance::Low => "low",
+            Importance::Medium => "medium",
+            Importance::High => "high",
+        };
+        write!(f, "{}", importance)
+    }
+}
+
+/// Represents a JSON schema embedded in the property spec (see `Datatype::Json`)
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct JsonSchema(pub Value);
+
+impl PartialOrd for JsonSchema {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.0.to_string().partial_cmp(&other.0.to_string())
+    }
+}
+
+/// Represents how the length of a string is measured
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, Hash, Ord, PartialOrd, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LengthUnit {
+    Bytes,
+    Chars,
+    /// user perceived characters; requires the `unicode-segmentation` feature
+    Graphemes,
+}
+
+impl fmt::Display for LengthUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let length_unit = match self {
+            LengthUnit::Bytes => "bytes",
+            LengthUnit::Chars => "chars",
+            LengthUnit::Graphemes => "graphemes",
+        };
+        write!(f, "{}", length_unit)
+    }
+}
+
+/// Represents what a path datatype has to point to
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, Hash, Ord, PartialOrd, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PathKind {
+    File,
+    Dir,
+}
+
+impl fmt::Display for PathKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self {
+            PathKind::File => "file",
+            PathKind::Dir => "directory",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+/// Represents a class of characters a password datatype may require
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, Hash, Ord, PartialOrd, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CharacterClass {
+    Lowercase,
+    Uppercase,
+    Digit,
+    /// every character that is no ASCII letter or digit
+    Special,
+}
+
+impl CharacterClass {
+    /// Returns the class of the character
+    ///
+    /// # Arguments
+    ///
+    /// * `c` - the character to be classified
+    ///
+    pub fn of(c: char) -> CharacterClass {
+        if c.is_ascii_lowercase() {
+            CharacterClass::Lowercase
+        } else if c.is_ascii_uppercase() {
+            CharacterClass::Uppercase
+        } else if c.is_ascii_digit() {
+            CharacterClass::Digit
+        } else {
+            CharacterClass::Special
+        }
+    }
+
+    /// Returns the number of characters in the class (printable ASCII characters for special
+    /// characters), used to estimate the entropy of passwords
+    pub fn size(&self) -> u32 {
+        match self {
+            CharacterClass::Lowercase | CharacterClass::Uppercase => 26,
+            CharacterClass::Digit => 10,
+            CharacterClass::Special => 33,
+        }
+    }
+}
+
+impl fmt::Display for CharacterClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let class = match self {
+            CharacterClass::Lowercase => "lowercase letter",
+            CharacterClass::Uppercase => "uppercase letter",
+            CharacterClass::Digit => "digit",
+            CharacterClass::Special => "special character",
+        };
+        write!(f, "{}", class)
+    }
+}
+
+/// Represents a property value consisting of delimited items (e.g. `bootstrap.servers`), each
+/// item is validated against the datatype, allowed values etc. of the property spec
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialOrd, PartialEq)]
+pub struct ListSpec {
+    #[serde(default = "ListSpec::default_delimiter")]
+    pub delimiter: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    // every item may only occur once
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unique: bool,
+}
+
+impl ListSpec {
+    fn default_delimiter() -> String {
+        ",".to_string()
+    }
+}
+
+impl Default for ListSpec {
+    fn default() -> Self {
+        ListSpec {
+            delimiter: ListSpec::default_delimiter(),
+            min_items: None,
+            max_items: None,
+            unique: false,
+        }
+    }
+}
+
+impl fmt::Display for ListSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}..{} items",
+            self.min_items
+                .map(|min| min.to_string())
+                .unwrap_or_default(),
+            self.max_items
+                .map(|max| max.to_string())
+                .unwrap_or_default()
+        )
+    }
+}
+
+/// Represents how user values are pre-processed before they are validated, e.g. values copied
+/// from YAML or shell scripts often carry trailing spaces or surrounding quotes. The normalized
+/// value is validated and returned instead of the provided one.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Normalization {
+    // remove leading and trailing whitespace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trim: Option<bool>,
+    // remove one pair of matching surrounding quotes (single or double)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strip_quotes: Option<bool>,
+    // replace every sequence of whitespace with a single space
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collapse_whitespace: Option<bool>,
+}
+
+impl Normalization {
+    /// Returns the normalization with flags that are not set here taken from the other one
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the normalization providing the fallback flags
+    ///
+    pub fn or(self, other: Normalization) -> Normalization {
+        Normalization {
+            trim: self.trim.or(other.trim),
+            strip_quotes: self.strip_quotes.or(other.strip_quotes),
+            collapse_whitespace: self.collapse_whitespace.or(other.collapse_whitespace),
+        }
+    }
+
+    /// Returns the normalized value or None if the value is not changed
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the value to be normalized
+    ///
+    pub fn apply(&self, value: &str) -> Option<String> {
+        let mut normalized = value;
+        if self.trim == Some(true) {
+            normalized = normalized.trim();
+        }
+        if self.strip_quotes == Some(true) {
+            for quote in ['"', '\''] {
+                if normalized.len() >= 2
+                    && normalized.starts_with(quote)
+                    && normalized.ends_with(quote)
+                {
+                    normalized = &normalized[1..normalized.len() - 1];
+                    break;
+                }
+            }
+        }
+        let normalized = if self.collapse_whitespace == Some(true) {
+            normalized.split_whitespace().collect::<Vec<_>>().join(" ")
+        } else {
+            normalized.to_string()
+        };
+
+        if normalized == value {
+            None
+        } else {
+            Some(normalized)
+        }
+    }
+}
+
+/// Represents the range of values recommended for a property: values within the datatype bounds
+/// but outside of this range are legal but discouraged
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialOrd, PartialEq)]
+pub struct RecommendedRange {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<String>,
+    // why values outside of the range are discouraged
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl fmt::Display for RecommendedRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}..{}",
+            self.min.as_deref().unwrap_or_default(),
+            self.max.as_deref().unwrap_or_default()
+        )
+    }
+}
+
+/// Represents the action required to apply a changed property value (ordered by impact)
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, Hash, Ord, PartialOrd, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangePolicy {
+    /// the change is applied without any further action
+    None,
+    /// the instances have to be restarted one after another
+    RollingRestart,
+    /// all instances have to be restarted
+    Restart,
+}
+
+/// Represents which properties may be set by the user when validating a user config
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationPolicy {
+    /// user values for properties with a stability not contained here result in an error;
+    /// recommended values of these properties are not added automatically
+    pub allowed_stability: Vec<Stability>,
+    /// user values for experimental properties result in an error unless explicitly allowed;
+    /// recommended values of experimental properties are only added if allowed
+    pub allow_experimental: bool,
+    /// float values like NaN or inf result in an error unless allowed here or via
+    /// `allow_non_finite` of the property spec
+    pub allow_non_finite_floats: bool,
+    /// references to environment variables in values (`${env:NAME}` or `$NAME`) are expanded
+    /// before validation if set; the variant decides how variables that are not set are handled
+    pub env_substitution: Option<MissingEnvVar>,
+    /// user values for properties that are not supported on the target platform result in a
+    /// warning instead of an error if set
+    pub allow_other_platforms: bool,
+}
+
+impl Default for ValidationPolicy {
+    /// Allows properties of any stability but no experimental properties, non finite floats or
+    /// properties for other platforms and does not expand environment variables
+    fn default() -> Self {
+        ValidationPolicy {
+            allowed_stability: vec![
+                Stability::Alpha,
+                Stability::Beta,
+                Stability::Stable,
+                Stability::Deprecated,
+            ],
+            allow_experimental: false,
+            allow_non_finite_floats: false,
+            env_substitution: None,
+            allow_other_platforms: false,
+        }
+    }
+}
+
+/// Represents how references to environment variables that are not set are handled
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MissingEnvVar {
+    /// the property value results in an error
+    Error,
+    /// the reference is replaced by an empty string
+    Empty,
+    /// the reference is kept as it is (e.g. to be expanded later by the product itself)
+    Keep,
+}
+
+/// Represents the format of a generated sample config
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SampleFormat {
+    /// `key=value` lines of the properties of kind `conf` (one section per config file)
+    Properties,
+    /// `key: value` lines of the properties of kind `conf` (one section per config file)
+    Yaml,
+    /// `KEY=value` lines of the properties of kind `env`
+    Env,
+}
+
+/// Represents how default values are written in a generated sample config
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SampleStyle {
+    /// properties with default are commented out, so only the required ones are set
+    CommentedDefaults,
+    /// properties with default are set to the default
+    ExplicitDefaults,
+}
+
+/// Represents how to handle units or property specs that exist in both specs when merging
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MergeStrategy {
+    /// The unit or property spec of the other spec replaces the existing one
+    Replace,
+    /// The property specs are combined: lists (e.g. allowed values, roles) are joined and
+    /// single values (e.g. datatype, description) of the other spec take precedence
+    Extend,
+    /// Differing units or property specs with the same name result in an error
+    Error,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{
+        Datatype, Description, DocLink, Importance, Normalization, PropertyName, PropertyNameKind,
+        PropertySpec, RegexFlag, Unit,
+    };
+    use rstest::*;
+
+    fn conf_name(name: &str, file: &str) -> PropertyName {
+        PropertyName {
+            name: name.to_string(),
+            kind: PropertyNameKind::Conf(file.to_string()),
+        }
+    }
+
+    #[rstest]
+    #[case(
+        r#"["NEW"]"#,
+        vec![vec![conf_name("NEW", "env.sh"), conf_name("NEW", "my.config")]]
+    )]
+    #[case(
+        r#"[{"name": "NEW", "kind": {"type": "conf", "file": "env.sh"}}]"#,
+        vec![vec![conf_name("NEW", "env.sh")]]
+    )]
+    #[case(
+        r#"[[{"name": "NEW", "kind": {"type": "conf", "file": "env.sh"}}, {"name": "new", "kind": {"type": "conf", "file": "my.config"}}]]"#,
+        vec![vec![conf_name("NEW", "env.sh"), conf_name("new", "my.config")]]
+    )]
+    #[trace]
+    fn test_deserialize_deprecated_for(
+        #[case] deprecated_for: &str,
+        #[case] expected: Vec<Vec<PropertyName>>,
+    ) {
+        let spec = format!(
+            r#"{{
+                "property_names": [
+                    {{"name": "OLD", "kind": {{"type": "conf", "file": "env.sh"}}}},
+                    {{"name": "old", "kind": {{"type": "conf", "file": "my.config"}}}}
+                ],
+                "datatype": {{"type": "bool"}},
+                "as_of_version": "0.1.0",
+                "deprecated_for": {}
+            }}"#,
+            deprecated_for
+        );
+
+        let spec: PropertySpec = serde_json::from_str(&spec).unwrap();
+
+        assert_eq!(spec.deprecated_for, Some(expected));
+    }
+
+    #[rstest]
+    #[case(r#""[0-9]+ mb""#, "[]", false, "512 MB", false)]
+    #[case(r#""[0-9]+ mb""#, r#"["i"]"#, false, "512 MB", true)]
+    #[case(r#""[0-9]+ mb""#, r#"["i"]"#, true, "512 MB!", false)]
+    #[case(
+        r#""[0-9]+ # amount\n mb # unit""#,
+        r#"["x", "i"]"#,
+        true,
+        "512MB",
+        true
+    )]
+    #[case(r#""^a.b$""#, r#"["s", "m"]"#, false, "x\na\nb", true)]
+    #[trace]
+    fn test_unit_compile_with_flags(
+        #[case] regex: &str,
+        #[case] flags: &str,
+        #[case] full_match: bool,
+        #[case] value: &str,
+        #[case] expected: bool,
+    ) {
+        let unit = format!(
+            r#"{{"name": "memory", "regex": {}, "flags": {}}}"#,
+            regex, flags
+        );
+
+        let unit: Unit = serde_json::from_str(&unit).unwrap();
+        let compiled = unit.compile(full_match).unwrap().unwrap();
+
+        assert_eq!(compiled.is_match(value), expected);
+    }
+
+    #[test]
+    fn test_serialize_regex_flags() {
+        let flags = vec![
+            RegexFlag::CaseInsensitive,
+            RegexFlag::MultiLine,
+            RegexFlag::DotMatchesNewLine,
+            RegexFlag::IgnoreWhitespace,
+        ];
+
+        assert_eq!(
+            serde_json::to_string(&flags).unwrap(),
+            r#"["i","m","s","x"]"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_description() {
+        let text: Description = serde_json::from_str(r#""Heap size""#).unwrap();
+        let localized: Description =
+            serde_json::from_str(r#"{"en": "Heap size", "de": "Größe des Heaps"}"#).unwrap();
+
+        assert_eq!(text, Description::Text("Heap size".to_string()));
+        assert_eq!(localized.text("de-DE", "en"), Some("Größe des Heaps"));
+        assert_eq!(localized.text("fr", "en"), Some("Heap size"));
+    }
+
+    #[test]
+    fn test_deserialize_doc_link() {
+        let links: Vec<DocLink> = serde_json::from_str(
+            r#"["https://kafka.apache.org", {"title": "Broker configs", "url": "https://kafka.apache.org/documentation/#brokerconfigs"}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            links,
+            vec![
+                DocLink {
+                    title: "https://kafka.apache.org".to_string(),
+                    url: "https://kafka.apache.org".to_string(),
+                },
+                DocLink {
+                    title: "Broker configs".to_string(),
+                    url: "https://kafka.apache.org/documentation/#brokerconfigs".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(r#""low""#, Importance::Low)]
+    #[case(r#""medium""#, Importance::Medium)]
+    #[case(r#""high""#, Importance::High)]
+    #[case(r#""optional""#, Importance::Low)]
+    #[case(r#""required""#, Importance::High)]
+    #[trace]
+    fn test_deserialize_importance(#[case] importance: &str, #[case] expected: Importance) {
+        assert_eq!(
+            serde_json::from_str::<Importance>(importance).unwrap(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(r#""rack-\\d+""#, vec!["rack-\\d+"])]
+    #[case(r#"["rack-\\d+", "default"]"#, vec!["rack-\\d+", "default"])]
+    #[trace]
+    fn test_deserialize_allowed_pattern(
+        #[case] allowed_pattern: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let spec = format!(
+            r#"{{
+                "property_names": [{{"name": "rack", "kind": {{"type": "conf", "file": "my.config"}}}}],
+                "datatype": {{"type": "string"}},
+                "as_of_version": "0.1.0",
+                "allowed_pattern": {}
+            }}"#,
+            allowed_pattern
+        );
+
+        let spec: PropertySpec = serde_json::from_str(&spec).unwrap();
+
+        assert_eq!(
+            spec.allowed_pattern,
+            Some(expected.into_iter().map(str::to_string).collect())
+        );
+    }
+
+    #[rstest]
+    #[case(
+        r#"{"type": "enum", "values": ["GZIP", "SNAPPY"]}"#,
+        "GZIP",
+        Some("GZIP")
+    )]
+    #[case(r#"{"type": "enum", "values": ["GZIP", "SNAPPY"]}"#, "gzip", None)]
+    #[case(
+        r#"{"type": "enum", "values": ["GZIP", "SNAPPY"], "case_insensitive": true}"#,
+        "gzip",
+        Some("GZIP")
+    )]
+    #[case(
+        r#"{"type": "enum", "values": ["GZIP", "SNAPPY"], "case_insensitive": true}"#,
+        "lz4",
+        None
+    )]
+    #[case(r#"{"type": "bool"}"#, "true", None)]
+    #[trace]
+    fn test_enum_variant(
+        #[case] datatype: &str,
+        #[case] value: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let datatype: Datatype = serde_json::from_str(datatype).unwrap();
+
+        assert_eq!(datatype.enum_variant(value), expected);
+    }
+
+    #[rstest]
+    #[case(Normalization::default(), " 8080 ", None)]
+    #[case(Normalization { trim: Some(true), ..Normalization::default() }, " 8080 ", Some("8080"))]
+    #[case(Normalization { trim: Some(true), ..Normalization::default() }, "8080", None)]
+    #[case(Normalization { strip_quotes: Some(true), ..Normalization::default() }, "'gzip'", Some("gzip"))]
+    #[case(Normalization { strip_quotes: Some(true), ..Normalization::default() }, "\"gzip'", None)]
+    #[case(Normalization { trim: Some(true), strip_quotes: Some(true), ..Normalization::default() }, " \"gzip\" ", Some("gzip"))]
+    #[case(Normalization { collapse_whitespace: Some(true), ..Normalization::default() }, "-Xmx1g \t -Xms1g", Some("-Xmx1g -Xms1g"))]
+    #[trace]
+    fn test_normalization_apply(
+        #[case] normalization: Normalization,
+        #[case] value: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(normalization.apply(value).as_deref(), expected);
+    }
+}
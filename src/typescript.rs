@@ -0,0 +1,428 @@
+//! Export of the properties of a product version as TypeScript type definitions, e.g. for
+//! configuration editors in web frontends.
+//!
+//! The definitions are derived from the JSON Schema export (see `json_schema`), so editors share
+//! the constraints and defaults of the validator: every property kind (`env`, `cli` or the name of
+//! the config file) becomes an interface with a member per property. Allowed values are unions of
+//! literal types, bounds, patterns and defaults are documented as JSDoc tags (like
+//! `typescript-json-schema` reads them) and the defaults are exported as constant as well.
+//! Properties of high importance are required, all others optional.
+use crate::json_schema;
+use crate::validation::ValidationResult;
+use crate::ProductConfigSpec;
+use semver::Version;
+use serde_json::{Map, Value};
+
+/// JSON Schema keywords that are documented as JSDoc tags (in this order)
+const TAGS: &[&str] = &[
+    "minimum",
+    "maximum",
+    "multipleOf",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "format",
+    "minItems",
+    "maxItems",
+    "uniqueItems",
+    "default",
+];
+
+/// Returns the type definitions of the properties available in the product version: an
+/// interface per kind, the interface with the provided name containing them and the defaults
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `product_version` - the product version
+/// * `type_name` - the name of the interface containing the kinds, e.g. `KafkaConfig`
+///
+pub(crate) fn definitions(
+    spec: &ProductConfigSpec,
+    product_version: &Version,
+    type_name: &str,
+) -> ValidationResult<String> {
+    let kinds = json_schema::kind_schemas(
+        &spec.config_spec,
+        &spec.property_specs,
+        product_version,
+        true,
+    )?;
+    let mut definitions = vec![];
+    let mut members = vec![];
+    let mut defaults = Map::new();
+
+    for (kind_name, schema) in &kinds {
+        let interface = format!("{}{}", type_name, pascal_case(kind_name));
+        definitions.push(format!(
+            "/** Properties of `{}` */\nexport interface {} {}\n",
+            comment(kind_name),
+            interface,
+            object_type(schema, "")
+        ));
+        let required = schema.get("required").is_some();
+        members.push(format!(
+            "  {}{}: {};\n",
+            key(kind_name),
+            if required { "" } else { "?" },
+            interface
+        ));
+
+        let kind_defaults: Map<String, Value> = schema["properties"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, property)| Some((name.clone(), property.get("default")?.clone())))
+            .collect();
+        if !kind_defaults.is_empty() {
+            defaults.insert(kind_name.clone(), Value::Object(kind_defaults));
+        }
+    }
+
+    definitions.push(format!(
+        "/** Configuration of the product version {} */\nexport interface {} {{\n{}}}\n",
+        product_version,
+        type_name,
+        members.concat()
+    ));
+    definitions.push(format!(
+        "/** Defaults of the product version {} */\nexport const {}Defaults = {} as const;\n",
+        product_version,
+        camel_case(type_name),
+        serde_json::to_string_pretty(&Value::Object(defaults)).unwrap_or_default()
+    ));
+
+    Ok(definitions.join("\n"))
+}
+
+/// Returns the TypeScript type of the schema: literal unions for `enum` and `const`, inline
+/// object types for objects with properties and `unknown` if the type is not restricted
+///
+/// # Arguments
+///
+/// * `schema` - the JSON schema
+/// * `indent` - the indentation of the members of object types
+///
+fn ts_type(schema: &Value, indent: &str) -> String {
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return values
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+    if let Some(value) = schema.get("const") {
+        return value.to_string();
+    }
+
+    let ts_type_of = |schema_type: &str| match schema_type {
+        "boolean" => "boolean".to_string(),
+        "integer" | "number" => "number".to_string(),
+        "string" => "string".to_string(),
+        "null" => "null".to_string(),
+        "array" => match schema.get("items") {
+            Some(items) => {
+                let items = ts_type(items, indent);
+                match items.contains(" | ") {
+                    true => format!("({})[]", items),
+                    false => format!("{}[]", items),
+                }
+            }
+            None => "unknown[]".to_string(),
+        },
+        "object" => object_type(schema, indent),
+        _ => "unknown".to_string(),
+    };
+    match schema.get("type") {
+        Some(Value::String(schema_type)) => ts_type_of(schema_type),
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .map(ts_type_of)
+            .collect::<Vec<_>>()
+            .join(" | "),
+        _ if schema.get("properties").is_some() => object_type(schema, indent),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Returns the object type with a (documented) member per property; members not listed as
+/// `required` are optional
+///
+/// # Arguments
+///
+/// * `schema` - the JSON schema of the object
+/// * `indent` - the indentation of the object (the members are indented by two more spaces)
+///
+fn object_type(schema: &Value, indent: &str) -> String {
+    let properties = match schema.get("properties").and_then(Value::as_object) {
+        Some(properties) => properties,
+        None => return "Record<string, unknown>".to_string(),
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    let member_indent = format!("{}  ", indent);
+
+    let mut object = "{\n".to_string();
+    for (name, property) in properties {
+        object.push_str(&doc_comment(property, &member_indent));
+        object.push_str(&format!(
+            "{}{}{}: {};\n",
+            member_indent,
+            key(name),
+            if required.contains(&name.as_str()) {
+                ""
+            } else {
+                "?"
+            },
+            ts_type(property, &member_indent)
+        ));
+    }
+    object.push_str(indent);
+    object.push('}');
+    object
+}
+
+/// Returns the JSDoc comment with the description, constraints and default of the property
+/// (empty if there is nothing to document)
+///
+/// # Arguments
+///
+/// * `schema` - the JSON schema of the property
+/// * `indent` - the indentation of the comment
+///
+fn doc_comment(schema: &Value, indent: &str) -> String {
+    let mut lines: Vec<String> = schema
+        .get("description")
+        .and_then(Value::as_str)
+        .map(|description| description.lines().map(comment).collect())
+        .unwrap_or_default();
+
+    // constraints of list items are documented like the ones of single values
+    let items = schema.get("items").unwrap_or(&Value::Null);
+    let mut tags = vec![];
+    for tag in TAGS {
+        match schema.get(*tag).or_else(|| match *tag {
+            "default" => None,
+            _ => items.get(*tag),
+        }) {
+            Some(Value::String(value)) if *tag != "default" => {
+                tags.push(format!("@{} {}", tag, comment(value)))
+            }
+            Some(value) => tags.push(format!("@{} {}", tag, comment(&value.to_string()))),
+            None => {}
+        }
+    }
+    if schema.get("deprecated") == Some(&Value::Bool(true)) {
+        tags.push("@deprecated".to_string());
+    }
+    if !lines.is_empty() && !tags.is_empty() {
+        lines.push(String::new());
+    }
+    lines.extend(tags);
+
+    match lines.len() {
+        0 => String::new(),
+        _ => format!(
+            "{}/**\n{}{} */\n",
+            indent,
+            lines
+                .iter()
+                .map(|line| match line.is_empty() {
+                    true => format!("{} *\n", indent),
+                    false => format!("{} * {}\n", indent, line.trim_end()),
+                })
+                .collect::<String>(),
+            indent
+        ),
+    }
+}
+
+/// Returns the text with `*/` escaped to be used in comments
+fn comment(text: &str) -> String {
+    text.replace("*/", "*\\/")
+}
+
+/// Returns the name as key of a member: quoted if it is not an identifier (e.g. `log.dirs`)
+fn key(name: &str) -> String {
+    let identifier = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    match identifier {
+        true => name.to_string(),
+        false => Value::String(name.to_string()).to_string(),
+    }
+}
+
+/// Returns the name in pascal case, e.g. `EnvSh` for `env.sh`
+fn pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Returns the name in camel case, e.g. `kafkaConfig` for `KafkaConfig`
+fn camel_case(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder};
+    use crate::types::{
+        Datatype, Importance, ListSpec, PropertyName, PropertyNameKind, PropertyValueSpec, Role,
+    };
+    use crate::typescript::{key, pascal_case, ts_type};
+    use rstest::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_to_typescript() {
+        let role = Role {
+            name: "broker".to_string(),
+            required: false,
+        };
+        let conf = |name: &str| PropertyName {
+            name: name.to_string(),
+            kind: PropertyNameKind::Conf("server.properties".to_string()),
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Port {
+                        allow_privileged: false,
+                        range: false,
+                    },
+                    "1.0.0",
+                )
+                .property_name(conf("port"))
+                .default_value(PropertyValueSpec {
+                    from_version: None,
+                    to_version: None,
+                    when: None,
+                    platform: None,
+                    value: "9092".to_string(),
+                })
+                .description("The listener port")
+                .importance(Importance::High)
+                .role(role.clone())
+                .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Enum {
+                        values: vec!["plain".to_string(), "gzip".to_string()],
+                        case_insensitive: false,
+                    },
+                    "1.0.0",
+                )
+                .property_name(conf("compression.types"))
+                .list(ListSpec::default())
+                .role(role.clone())
+                .build(),
+            )
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "1.0.0")
+                    .property_name(PropertyName {
+                        name: "DEBUG".to_string(),
+                        kind: PropertyNameKind::Env,
+                    })
+                    .deprecated_since("1.0.0")
+                    .role(role)
+                    .build(),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            spec.to_typescript("1.0.0", "KafkaConfig").unwrap(),
+            r#"/** Properties of `env` */
+export interface KafkaConfigEnv {
+  /**
+   * @deprecated
+   */
+  DEBUG?: boolean;
+}
+
+/** Properties of `server.properties` */
+export interface KafkaConfigServerProperties {
+  "compression.types"?: ("plain" | "gzip")[];
+  /**
+   * The listener port
+   *
+   * @minimum 1024
+   * @maximum 65535
+   * @default 9092
+   */
+  port: number;
+}
+
+/** Configuration of the product version 1.0.0 */
+export interface KafkaConfig {
+  env?: KafkaConfigEnv;
+  "server.properties": KafkaConfigServerProperties;
+}
+
+/** Defaults of the product version 1.0.0 */
+export const kafkaConfigDefaults = {
+  "server.properties": {
+    "port": 9092
+  }
+} as const;
+"#
+        );
+    }
+
+    #[rstest]
+    #[case(json!({"type": "string"}), "string")]
+    #[case(json!({"type": ["integer", "null"]}), "number | null")]
+    #[case(json!({"type": "array", "items": {"type": "boolean"}}), "boolean[]")]
+    #[case(json!({"type": "array", "items": {"enum": [1, 2]}}), "(1 | 2)[]")]
+    #[case(json!({"type": "object"}), "Record<string, unknown>")]
+    #[case(
+        json!({"type": "object", "properties": {"a": {"type": "number"}}, "required": ["a"]}),
+        "{\n  a: number;\n}"
+    )]
+    #[case(json!({"const": "x"}), "\"x\"")]
+    #[case(json!({}), "unknown")]
+    #[trace]
+    fn test_ts_type(#[case] schema: Value, #[case] expected: &str) {
+        assert_eq!(ts_type(&schema, ""), expected);
+    }
+
+    #[rstest]
+    #[case("port", "port")]
+    #[case("log.dirs", "\"log.dirs\"")]
+    #[case("--port", "\"--port\"")]
+    #[case("1st", "\"1st\"")]
+    #[trace]
+    fn test_key(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(key(name), expected);
+    }
+
+    #[rstest]
+    #[case("env.sh", "EnvSh")]
+    #[case("server.properties", "ServerProperties")]
+    #[case("cli", "Cli")]
+    #[trace]
+    fn test_pascal_case(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(pascal_case(name), expected);
+    }
+}
@@ -0,0 +1,408 @@
+//! Conversion tables for numbers with unit suffix like memory sizes (`512mb`) and durations
+//! (`1.5h`).
+//!
+//! The tables are used to validate `memorysize` and `duration` properties as well as numeric
+//! properties with `accepted_units`. Values are converted via the base unit of their table
+//! (bytes respectively milliseconds), e.g. to emit a value in the unit a product expects
+//! regardless of how the user wrote it:
+//!
+//! ```
+//! use product_config::units::MEMORY_SIZE;
+//!
+//! assert_eq!(MEMORY_SIZE.convert(1.5, "g", "m"), Some(1536.0));
+//! ```
+use crate::error::Error;
+use crate::types::{Datatype, PropertyName, PropertySpec};
+use crate::validation::ValidationResult;
+
+/// A table of units and their factors to convert numbers to a common base unit
+#[derive(Debug)]
+pub struct Quantity {
+    /// name of the quantity (e.g. for error messages)
+    pub name: &'static str,
+    /// the known units and their factor to convert to the base unit
+    pub units: &'static [(&'static str, f64)],
+    /// if units are matched case insensitive (e.g. `MB` and `mb`)
+    pub case_insensitive: bool,
+}
+
+impl Quantity {
+    /// Returns the factor to convert the unit to the base unit or None if the unit is unknown
+    ///
+    /// # Arguments
+    ///
+    /// * `unit` - the unit, e.g. `mb`
+    ///
+    pub fn factor(&self, unit: &str) -> Option<f64> {
+        self.units
+            .iter()
+            .find(|(name, _)| {
+                if self.case_insensitive {
+                    name.eq_ignore_ascii_case(unit)
+                } else {
+                    *name == unit
+                }
+            })
+            .map(|(_, factor)| *factor)
+    }
+
+    /// Returns the number converted from one unit to another or None if one of the units is
+    /// unknown
+    ///
+    /// # Arguments
+    ///
+    /// * `number` - the number to be converted
+    /// * `unit` - the unit of the number
+    /// * `target_unit` - the unit to convert the number to
+    ///
+    pub fn convert(&self, number: f64, unit: &str, target_unit: &str) -> Option<f64> {
+        Some(number * self.factor(unit)? / self.factor(target_unit)?)
+    }
+}
+
+/// Time units and their factor to convert to milliseconds
+pub const DURATION: Quantity = Quantity {
+    name: "duration",
+    units: &[
+        ("ns", 0.000_001),
+        ("mus", 0.001),
+        ("ms", 1.0),
+        ("s", 1_000.0),
+        ("m", 60_000.0),
+        ("h", 3_600_000.0),
+        ("d", 86_400_000.0),
+    ],
+    case_insensitive: false,
+};
+
+const KIB: f64 = 1024.0;
+
+/// Memory units and their factor to convert to bytes
+pub const MEMORY_SIZE: Quantity = Quantity {
+    name: "memory size",
+    units: &[
+        ("b", 1.0),
+        ("k", KIB),
+        ("kb", KIB),
+        ("m", KIB * KIB),
+        ("mb", KIB * KIB),
+        ("g", KIB * KIB * KIB),
+        ("gb", KIB * KIB * KIB),
+        ("t", KIB * KIB * KIB * KIB),
+        ("tb", KIB * KIB * KIB * KIB),
+        ("p", KIB * KIB * KIB * KIB * KIB),
+        ("pb", KIB * KIB * KIB * KIB * KIB),
+    ],
+    case_insensitive: true,
+};
+
+/// The quantities whose units may be used as accepted units of numeric datatypes
+pub const QUANTITIES: [&Quantity; 2] = [&MEMORY_SIZE, &DURATION];
+
+/// Returns the first quantity that knows all provided units (e.g. `m` is a memory size unit
+/// but together with `h` only a duration unit)
+///
+/// # Arguments
+///
+/// * `units` - the units that have to be convertible into each other
+///
+pub fn quantity_of(units: &[&str]) -> Option<&'static Quantity> {
+    QUANTITIES
+        .iter()
+        .copied()
+        .find(|quantity| units.iter().all(|unit| quantity.factor(unit).is_some()))
+}
+
+/// Returns the number and the unit suffix of a value, e.g. `("512", "mb")` for `512 mb`
+///
+/// # Arguments
+///
+/// * `value` - the value with optional unit suffix
+///
+pub fn split_unit(value: &str) -> (&str, &str) {
+    let trimmed = value.trim();
+    let (number, unit) = trimmed.split_at(
+        trimmed
+            .find(|c: char| c.is_alphabetic())
+            .unwrap_or(trimmed.len()),
+    );
+    (number.trim(), unit.trim())
+}
+
+/// Returns the value of a property converted to the target unit (with the target unit as
+/// suffix). Values without unit suffix are given in the default unit of numeric datatypes.
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property` - the property spec (a duration, memory size or numeric datatype with units)
+/// * `value` - the value to be converted
+/// * `target_unit` - the unit to convert the value to
+///
+pub(crate) fn normalize(
+    property_name: &PropertyName,
+    property: &PropertySpec,
+    value: &str,
+    target_unit: &str,
+) -> ValidationResult<String> {
+    let (number, unit) = split_unit(value);
+
+    let (quantity, unit) = match &property.datatype {
+        Datatype::Duration { .. } => (Some(&DURATION), unit),
+        Datatype::MemorySize { .. } => (Some(&MEMORY_SIZE), unit),
+        Datatype::Integer {
+            accepted_units,
+            default_unit,
+            ..
+        }
+        | Datatype::UnsignedInteger {
+            accepted_units,
+            default_unit,
+            ..
+        }
+        | Datatype::Float {
+            accepted_units,
+            default_unit,
+            ..
+        } => {
+            let unit = match (unit, default_unit) {
+                ("", Some(default_unit)) => default_unit.as_str(),
+                ("", None) => accepted_units
+                    .iter()
+                    .flatten()
+                    .next()
+                    .map_or("", String::as_str),
+                (unit, _) => unit,
+            };
+            // all units of the datatype decide about the quantity (e.g. `m` with `h` are minutes)
+            let mut all_units: Vec<&str> = accepted_units
+                .iter()
+                .flatten()
+                .map(String::as_str)
+                .collect();
+            all_units.extend(default_unit.as_deref());
+            all_units.extend([unit, target_unit]);
+            (quantity_of(&all_units), unit)
+        }
+        _ => (None, unit),
+    };
+
+    let number = number
+        .parse::<f64>()
+        .map_err(|_| Error::DatatypeNotMatching {
+            property_name: property_name.clone(),
+            value: value.to_string(),
+            datatype: quantity
+                .map_or("number", |quantity| quantity.name)
+                .to_string(),
+        })?;
+
+    let converted = quantity
+        .and_then(|quantity| quantity.convert(number, unit, target_unit))
+        .ok_or_else(|| Error::UnitConversionNotSupported {
+            property_name: property_name.clone(),
+            unit: unit.to_string(),
+            target_unit: target_unit.to_string(),
+        })?;
+
+    Ok(format!("{}{}", format_number(converted), target_unit))
+}
+
+/// Returns the value of a duration, memory size or numeric property (with accepted units) in
+/// its canonical form: converted to the largest unit (of the units accepted by the datatype) in
+/// which the number is whole, e.g. `1g` for `1024MB` and `90s` for `1.5m`. Returns None for
+/// other properties and values that cannot be converted.
+///
+/// # Arguments
+///
+/// * `property` - the property spec
+/// * `value` - the value to be converted
+///
+pub(crate) fn canonical_unit(property: &PropertySpec, value: &str) -> Option<String> {
+    let (number, unit) = split_unit(value);
+    let number = number.parse::<f64>().ok()?;
+
+    let (quantity, unit, units): (&Quantity, &str, Vec<&str>) = match &property.datatype {
+        Datatype::Duration { .. } => (
+            &DURATION,
+            unit,
+            DURATION.units.iter().map(|(unit, _)| *unit).collect(),
+        ),
+        Datatype::MemorySize { .. } => (
+            &MEMORY_SIZE,
+            unit,
+            MEMORY_SIZE.units.iter().map(|(unit, _)| *unit).collect(),
+        ),
+        Datatype::Integer {
+            accepted_units: Some(accepted_units),
+            default_unit,
+            ..
+        }
+        | Datatype::UnsignedInteger {
+            accepted_units: Some(accepted_units),
+            default_unit,
+            ..
+        }
+        | Datatype::Float {
+            accepted_units: Some(accepted_units),
+            default_unit,
+            ..
+        } => {
+            let mut units: Vec<&str> = accepted_units.iter().map(String::as_str).collect();
+            units.extend(default_unit.as_deref());
+            let unit = match (unit, default_unit) {
+                ("", Some(default_unit)) => default_unit.as_str(),
+                ("", None) => units.first().copied()?,
+                (unit, _) => unit,
+            };
+            let mut all_units = units.clone();
+            all_units.push(unit);
+            (quantity_of(&all_units)?, unit, units)
+        }
+        _ => return None,
+    };
+
+    let base = number * quantity.factor(unit)?;
+    let mut units: Vec<(&str, f64)> = units
+        .into_iter()
+        .filter_map(|unit| Some((unit, quantity.factor(unit)?)))
+        .collect();
+    // the largest unit first; units with the same factor (e.g. `k` and `kb`) keep their order
+    units.sort_by(|(_, factor), (_, other)| other.total_cmp(factor));
+    let (unit, factor) = units
+        .iter()
+        .find(|(_, factor)| {
+            let number = base / factor;
+            number != 0.0 && format_number(number) == format_number(number.round())
+        })
+        .or_else(|| units.last())?;
+
+    Some(format!("{}{}", format_number(base / factor), unit))
+}
+
+/// Returns the number without floating point artifacts like 1536.0000000000002
+///
+/// # Arguments
+///
+/// * `number` - the (converted) number
+///
+pub(crate) fn format_number(number: f64) -> String {
+    ((number * 1e10).round() / 1e10).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::PropertySpecBuilder;
+    use crate::error::Error;
+    use crate::types::{Datatype, PropertyName, PropertyNameKind};
+    use crate::units::{canonical_unit, normalize, quantity_of, split_unit, DURATION, MEMORY_SIZE};
+    use rstest::*;
+
+    #[rstest]
+    #[case("512mb", ("512", "mb"))]
+    #[case(" 1.5 h ", ("1.5", "h"))]
+    #[case("42", ("42", ""))]
+    #[trace]
+    fn test_split_unit(#[case] value: &str, #[case] expected: (&str, &str)) {
+        assert_eq!(split_unit(value), expected);
+    }
+
+    #[rstest]
+    #[case(&["m", "g"], Some("memory size"))]
+    #[case(&["m", "h"], Some("duration"))]
+    #[case(&["m", "parsec"], None)]
+    #[trace]
+    fn test_quantity_of(#[case] units: &[&str], #[case] expected: Option<&str>) {
+        assert_eq!(quantity_of(units).map(|quantity| quantity.name), expected);
+    }
+
+    #[rstest]
+    #[case(Datatype::MemorySize { min: None, max: None }, "1024MB", Some("1g"))]
+    #[case(Datatype::MemorySize { min: None, max: None }, "1536m", Some("1536m"))]
+    #[case(Datatype::MemorySize { min: None, max: None }, "0gb", Some("0b"))]
+    #[case(Datatype::Duration { min: None, max: None }, "1.5m", Some("90s"))]
+    #[case(Datatype::Duration { min: None, max: None }, "120m", Some("2h"))]
+    #[case(
+        Datatype::Integer {
+            min: None,
+            max: None,
+            unit: None,
+            accepted_units: Some(vec!["mb".to_string(), "gb".to_string()]),
+            default_unit: Some("mb".to_string()),
+            multiple_of: None,
+        },
+        "2048",
+        Some("2gb")
+    )]
+    #[case(Datatype::Duration { min: None, max: None }, "abc", None)]
+    #[case(Datatype::Bool, "true", None)]
+    #[trace]
+    fn test_canonical_unit(
+        #[case] datatype: Datatype,
+        #[case] value: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let property = PropertySpecBuilder::new(datatype, "0.1.0").build();
+
+        assert_eq!(canonical_unit(&property, value).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(Datatype::MemorySize { min: None, max: None }, "2g", "m", Ok("2048m"))]
+    #[case(Datatype::MemorySize { min: None, max: None }, "1536MB", "gb", Ok("1.5gb"))]
+    #[case(Datatype::Duration { min: None, max: None }, "90s", "m", Ok("1.5m"))]
+    #[case(
+        Datatype::Integer {
+            min: None,
+            max: None,
+            unit: None,
+            accepted_units: Some(vec!["mb".to_string(), "gb".to_string()]),
+            default_unit: Some("mb".to_string()),
+            multiple_of: None,
+        },
+        "512",
+        "b",
+        Ok("536870912b")
+    )]
+    #[case(Datatype::Duration { min: None, max: None }, "90s", "mb", Err("s"))]
+    #[case(Datatype::Bool, "true", "s", Err(""))]
+    #[trace]
+    fn test_normalize(
+        #[case] datatype: Datatype,
+        #[case] value: &str,
+        #[case] target_unit: &str,
+        #[case] expected: Result<&str, &str>,
+    ) {
+        let property_name = PropertyName {
+            name: "heap".to_string(),
+            kind: PropertyNameKind::Env,
+        };
+        let property = PropertySpecBuilder::new(datatype, "0.1.0")
+            .property_name(property_name.clone())
+            .build();
+
+        let result = normalize(&property_name, &property, value, target_unit);
+
+        match expected {
+            Ok(expected) => assert_eq!(result, Ok(expected.to_string())),
+            Err("") => {
+                assert!(matches!(result, Err(Error::DatatypeNotMatching { .. })))
+            }
+            Err(unit) => assert_eq!(
+                result,
+                Err(Error::UnitConversionNotSupported {
+                    property_name,
+                    unit: unit.to_string(),
+                    target_unit: target_unit.to_string(),
+                })
+            ),
+        }
+    }
+
+    #[test]
+    fn test_convert() {
+        assert_eq!(DURATION.convert(2.0, "h", "m"), Some(120.0));
+        assert_eq!(MEMORY_SIZE.convert(1.0, "GB", "kb"), Some(1_048_576.0));
+        assert_eq!(DURATION.convert(1.0, "h", "mb"), None);
+    }
+}
@@ -1,8 +1,10 @@
 use crate::error::Error;
 use crate::types::{
-    PropertyDependency, PropertyName, PropertyNameKind, PropertySpec, PropertyValueSpec,
+    ChangePolicy, Platform, PropertyDependency, PropertyName, PropertyNameKind, PropertySpec,
+    PropertyValueSpec,
 };
 use crate::validation::ValidationResult;
+use crate::{ChangeImpact, DeprecatedProperty};
 use semver::Version;
 use std::collections::HashMap;
 
@@ -18,12 +20,16 @@ use std::collections::HashMap;
 /// * `kind` - property name kind provided by the user
 /// * `role` - the role required / used for the property
 /// * `product_version` - the provided product version
+/// * `user_config` - map with the user config names and according values
+/// * `platform` - the target platform
 ///
 pub(crate) fn get_matching_properties(
     property_spec: &HashMap<PropertyName, PropertySpec>,
     kind: &PropertyNameKind,
     role: Option<&str>,
     product_version: &Version,
+    user_config: &HashMap<String, String>,
+    platform: &Platform,
 ) -> ValidationResult<HashMap<String, String>> {
     let mut properties = HashMap::new();
 
@@ -40,6 +46,13 @@ pub(crate) fn get_matching_properties(
             continue;
         }
 
+        // Ignore this configuration property if it was removed in the version we're checking against.
+        if let Some(removed_in) = &spec.removed_in {
+            if Version::parse(removed_in)? <= *product_version {
+                continue;
+            }
+        }
+
         // ignore completely if role is None
         // ignore this property if role does not match or is not required
         if let Some(property_roles) = &spec.roles {
@@ -48,11 +61,18 @@ pub(crate) fn get_matching_properties(
                 if Some(property_role.name.as_str()) == role && property_role.required {
                     // check for recommended value and matching version
                     if let Some(recommended) = &spec.recommended_values {
-                        let property_value = get_property_value_for_version(
+                        // conditional values may not apply to the user config
+                        let property_value = match get_property_value_for_version(
                             property_name,
                             recommended,
                             product_version,
-                        )?;
+                            Some(user_config),
+                            Some(platform),
+                        ) {
+                            Ok(property_value) => property_value,
+                            Err(Error::PropertySpecValueMissingForVersion { .. }) => continue,
+                            Err(err) => return Err(err),
+                        };
 
                         properties.insert(property_name.name.clone(), property_value.value);
 
@@ -61,7 +81,9 @@ pub(crate) fn get_matching_properties(
                                 property_spec,
                                 product_version,
                                 property_name,
-                                &property_dependencies,
+                                property_dependencies,
+                                user_config,
+                                platform,
                             )?;
 
                             properties.extend(dependencies);
@@ -83,12 +105,14 @@ pub(crate) fn get_matching_properties(
 /// * `user_config` - map with the user config names and according values
 /// * `version` - the provided product version
 /// * `kind` - property name kind provided by the user
+/// * `platform` - the target platform
 ///
 pub(crate) fn get_matching_dependencies(
     property_spec: &HashMap<PropertyName, PropertySpec>,
     user_config: &HashMap<String, String>,
     version: &Version,
     kind: &PropertyNameKind,
+    platform: &Platform,
 ) -> ValidationResult<HashMap<String, String>> {
     let mut user_dependencies = HashMap::new();
     for name in user_config.keys() {
@@ -104,6 +128,8 @@ pub(crate) fn get_matching_dependencies(
                     version,
                     &property_name,
                     dependencies,
+                    user_config,
+                    platform,
                 )?);
             }
         }
@@ -121,12 +147,16 @@ pub(crate) fn get_matching_dependencies(
 /// * `product_version` - the provided product version
 /// * `property_name` - name of the property
 /// * `property_dependencies` - the dependencies of the property to check
+/// * `user_config` - map with the user config names and according values
+/// * `platform` - the target platform
 ///
 fn get_config_dependencies_and_values(
     property_spec: &HashMap<PropertyName, PropertySpec>,
     product_version: &Version,
     property_name: &PropertyName,
     property_dependencies: &[PropertyDependency],
+    user_config: &HashMap<String, String>,
+    platform: &Platform,
 ) -> ValidationResult<HashMap<String, String>> {
     let mut dependencies = HashMap::new();
     for property_dependency in property_dependencies {
@@ -143,15 +173,23 @@ fn get_config_dependencies_and_values(
                 // we check the dependency for a recommended value
                 if let Some(dependency_property) = property_spec.get(property_dependency_name) {
                     if let Some(recommended) = &dependency_property.recommended_values {
-                        let recommended_value = get_property_value_for_version(
+                        match get_property_value_for_version(
                             property_name,
                             recommended,
                             product_version,
-                        )?;
-                        dependencies.insert(
-                            property_dependency_name.name.clone(),
-                            recommended_value.value,
-                        );
+                            Some(user_config),
+                            Some(platform),
+                        ) {
+                            Ok(recommended_value) => {
+                                dependencies.insert(
+                                    property_dependency_name.name.clone(),
+                                    recommended_value.value,
+                                );
+                            }
+                            // conditional values may not apply to the user config
+                            Err(Error::PropertySpecValueMissingForVersion { .. }) => {}
+                            Err(err) => return Err(err),
+                        }
                     }
                 }
             }
@@ -165,18 +203,30 @@ fn get_config_dependencies_and_values(
 /// Check if there exists a recommended / default value that has a range (if provided) with
 /// from_version and to_version that includes the product version. E.g. if from_version is 1.0.0
 /// and to_version is 1.9.99, we have a value for product version 1.5.0 but not 2.0.0.
+/// If properties are provided, values with a matching `when` condition take precedence over
+/// values without condition; values with a condition that does not match are ignored. Otherwise
+/// conditions are ignored. The same applies to the platform of values if a target platform is
+/// provided (with lower precedence than conditions). Within the same precedence the first
+/// matching value is used.
 ///
 /// # Arguments
 ///
 /// * `property_name` - name of the property
 /// * `property_values` - list of property values and their respective versions
 /// * `product_version` - the product version
+/// * `properties` - map with property name and values to evaluate `when` conditions against
+/// * `platform` - the target platform to select platform specific values
 ///
 pub(crate) fn get_property_value_for_version(
     property_name: &PropertyName,
     property_values: &[PropertyValueSpec],
     product_version: &Version,
+    properties: Option<&HashMap<String, String>>,
+    platform: Option<&Platform>,
 ) -> ValidationResult<PropertyValueSpec> {
+    // the matching value with the highest precedence (conditional, platform specific)
+    let mut selected: Option<((bool, bool), &PropertyValueSpec)> = None;
+
     for value in property_values {
         if let Some(from) = &value.from_version {
             let from_version = Version::parse(from)?;
@@ -194,12 +244,285 @@ pub(crate) fn get_property_value_for_version(
             }
         }
 
-        return Ok(value.clone());
+        let conditional = match (&value.when, properties) {
+            (Some(condition), Some(properties)) => {
+                if !condition_matches(property_name, condition, properties) {
+                    continue;
+                }
+                true
+            }
+            _ => false,
+        };
+
+        let platform_specific = match (&value.platform, platform) {
+            (Some(selector), Some(platform)) => {
+                if !selector.matches(platform) {
+                    continue;
+                }
+                true
+            }
+            _ => false,
+        };
+
+        let precedence = (conditional, platform_specific);
+        if selected.is_none_or(|(selected, _)| precedence > selected) {
+            selected = Some((precedence, value));
+        }
+    }
+
+    selected.map(|(_, value)| value.clone()).ok_or_else(|| {
+        Error::PropertySpecValueMissingForVersion {
+            property_name: property_name.clone(),
+            property_values: Vec::from(property_values),
+            version: product_version.to_string(),
+        }
+    })
+}
+
+/// Returns the default value of the property for the product version that applies regardless of
+/// other properties and the platform (values with `when` condition or platform are ignored), or
+/// None if there is no such value, e.g. for generated docs, samples and schemas.
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property` - the property spec
+/// * `product_version` - the product version
+///
+pub(crate) fn get_unconditional_default(
+    property_name: &PropertyName,
+    property: &PropertySpec,
+    product_version: &Version,
+) -> ValidationResult<Option<PropertyValueSpec>> {
+    let values: Vec<PropertyValueSpec> = property
+        .default_values
+        .iter()
+        .flatten()
+        .filter(|value| value.when.is_none() && value.platform.is_none())
+        .cloned()
+        .collect();
+
+    match get_property_value_for_version(property_name, &values, product_version, None, None) {
+        Ok(value) => Ok(Some(value)),
+        Err(Error::PropertySpecValueMissingForVersion { .. }) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Check if the condition of a value is fulfilled: the property (of the same kind) is set and
+/// (if required) has the provided value.
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property the value belongs to
+/// * `condition` - the property and value the value depends on
+/// * `properties` - map with property name and values
+///
+fn condition_matches(
+    property_name: &PropertyName,
+    condition: &PropertyDependency,
+    properties: &HashMap<String, String>,
+) -> bool {
+    condition
+        .property_names
+        .iter()
+        .filter(|name| name.kind == property_name.kind)
+        .any(
+            |name| match (properties.get(&name.name), &condition.value) {
+                (Some(value), Some(required)) => value == required,
+                (Some(_), None) => true,
+                (None, _) => false,
+            },
+        )
+}
+
+/// Check if the property is available in the product version: introduced (`as_of_version`) in
+/// or before and not removed (`removed_in`) up to this version
+///
+/// # Arguments
+///
+/// * `spec` - the property spec
+/// * `product_version` - the provided product version
+///
+pub(crate) fn is_available_in_version(
+    spec: &PropertySpec,
+    product_version: &Version,
+) -> ValidationResult<bool> {
+    if Version::parse(&spec.as_of_version)? > *product_version {
+        return Ok(false);
+    }
+    match &spec.removed_in {
+        Some(removed_in) => Ok(Version::parse(removed_in)? > *product_version),
+        None => Ok(true),
+    }
+}
+
+/// Returns the lowest product version in which all properties of the user config are available
+/// (`as_of_version`) and not yet deprecated (`deprecated_since`) or removed (`removed_in`), or
+/// None if there is no such version. Properties unknown to the spec are ignored.
+///
+/// # Arguments
+///
+/// * `property_spec` - map with property name as key and the corresponding property spec as value
+/// * `kind` - kind of the user config (e.g. env, conf)
+/// * `prefix` - prefix of the property names of the kind (stripped from the user config names)
+/// * `user_config` - map with property name and values (the explicit user config properties)
+///
+pub(crate) fn get_minimum_version(
+    property_spec: &HashMap<PropertyName, PropertySpec>,
+    kind: &PropertyNameKind,
+    prefix: &str,
+    user_config: &HashMap<String, String>,
+) -> ValidationResult<Option<Version>> {
+    let mut minimum = Version::new(0, 0, 0);
+    // the first version in which any of the properties is deprecated or removed
+    let mut end: Option<Version> = None;
+
+    for name in user_config.keys() {
+        let property_name = PropertyName {
+            name: name.strip_prefix(prefix).unwrap_or(name).to_string(),
+            kind: kind.clone(),
+        };
+        let spec = match property_spec.get(&property_name) {
+            Some(spec) => spec,
+            None => continue,
+        };
+
+        minimum = minimum.max(Version::parse(&spec.as_of_version)?);
+        for version in [&spec.deprecated_since, &spec.removed_in]
+            .iter()
+            .copied()
+            .flatten()
+        {
+            let version = Version::parse(version)?;
+            end = Some(match end {
+                Some(end) => end.min(version),
+                None => version,
+            });
+        }
+    }
+
+    match end {
+        Some(end) if end <= minimum => Ok(None),
+        _ => Ok(Some(minimum)),
+    }
+}
+
+/// Collect all property names whose version (e.g. as_of_version or deprecated_since) is within
+/// the version range (from_version, to_version]: after from_version up to and including to_version.
+/// The result is sorted by property name.
+///
+/// # Arguments
+///
+/// * `property_spec` - map with property name as key and the corresponding property spec as value
+/// * `from_version` - lower version bound (exclusive)
+/// * `to_version` - upper version bound (inclusive)
+/// * `property_version` - extracts the version to be checked from the property spec
+///
+pub(crate) fn get_properties_in_version_range(
+    property_spec: &HashMap<PropertyName, PropertySpec>,
+    from_version: &Version,
+    to_version: &Version,
+    property_version: fn(&PropertySpec) -> Option<&String>,
+) -> ValidationResult<Vec<PropertyName>> {
+    let mut properties = vec![];
+
+    for (property_name, spec) in property_spec {
+        if let Some(version) = property_version(spec) {
+            let version = Version::parse(version)?;
+            if version > *from_version && version <= *to_version {
+                properties.push(property_name.clone());
+            }
+        }
+    }
+
+    properties.sort();
+    Ok(properties)
+}
+
+/// Collect all properties that are deprecated (`deprecated_since` <= product version) together
+/// with their replacements. The result is sorted by property name.
+///
+/// # Arguments
+///
+/// * `property_spec` - map with property name as key and the corresponding property spec as value
+/// * `product_version` - the provided product version
+///
+pub(crate) fn get_deprecated_properties(
+    property_spec: &HashMap<PropertyName, PropertySpec>,
+    product_version: &Version,
+) -> ValidationResult<Vec<DeprecatedProperty>> {
+    let mut deprecated = vec![];
+
+    for (property_name, spec) in property_spec {
+        if let Some(deprecated_since) = &spec.deprecated_since {
+            if Version::parse(deprecated_since)? <= *product_version {
+                deprecated.push(DeprecatedProperty {
+                    property_name: property_name.clone(),
+                    deprecated_since: deprecated_since.clone(),
+                    deprecated_for: spec.deprecated_for.clone().unwrap_or_default(),
+                });
+            }
+        }
+    }
+
+    deprecated.sort_by(|a, b| a.property_name.cmp(&b.property_name));
+    Ok(deprecated)
+}
+
+/// Returns the action required to apply a changed value of the property: properties without
+/// a change policy require a restart if `restart_required` is set
+///
+/// # Arguments
+///
+/// * `spec` - the property spec
+///
+pub(crate) fn change_policy(spec: &PropertySpec) -> ChangePolicy {
+    match (spec.change_policy, spec.restart_required) {
+        (Some(change_policy), _) => change_policy,
+        (None, Some(true)) => ChangePolicy::Restart,
+        (None, _) => ChangePolicy::None,
+    }
+}
+
+/// Determine the action required to apply changes of the provided properties. Properties
+/// without a change policy require a restart if `restart_required` is set.
+///
+/// # Arguments
+///
+/// * `property_spec` - map with property name as key and the corresponding property spec as value
+/// * `kind` - property name kind of the changed properties
+/// * `changed_properties` - names of the changed properties
+///
+pub(crate) fn get_change_impact(
+    property_spec: &HashMap<PropertyName, PropertySpec>,
+    kind: &PropertyNameKind,
+    changed_properties: &[&str],
+) -> ValidationResult<ChangeImpact> {
+    let mut properties = vec![];
+
+    for name in changed_properties {
+        let property_name = PropertyName {
+            name: name.to_string(),
+            kind: kind.clone(),
+        };
+
+        let spec = match property_spec.get(&property_name) {
+            Some(spec) => spec,
+            None => return Err(Error::PropertyNotFound { property_name }),
+        };
+
+        properties.push((property_name, change_policy(spec)));
     }
 
-    Err(Error::PropertySpecValueMissingForVersion {
-        property_name: property_name.clone(),
-        property_values: Vec::from(property_values),
-        version: product_version.to_string(),
+    properties.sort();
+
+    Ok(ChangeImpact {
+        required_action: properties
+            .iter()
+            .map(|(_, change_policy)| *change_policy)
+            .max()
+            .unwrap_or(ChangePolicy::None),
+        properties,
     })
 }
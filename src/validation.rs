@@ -1,13 +1,21 @@
 use crate::error::Error;
+use crate::json_schema;
 use crate::types::{
-    Datatype, ProductConfigSpecProperties, PropertyName, PropertySpec, PropertyValueSpec, Role,
+    CharacterClass, Datatype, DocLink, LengthUnit, PathKind, Platform, ProductConfigSpecProperties,
+    PropertyName, PropertySpec, PropertyValueSpec, Role, Stability, UnitCaptures, ValidationPolicy,
 };
+use crate::units::{self, quantity_of, split_unit, Quantity, DURATION, MEMORY_SIZE};
 use crate::util;
-use crate::PropertyValidationResult;
+use crate::validator::CustomValidators;
+use crate::{ProductConfigSpec, PropertyValidationResult};
+use product_config_core::{max_bound, min_bound, CheckError, RegexBackend};
 use regex::Regex;
 use semver::Version;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::convert::TryFrom;
 use std::fmt::Display;
+use std::net::IpAddr;
+use std::path::Path;
 use std::str::FromStr;
 
 pub type ValidationResult<T> = Result<T, Error>;
@@ -15,8 +23,7 @@ pub type ValidationResult<T> = Result<T, Error>;
 /// Returns the provided property_value if no validation errors appear
 ///
 /// # Arguments
-/// * `property_spec` - map with PropertyName as key and the corresponding PropertySpec as value
-/// * `config_spec` - config spec that contains customizable data like e.g. unit and regex
+/// * `product_config` - the product config spec (property specs, units and validation policy)
 /// * `merged_properties` - merged user and property spec (matching role, kind etc.)
 /// * `product_version` - version of the currently active product version
 /// * `role` - the user role to validate against
@@ -24,8 +31,7 @@ pub type ValidationResult<T> = Result<T, Error>;
 /// * `property_value` - property value to be validated
 ///
 pub(crate) fn validate(
-    property_spec: &HashMap<PropertyName, PropertySpec>,
-    config_spec: &ProductConfigSpecProperties,
+    product_config: &ProductConfigSpec,
     merged_properties: &HashMap<String, String>,
     product_version: &Version,
     role: Option<&str>,
@@ -33,7 +39,7 @@ pub(crate) fn validate(
     property_value: &str,
 ) -> PropertyValidationResult {
     // a missing / wrong property stops us from doing any other validation
-    let property = match property_spec.get(&property_name) {
+    let property = match product_config.property_specs.get(property_name) {
         None => {
             return PropertyValidationResult::Error(Error::PropertyNotFound {
                 property_name: property_name.clone(),
@@ -42,37 +48,101 @@ pub(crate) fn validate(
         Some(opt) => opt,
     };
 
+    let check_removed = check_version_removed(
+        property_name,
+        product_version,
+        &property.removed_in,
+        &property.deprecated_for,
+    );
+
+    if check_removed.is_err() {
+        return PropertyValidationResult::Error(check_removed.err().unwrap());
+    }
+
     let check_version = check_version_supported_or_deprecated(
-        &property_name,
+        property_name,
         product_version,
         &property.as_of_version[..],
         &property.deprecated_since,
     );
 
-    if check_version.is_err() {
-        return PropertyValidationResult::Error(check_version.err().unwrap());
+    let check_policy =
+        check_validation_policy(property_name, property, &product_config.validation_policy);
+    if check_policy.is_err() {
+        return PropertyValidationResult::Error(check_policy.err().unwrap());
+    }
+
+    // properties for other platforms are only accepted (with a warning) if allowed by the policy
+    let other_platform = match check_platform(property_name, property, &product_config.platform) {
+        Err(err) if !product_config.validation_policy.allow_other_platforms => {
+            return PropertyValidationResult::Error(err)
+        }
+        result => result.err(),
+    };
+
+    // deprecated properties may still be used (with caution) until they are removed
+    let deprecated = match check_version {
+        Err(Error::VersionDeprecated { .. }) => check_version.err(),
+        Err(err) => return PropertyValidationResult::Error(err),
+        Ok(()) => None,
+    };
+
+    // values are pre-processed (e.g. trimmed) before validation if configured
+    let normalized_value = normalized_value(&product_config.config_spec, property, property_value);
+    let property_value = normalized_value.as_deref().unwrap_or(property_value);
+
+    // some values are validated and reported in their canonical form (e.g. enum variants)
+    let canonical_value = canonical_value(property, property_value);
+    let property_value = canonical_value.as_deref().unwrap_or(property_value);
+
+    // an empty value ("") is accepted without any further checks for properties that allow it
+    if property_value.is_empty() && property.allow_empty == Some(true) {
+        return match deprecated {
+            Some(deprecated) => PropertyValidationResult::Warn(String::new(), deprecated),
+            None => PropertyValidationResult::Valid(String::new()),
+        };
     }
 
-    // for an empty value (""), ignore checks for the value (check_datatype, check_allowed_values..)
+    // for an empty value (""), ignore checks for the value (check_list, check_value..)
     if !property_value.is_empty() {
-        let check_datatype = check_datatype(
-            &config_spec.units,
-            &property_name,
-            property_value,
-            &property.datatype,
-        );
-        if check_datatype.is_err() {
-            return PropertyValidationResult::Error(check_datatype.err().unwrap());
+        let check_list = check_list(property_name, property, property_value);
+        if check_list.is_err() {
+            return PropertyValidationResult::Error(check_list.err().unwrap());
         }
 
-        let check_allowed_values =
-            check_allowed_values(&property_name, property_value, &property.allowed_values);
-        if check_allowed_values.is_err() {
-            return PropertyValidationResult::Error(check_allowed_values.err().unwrap());
+        // list properties are validated item by item
+        for item in list_items(property, property_value) {
+            let check_value = check_value(
+                product_config,
+                merged_properties,
+                property_name,
+                property,
+                item,
+            );
+            if check_value.is_err() {
+                return PropertyValidationResult::Error(check_value.err().unwrap());
+            }
         }
     }
 
-    let check_dependencies = check_dependencies(property_name, property, &merged_properties);
+    // legal but discouraged values are reported after all other checks passed
+    let not_recommended = if property_value.is_empty() {
+        None
+    } else {
+        list_items(property, property_value)
+            .into_iter()
+            .find_map(|item| {
+                check_recommended_range(
+                    &product_config.config_spec.units,
+                    property_name,
+                    property,
+                    item,
+                )
+                .err()
+            })
+    };
+
+    let check_dependencies = check_dependencies(property_name, property, merged_properties);
     if check_dependencies.is_err() {
         match check_dependencies.err() {
             None => {}
@@ -87,6 +157,11 @@ pub(crate) fn validate(
         }
     }
 
+    let check_conflicts = check_conflicts(property_name, property, merged_properties);
+    if check_conflicts.is_err() {
+        return PropertyValidationResult::Error(check_conflicts.err().unwrap());
+    }
+
     let check_role = check_role(property_name, &property.roles, role);
     if check_role.is_err() {
         return PropertyValidationResult::Warn(
@@ -95,13 +170,27 @@ pub(crate) fn validate(
         );
     }
 
+    if let Some(deprecated) = deprecated {
+        return PropertyValidationResult::Warn(property_value.to_string(), deprecated);
+    }
+
+    if let Some(other_platform) = other_platform {
+        return PropertyValidationResult::Warn(property_value.to_string(), other_platform);
+    }
+
+    if let Some(not_recommended) = not_recommended {
+        return PropertyValidationResult::Warn(property_value.to_string(), not_recommended);
+    }
+
     // was provided by recommended value?
     if Ok(true)
         == check_property_value_used(
             property_name,
             property_value,
             &property.recommended_values,
-            &product_version,
+            product_version,
+            merged_properties,
+            &product_config.platform,
         )
     {
         return PropertyValidationResult::RecommendedDefault(property_value.to_string());
@@ -113,7 +202,9 @@ pub(crate) fn validate(
             property_name,
             property_value,
             &property.default_values,
-            &product_version,
+            product_version,
+            merged_properties,
+            &product_config.platform,
         )
     {
         return PropertyValidationResult::Default(property_value.to_string());
@@ -122,11 +213,342 @@ pub(crate) fn validate(
     PropertyValidationResult::Valid(property_value.to_string())
 }
 
+/// Check if the value of a property may be changed from the old to the new value: the new value
+/// has to be valid and immutable properties may only be set once.
+///
+/// # Arguments
+/// * `product_config` - the product config spec (property specs, units and validation policy)
+/// * `product_version` - version of the currently active product version
+/// * `property_name` - name of the property
+/// * `old_value` - the current value of the property (None if not set yet)
+/// * `new_value` - the value the property should be changed to
+///
+pub(crate) fn validate_change(
+    product_config: &ProductConfigSpec,
+    product_version: &Version,
+    property_name: &PropertyName,
+    old_value: Option<&str>,
+    new_value: &str,
+) -> ValidationResult<()> {
+    let property = match product_config.property_specs.get(property_name) {
+        None => {
+            return Err(Error::PropertyNotFound {
+                property_name: property_name.clone(),
+            })
+        }
+        Some(property) => property,
+    };
+
+    check_version_removed(
+        property_name,
+        product_version,
+        &property.removed_in,
+        &property.deprecated_for,
+    )?;
+
+    match check_version_supported_or_deprecated(
+        property_name,
+        product_version,
+        &property.as_of_version,
+        &property.deprecated_since,
+    ) {
+        Err(Error::VersionDeprecated { .. }) | Ok(()) => {}
+        Err(err) => return Err(err),
+    }
+
+    check_validation_policy(property_name, property, &product_config.validation_policy)?;
+    if !product_config.validation_policy.allow_other_platforms {
+        check_platform(property_name, property, &product_config.platform)?;
+    }
+    check_immutable(property_name, property, old_value, new_value)?;
+
+    let normalized_value = normalized_value(&product_config.config_spec, property, new_value);
+    let new_value = normalized_value.as_deref().unwrap_or(new_value);
+    let canonical_value = canonical_value(property, new_value);
+    let new_value = canonical_value.as_deref().unwrap_or(new_value);
+
+    // an empty value ("") is not validated any further
+    if new_value.is_empty() {
+        return Ok(());
+    }
+
+    check_list(property_name, property, new_value)?;
+    for item in list_items(property, new_value) {
+        check_value(
+            product_config,
+            &HashMap::new(),
+            property_name,
+            property,
+            item,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Check the property value with the custom validator referenced by the property spec (if any).
+///
+/// # Arguments
+/// * `validators` - the registered custom validators
+/// * `property_name` - name of the property
+/// * `property` - the property spec referencing the validator
+/// * `property_value` - property value to be validated
+///
+fn check_custom_validator(
+    validators: &CustomValidators,
+    property_name: &PropertyName,
+    property: &PropertySpec,
+    property_value: &str,
+) -> ValidationResult<()> {
+    let name = match &property.validator {
+        Some(name) => name,
+        None => return Ok(()),
+    };
+
+    let validator = match validators.get(name) {
+        Some(validator) => validator,
+        None => {
+            return Err(Error::CustomValidatorNotFound {
+                property_name: property_name.clone(),
+                validator: name.clone(),
+            })
+        }
+    };
+
+    validator
+        .validate(property_name, property_value)
+        .map_err(|reason| Error::CustomValidationFailed {
+            property_name: property_name.clone(),
+            value: property_value.to_string(),
+            validator: name.clone(),
+            reason,
+        })
+}
+
+/// Returns the value pre-processed according to the normalization of the property spec (taking
+/// precedence) and the config spec or None if the value is not changed.
+///
+/// # Arguments
+/// * `config_spec` - the config spec providing the global normalization
+/// * `property` - the property spec
+/// * `property_value` - the value provided by the user
+///
+pub(crate) fn normalized_value(
+    config_spec: &ProductConfigSpecProperties,
+    property: &PropertySpec,
+    property_value: &str,
+) -> Option<String> {
+    let normalization = match (property.normalization, config_spec.normalization) {
+        (Some(normalization), Some(global)) => normalization.or(global),
+        (normalization, global) => normalization.or(global)?,
+    };
+    normalization.apply(property_value)
+}
+
+/// Returns the canonical form of a valid value if the datatype defines one: enum variants are
+/// matched (e.g. case insensitive), percentages are normalized to the configured notation and
+/// integer literals with radix prefix (if enabled) are converted to decimal. The items of list
+/// properties are converted one by one.
+///
+/// # Arguments
+/// * `property` - the property spec
+/// * `property_value` - the value provided by the user
+///
+pub(crate) fn canonical_value(property: &PropertySpec, property_value: &str) -> Option<String> {
+    if let Some(list) = &property.list {
+        let items: Vec<String> = list_items(property, property_value)
+            .into_iter()
+            .map(|item| canonical_item(property, item).unwrap_or_else(|| item.to_string()))
+            .collect();
+        let canonical = items.join(&list.delimiter);
+        return if canonical == property_value {
+            None
+        } else {
+            Some(canonical)
+        };
+    }
+    canonical_item(property, property_value)
+}
+
+/// Returns the canonical form of a single value (or list item), see `canonical_value`
+///
+/// # Arguments
+/// * `property` - the property spec
+/// * `property_value` - the value provided by the user
+///
+fn canonical_item(property: &PropertySpec, property_value: &str) -> Option<String> {
+    let datatype = &property.datatype;
+    match datatype {
+        Datatype::Integer { .. } | Datatype::UnsignedInteger { .. }
+            if property.radix_prefixes == Some(true) =>
+        {
+            radix_literal(property_value)
+        }
+        Datatype::Enum { .. } => datatype.enum_variant(property_value).map(str::to_string),
+        Datatype::Percent { as_fraction } => {
+            let fraction = parse_percent(property_value, *as_fraction)?;
+            if !(0.0..=1.0).contains(&fraction) {
+                return None;
+            }
+            let value = if *as_fraction {
+                fraction
+            } else {
+                fraction * 100.0
+            };
+            // avoid floating point artifacts like 7.000000000000001
+            Some(((value * 1e10).round() / 1e10).to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Returns the decimal representation of an integer literal with radix prefix (0x, 0o, 0b) or
+/// None if the value has no prefix or is no valid literal
+///
+/// # Arguments
+/// * `property_value` - the value to be converted, e.g. `0x1F` or `-0b1010`
+///
+fn radix_literal(property_value: &str) -> Option<String> {
+    let value = property_value.trim();
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value),
+    };
+
+    let (radix, digits) = match value.get(..2)?.to_ascii_lowercase().as_str() {
+        "0x" => (16, &value[2..]),
+        "0o" => (8, &value[2..]),
+        "0b" => (2, &value[2..]),
+        _ => return None,
+    };
+
+    let number = i128::from_str_radix(digits, radix).ok()?;
+    Some(if negative { -number } else { number }.to_string())
+}
+
+/// Parse a percentage as fraction: values with `%` suffix are percentages, other values are
+/// fractions (0.0 - 1.0) if `as_fraction` is set or percentages (0 - 100) otherwise
+///
+/// # Arguments
+/// * `property_value` - the value to be parsed
+/// * `as_fraction` - if values without `%` suffix are fractions
+///
+fn parse_percent(property_value: &str, as_fraction: bool) -> Option<f64> {
+    let value = property_value.trim();
+    let (number, percent) = match value.strip_suffix('%') {
+        Some(number) => (number.trim(), true),
+        None => (value, !as_fraction),
+    };
+
+    let number = number
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())?;
+    Some(if percent { number / 100.0 } else { number })
+}
+
+/// Replace min / max bounds that reference another property (e.g. `${heap.max}`) with the value
+/// of that property. References to properties that are not set are ignored.
+///
+/// # Arguments
+///
+/// * `datatype` - the datatype of the property
+/// * `properties` - map with property name and values (of the kind of the property)
+///
+pub(crate) fn resolve_bounds(
+    datatype: &Datatype,
+    properties: &HashMap<String, String>,
+) -> Datatype {
+    let mut datatype = datatype.clone();
+
+    if let Datatype::Integer { min, max, .. }
+    | Datatype::UnsignedInteger { min, max, .. }
+    | Datatype::Float { min, max, .. }
+    | Datatype::String { min, max, .. }
+    | Datatype::Duration { min, max }
+    | Datatype::MemorySize { min, max }
+    | Datatype::DateTime { min, max, .. }
+    | Datatype::Decimal { min, max, .. } = &mut datatype
+    {
+        for bound in [min, max] {
+            if let Some(reference) = bound.as_deref().and_then(bound_reference) {
+                *bound = properties.get(reference).cloned();
+            }
+        }
+    }
+
+    datatype
+}
+
+/// Returns the referenced property name if the bound has the form `${property}`
+fn bound_reference(bound: &str) -> Option<&str> {
+    bound
+        .trim()
+        .strip_prefix("${")?
+        .strip_suffix('}')
+        .map(str::trim)
+}
+
+/// Returns warnings about the config spec that do not prevent using it:
+/// - unit regexes that are neither anchored (`^...$`) nor use full match semantics
+///
+/// # Arguments
+/// * `config_spec` - the units and further settings of the config spec
+///
+pub(crate) fn lint_config_spec(config_spec: &ProductConfigSpecProperties) -> Vec<Error> {
+    let full_match = config_spec.full_match.unwrap_or(false);
+    config_spec
+        .unit_specs
+        .iter()
+        .filter(|unit| !unit.full_match.unwrap_or(full_match))
+        .filter_map(|unit| {
+            let regex = unit.regex.as_ref()?;
+            if is_anchored(regex) {
+                None
+            } else {
+                Some(Error::UnitRegexNotAnchored {
+                    unit: unit.name.clone(),
+                    regex: regex.clone(),
+                })
+            }
+        })
+        .collect()
+}
+
+/// Returns true if the regex pattern starts with `^` (or `\A`) and ends with `$` (or `\z`).
+/// Leading inline flags like `(?i)` are skipped.
+///
+/// # Arguments
+/// * `pattern` - the regex pattern
+///
+fn is_anchored(mut pattern: &str) -> bool {
+    while let Some(rest) = pattern.strip_prefix("(?") {
+        match rest.find(')') {
+            Some(end)
+                if rest[..end]
+                    .chars()
+                    .all(|c| c.is_ascii_alphabetic() || c == '-') =>
+            {
+                pattern = &rest[end + 1..]
+            }
+            _ => break,
+        }
+    }
+
+    let starts_anchored = pattern.starts_with('^') || pattern.starts_with("\\A");
+    let ends_anchored =
+        (pattern.ends_with('$') && !pattern.ends_with("\\$")) || pattern.ends_with("\\z");
+    starts_anchored && ends_anchored
+}
+
 /// Check if the provided property spec is correct. Checks include:
 /// - if default / recommended values match version, min / max, datatype, unit and regex
-/// - if default / recommended values match allowed values if available
+/// - if allowed patterns are valid regular expressions
+/// - if the recommended range bounds match the datatype
+/// - if default / recommended values match allowed values if available and are not forbidden
 /// - if dependencies and required values match recommended values of that dependency
 /// - if roles are available
+/// - if additional doc links are absolute http(s) urls
 ///
 /// # Arguments
 /// * `config_spec` - map with unit name and respective regular expression to evaluate the datatype
@@ -139,29 +561,71 @@ pub(crate) fn validate_property_spec(
     for (name, spec) in property_spec {
         let as_of_version = Version::parse(&spec.as_of_version)?;
 
+        if let Some(removed_in) = &spec.removed_in {
+            Version::parse(removed_in)?;
+        }
+
+        // bounds may reference other properties of the same kind, these are checked when
+        // validating a user config
+        let (min, max) = spec.datatype.bounds();
+        for reference in min
+            .into_iter()
+            .chain(max)
+            .filter_map(|b| bound_reference(b))
+        {
+            let reference_name = PropertyName {
+                name: reference.to_string(),
+                kind: name.kind.clone(),
+            };
+            if !property_spec.contains_key(&reference_name) {
+                return Err(Error::PropertyNotFound {
+                    property_name: reference_name,
+                });
+            }
+        }
+        let datatype = resolve_bounds(&spec.datatype, &HashMap::new());
+
+        for pattern in spec.allowed_pattern.iter().flatten() {
+            compile_allowed_pattern(name, pattern)?;
+        }
+
+        if let Some(range) = &spec.recommended_range {
+            for bound in range.min.iter().chain(&range.max) {
+                check_datatype(&config_spec.units, name, bound, &datatype)?;
+            }
+        }
+
         // 1) check for default values
         if let Some(values) = &spec.default_values {
             // 1.1) check if a provided default version matches as_of_version
-            util::get_property_value_for_version(name, values, &as_of_version)?;
+            util::get_property_value_for_version(name, values, &as_of_version, None, None)?;
 
             for val in values {
-                // 1.2) check if default matches the allowed values
-                check_allowed_values(name, &val.value, &spec.allowed_values)?;
-                // 1.3) check if default values match datatype (min, max, unit...)
-                check_datatype(&config_spec.units, name, &val.value, &spec.datatype)?
+                check_list(name, spec, &val.value)?;
+                for item in list_items(spec, &val.value) {
+                    // 1.2) check if default matches the allowed values
+                    check_allowed_values(name, item, &spec.allowed_values, &spec.allowed_pattern)?;
+                    check_forbidden_values(name, item, &spec.forbidden_values)?;
+                    // 1.3) check if default values match datatype (min, max, unit...)
+                    check_datatype(&config_spec.units, name, item, &datatype)?
+                }
             }
         }
 
         // 2) check for recommended values
         if let Some(values) = &spec.recommended_values {
             // 2.1) check if a provided recommended version matches as_of_version
-            util::get_property_value_for_version(name, values, &as_of_version)?;
+            util::get_property_value_for_version(name, values, &as_of_version, None, None)?;
 
             for val in values {
-                // 2.2) check if recommended matches the allowed values
-                check_allowed_values(name, &val.value, &spec.allowed_values)?;
-                // 2.3) check if recommended values match datatype (min, max, unit...)
-                check_datatype(&config_spec.units, name, &val.value, &spec.datatype)?
+                check_list(name, spec, &val.value)?;
+                for item in list_items(spec, &val.value) {
+                    // 2.2) check if recommended matches the allowed values
+                    check_allowed_values(name, item, &spec.allowed_values, &spec.allowed_pattern)?;
+                    check_forbidden_values(name, item, &spec.forbidden_values)?;
+                    // 2.3) check if recommended values match datatype (min, max, unit...)
+                    check_datatype(&config_spec.units, name, item, &datatype)?
+                }
             }
         }
 
@@ -175,9 +639,11 @@ pub(crate) fn validate_property_spec(
                             &dependency_property.recommended_values
                         {
                             let filtered_value = util::get_property_value_for_version(
-                                &dep_name,
+                                dep_name,
                                 dependency_property_recommended,
                                 &as_of_version,
+                                None,
+                                None,
                             )?;
 
                             user_data.insert(dep_name.name.clone(), filtered_value.value.clone());
@@ -198,6 +664,47 @@ pub(crate) fn validate_property_spec(
         if spec.roles.is_none() {
             return Err(Error::PropertySpecRoleNotProvided { name: name.clone() });
         }
+
+        // 5) check if doc links are usable by doc generators
+        for link in spec.additional_doc.iter().flatten() {
+            check_doc_link(name, link)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Check if the url of the doc link is an absolute `http` or `https` url with host (independent of
+/// the `url` feature, which is only required for url datatypes) and the title is not empty
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `link` - the additional doc link of the property
+///
+fn check_doc_link(property_name: &PropertyName, link: &DocLink) -> ValidationResult<()> {
+    let invalid_link = |reason: &str| Error::InvalidDocLink {
+        property_name: property_name.clone(),
+        url: link.url.clone(),
+        reason: reason.to_string(),
+    };
+
+    let (scheme, rest) = link
+        .url
+        .split_once("://")
+        .ok_or_else(|| invalid_link("url is not absolute"))?;
+    if !scheme.eq_ignore_ascii_case("http") && !scheme.eq_ignore_ascii_case("https") {
+        return Err(invalid_link("scheme is not http or https"));
+    }
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if host.is_empty() {
+        return Err(invalid_link("host is missing"));
+    }
+    if link.url.contains(char::is_whitespace) {
+        return Err(invalid_link("url contains whitespace"));
+    }
+    if link.title.trim().is_empty() {
+        return Err(invalid_link("title is empty"));
     }
 
     Ok(())
@@ -211,15 +718,25 @@ pub(crate) fn validate_property_spec(
 /// * `property_value` - the final value used
 /// * `property_values` - possible property names e.g. default or recommended values
 /// * `product_version` - the provided product version
+/// * `properties` - map with property name and values to evaluate conditional values against
+/// * `platform` - the target platform to select platform specific values
 ///
 fn check_property_value_used(
     property_name: &PropertyName,
     property_value: &str,
     property_values: &Option<Vec<PropertyValueSpec>>,
     product_version: &Version,
+    properties: &HashMap<String, String>,
+    platform: &Platform,
 ) -> ValidationResult<bool> {
     if let Some(values) = property_values {
-        let val = util::get_property_value_for_version(property_name, values, product_version)?;
+        let val = util::get_property_value_for_version(
+            property_name,
+            values,
+            product_version,
+            Some(properties),
+            Some(platform),
+        )?;
         if val.value == property_value {
             return Ok(true);
         }
@@ -309,79 +826,43 @@ fn check_version_supported_or_deprecated(
     Ok(())
 }
 
-/// Check whether properties have provided dependencies and if they are contained the user properties
-/// TODO: add dependency automatically if missing?
+/// Check if property was removed regarding the product version
 ///
 /// # Arguments
 ///
 /// * `property_name` - name of the property
-/// * `property` - the respective property spec
-/// * `user_properties` - map with property name and potential value provided by user
+/// * `version` - the current product version
+/// * `removed_in` - version from which point onwards the property is not available anymore
+/// * `deprecated_for` - the replacements of the property
 ///
-fn check_dependencies(
+fn check_version_removed(
     property_name: &PropertyName,
-    property: &PropertySpec,
-    user_properties: &HashMap<String, String>,
+    version: &Version,
+    removed_in: &Option<String>,
+    deprecated_for: &Option<Vec<Vec<PropertyName>>>,
 ) -> ValidationResult<()> {
-    // check if property has dependencies
-    let property_dependencies = match &property.depends_on {
-        None => return Ok(()),
-        Some(dependencies) => dependencies,
-    };
-
-    // for each dependency, check if user_properties contain the property and the correct value
-    for property_dependency in property_dependencies {
-        // check if we find any matches, otherwise return error after the loop
-        let mut found_match = false;
-        // for each property name provided within the dependency
-        for dependency_property_name in &property_dependency.property_names {
-            if !user_properties.contains_key(&dependency_property_name.name) {
-                continue;
-            }
-
-            found_match = true;
-
-            match (
-                user_properties.get(&dependency_property_name.name),
-                &property_dependency.value,
-            ) {
-                // no user value, no property value -> ok
-                (None, None) => continue,
-                // no user value but property value required -> error
-                (None, Some(config_value)) => {
-                    return Err(Error::PropertyDependencyUserValueMissing {
-                        property_name: property_name.clone(),
-                        dependency: dependency_property_name.name.clone(),
-                        required_value: config_value.clone(),
-                    })
-                }
-                // user value but no property value required -> error
-                (Some(user_value), None) => {
-                    return Err(Error::PropertyDependencyUserValueNotRequired {
-                        property_name: property_name.clone(),
-                        dependency: dependency_property_name.name.clone(),
-                        user_value: user_value.clone(),
-                    })
-                }
-                // both values available -> check if match
-                (Some(user_value), Some(config_value)) => {
-                    if user_value != config_value {
-                        return Err(Error::PropertyDependencyValueInvalid {
-                            property_name: property_name.clone(),
-                            dependency: dependency_property_name.name.clone(),
-                            user_value: user_value.clone(),
-                            required_value: config_value.clone(),
-                        });
-                    }
-                }
-            }
-        }
+    if let Some(removed) = removed_in {
+        let removed_version = Version::parse(removed)?;
+
+        if removed_version <= *version {
+            // name the replacement with the same kind (if available)
+            let replaced_by = deprecated_for
+                .iter()
+                .flatten()
+                .filter_map(|replacement| {
+                    replacement
+                        .iter()
+                        .find(|name| name.kind == property_name.kind)
+                        .or_else(|| replacement.first())
+                        .map(|name| name.name.clone())
+                })
+                .collect();
 
-        if !found_match {
-            // TODO: Error or just add the correct dependency?
-            return Err(Error::PropertyDependencyMissing {
+            return Err(Error::VersionRemoved {
                 property_name: property_name.clone(),
-                dependency: property_dependency.property_names.clone(),
+                product_version: version.to_string(),
+                removed_version: removed_version.to_string(),
+                replaced_by,
             });
         }
     }
@@ -389,301 +870,1836 @@ fn check_dependencies(
     Ok(())
 }
 
-/// Check if property value fits the provided datatype
+/// Check if the property may be set regarding the validation policy (stability and experimental)
+///
 /// # Arguments
 ///
-/// * `config_spec_units` - map with unit name and respective regular expression to evaluate the datatype
 /// * `property_name` - name of the property
-/// * `property_value` - property value to be validated
-/// * `datatype` - property datatype containing min/max bounds, units etc.
+/// * `property` - the property spec
+/// * `validation_policy` - the policy containing the allowed stability and experimental opt-in
 ///
-fn check_datatype(
-    config_spec_units: &HashMap<String, Regex>,
+pub(crate) fn check_validation_policy(
     property_name: &PropertyName,
-    property_value: &str,
-    datatype: &Datatype,
+    property: &PropertySpec,
+    validation_policy: &ValidationPolicy,
 ) -> ValidationResult<()> {
-    match datatype {
-        Datatype::Bool => {
-            check_datatype_scalar::<bool>(property_name, property_value, &None, &None)?;
-        }
-        Datatype::Integer { min, max, .. } => {
-            check_datatype_scalar::<i64>(property_name, property_value, min, max)?;
-        }
-        Datatype::Float { min, max, .. } => {
-            check_datatype_scalar::<f64>(property_name, property_value, min, max)?;
-        }
-        Datatype::String { min, max, unit, .. } => {
-            check_datatype_string(
-                config_spec_units,
-                property_name,
-                property_value,
-                min,
-                max,
-                unit,
-            )?;
-        }
-        Datatype::Array { .. } => {
-            // TODO: implement logic for array type
-        }
+    let stability = property.stability.unwrap_or(Stability::Stable);
+
+    if !validation_policy.allowed_stability.contains(&stability) {
+        return Err(Error::StabilityNotAllowed {
+            property_name: property_name.clone(),
+            stability,
+        });
+    }
+
+    if property.experimental == Some(true) && !validation_policy.allow_experimental {
+        return Err(Error::ExperimentalPropertyNotAllowed {
+            property_name: property_name.clone(),
+        });
     }
+
     Ok(())
 }
 
-/// Check if property value is in allowed values
+/// Check if the property is supported on the target platform
+///
 /// # Arguments
 ///
 /// * `property_name` - name of the property
-/// * `property_value` - property value to be validated
-/// * `allowed_values` - vector of allowed values
+/// * `property` - the property spec
+/// * `platform` - the target platform
 ///
-fn check_allowed_values(
+pub(crate) fn check_platform(
     property_name: &PropertyName,
-    property_value: &str,
-    allowed_values: &Option<Vec<String>>,
+    property: &PropertySpec,
+    platform: &Platform,
 ) -> ValidationResult<()> {
-    if allowed_values.is_some() {
-        let allowed_values = allowed_values.clone().unwrap();
-        if !allowed_values.is_empty() && !allowed_values.contains(&property_value.to_string()) {
-            return Err(Error::PropertyValueNotInAllowedValues {
+    if let Some(platforms) = &property.platforms {
+        if !platforms
+            .iter()
+            .any(|supported| Platform::from(supported.as_str()).matches(platform))
+        {
+            return Err(Error::PropertyNotSupportedOnPlatform {
                 property_name: property_name.clone(),
-                value: property_value.to_string(),
-                allowed_values,
+                platform: platform.to_string(),
+                platforms: platforms.clone(),
             });
         }
     }
+
     Ok(())
 }
 
-/// Returns the provided scalar parameter value of type T (i16, i32, i64, f32, f62-..) if no parsing errors appear
+/// Check if a float value is NaN or infinite although not allowed by the property spec or (if
+/// not specified there) the validation policy. Non finite values would otherwise pass min / max
+/// checks since comparisons with NaN are always false.
 ///
 /// # Arguments
 ///
 /// * `property_name` - name of the property
-/// * `property_value` - the value belonging to the property to be validated
-/// * `min` - minimum value specified
-/// * `max` - maximum value specified
+/// * `property` - the property spec
+/// * `validation_policy` - the policy applied if the property spec does not decide
+/// * `property_value` - the value to be validated
 ///
-fn check_datatype_scalar<T>(
+fn check_non_finite(
     property_name: &PropertyName,
+    property: &PropertySpec,
+    validation_policy: &ValidationPolicy,
     property_value: &str,
-    min: &Option<String>,
-    max: &Option<String>,
-) -> ValidationResult<T>
-where
-    T: FromStr + std::cmp::PartialOrd + Display + Copy,
-{
-    // check if config_value fits datatype
-    let val: T = parse::<T>(property_name, property_value)?;
-    // check min bound
-    check_bound(property_name, val, min, min_bound)?;
-    // check max bound
-    check_bound(property_name, val, max, max_bound)?;
+) -> ValidationResult<()> {
+    if let Datatype::Float { .. } = property.datatype {
+        let allowed = property
+            .allow_non_finite
+            .unwrap_or(validation_policy.allow_non_finite_floats);
+        let non_finite = property_value
+            .parse::<f64>()
+            .is_ok_and(|value| !value.is_finite());
+
+        if non_finite && !allowed {
+            return Err(Error::NonFiniteFloatNotAllowed {
+                property_name: property_name.clone(),
+                value: property_value.to_string(),
+            });
+        }
+    }
 
-    Ok(val)
+    Ok(())
 }
 
-/// Returns the provided text parameter value of type T if no parsing errors appear
+/// Check if an immutable property is changed after it was initially set
 ///
 /// # Arguments
 ///
-/// * `config_spec_units` - map with unit name and respective regular expression to evaluate the datatype
 /// * `property_name` - name of the property
-/// * `property_value` - the value belonging to the property to be validated
-/// * `min` - minimum value specified
-/// * `max` - maximum value specified
-/// * `unit` - provided unit to get the regular expression to parse the property_value
+/// * `property` - the property spec
+/// * `old_value` - the current value of the property (None if not set yet)
+/// * `new_value` - the value the property should be changed to
 ///
-fn check_datatype_string(
-    config_spec_units: &HashMap<String, Regex>,
+fn check_immutable(
     property_name: &PropertyName,
-    property_value: &str,
-    min: &Option<String>,
-    max: &Option<String>,
-    unit: &Option<String>,
+    property: &PropertySpec,
+    old_value: Option<&str>,
+    new_value: &str,
 ) -> ValidationResult<()> {
-    let len: usize = property_value.len();
-    check_bound::<usize>(property_name, len, min, min_bound)?;
-    check_bound::<usize>(property_name, len, max, max_bound)?;
-
-    if let Some(unit_name) = unit {
-        match config_spec_units.get(unit_name.as_str()) {
-            None => {
-                return Err(Error::UnitSettingNotFound {
-                    property_name: property_name.clone(),
-                    unit: unit_name.clone(),
-                })
-            }
-            Some(regex) => {
-                if !regex.is_match(property_value) {
-                    return Err(Error::DatatypeRegexNotMatching {
-                        property_name: property_name.clone(),
-                        value: property_value.to_string(),
-                    });
-                }
-            }
+    if let Some(old_value) = old_value {
+        if property.immutable == Some(true) && old_value != new_value {
+            return Err(Error::ImmutablePropertyChanged {
+                property_name: property_name.clone(),
+                old_value: old_value.to_string(),
+                new_value: new_value.to_string(),
+            });
         }
-    } else {
-        return Err(Error::UnitNotProvided {
-            property_name: property_name.clone(),
-        });
     }
 
     Ok(())
 }
 
-/// Check if value is out of min bound
-///
-/// # Arguments
-///
-/// * `val` - value to be validated
-/// * `min` - min border (exclusive)
-///
-fn min_bound<T>(val: T, min: T) -> bool
-where
-    T: FromStr + std::cmp::PartialOrd + Display + Copy,
-{
-    val < min
-}
-
-/// Check if value is out of max bound
-///
-/// # Arguments
-///
-/// * `val` - value to be validated
-/// * `max` - max border (exclusive)
-///
-fn max_bound<T>(val: T, min: T) -> bool
-where
-    T: FromStr + std::cmp::PartialOrd + Display + Copy,
-{
-    val > min
-}
-
-/// Check if a value is inside a certain bound
+/// Check that none of the conflicting properties is set together with the property
 ///
 /// # Arguments
 ///
 /// * `property_name` - name of the property
-/// * `value` - value to be validated
-/// * `bound` - upper/lower bound
-/// * `check_out_of_bound` - the method to check against the bound
+/// * `property` - the property spec
+/// * `user_properties` - map with property name and values (of the kind of the property)
 ///
-fn check_bound<T>(
+fn check_conflicts(
     property_name: &PropertyName,
-    value: T,
-    bound: &Option<String>,
-    check_out_of_bound: fn(T, T) -> bool,
-) -> ValidationResult<T>
-where
-    T: FromStr + std::cmp::PartialOrd + Display + Copy,
-{
-    if let Some(bound) = bound {
-        let bound: T = parse::<T>(property_name, bound.as_str())?;
-        if check_out_of_bound(value, bound) {
-            return Err(Error::PropertyValueOutOfBounds {
+    property: &PropertySpec,
+    user_properties: &HashMap<String, String>,
+) -> ValidationResult<()> {
+    let conflicts = match &property.conflicts_with {
+        None => return Ok(()),
+        Some(conflicts) => conflicts,
+    };
+
+    for conflict in conflicts {
+        if conflict.kind != property_name.kind {
+            continue;
+        }
+
+        let mut names: Vec<&String> = user_properties
+            .keys()
+            .filter(|name| *name != &property_name.name)
+            .filter(|name| match conflict.name.strip_suffix('*') {
+                Some(prefix) => name.starts_with(prefix),
+                None => **name == conflict.name,
+            })
+            .collect();
+        names.sort();
+
+        if let Some(name) = names.first() {
+            return Err(Error::PropertyConflict {
                 property_name: property_name.clone(),
-                received: value.to_string(),
-                expected: bound.to_string(),
+                conflicting_property: name.to_string(),
             });
         }
     }
 
-    Ok(value)
+    Ok(())
 }
 
-/// Parse a value to a certain datatype and throw error if parsing not possible
+/// Check whether properties have provided dependencies and if they are contained the user properties
+/// TODO: add dependency automatically if missing?
 ///
 /// # Arguments
 ///
 /// * `property_name` - name of the property
-/// * `to_parse` - value to be parsed into a certain T
+/// * `property` - the respective property spec
+/// * `user_properties` - map with property name and potential value provided by user
 ///
-fn parse<T: FromStr>(property_name: &PropertyName, to_parse: &str) -> Result<T, Error> {
-    match to_parse.parse::<T>() {
-        Ok(to_parse) => Ok(to_parse),
-        Err(_) => {
-            return Err(Error::DatatypeNotMatching {
-                property_name: property_name.clone(),
-                value: to_parse.to_string(),
-                datatype: std::any::type_name::<T>().to_string(),
-            })
-        }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    macro_rules! hashmap {
-        ($( $key: expr => $val: expr ),*) => {{
-             let mut map = ::std::collections::HashMap::new();
-             $( map.insert($key, $val); )*
-             map
-        }}
-    }
-
-    use crate::error::Error;
-    use crate::reader::ConfigJsonReader;
-    use crate::types::{Datatype, PropertyName, PropertyNameKind, Role};
-    use crate::validation::{
-        check_allowed_values, check_datatype, check_dependencies, check_role,
-        check_version_supported_or_deprecated,
+fn check_dependencies(
+    property_name: &PropertyName,
+    property: &PropertySpec,
+    user_properties: &HashMap<String, String>,
+) -> ValidationResult<()> {
+    // check if property has dependencies
+    let property_dependencies = match &property.depends_on {
+        None => return Ok(()),
+        Some(dependencies) => dependencies,
     };
-    use crate::ProductConfigSpec;
-    use rstest::*;
-    use semver::Version;
-    use std::collections::HashMap;
 
-    const ENV_INTEGER_PORT_MIN_MAX: &str = "ENV_INTEGER_PORT_MIN_MAX";
-    const ENV_PROPERTY_STRING_MEMORY: &str = "ENV_PROPERTY_STRING_MEMORY";
-    const ENV_SSL_CERTIFICATE_PATH: &str = "ENV_SSL_CERTIFICATE_PATH";
-    const ENV_SSL_ENABLED: &str = "ENV_SSL_ENABLED";
-    const CONF_SSL_ENABLED: &str = "conf.ssl.enabled";
-    const ENV_ALLOWED_VALUES: &str = "ENV_ALLOWED_VALUES";
-    const ENV_VAR_FLOAT: &str = "ENV_VAR_FLOAT";
+    // for each dependency, check if user_properties contain the property and the correct value
+    for property_dependency in property_dependencies {
+        // check if we find any matches, otherwise return error after the loop
+        let mut found_match = false;
+        // for each property name provided within the dependency
+        for dependency_property_name in &property_dependency.property_names {
+            if !user_properties.contains_key(&dependency_property_name.name) {
+                continue;
+            }
 
-    const CONFIG_FILE: &str = "env.sh";
-    const CONFIG_FILE_2: &str = "my.config";
+            found_match = true;
 
-    const V_1_5_0: &str = "1.5.0";
-    const V_1_0_0: &str = "1.0.0";
-    const V_0_5_0: &str = "0.5.0";
-    const V_0_1_0: &str = "0.1.0";
+            match (
+                user_properties.get(&dependency_property_name.name),
+                &property_dependency.value,
+            ) {
+                // no user value, no property value -> ok
+                (None, None) => continue,
+                // no user value but property value required -> error
+                (None, Some(config_value)) => {
+                    return Err(Error::PropertyDependencyUserValueMissing {
+                        property_name: property_name.clone(),
+                        dependency: dependency_property_name.name.clone(),
+                        required_value: config_value.clone(),
+                    })
+                }
+                // user value but no property value required -> error
+                (Some(user_value), None) => {
+                    return Err(Error::PropertyDependencyUserValueNotRequired {
+                        property_name: property_name.clone(),
+                        dependency: dependency_property_name.name.clone(),
+                        user_value: user_value.clone(),
+                    })
+                }
+                // both values available -> check if match
+                (Some(user_value), Some(config_value)) => {
+                    if user_value != config_value {
+                        return Err(Error::PropertyDependencyValueInvalid {
+                            property_name: property_name.clone(),
+                            dependency: dependency_property_name.name.clone(),
+                            user_value: user_value.clone(),
+                            required_value: config_value.clone(),
+                        });
+                    }
+                }
+            }
+        }
 
-    fn get_conf_property_name(name: &str, file: &str) -> PropertyName {
-        PropertyName {
-            name: name.to_string(),
-            kind: PropertyNameKind::Conf(file.to_string()),
+        if !found_match {
+            // TODO: Error or just add the correct dependency?
+            return Err(Error::PropertyDependencyMissing {
+                property_name: property_name.clone(),
+                dependency: property_dependency.property_names.clone(),
+            });
         }
     }
 
-    fn get_product_config() -> ProductConfigSpec {
-        ProductConfigSpec::new(ConfigJsonReader::new(
-            "data/test_config_spec.json",
-            "data/test_property_spec.json",
-        ))
-        .unwrap()
-    }
+    Ok(())
+}
 
-    #[rstest]
-    #[case(get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), V_1_0_0, V_0_5_0, None, Ok(()))]
-    #[case(get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), V_0_1_0, V_1_0_0, Some(V_0_5_0.to_string()),
-            Err(Error::VersionNotSupported { property_name: property_name.clone(), product_version: V_0_1_0.to_string(), required_version: V_1_0_0.to_string() }))]
-    #[case(get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), V_1_5_0, V_0_5_0, Some(V_1_0_0.to_string()),
-            Err(Error::VersionDeprecated { property_name: property_name.clone(), product_version: V_1_5_0.to_string(), deprecated_version: V_1_0_0.to_string() }))]
-    #[trace]
-    fn test_check_version_supported_or_deprecated(
+/// Check if property value fits the provided datatype
+/// # Arguments
+///
+/// * `config_spec_units` - map with unit name and respective regular expression to evaluate the datatype
+/// * `property_name` - name of the property
+/// * `property_value` - property value to be validated
+/// * `datatype` - property datatype containing min/max bounds, units etc.
+///
+pub(crate) fn check_datatype(
+    config_spec_units: &HashMap<String, Regex>,
+    property_name: &PropertyName,
+    property_value: &str,
+    datatype: &Datatype,
+) -> ValidationResult<()> {
+    match datatype {
+        Datatype::Bool => {
+            check_datatype_scalar::<bool>(property_name, property_value, &None, &None)?;
+        }
+        Datatype::Integer {
+            min,
+            max,
+            accepted_units,
+            default_unit,
+            multiple_of,
+            ..
+        } => {
+            let value = value_in_unit(
+                property_name,
+                property_value,
+                accepted_units,
+                default_unit,
+                (min, max),
+                true,
+            )?;
+            let (number, min, max) = (value.number.as_str(), &value.min, &value.max);
+            // values or bounds exceeding i64 (e.g. large byte counts) are validated as i128
+            let exceeds_i64 =
+                |value: &str| value.parse::<i64>().is_err() && value.parse::<i128>().is_ok();
+            let parsed = if exceeds_i64(number) || min.iter().chain(max).any(|b| exceeds_i64(b)) {
+                check_datatype_scalar::<i128>(property_name, number, min, max)
+            } else {
+                check_datatype_scalar::<i64>(property_name, number, min, max).map(i128::from)
+            }
+            .map_err(|err| with_provided_value(err, property_value, &value.unit))?;
+            check_multiple_of(property_name, number, parsed, multiple_of)
+                .map_err(|err| with_provided_value(err, property_value, &value.unit))?;
+        }
+        Datatype::UnsignedInteger {
+            min,
+            max,
+            accepted_units,
+            default_unit,
+            ..
+        } => {
+            let value = value_in_unit(
+                property_name,
+                property_value,
+                accepted_units,
+                default_unit,
+                (min, max),
+                true,
+            )?;
+            check_datatype_scalar::<u64>(property_name, &value.number, &value.min, &value.max)
+                .map_err(|err| with_provided_value(err, property_value, &value.unit))?;
+        }
+        Datatype::Float {
+            min,
+            max,
+            accepted_units,
+            default_unit,
+            ..
+        } => {
+            let value = value_in_unit(
+                property_name,
+                property_value,
+                accepted_units,
+                default_unit,
+                (min, max),
+                false,
+            )?;
+            check_datatype_scalar::<f64>(property_name, &value.number, &value.min, &value.max)
+                .map_err(|err| with_provided_value(err, property_value, &value.unit))?;
+        }
+        Datatype::String {
+            min,
+            max,
+            unit,
+            length_unit,
+            ..
+        } => {
+            check_datatype_string(
+                config_spec_units,
+                property_name,
+                property_value,
+                min,
+                max,
+                unit,
+                length_unit.unwrap_or(LengthUnit::Bytes),
+            )?;
+        }
+        Datatype::Duration { min, max } => {
+            check_datatype_quantity(property_name, property_value, min, max, &DURATION)?;
+        }
+        Datatype::MemorySize { min, max } => {
+            check_datatype_quantity(property_name, property_value, min, max, &MEMORY_SIZE)?;
+        }
+        Datatype::Port {
+            allow_privileged,
+            range,
+        } => {
+            check_datatype_port(property_name, property_value, *allow_privileged, *range)?;
+        }
+        Datatype::Url {
+            allowed_schemes,
+            require_host,
+        } => {
+            check_datatype_url(
+                property_name,
+                property_value,
+                allowed_schemes,
+                *require_host,
+            )?;
+        }
+        Datatype::IpAddress {
+            v4_only,
+            v6_only,
+            allow_cidr,
+        } => {
+            check_datatype_ip_address(
+                property_name,
+                property_value,
+                *v4_only,
+                *v6_only,
+                *allow_cidr,
+            )?;
+        }
+        Datatype::Path {
+            must_be_absolute,
+            must_exist,
+            kind,
+        } => {
+            check_datatype_path(
+                property_name,
+                property_value,
+                *must_be_absolute,
+                *must_exist,
+                kind,
+            )?;
+        }
+        Datatype::Decimal {
+            precision,
+            scale,
+            min,
+            max,
+        } => {
+            check_datatype_decimal(property_name, property_value, *precision, *scale, min, max)?;
+        }
+        Datatype::DateTime { format, min, max } => {
+            check_datatype_date_time(property_name, property_value, format, min, max)?;
+        }
+        Datatype::Percent { as_fraction } => {
+            let fraction = parse_percent(property_value, *as_fraction).ok_or_else(|| {
+                Error::DatatypeNotMatching {
+                    property_name: property_name.clone(),
+                    value: property_value.to_string(),
+                    datatype: "percent".to_string(),
+                }
+            })?;
+            if !(0.0..=1.0).contains(&fraction) {
+                return Err(Error::PropertyValueOutOfBounds {
+                    property_name: property_name.clone(),
+                    received: property_value.to_string(),
+                    expected: if *as_fraction { "0.0 - 1.0" } else { "0 - 100" }.to_string(),
+                });
+            }
+        }
+        Datatype::Regex => {
+            if let Err(err) = Regex::new(property_value) {
+                return Err(Error::InvalidRegex {
+                    property_name: property_name.clone(),
+                    value: property_value.to_string(),
+                    reason: err.to_string(),
+                });
+            }
+        }
+        Datatype::Json { schema } => {
+            let invalid_json = |reason: String| Error::InvalidJson {
+                property_name: property_name.clone(),
+                value: property_value.to_string(),
+                reason,
+            };
+
+            let value = serde_json::from_str(property_value)
+                .map_err(|err| invalid_json(err.to_string()))?;
+            if let Some(schema) = schema {
+                json_schema::validate(&schema.0, &value).map_err(invalid_json)?;
+            }
+        }
+        Datatype::Array { .. } => {
+            // TODO: implement logic for array type
+        }
+        Datatype::Enum { values, .. } => {
+            if datatype.enum_variant(property_value).is_none() {
+                return Err(Error::PropertyValueNotInAllowedValues {
+                    property_name: property_name.clone(),
+                    value: property_value.to_string(),
+                    allowed_values: values.clone(),
+                });
+            }
+        }
+        Datatype::Password {
+            min_length,
+            required_classes,
+            min_entropy,
+        } => {
+            check_datatype_password(
+                property_name,
+                property_value,
+                *min_length,
+                required_classes,
+                *min_entropy,
+            )?;
+        }
+        Datatype::Ref { name } => {
+            // references are replaced by their definition when reading the spec
+            return Err(Error::DatatypeDefinitionNotFound {
+                property_name: property_name.clone(),
+                name: name.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Check if property value is in allowed values or matches one of the allowed patterns. If only
+/// one of both is provided, the value has to satisfy that one.
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - property value to be validated
+/// * `allowed_values` - vector of allowed values
+/// * `allowed_pattern` - vector of regular expressions that have to match the whole value
+///
+pub(crate) fn check_allowed_values(
+    property_name: &PropertyName,
+    property_value: &str,
+    allowed_values: &Option<Vec<String>>,
+    allowed_pattern: &Option<Vec<String>>,
+) -> ValidationResult<()> {
+    let allowed_values = allowed_values.clone().unwrap_or_default();
+    let allowed_pattern = allowed_pattern.clone().unwrap_or_default();
+
+    match product_config_core::check_allowed_values(
+        property_value,
+        &allowed_values,
+        &allowed_pattern,
+        &RegexCrate,
+    ) {
+        Ok(()) => Ok(()),
+        Err(CheckError::NotMatchingAllowedPattern { value }) => {
+            Err(Error::PropertyValueNotMatchingAllowedPattern {
+                property_name: property_name.clone(),
+                value,
+                allowed_values,
+                allowed_pattern,
+            })
+        }
+        Err(CheckError::NotInAllowedValues { value }) => {
+            Err(Error::PropertyValueNotInAllowedValues {
+                property_name: property_name.clone(),
+                value,
+                allowed_values,
+            })
+        }
+        Err(err) => Err(check_error(property_name, err)),
+    }
+}
+
+/// Check a single (non empty) value or list item: datatype, allowed / forbidden values and the
+/// custom validator
+///
+/// # Arguments
+/// * `product_config` - the product config spec (property specs, units and validation policy)
+/// * `properties` - the user properties to resolve bound references
+/// * `property_name` - name of the property
+/// * `property` - the property spec
+/// * `property_value` - the value or list item to be validated
+///
+fn check_value(
+    product_config: &ProductConfigSpec,
+    properties: &HashMap<String, String>,
+    property_name: &PropertyName,
+    property: &PropertySpec,
+    property_value: &str,
+) -> ValidationResult<()> {
+    check_non_finite(
+        property_name,
+        property,
+        &product_config.validation_policy,
+        property_value,
+    )?;
+    check_datatype(
+        &product_config.config_spec.units,
+        property_name,
+        property_value,
+        &resolve_bounds(&property.datatype, properties),
+    )?;
+    check_allowed_values(
+        property_name,
+        property_value,
+        &property.allowed_values,
+        &property.allowed_pattern,
+    )?;
+    check_forbidden_values(property_name, property_value, &property.forbidden_values)?;
+    check_custom_validator(
+        &product_config.validators,
+        property_name,
+        property,
+        property_value,
+    )
+}
+
+/// Returns the (trimmed) items of a list property or the value itself for other properties
+///
+/// # Arguments
+/// * `property` - the property spec
+/// * `property_value` - the value to be split
+///
+pub(crate) fn list_items<'a>(property: &PropertySpec, property_value: &'a str) -> Vec<&'a str> {
+    match &property.list {
+        Some(list) => property_value
+            .split(list.delimiter.as_str())
+            .map(str::trim)
+            .collect(),
+        None => vec![property_value],
+    }
+}
+
+/// Check the number of items and their uniqueness for list properties
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property` - the property spec
+/// * `property_value` - the delimited list value
+///
+pub(crate) fn check_list(
+    property_name: &PropertyName,
+    property: &PropertySpec,
+    property_value: &str,
+) -> ValidationResult<()> {
+    let list = match &property.list {
+        Some(list) => list,
+        None => return Ok(()),
+    };
+
+    let items = list_items(property, property_value);
+    let too_few = list.min_items.is_some_and(|min| items.len() < min);
+    let too_many = list.max_items.is_some_and(|max| items.len() > max);
+    if too_few || too_many {
+        return Err(Error::ListItemCountOutOfBounds {
+            property_name: property_name.clone(),
+            value: property_value.to_string(),
+            count: items.len(),
+            expected: list.to_string(),
+        });
+    }
+
+    if list.unique {
+        for (index, item) in items.iter().enumerate() {
+            if items[..index].contains(item) {
+                return Err(Error::ListItemNotUnique {
+                    property_name: property_name.clone(),
+                    value: property_value.to_string(),
+                    item: item.to_string(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check if property value is within the recommended range (if provided). Values outside of
+/// the range result in a `PropertyValueNotRecommended` error to be reported as warning.
+/// # Arguments
+///
+/// * `config_spec_units` - map with unit name and respective regular expression to evaluate the datatype
+/// * `property_name` - name of the property
+/// * `property` - the property spec
+/// * `property_value` - property value to be validated
+///
+pub(crate) fn check_recommended_range(
+    config_spec_units: &HashMap<String, Regex>,
+    property_name: &PropertyName,
+    property: &PropertySpec,
+    property_value: &str,
+) -> ValidationResult<()> {
+    let range = match &property.recommended_range {
+        Some(range) => range,
+        None => return Ok(()),
+    };
+
+    let datatype = property
+        .datatype
+        .with_bounds(range.min.clone(), range.max.clone());
+    match check_datatype(config_spec_units, property_name, property_value, &datatype) {
+        Err(Error::PropertyValueOutOfBounds { .. })
+        | Err(Error::PropertyValueOutOfBoundsInUnit { .. })
+        | Err(Error::StringLengthOutOfBounds { .. }) => Err(Error::PropertyValueNotRecommended {
+            property_name: property_name.clone(),
+            value: property_value.to_string(),
+            recommended_range: range.to_string(),
+            reason: range
+                .reason
+                .clone()
+                .unwrap_or_else(|| "use with caution".to_string()),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Check if property value is not one of the forbidden values
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - property value to be validated
+/// * `forbidden_values` - vector of forbidden values
+///
+pub(crate) fn check_forbidden_values(
+    property_name: &PropertyName,
+    property_value: &str,
+    forbidden_values: &Option<Vec<String>>,
+) -> ValidationResult<()> {
+    if let Some(forbidden_values) = forbidden_values {
+        if forbidden_values.iter().any(|value| value == property_value) {
+            return Err(Error::PropertyValueForbidden {
+                property_name: property_name.clone(),
+                value: property_value.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns the allowed pattern compiled to a regex matching the whole value
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `pattern` - the allowed pattern
+///
+fn compile_allowed_pattern(property_name: &PropertyName, pattern: &str) -> ValidationResult<Regex> {
+    full_match_regex(pattern).map_err(|err| Error::InvalidAllowedPattern {
+        property_name: property_name.clone(),
+        pattern: pattern.to_string(),
+        reason: err.to_string(),
+    })
+}
+
+/// Returns the regex matching the whole value for the allowed pattern
+fn full_match_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    Regex::new(&format!("^(?:{})$", pattern))
+}
+
+/// The `regex` crate as regex engine of the validation core
+struct RegexCrate;
+
+impl RegexBackend for RegexCrate {
+    fn is_full_match(&self, pattern: &str, value: &str) -> Result<bool, String> {
+        full_match_regex(pattern)
+            .map(|regex| regex.is_match(value))
+            .map_err(|err| err.to_string())
+    }
+}
+
+/// Returns the error of the validation core as error of the property
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `err` - the error of the validation core
+///
+fn check_error(property_name: &PropertyName, err: CheckError) -> Error {
+    match err {
+        CheckError::DatatypeNotMatching { value, datatype } => Error::DatatypeNotMatching {
+            property_name: property_name.clone(),
+            value,
+            datatype: datatype.to_string(),
+        },
+        CheckError::OutOfBounds { received, expected } => Error::PropertyValueOutOfBounds {
+            property_name: property_name.clone(),
+            received,
+            expected,
+        },
+        CheckError::NotInAllowedValues { value } => Error::PropertyValueNotInAllowedValues {
+            property_name: property_name.clone(),
+            value,
+            allowed_values: vec![],
+        },
+        CheckError::NotMatchingAllowedPattern { value } => {
+            Error::PropertyValueNotMatchingAllowedPattern {
+                property_name: property_name.clone(),
+                value,
+                allowed_values: vec![],
+                allowed_pattern: vec![],
+            }
+        }
+        CheckError::InvalidPattern { pattern, reason } => Error::InvalidAllowedPattern {
+            property_name: property_name.clone(),
+            pattern,
+            reason,
+        },
+    }
+}
+
+/// Returns the provided scalar parameter value of type T (i16, i32, i64, f32, f62-..) if no parsing errors appear
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - the value belonging to the property to be validated
+/// * `min` - minimum value specified
+/// * `max` - maximum value specified
+///
+fn check_datatype_scalar<T>(
+    property_name: &PropertyName,
+    property_value: &str,
+    min: &Option<String>,
+    max: &Option<String>,
+) -> ValidationResult<T>
+where
+    T: FromStr + std::cmp::PartialOrd + Display + Copy,
+{
+    product_config_core::check_scalar(property_value, min.as_deref(), max.as_deref())
+        .map_err(|err| check_error(property_name, err))
+}
+
+/// Check if an integer value is a multiple of the provided value (if any)
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - the value belonging to the property to be validated
+/// * `value` - the parsed value
+/// * `multiple_of` - the value has to be a multiple of this
+///
+fn check_multiple_of(
+    property_name: &PropertyName,
+    property_value: &str,
+    value: i128,
+    multiple_of: &Option<String>,
+) -> ValidationResult<()> {
+    if let Some(multiple_of) = multiple_of {
+        let divisor = parse::<i128>(property_name, multiple_of)?;
+        if divisor != 0 && value % divisor != 0 {
+            return Err(Error::PropertyValueNotMultipleOf {
+                property_name: property_name.clone(),
+                value: property_value.to_string(),
+                multiple_of: multiple_of.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// A numeric value converted to the default unit of its datatype
+struct ValueInUnit {
+    // the number converted to the unit
+    number: String,
+    // the default unit (or first accepted unit), empty if the datatype has no units
+    unit: String,
+    // the bounds still to be checked (bounds of convertible units are already checked)
+    min: Option<String>,
+    max: Option<String>,
+}
+
+/// Returns a numeric value with optional unit suffix (e.g. `512mb`) converted to the default
+/// unit (or the first accepted unit) of its datatype. A value without suffix is given in the
+/// default unit. If the units are memory size or duration units (see the `units` module), the
+/// bounds may have unit suffixes as well and are checked here after the conversion to a common
+/// unit.
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - the value belonging to the property to be validated
+/// * `accepted_units` - the unit suffixes that may be provided
+/// * `default_unit` - the unit of values without suffix
+/// * `bounds` - min and max bound of the datatype
+/// * `integral` - if the converted number has to be an integer
+///
+fn value_in_unit(
+    property_name: &PropertyName,
+    property_value: &str,
+    accepted_units: &Option<Vec<String>>,
+    default_unit: &Option<String>,
+    (min, max): (&Option<String>, &Option<String>),
+    integral: bool,
+) -> ValidationResult<ValueInUnit> {
+    let accepted_units = accepted_units.clone().unwrap_or_default();
+    let target_unit = match default_unit.as_ref().or_else(|| accepted_units.first()) {
+        Some(target_unit) => target_unit,
+        None => {
+            return Ok(ValueInUnit {
+                number: property_value.to_string(),
+                unit: String::new(),
+                min: min.clone(),
+                max: max.clone(),
+            })
+        }
+    };
+
+    let (number, unit) = split_unit(property_value);
+    let unit = match unit {
+        "" => target_unit.as_str(),
+        unit if accepted_units.iter().any(|accepted| accepted == unit) => unit,
+        unit if default_unit.as_deref() == Some(unit) => unit,
+        unit => {
+            return Err(Error::UnitNotAccepted {
+                property_name: property_name.clone(),
+                value: property_value.to_string(),
+                unit: unit.to_string(),
+                accepted_units,
+            })
+        }
+    };
+
+    // all units of the datatype decide about the quantity (e.g. `m` with `h` are minutes)
+    let mut all_units: Vec<&str> = accepted_units.iter().map(String::as_str).collect();
+    all_units.extend(default_unit.as_deref());
+    let quantity = match quantity_of(&all_units) {
+        Some(quantity) => quantity,
+        // units without conversion table can only be compared with plain bounds
+        None if unit == target_unit => {
+            return Ok(ValueInUnit {
+                number: number.to_string(),
+                unit: target_unit.clone(),
+                min: min.clone(),
+                max: max.clone(),
+            })
+        }
+        None => {
+            return Err(Error::UnitConversionNotSupported {
+                property_name: property_name.clone(),
+                unit: unit.to_string(),
+                target_unit: target_unit.clone(),
+            })
+        }
+    };
+
+    let number = parse::<f64>(property_name, number)
+        .map_err(|err| with_provided_value(err, property_value, ""))?;
+    let converted = quantity
+        .convert(number, unit, target_unit)
+        .unwrap_or(number);
+    if integral && converted.fract() != 0.0 {
+        return Err(Error::DatatypeNotMatching {
+            property_name: property_name.clone(),
+            value: property_value.to_string(),
+            datatype: format!("integer ({})", target_unit),
+        });
+    }
+
+    check_bounds_in_unit(
+        property_name,
+        property_value,
+        (number, unit),
+        (min, max),
+        quantity,
+        Some(target_unit),
+    )?;
+
+    Ok(ValueInUnit {
+        number: units::format_number(converted),
+        unit: target_unit.clone(),
+        min: None,
+        max: None,
+    })
+}
+
+/// Check if a number with unit is within the min / max bounds (numbers with unit as well). The
+/// value is converted to the unit of the violated bound for the error, e.g. `90s` violates the
+/// max bound `1m` with `1.5m`.
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - the value belonging to the property to be validated
+/// * `(number, unit)` - the parsed value
+/// * `(min, max)` - the bounds
+/// * `quantity` - the units the value and bounds are given in
+/// * `default_unit` - the unit of bounds without suffix (bounds need a suffix if None)
+///
+fn check_bounds_in_unit(
+    property_name: &PropertyName,
+    property_value: &str,
+    (number, unit): (f64, &str),
+    (min, max): (&Option<String>, &Option<String>),
+    quantity: &Quantity,
+    default_unit: Option<&str>,
+) -> ValidationResult<()> {
+    for (bound, check_out_of_bound) in [
+        (min, min_bound as fn(f64, f64) -> bool),
+        (max, max_bound as fn(f64, f64) -> bool),
+    ] {
+        let bound = match bound {
+            Some(bound) => bound,
+            None => continue,
+        };
+
+        let (bound_number, bound_unit) = split_unit(bound);
+        let bound_unit = match (bound_unit, default_unit) {
+            ("", Some(default_unit)) => default_unit,
+            (bound_unit, _) => bound_unit,
+        };
+        let invalid_bound = || Error::DatatypeNotMatching {
+            property_name: property_name.clone(),
+            value: bound.clone(),
+            datatype: quantity.name.to_string(),
+        };
+        let bound_number = bound_number.parse::<f64>().map_err(|_| invalid_bound())?;
+        let received = quantity
+            .convert(number, unit, bound_unit)
+            .ok_or_else(invalid_bound)?;
+
+        if check_out_of_bound(received, bound_number) {
+            return Err(Error::PropertyValueOutOfBoundsInUnit {
+                property_name: property_name.clone(),
+                received: property_value.to_string(),
+                received_in_unit: format!("{}{}", units::format_number(received), bound_unit),
+                expected: format!("{}{}", units::format_number(bound_number), bound_unit),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the error reporting the value as provided by the user instead of its magnitude
+/// (bounds are reported with the unit they are expressed in)
+///
+/// # Arguments
+///
+/// * `err` - the error raised for the magnitude
+/// * `property_value` - the value provided by the user
+/// * `unit` - the unit of the magnitude and bounds (empty if the datatype has no units)
+///
+fn with_provided_value(err: Error, property_value: &str, unit: &str) -> Error {
+    match err {
+        Error::PropertyValueOutOfBounds {
+            property_name,
+            expected,
+            ..
+        } if !unit.is_empty() => Error::PropertyValueOutOfBounds {
+            property_name,
+            received: property_value.to_string(),
+            expected: format!("{}{}", expected, unit),
+        },
+        Error::DatatypeNotMatching {
+            property_name,
+            datatype,
+            ..
+        } => Error::DatatypeNotMatching {
+            property_name,
+            value: property_value.to_string(),
+            datatype,
+        },
+        Error::PropertyValueNotMultipleOf {
+            property_name,
+            multiple_of,
+            ..
+        } => Error::PropertyValueNotMultipleOf {
+            property_name,
+            value: property_value.to_string(),
+            multiple_of,
+        },
+        err => err,
+    }
+}
+
+/// Returns the length of the value measured in the provided unit
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - the value to be measured
+/// * `length_unit` - bytes, chars or graphemes (requires the `unicode-segmentation` feature)
+///
+#[cfg_attr(feature = "unicode-segmentation", allow(unused_variables))]
+fn string_length(
+    property_name: &PropertyName,
+    property_value: &str,
+    length_unit: LengthUnit,
+) -> ValidationResult<usize> {
+    match length_unit {
+        LengthUnit::Bytes => Ok(property_value.len()),
+        LengthUnit::Chars => Ok(property_value.chars().count()),
+        #[cfg(feature = "unicode-segmentation")]
+        LengthUnit::Graphemes => {
+            Ok(unicode_segmentation::UnicodeSegmentation::graphemes(property_value, true).count())
+        }
+        #[cfg(not(feature = "unicode-segmentation"))]
+        LengthUnit::Graphemes => Err(Error::DatatypeFeatureNotEnabled {
+            property_name: property_name.clone(),
+            datatype: "string (graphemes)".to_string(),
+            feature: "unicode-segmentation".to_string(),
+        }),
+    }
+}
+
+/// Returns the provided text parameter value of type T if no parsing errors appear
+///
+/// # Arguments
+///
+/// * `config_spec_units` - map with unit name and respective regular expression to evaluate the datatype
+/// * `property_name` - name of the property
+/// * `property_value` - the value belonging to the property to be validated
+/// * `min` - minimum value specified
+/// * `max` - maximum value specified
+/// * `unit` - provided unit to get the regular expression to parse the property_value
+/// * `length_unit` - how the length is measured for the min / max check
+///
+fn check_datatype_string(
+    config_spec_units: &HashMap<String, Regex>,
+    property_name: &PropertyName,
+    property_value: &str,
+    min: &Option<String>,
+    max: &Option<String>,
+    unit: &Option<String>,
+    length_unit: LengthUnit,
+) -> ValidationResult<()> {
+    if min.is_some() || max.is_some() {
+        let length = string_length(property_name, property_value, length_unit)?;
+        for (bound, check_out_of_bound) in [
+            (min, min_bound as fn(usize, usize) -> bool),
+            (max, max_bound as fn(usize, usize) -> bool),
+        ] {
+            if let Some(bound) = bound {
+                if check_out_of_bound(length, parse::<usize>(property_name, bound)?) {
+                    return Err(Error::StringLengthOutOfBounds {
+                        property_name: property_name.clone(),
+                        value: property_value.to_string(),
+                        length,
+                        length_unit,
+                        expected: bound.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    if let Some(unit_name) = unit {
+        match config_spec_units.get(unit_name.as_str()) {
+            None => {
+                return Err(Error::UnitSettingNotFound {
+                    property_name: property_name.clone(),
+                    unit: unit_name.clone(),
+                })
+            }
+            Some(regex) => {
+                if !regex.is_match(property_value) {
+                    return Err(Error::DatatypeRegexNotMatching {
+                        property_name: property_name.clone(),
+                        value: property_value.to_string(),
+                    });
+                }
+            }
+        }
+    } else {
+        return Err(Error::UnitNotProvided {
+            property_name: property_name.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns the parts of the property value captured by the named groups of its unit regex.
+/// The value is validated against the datatype of the property first.
+///
+/// # Arguments
+///
+/// * `config_spec_units` - map with unit name and respective regular expression
+/// * `property_name` - name of the property
+/// * `property` - the property spec (a string datatype with unit)
+/// * `property_value` - the value to be validated and captured
+///
+pub(crate) fn unit_captures(
+    config_spec_units: &HashMap<String, Regex>,
+    property_name: &PropertyName,
+    property: &PropertySpec,
+    property_value: &str,
+) -> ValidationResult<UnitCaptures> {
+    check_datatype(
+        config_spec_units,
+        property_name,
+        property_value,
+        &property.datatype,
+    )?;
+
+    let unit = match &property.datatype {
+        Datatype::String {
+            unit: Some(unit), ..
+        } => unit,
+        _ => {
+            return Err(Error::UnitNotProvided {
+                property_name: property_name.clone(),
+            })
+        }
+    };
+
+    // the regex exists and matches after the datatype check above
+    let regex = &config_spec_units[unit];
+    let groups = regex
+        .captures(property_value)
+        .map(|captures| {
+            regex
+                .capture_names()
+                .flatten()
+                .filter_map(|name| {
+                    captures
+                        .name(name)
+                        .map(|group| (name.to_string(), group.as_str().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(UnitCaptures {
+        unit: unit.clone(),
+        groups,
+    })
+}
+
+/// Ports below are privileged (require root permissions)
+const PRIVILEGED_PORT_LIMIT: u16 = 1024;
+
+/// Check if the property value is a valid port or port range (e.g. `9000-9010`, if allowed)
+/// and does not contain privileged ports (if not allowed).
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - the value belonging to the property to be validated
+/// * `allow_privileged` - if ports below 1024 are accepted
+/// * `range` - if port ranges are accepted
+///
+fn check_datatype_port(
+    property_name: &PropertyName,
+    property_value: &str,
+    allow_privileged: bool,
+    range: bool,
+) -> ValidationResult<()> {
+    let not_matching = || Error::DatatypeNotMatching {
+        property_name: property_name.clone(),
+        value: property_value.to_string(),
+        datatype: if range { "port range" } else { "port" }.to_string(),
+    };
+
+    let (from, to) = match property_value.split_once('-') {
+        Some((from, to)) if range => (from, to),
+        Some(_) => return Err(not_matching()),
+        None => (property_value, property_value),
+    };
+
+    let mut ports = [0u16; 2];
+    for (port, value) in ports.iter_mut().zip([from, to]) {
+        let value = value.trim().parse::<u32>().map_err(|_| not_matching())?;
+        *port = u16::try_from(value).map_err(|_| Error::PropertyValueOutOfBounds {
+            property_name: property_name.clone(),
+            received: value.to_string(),
+            expected: u16::MAX.to_string(),
+        })?;
+    }
+
+    let [from, to] = ports;
+    if from > to {
+        return Err(not_matching());
+    }
+
+    if !allow_privileged && from < PRIVILEGED_PORT_LIMIT {
+        return Err(Error::PrivilegedPortNotAllowed {
+            property_name: property_name.clone(),
+            port: from,
+        });
+    }
+
+    Ok(())
+}
+
+/// Check if the property value is an IP address (or CIDR block if allowed) of the required
+/// version.
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - the value belonging to the property to be validated
+/// * `v4_only` - if only IPv4 addresses are accepted
+/// * `v6_only` - if only IPv6 addresses are accepted
+/// * `allow_cidr` - if CIDR blocks (address with prefix length) are accepted
+///
+fn check_datatype_ip_address(
+    property_name: &PropertyName,
+    property_value: &str,
+    v4_only: bool,
+    v6_only: bool,
+    allow_cidr: bool,
+) -> ValidationResult<()> {
+    let datatype = match (v4_only, v6_only) {
+        (true, false) => "ipv4",
+        (false, true) => "ipv6",
+        _ => "ip",
+    };
+    let not_matching = || Error::DatatypeNotMatching {
+        property_name: property_name.clone(),
+        value: property_value.to_string(),
+        datatype: if allow_cidr {
+            format!("{} cidr", datatype)
+        } else {
+            datatype.to_string()
+        },
+    };
+
+    let (address, prefix_length) = match property_value.split_once('/') {
+        Some((address, prefix_length)) if allow_cidr => (address, Some(prefix_length)),
+        Some(_) => return Err(not_matching()),
+        None => (property_value, None),
+    };
+
+    let address = address.parse::<IpAddr>().map_err(|_| not_matching())?;
+    if (v4_only && !address.is_ipv4()) || (v6_only && !address.is_ipv6()) {
+        return Err(not_matching());
+    }
+
+    if let Some(prefix_length) = prefix_length {
+        let max_prefix_length = if address.is_ipv4() { 32 } else { 128 };
+        match prefix_length.parse::<u8>() {
+            Ok(prefix_length) if prefix_length <= max_prefix_length => {}
+            _ => return Err(not_matching()),
+        }
+    }
+
+    Ok(())
+}
+
+/// Check if the property value is an (absolute) path. The filesystem is only accessed if the
+/// path must exist.
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - the value belonging to the property to be validated
+/// * `must_be_absolute` - if relative paths are rejected
+/// * `must_exist` - if the path has to exist (as file or directory if kind is provided)
+/// * `kind` - what the path has to point to if it must exist
+///
+fn check_datatype_path(
+    property_name: &PropertyName,
+    property_value: &str,
+    must_be_absolute: bool,
+    must_exist: bool,
+    kind: &Option<PathKind>,
+) -> ValidationResult<()> {
+    let invalid_path = |reason: String| Error::InvalidPath {
+        property_name: property_name.clone(),
+        value: property_value.to_string(),
+        reason,
+    };
+
+    let path = Path::new(property_value);
+    if must_be_absolute && !path.is_absolute() {
+        return Err(invalid_path("path must be absolute".to_string()));
+    }
+
+    if must_exist {
+        let exists = match kind {
+            Some(PathKind::File) => path.is_file(),
+            Some(PathKind::Dir) => path.is_dir(),
+            None => path.exists(),
+        };
+        if !exists {
+            return Err(invalid_path(match kind {
+                Some(kind) => format!("{} does not exist", kind),
+                None => "path does not exist".to_string(),
+            }));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check if the property value is a strong enough password. All unmet requirements are reported
+/// at once (without the value) so they can be fixed in one go.
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - the value belonging to the property to be validated
+/// * `min_length` - minimum number of characters
+/// * `required_classes` - character classes that have to occur at least once
+/// * `min_entropy` - minimum estimated entropy in bits
+///
+fn check_datatype_password(
+    property_name: &PropertyName,
+    property_value: &str,
+    min_length: Option<usize>,
+    required_classes: &Option<Vec<CharacterClass>>,
+    min_entropy: Option<u32>,
+) -> ValidationResult<()> {
+    let mut reasons = vec![];
+
+    let length = property_value.chars().count();
+    if let Some(min_length) = min_length {
+        if length < min_length {
+            reasons.push(format!(
+                "at least {} characters required, got {}",
+                min_length, length
+            ));
+        }
+    }
+
+    let classes: BTreeSet<CharacterClass> =
+        property_value.chars().map(CharacterClass::of).collect();
+    for class in required_classes.iter().flatten() {
+        if !classes.contains(class) {
+            reasons.push(format!("at least one {} required", class));
+        }
+    }
+
+    if let Some(min_entropy) = min_entropy {
+        let entropy = password_entropy(length, &classes);
+        if entropy < f64::from(min_entropy) {
+            reasons.push(format!(
+                "estimated entropy of {:.1} bits is below {} bits (use a longer password or more character classes)",
+                entropy, min_entropy
+            ));
+        }
+    }
+
+    if reasons.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::PasswordTooWeak {
+            property_name: property_name.clone(),
+            reasons,
+        })
+    }
+}
+
+/// Returns the estimated entropy of a password in bits, i.e. the length times the bits required
+/// for one character of the used character classes
+///
+/// # Arguments
+///
+/// * `length` - number of characters of the password
+/// * `classes` - the character classes used in the password
+///
+fn password_entropy(length: usize, classes: &BTreeSet<CharacterClass>) -> f64 {
+    let pool: u32 = classes.iter().map(CharacterClass::size).sum();
+    if pool == 0 {
+        return 0.0;
+    }
+    length as f64 * f64::from(pool).log2()
+}
+
+/// Check if the property value is an exact decimal number within the min / max bounds that
+/// fits precision and scale: at most `scale` digits after the decimal point and at most
+/// `precision - scale` digits before (trailing zeros are ignored).
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - the value belonging to the property to be validated
+/// * `precision` - maximum number of digits
+/// * `scale` - maximum number of digits after the decimal point
+/// * `min` - minimum value specified
+/// * `max` - maximum value specified
+///
+#[cfg(feature = "rust_decimal")]
+fn check_datatype_decimal(
+    property_name: &PropertyName,
+    property_value: &str,
+    precision: Option<u32>,
+    scale: Option<u32>,
+    min: &Option<String>,
+    max: &Option<String>,
+) -> ValidationResult<()> {
+    use rust_decimal::Decimal;
+
+    let parse = |value: &str| {
+        value
+            .trim()
+            .parse::<Decimal>()
+            .map_err(|_| Error::DatatypeNotMatching {
+                property_name: property_name.clone(),
+                value: value.to_string(),
+                datatype: "decimal".to_string(),
+            })
+    };
+
+    let value = parse(property_value)?.normalize();
+    let precision_exceeded = |reason: String| Error::DecimalPrecisionExceeded {
+        property_name: property_name.clone(),
+        value: property_value.to_string(),
+        reason,
+    };
+
+    let fraction_digits = value.scale();
+    if let Some(scale) = scale {
+        if fraction_digits > scale {
+            return Err(precision_exceeded(format!(
+                "more than {} digits after the decimal point",
+                scale
+            )));
+        }
+    }
+
+    if let Some(precision) = precision {
+        let integer = value.trunc().abs();
+        let integer_digits = if integer.is_zero() {
+            0
+        } else {
+            integer.to_string().len() as u32
+        };
+        if integer_digits + scale.unwrap_or(fraction_digits) > precision {
+            return Err(precision_exceeded(format!(
+                "more than {} digits",
+                precision
+            )));
+        }
+    }
+
+    for (bound, check_out_of_bound) in [
+        (min, min_bound as fn(Decimal, Decimal) -> bool),
+        (max, max_bound as fn(Decimal, Decimal) -> bool),
+    ] {
+        if let Some(bound) = bound {
+            if check_out_of_bound(value, parse(bound)?) {
+                return Err(Error::PropertyValueOutOfBounds {
+                    property_name: property_name.clone(),
+                    received: property_value.to_string(),
+                    expected: bound.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "rust_decimal"))]
+fn check_datatype_decimal(
+    property_name: &PropertyName,
+    _property_value: &str,
+    _precision: Option<u32>,
+    _scale: Option<u32>,
+    _min: &Option<String>,
+    _max: &Option<String>,
+) -> ValidationResult<()> {
+    Err(Error::DatatypeFeatureNotEnabled {
+        property_name: property_name.clone(),
+        datatype: "decimal".to_string(),
+        feature: "rust_decimal".to_string(),
+    })
+}
+
+/// Check if the property value is a date / time in the provided format (RFC 3339 if not
+/// provided) and within the min / max bounds (in the same format). Values with time zone offset
+/// are compared in UTC, dates without time as midnight.
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - the value belonging to the property to be validated
+/// * `format` - the chrono format string (e.g. `%Y-%m-%d`)
+/// * `min` - minimum value specified
+/// * `max` - maximum value specified
+///
+#[cfg(feature = "chrono")]
+fn check_datatype_date_time(
+    property_name: &PropertyName,
+    property_value: &str,
+    format: &Option<String>,
+    min: &Option<String>,
+    max: &Option<String>,
+) -> ValidationResult<()> {
+    use chrono::{DateTime, NaiveDate, NaiveDateTime};
+
+    let parse = |value: &str| -> ValidationResult<NaiveDateTime> {
+        let parsed = match format {
+            None => DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|date_time| date_time.naive_utc()),
+            Some(format) => DateTime::parse_from_str(value, format)
+                .map(|date_time| date_time.naive_utc())
+                .or_else(|_| NaiveDateTime::parse_from_str(value, format))
+                .or_else(|_| {
+                    NaiveDate::parse_from_str(value, format)
+                        .map(|date| date.and_hms_opt(0, 0, 0).unwrap_or_default())
+                })
+                .ok(),
+        };
+
+        parsed.ok_or_else(|| Error::DatatypeNotMatching {
+            property_name: property_name.clone(),
+            value: value.to_string(),
+            datatype: format!("datetime ({})", format.as_deref().unwrap_or("rfc3339")),
+        })
+    };
+
+    let value = parse(property_value)?;
+
+    for (bound, check_out_of_bound) in [
+        (min, min_bound as fn(NaiveDateTime, NaiveDateTime) -> bool),
+        (max, max_bound as fn(NaiveDateTime, NaiveDateTime) -> bool),
+    ] {
+        if let Some(bound) = bound {
+            if check_out_of_bound(value, parse(bound)?) {
+                return Err(Error::PropertyValueOutOfBounds {
+                    property_name: property_name.clone(),
+                    received: property_value.to_string(),
+                    expected: bound.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "chrono"))]
+fn check_datatype_date_time(
+    property_name: &PropertyName,
+    _property_value: &str,
+    _format: &Option<String>,
+    _min: &Option<String>,
+    _max: &Option<String>,
+) -> ValidationResult<()> {
+    Err(Error::DatatypeFeatureNotEnabled {
+        property_name: property_name.clone(),
+        datatype: "datetime".to_string(),
+        feature: "chrono".to_string(),
+    })
+}
+
+/// Check if the property value is an absolute URL with one of the allowed schemes (if provided)
+/// and a host (if required).
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - the value belonging to the property to be validated
+/// * `allowed_schemes` - the accepted schemes (e.g. http, https)
+/// * `require_host` - if the URL must contain a host
+///
+#[cfg(feature = "url")]
+fn check_datatype_url(
+    property_name: &PropertyName,
+    property_value: &str,
+    allowed_schemes: &Option<Vec<String>>,
+    require_host: bool,
+) -> ValidationResult<()> {
+    let invalid_url = |reason: String| Error::InvalidUrl {
+        property_name: property_name.clone(),
+        value: property_value.to_string(),
+        reason,
+    };
+
+    let url = url::Url::parse(property_value).map_err(|err| invalid_url(err.to_string()))?;
+
+    if let Some(allowed_schemes) = allowed_schemes {
+        if !allowed_schemes
+            .iter()
+            .any(|scheme| scheme.eq_ignore_ascii_case(url.scheme()))
+        {
+            return Err(invalid_url(format!(
+                "scheme '{}' is not one of {:?}",
+                url.scheme(),
+                allowed_schemes
+            )));
+        }
+    }
+
+    if require_host && url.host_str().is_none_or(str::is_empty) {
+        return Err(invalid_url("host is missing".to_string()));
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "url"))]
+fn check_datatype_url(
+    property_name: &PropertyName,
+    _property_value: &str,
+    _allowed_schemes: &Option<Vec<String>>,
+    _require_host: bool,
+) -> ValidationResult<()> {
+    Err(Error::DatatypeFeatureNotEnabled {
+        property_name: property_name.clone(),
+        datatype: "url".to_string(),
+        feature: "url".to_string(),
+    })
+}
+
+/// Check if the property value is a number with one of the units of the quantity and within the
+/// min / max bounds (which are numbers with unit as well). Values and bounds are compared after
+/// the conversion to a common unit, e.g. `90s` exceeds a max of `1m`.
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - the value belonging to the property to be validated
+/// * `min` - minimum value specified
+/// * `max` - maximum value specified
+/// * `quantity` - the accepted units
+///
+fn check_datatype_quantity(
+    property_name: &PropertyName,
+    property_value: &str,
+    min: &Option<String>,
+    max: &Option<String>,
+    quantity: &Quantity,
+) -> ValidationResult<()> {
+    parse_quantity(property_name, property_value, quantity)?;
+
+    let (number, unit) = split_unit(property_value);
+    let number = parse::<f64>(property_name, number)?;
+    check_bounds_in_unit(
+        property_name,
+        property_value,
+        (number, unit),
+        (min, max),
+        quantity,
+        None,
+    )
+}
+
+/// Parse a non negative number with unit (e.g. `1.5h`) and convert it to the base unit
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `to_parse` - value to be parsed
+/// * `quantity` - the accepted units
+///
+fn parse_quantity(
+    property_name: &PropertyName,
+    to_parse: &str,
+    quantity: &Quantity,
+) -> ValidationResult<f64> {
+    let trimmed = to_parse.trim();
+    let (number, unit) = trimmed.split_at(
+        trimmed
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(trimmed.len()),
+    );
+    let unit = unit.trim();
+
+    let factor = quantity.factor(unit);
+
+    match (number.parse::<f64>(), factor) {
+        (Ok(number), Some(factor)) => Ok(number * factor),
+        _ => Err(Error::DatatypeNotMatching {
+            property_name: property_name.clone(),
+            value: to_parse.to_string(),
+            datatype: quantity.name.to_string(),
+        }),
+    }
+}
+
+/// Parse a value to a certain datatype and throw error if parsing not possible
+///
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `to_parse` - value to be parsed into a certain T
+///
+fn parse<T: FromStr>(property_name: &PropertyName, to_parse: &str) -> Result<T, Error> {
+    product_config_core::parse(to_parse).map_err(|err| check_error(property_name, err))
+}
+
+#[cfg(test)]
+mod tests {
+    macro_rules! hashmap {
+        ($( $key: expr => $val: expr ),*) => {{
+             let mut map = ::std::collections::HashMap::new();
+             $( map.insert($key, $val); )*
+             map
+        }}
+    }
+
+    use crate::builder::PropertySpecBuilder;
+    use crate::error::Error;
+    use crate::reader::ConfigJsonReader;
+    use crate::types::{
+        CharacterClass, Datatype, JsonSchema, LengthUnit, PathKind, PropertyName, PropertyNameKind,
+        RecommendedRange, Role,
+    };
+    use crate::validation::{
+        canonical_value, check_allowed_values, check_conflicts, check_datatype, check_dependencies,
+        check_forbidden_values, check_recommended_range, check_role, check_version_removed,
+        check_version_supported_or_deprecated, is_anchored, resolve_bounds,
+    };
+    use crate::ProductConfigSpec;
+    use rstest::*;
+    use semver::Version;
+    use std::collections::HashMap;
+
+    const ENV_INTEGER_PORT_MIN_MAX: &str = "ENV_INTEGER_PORT_MIN_MAX";
+    const ENV_PROPERTY_STRING_MEMORY: &str = "ENV_PROPERTY_STRING_MEMORY";
+    const ENV_SSL_CERTIFICATE_PATH: &str = "ENV_SSL_CERTIFICATE_PATH";
+    const ENV_SSL_ENABLED: &str = "ENV_SSL_ENABLED";
+    const CONF_SSL_ENABLED: &str = "conf.ssl.enabled";
+    const ENV_ALLOWED_VALUES: &str = "ENV_ALLOWED_VALUES";
+    const ENV_VAR_FLOAT: &str = "ENV_VAR_FLOAT";
+
+    const CONFIG_FILE: &str = "env.sh";
+    const CONFIG_FILE_2: &str = "my.config";
+
+    const V_1_5_0: &str = "1.5.0";
+    const V_1_0_0: &str = "1.0.0";
+    const V_0_5_0: &str = "0.5.0";
+    const V_0_1_0: &str = "0.1.0";
+
+    fn get_conf_property_name(name: &str, file: &str) -> PropertyName {
+        PropertyName {
+            name: name.to_string(),
+            kind: PropertyNameKind::Conf(file.to_string()),
+        }
+    }
+
+    fn get_product_config() -> ProductConfigSpec {
+        ProductConfigSpec::new(ConfigJsonReader::new(
+            "data/test_config_spec.json",
+            "data/test_property_spec.json",
+        ))
+        .unwrap()
+    }
+
+    #[rstest]
+    #[case(get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), V_1_0_0, V_0_5_0, None, Ok(()))]
+    #[case(get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), V_0_1_0, V_1_0_0, Some(V_0_5_0.to_string()),
+            Err(Error::VersionNotSupported { property_name: property_name.clone(), product_version: V_0_1_0.to_string(), required_version: V_1_0_0.to_string() }))]
+    #[case(get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), V_1_5_0, V_0_5_0, Some(V_1_0_0.to_string()),
+            Err(Error::VersionDeprecated { property_name: property_name.clone(), product_version: V_1_5_0.to_string(), deprecated_version: V_1_0_0.to_string() }))]
+    #[trace]
+    fn test_check_version_supported_or_deprecated(
         #[case] property_name: PropertyName,
         #[case] product_version: &str,
-        #[case] property_version: &str,
-        #[case] deprecated_since: Option<String>,
+        #[case] property_version: &str,
+        #[case] deprecated_since: Option<String>,
+        #[case] expected: Result<(), Error>,
+    ) {
+        let result = check_version_supported_or_deprecated(
+            &property_name,
+            &Version::parse(product_version).unwrap(),
+            property_version,
+            &deprecated_since,
+        );
+
+        assert_eq!(result, expected)
+    }
+
+    #[rstest]
+    #[case(V_0_5_0, None, None, Ok(()))]
+    #[case(V_0_5_0, Some(V_1_0_0.to_string()), None, Ok(()))]
+    #[case(V_1_0_0, Some(V_1_0_0.to_string()), Some(vec![vec![get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE_2), get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE)]]),
+            Err(Error::VersionRemoved { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), product_version: V_1_0_0.to_string(), removed_version: V_1_0_0.to_string(), replaced_by: vec![ENV_PROPERTY_STRING_MEMORY.to_string()] }))]
+    #[case(V_1_5_0, Some(V_1_0_0.to_string()), None,
+            Err(Error::VersionRemoved { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), product_version: V_1_5_0.to_string(), removed_version: V_1_0_0.to_string(), replaced_by: vec![] }))]
+    #[trace]
+    fn test_check_version_removed(
+        #[case] product_version: &str,
+        #[case] removed_in: Option<String>,
+        #[case] deprecated_for: Option<Vec<Vec<PropertyName>>>,
         #[case] expected: Result<(), Error>,
     ) {
-        let result = check_version_supported_or_deprecated(
-            &property_name,
+        let result = check_version_removed(
+            &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
             &Version::parse(product_version).unwrap(),
-            property_version,
-            &deprecated_since,
+            &removed_in,
+            &deprecated_for,
         );
 
         assert_eq!(result, expected)
@@ -731,115 +2747,481 @@ mod tests {
     }
 
     #[rstest]
+    #[case(Some("${heap.max}"), hashmap!{ "heap.max".to_string() => "1024".to_string() }, Some("1024".to_string()))]
+    #[case(Some("${ heap.max }"), hashmap!{ "heap.max".to_string() => "1024".to_string() }, Some("1024".to_string()))]
+    #[case(Some("${heap.max}"), HashMap::new(), None)]
+    #[case(Some("2048"), hashmap!{ "heap.max".to_string() => "1024".to_string() }, Some("2048".to_string()))]
+    #[case(None, hashmap!{ "heap.max".to_string() => "1024".to_string() }, None)]
+    #[trace]
+    fn test_resolve_bounds(
+        #[case] max: Option<&str>,
+        #[case] properties: HashMap<String, String>,
+        #[case] expected: Option<String>,
+    ) {
+        let datatype = Datatype::Integer {
+            min: Some("1".to_string()),
+            max: max.map(str::to_string),
+            unit: None,
+            accepted_units: None,
+            default_unit: None,
+            multiple_of: None,
+        };
+
+        let resolved = resolve_bounds(&datatype, &properties);
+
+        assert_eq!(
+            resolved.bounds(),
+            (Some(&"1".to_string()), expected.as_ref())
+        );
+    }
+
+    #[rstest]
+    #[case(hashmap!{ "sasl.plain.enabled".to_string() => "true".to_string() }, Ok(()))]
+    #[case(
+        hashmap!{
+            "sasl.plain.enabled".to_string() => "true".to_string(),
+            "kerberos.realm".to_string() => "EXAMPLE.COM".to_string()
+        },
+        Err(Error::PropertyConflict {
+            property_name: get_conf_property_name("sasl.plain.enabled", CONFIG_FILE),
+            conflicting_property: "kerberos.realm".to_string()
+        })
+    )]
+    #[case(
+        hashmap!{
+            "sasl.plain.enabled".to_string() => "true".to_string(),
+            "ssl.enabled".to_string() => "true".to_string()
+        },
+        Err(Error::PropertyConflict {
+            property_name: get_conf_property_name("sasl.plain.enabled", CONFIG_FILE),
+            conflicting_property: "ssl.enabled".to_string()
+        })
+    )]
+    #[trace]
+    fn test_check_conflicts(
+        #[case] user_properties: HashMap<String, String>,
+        #[case] expected: Result<(), Error>,
+    ) {
+        let property_name = get_conf_property_name("sasl.plain.enabled", CONFIG_FILE);
+        let property_spec = PropertySpecBuilder::new(Datatype::Bool, V_0_1_0)
+            .property_name(property_name.clone())
+            .conflicts_with(get_conf_property_name("kerberos.*", CONFIG_FILE))
+            .conflicts_with(get_conf_property_name("ssl.enabled", CONFIG_FILE))
+            .conflicts_with(get_conf_property_name("sasl.plain.enabled", CONFIG_FILE_2))
+            .build();
+
+        let result = check_conflicts(&property_name, &property_spec, &user_properties);
+
+        assert_eq!(result, expected)
+    }
+
+    #[rstest]
+    #[case(
+        &get_conf_property_name(ENV_SSL_CERTIFICATE_PATH, CONFIG_FILE),
+        hashmap!{
+            ENV_SSL_CERTIFICATE_PATH.to_string() => "some/path/to/certificate".to_string()
+        },
+        Err(Error::PropertyDependencyMissing {
+            property_name: get_conf_property_name(ENV_SSL_CERTIFICATE_PATH, CONFIG_FILE),
+            dependency: vec![
+                PropertyName { name: ENV_SSL_ENABLED.to_string(), kind: PropertyNameKind::Conf(CONFIG_FILE.to_string()) },
+                PropertyName { name: CONF_SSL_ENABLED.to_string(), kind: PropertyNameKind::Conf(CONFIG_FILE_2.to_string()) }
+            ]
+        })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_SSL_CERTIFICATE_PATH, CONFIG_FILE),
+        hashmap!{
+            ENV_SSL_CERTIFICATE_PATH.to_string() => "some/path/to/certificate".to_string(),
+            CONF_SSL_ENABLED.to_string() => "true".to_string()
+        },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_SSL_CERTIFICATE_PATH, CONFIG_FILE),
+        hashmap!{
+            ENV_SSL_CERTIFICATE_PATH.to_string() => "some/path/to/certificate".to_string(),
+            CONF_SSL_ENABLED.to_string() => "false".to_string()
+        },
+        Err(Error::PropertyDependencyValueInvalid {
+            property_name: get_conf_property_name(ENV_SSL_CERTIFICATE_PATH, CONFIG_FILE),
+            dependency: CONF_SSL_ENABLED.to_string(),
+            user_value: "false".to_string(),
+            required_value: "true".to_string()
+        })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_SSL_CERTIFICATE_PATH, CONFIG_FILE),
+        hashmap!{
+            ENV_SSL_CERTIFICATE_PATH.to_string() => "some/path/to/certificate".to_string(),
+            ENV_SSL_ENABLED.to_string() => "false".to_string()
+        },
+        Err(Error::PropertyDependencyValueInvalid {
+            property_name: get_conf_property_name(ENV_SSL_CERTIFICATE_PATH, CONFIG_FILE),
+            dependency: "ENV_SSL_ENABLED".to_string(),
+            user_value: "false".to_string(),
+            required_value: "true".to_string()
+        })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_SSL_CERTIFICATE_PATH, CONFIG_FILE),
+        hashmap!{
+            ENV_SSL_CERTIFICATE_PATH.to_string() => "some/path/to/certificate".to_string(),
+            ENV_SSL_ENABLED.to_string() => "true".to_string()
+        },
+        Ok(())
+    )]
+    #[trace]
+    fn test_check_dependencies(
+        #[case] property_name: &PropertyName,
+        #[case] user_properties: HashMap<String, String>,
+        #[case] expected: Result<(), Error>,
+    ) {
+        let product_config = get_product_config();
+        let property_spec = product_config.property_specs.get(property_name).unwrap();
+
+        let result = check_dependencies(property_name, property_spec, &user_properties);
+
+        assert_eq!(result, expected)
+    }
+
+    const MIN_PORT: &str = "1";
+    const MAX_PORT: &str = "65535";
+    const PORT_CORRECT: &str = "12345";
+    const PORT_BAD_DATATYPE: &str = "123aaa";
+    const PORT_OUT_OF_BOUNDS: &str = "77777";
+
+    const MEMORY_CORRECT_MB: &str = "512mb";
+    const MEMORY_CORRECT_GB: &str = "2gb";
+    const MEMORY_MISSING_UNIT: &str = "512";
+
+    const FLOAT_CORRECT: &str = "87.2123";
+    const FLOAT_BAD: &str = "100,0";
+
+    #[rstest]
+    #[case(
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        PORT_CORRECT,
+        &Datatype::Integer{ min: Some(MIN_PORT.to_string()), max: Some(MAX_PORT.to_string()), unit: Some("port".to_string()), accepted_units: None, default_unit:None, multiple_of: None },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        PORT_BAD_DATATYPE,
+        &Datatype::Integer{ min: Some(MIN_PORT.to_string()), max: Some(MAX_PORT.to_string()), unit: Some("port".to_string()), accepted_units: None, default_unit:None, multiple_of: None },
+        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), value: PORT_BAD_DATATYPE.to_string(), datatype: "i64".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        PORT_OUT_OF_BOUNDS,
+        &Datatype::Integer{ min: Some(MIN_PORT.to_string()), max: Some(MAX_PORT.to_string()), unit: Some("port".to_string()), accepted_units: None, default_unit:None, multiple_of: None },
+        Err(Error::PropertyValueOutOfBounds { property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), received: PORT_OUT_OF_BOUNDS.to_string(), expected: MAX_PORT.to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE),
+        MEMORY_CORRECT_MB,
+        &Datatype::String{ min: None, max: None, unit: Some("memory".to_string()), accepted_units: None, default_unit:None, length_unit: None },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE),
+        MEMORY_CORRECT_GB,
+        &Datatype::String{ min: None, max: None, unit: Some("memory".to_string()), accepted_units: None, default_unit:None, length_unit: None },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE),
+        MEMORY_MISSING_UNIT,
+        &Datatype::String{ min: None, max: None, unit: Some("memory".to_string()), accepted_units: None, default_unit:None, length_unit: None },
+        Err(Error::DatatypeRegexNotMatching { property_name: get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE), value: MEMORY_MISSING_UNIT.to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        FLOAT_CORRECT,
+        &Datatype::Float{ min: Some("0.0".to_string()), max: Some("100.0".to_string()), unit: None, accepted_units: None, default_unit:None },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        FLOAT_BAD,
+        &Datatype::Float{ min: Some("0.0".to_string()), max: Some("100.0".to_string()), unit: None, accepted_units: None, default_unit:None },
+        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), value: FLOAT_BAD.to_string(), datatype: "f64".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "500ms",
+        &Datatype::Duration{ min: Some("100ms".to_string()), max: Some("1m".to_string()) },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "1.5s",
+        &Datatype::Duration{ min: Some("1s".to_string()), max: None },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "90s",
+        &Datatype::Duration{ min: None, max: Some("1m".to_string()) },
+        Err(Error::PropertyValueOutOfBoundsInUnit { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), received: "90s".to_string(), received_in_unit: "1.5m".to_string(), expected: "1m".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "50ms",
+        &Datatype::Duration{ min: Some("0.1s".to_string()), max: None },
+        Err(Error::PropertyValueOutOfBoundsInUnit { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), received: "50ms".to_string(), received_in_unit: "0.05s".to_string(), expected: "0.1s".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "10",
+        &Datatype::Duration{ min: None, max: None },
+        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), value: "10".to_string(), datatype: "duration".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "2w",
+        &Datatype::Duration{ min: None, max: None },
+        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), value: "2w".to_string(), datatype: "duration".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE),
+        "512mb",
+        &Datatype::MemorySize{ min: Some("256mb".to_string()), max: Some("2gb".to_string()) },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE),
+        "1G",
+        &Datatype::MemorySize{ min: Some("1024m".to_string()), max: Some("1g".to_string()) },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE),
+        "3000gb",
+        &Datatype::MemorySize{ min: Some("256mb".to_string()), max: Some("2gb".to_string()) },
+        Err(Error::PropertyValueOutOfBoundsInUnit { property_name: get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE), received: "3000gb".to_string(), received_in_unit: "3000gb".to_string(), expected: "2gb".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE),
+        "128 mb",
+        &Datatype::MemorySize{ min: Some("256mb".to_string()), max: None },
+        Err(Error::PropertyValueOutOfBoundsInUnit { property_name: get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE), received: "128 mb".to_string(), received_in_unit: "128mb".to_string(), expected: "256mb".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE),
+        MEMORY_MISSING_UNIT,
+        &Datatype::MemorySize{ min: None, max: None },
+        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE), value: MEMORY_MISSING_UNIT.to_string(), datatype: "memory size".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        PORT_CORRECT,
+        &Datatype::Port{ allow_privileged: false, range: false },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        "80",
+        &Datatype::Port{ allow_privileged: false, range: false },
+        Err(Error::PrivilegedPortNotAllowed { property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), port: 80 })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        "80",
+        &Datatype::Port{ allow_privileged: true, range: false },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        PORT_OUT_OF_BOUNDS,
+        &Datatype::Port{ allow_privileged: false, range: false },
+        Err(Error::PropertyValueOutOfBounds { property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), received: PORT_OUT_OF_BOUNDS.to_string(), expected: MAX_PORT.to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        "9000-9010",
+        &Datatype::Port{ allow_privileged: false, range: true },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        "9000-9010",
+        &Datatype::Port{ allow_privileged: false, range: false },
+        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), value: "9000-9010".to_string(), datatype: "port".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        "9010-9000",
+        &Datatype::Port{ allow_privileged: false, range: true },
+        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), value: "9010-9000".to_string(), datatype: "port range".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "192.168.0.1",
+        &Datatype::IpAddress{ v4_only: false, v6_only: false, allow_cidr: false },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "2001:db8::7",
+        &Datatype::IpAddress{ v4_only: false, v6_only: true, allow_cidr: false },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "2001:db8::7",
+        &Datatype::IpAddress{ v4_only: true, v6_only: false, allow_cidr: false },
+        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), value: "2001:db8::7".to_string(), datatype: "ipv4".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "256.0.0.1",
+        &Datatype::IpAddress{ v4_only: false, v6_only: false, allow_cidr: false },
+        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), value: "256.0.0.1".to_string(), datatype: "ip".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "10.0.0.0/8",
+        &Datatype::IpAddress{ v4_only: false, v6_only: false, allow_cidr: true },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "2001:db8::/129",
+        &Datatype::IpAddress{ v4_only: false, v6_only: false, allow_cidr: true },
+        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), value: "2001:db8::/129".to_string(), datatype: "ip cidr".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "10.0.0.0/8",
+        &Datatype::IpAddress{ v4_only: false, v6_only: false, allow_cidr: false },
+        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), value: "10.0.0.0/8".to_string(), datatype: "ip".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "data/dir",
+        &Datatype::Path{ must_be_absolute: false, must_exist: false, kind: Some(PathKind::Dir) },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "data/dir",
+        &Datatype::Path{ must_be_absolute: true, must_exist: false, kind: None },
+        Err(Error::InvalidPath { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), value: "data/dir".to_string(), reason: "path must be absolute".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "data/test_config_spec.json",
+        &Datatype::Path{ must_be_absolute: false, must_exist: true, kind: Some(PathKind::File) },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "data/test_config_spec.json",
+        &Datatype::Path{ must_be_absolute: false, must_exist: true, kind: Some(PathKind::Dir) },
+        Err(Error::InvalidPath { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), value: "data/test_config_spec.json".to_string(), reason: "directory does not exist".to_string() })
+    )]
     #[case(
-        &get_conf_property_name(ENV_SSL_CERTIFICATE_PATH, CONFIG_FILE),
-        hashmap!{
-            ENV_SSL_CERTIFICATE_PATH.to_string() => "some/path/to/certificate".to_string()
-        },
-        Err(Error::PropertyDependencyMissing {
-            property_name: get_conf_property_name(ENV_SSL_CERTIFICATE_PATH, CONFIG_FILE),
-            dependency: vec![
-                PropertyName { name: ENV_SSL_ENABLED.to_string(), kind: PropertyNameKind::Conf(CONFIG_FILE.to_string()) },
-                PropertyName { name: CONF_SSL_ENABLED.to_string(), kind: PropertyNameKind::Conf(CONFIG_FILE_2.to_string()) }
-            ]
-        })
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "data/missing",
+        &Datatype::Path{ must_be_absolute: false, must_exist: true, kind: None },
+        Err(Error::InvalidPath { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), value: "data/missing".to_string(), reason: "path does not exist".to_string() })
     )]
     #[case(
-        &get_conf_property_name(ENV_SSL_CERTIFICATE_PATH, CONFIG_FILE),
-        hashmap!{
-            ENV_SSL_CERTIFICATE_PATH.to_string() => "some/path/to/certificate".to_string(),
-            ENV_SSL_ENABLED.to_string() => "false".to_string()
-        },
-        Err(Error::PropertyDependencyValueInvalid {
-            property_name: get_conf_property_name(ENV_SSL_CERTIFICATE_PATH, CONFIG_FILE),
-            dependency: "ENV_SSL_ENABLED".to_string(),
-            user_value: "false".to_string(),
-            required_value: "true".to_string()
-        })
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "^topic-[a-z]+$",
+        &Datatype::Regex,
+        Ok(())
     )]
     #[case(
-        &get_conf_property_name(ENV_SSL_CERTIFICATE_PATH, CONFIG_FILE),
-        hashmap!{
-            ENV_SSL_CERTIFICATE_PATH.to_string() => "some/path/to/certificate".to_string(),
-            ENV_SSL_ENABLED.to_string() => "true".to_string()
-        },
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        r#"{"host": "localhost", "port": 9092}"#,
+        &Datatype::Json{ schema: Some(JsonSchema(serde_json::json!({"required": ["host"]}))) },
         Ok(())
     )]
-    #[trace]
-    fn test_check_dependencies(
-        #[case] property_name: &PropertyName,
-        #[case] user_properties: HashMap<String, String>,
-        #[case] expected: Result<(), Error>,
-    ) {
-        let product_config = get_product_config();
-        let property_spec = product_config.property_specs.get(&property_name).unwrap();
-
-        let result = check_dependencies(&property_name, property_spec, &user_properties);
-
-        assert_eq!(result, expected)
-    }
-
-    const MIN_PORT: &str = "1";
-    const MAX_PORT: &str = "65535";
-    const PORT_CORRECT: &str = "12345";
-    const PORT_BAD_DATATYPE: &str = "123aaa";
-    const PORT_OUT_OF_BOUNDS: &str = "77777";
-
-    const MEMORY_CORRECT_MB: &str = "512mb";
-    const MEMORY_CORRECT_GB: &str = "2gb";
-    const MEMORY_MISSING_UNIT: &str = "512";
-
-    const FLOAT_CORRECT: &str = "87.2123";
-    const FLOAT_BAD: &str = "100,0";
-
-    #[rstest]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        r#"{"port": 9092}"#,
+        &Datatype::Json{ schema: Some(JsonSchema(serde_json::json!({"required": ["host"]}))) },
+        Err(Error::InvalidJson { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), value: r#"{"port": 9092}"#.to_string(), reason: "/: missing required property 'host'".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "{port}",
+        &Datatype::Json{ schema: None },
+        Err(Error::InvalidJson { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), value: "{port}".to_string(), reason: "key must be a string at line 1 column 2".to_string() })
+    )]
     #[case(
         &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
-        PORT_CORRECT,
-        &Datatype::Integer{ min: Some(MIN_PORT.to_string()), max: Some(MAX_PORT.to_string()), unit: Some("port".to_string()), accepted_units: None, default_unit:None },
+        "18446744073709551615",
+        &Datatype::UnsignedInteger{ min: None, max: None, unit: None, accepted_units: None, default_unit:None },
         Ok(())
     )]
     #[case(
         &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
-        PORT_BAD_DATATYPE,
-        &Datatype::Integer{ min: Some(MIN_PORT.to_string()), max: Some(MAX_PORT.to_string()), unit: Some("port".to_string()), accepted_units: None, default_unit:None },
-        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), value: PORT_BAD_DATATYPE.to_string(), datatype: "i64".to_string() })
+        "-1",
+        &Datatype::UnsignedInteger{ min: None, max: None, unit: None, accepted_units: None, default_unit:None },
+        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), value: "-1".to_string(), datatype: "u64".to_string() })
     )]
     #[case(
         &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
-        PORT_OUT_OF_BOUNDS,
-        &Datatype::Integer{ min: Some(MIN_PORT.to_string()), max: Some(MAX_PORT.to_string()), unit: Some("port".to_string()), accepted_units: None, default_unit:None },
-        Err(Error::PropertyValueOutOfBounds { property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), received: PORT_OUT_OF_BOUNDS.to_string(), expected: MAX_PORT.to_string() })
+        "10",
+        &Datatype::UnsignedInteger{ min: Some("100".to_string()), max: None, unit: None, accepted_units: None, default_unit:None },
+        Err(Error::PropertyValueOutOfBounds { property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), received: "10".to_string(), expected: "100".to_string() })
     )]
     #[case(
-        &get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE),
-        MEMORY_CORRECT_MB,
-        &Datatype::String{ min: None, max: None, unit: Some("memory".to_string()), accepted_units: None, default_unit:None },
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        "9223372036854775808",
+        &Datatype::Integer{ min: Some("0".to_string()), max: None, unit: None, accepted_units: None, default_unit:None, multiple_of: None },
         Ok(())
     )]
     #[case(
-        &get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE),
-        MEMORY_CORRECT_GB,
-        &Datatype::String{ min: None, max: None, unit: Some("memory".to_string()), accepted_units: None, default_unit:None },
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        "9223372036854775808",
+        &Datatype::Integer{ min: None, max: Some("9223372036854775807".to_string()), unit: None, accepted_units: None, default_unit:None, multiple_of: None },
+        Err(Error::PropertyValueOutOfBounds { property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), received: "9223372036854775808".to_string(), expected: "9223372036854775807".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        "100",
+        &Datatype::Integer{ min: None, max: Some("100000000000000000000".to_string()), unit: None, accepted_units: None, default_unit:None, multiple_of: None },
         Ok(())
     )]
     #[case(
-        &get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE),
-        MEMORY_MISSING_UNIT,
-        &Datatype::String{ min: None, max: None, unit: Some("memory".to_string()), accepted_units: None, default_unit:None },
-        Err(Error::DatatypeRegexNotMatching { property_name: get_conf_property_name(ENV_PROPERTY_STRING_MEMORY, CONFIG_FILE), value: MEMORY_MISSING_UNIT.to_string() })
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "80%",
+        &Datatype::Percent{ as_fraction: true },
+        Ok(())
     )]
     #[case(
         &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
-        FLOAT_CORRECT,
-        &Datatype::Float{ min: Some("0.0".to_string()), max: Some("100.0".to_string()), unit: None, accepted_units: None, default_unit:None },
+        "80",
+        &Datatype::Percent{ as_fraction: true },
+        Err(Error::PropertyValueOutOfBounds { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), received: "80".to_string(), expected: "0.0 - 1.0".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
+        "80",
+        &Datatype::Percent{ as_fraction: false },
         Ok(())
     )]
     #[case(
         &get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE),
-        FLOAT_BAD,
-        &Datatype::Float{ min: Some("0.0".to_string()), max: Some("100.0".to_string()), unit: None, accepted_units: None, default_unit:None },
-        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), value: FLOAT_BAD.to_string(), datatype: "f64".to_string() })
+        "eighty",
+        &Datatype::Percent{ as_fraction: false },
+        Err(Error::DatatypeNotMatching { property_name: get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE), value: "eighty".to_string(), datatype: "percent".to_string() })
+    )]
+    #[case(
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        "4096",
+        &Datatype::Integer{ min: None, max: None, unit: None, accepted_units: None, default_unit:None, multiple_of: Some("1024".to_string()) },
+        Ok(())
+    )]
+    #[case(
+        &get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        "4000",
+        &Datatype::Integer{ min: None, max: None, unit: None, accepted_units: None, default_unit:None, multiple_of: Some("1024".to_string()) },
+        Err(Error::PropertyValueNotMultipleOf { property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE), value: "4000".to_string(), multiple_of: "1024".to_string() })
     )]
     #[trace]
     fn test_check_datatype(
@@ -850,11 +3232,441 @@ mod tests {
     ) {
         let config_spec_units = get_product_config().config_spec.units;
 
-        let result = check_datatype(&config_spec_units, property_name, property_value, &datatype);
+        let result = check_datatype(&config_spec_units, property_name, property_value, datatype);
+
+        assert_eq!(result, expected)
+    }
+
+    #[rstest]
+    #[case("512", Ok(()))]
+    #[case("512mb", Ok(()))]
+    #[case("1.5gb", Ok(()))]
+    #[case("8gb", Err(Error::PropertyValueOutOfBoundsInUnit {
+        property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        received: "8gb".to_string(),
+        received_in_unit: "8192mb".to_string(),
+        expected: "4096mb".to_string(),
+    }))]
+    #[case("1tb", Err(Error::UnitNotAccepted {
+        property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        value: "1tb".to_string(),
+        unit: "tb".to_string(),
+        accepted_units: vec!["mb".to_string(), "gb".to_string()],
+    }))]
+    #[case("0.5gb", Ok(()))]
+    #[case("0.5mb", Err(Error::DatatypeNotMatching {
+        property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        value: "0.5mb".to_string(),
+        datatype: "integer (mb)".to_string(),
+    }))]
+    #[case("0.0001gb", Err(Error::DatatypeNotMatching {
+        property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        value: "0.0001gb".to_string(),
+        datatype: "integer (mb)".to_string(),
+    }))]
+    #[trace]
+    fn test_check_datatype_with_units(
+        #[case] property_value: &str,
+        #[case] expected: Result<(), Error>,
+    ) {
+        let property_name = get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE);
+        let datatype = Datatype::Integer {
+            min: Some("128".to_string()),
+            max: Some("4096".to_string()),
+            unit: None,
+            accepted_units: Some(vec!["mb".to_string(), "gb".to_string()]),
+            default_unit: Some("mb".to_string()),
+            multiple_of: None,
+        };
+
+        let result = check_datatype(&HashMap::new(), &property_name, property_value, &datatype);
+
+        assert_eq!(result, expected)
+    }
+
+    #[rstest]
+    #[case("1.5h", Ok(()))]
+    #[case("90", Ok(()))]
+    #[case("2d", Err(Error::PropertyValueOutOfBoundsInUnit {
+        property_name: get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE),
+        received: "2d".to_string(),
+        received_in_unit: "2d".to_string(),
+        expected: "1d".to_string(),
+    }))]
+    #[trace]
+    fn test_check_datatype_float_with_units(
+        #[case] property_value: &str,
+        #[case] expected: Result<(), Error>,
+    ) {
+        let property_name = get_conf_property_name(ENV_INTEGER_PORT_MIN_MAX, CONFIG_FILE);
+        let datatype = Datatype::Float {
+            min: None,
+            max: Some("1d".to_string()),
+            unit: None,
+            accepted_units: Some(vec!["m".to_string(), "h".to_string(), "d".to_string()]),
+            default_unit: None,
+        };
+
+        let result = check_datatype(&HashMap::new(), &property_name, property_value, &datatype);
 
         assert_eq!(result, expected)
     }
 
+    #[rstest]
+    #[case(Datatype::Percent{ as_fraction: true }, false, "80%", Some("0.8"))]
+    #[case(Datatype::Percent{ as_fraction: true }, false, "0.8", Some("0.8"))]
+    #[case(Datatype::Percent{ as_fraction: false }, false, "0.07%", Some("0.07"))]
+    #[case(Datatype::Percent{ as_fraction: false }, false, "7", Some("7"))]
+    #[case(Datatype::Percent{ as_fraction: false }, false, "150%", None)]
+    #[case(Datatype::Enum{ values: vec!["GZIP".to_string()], case_insensitive: true }, false, "gzip", Some("GZIP"))]
+    #[case(Datatype::Integer{ min: None, max: None, unit: None, accepted_units: None, default_unit: None, multiple_of: None }, true, "0x1F", Some("31"))]
+    #[case(Datatype::Integer{ min: None, max: None, unit: None, accepted_units: None, default_unit: None, multiple_of: None }, true, "-0o17", Some("-15"))]
+    #[case(Datatype::Integer{ min: None, max: None, unit: None, accepted_units: None, default_unit: None, multiple_of: None }, true, "0B1010", Some("10"))]
+    #[case(Datatype::Integer{ min: None, max: None, unit: None, accepted_units: None, default_unit: None, multiple_of: None }, true, "0x1G", None)]
+    #[case(Datatype::Integer{ min: None, max: None, unit: None, accepted_units: None, default_unit: None, multiple_of: None }, true, "31", None)]
+    #[case(Datatype::Integer{ min: None, max: None, unit: None, accepted_units: None, default_unit: None, multiple_of: None }, false, "0x1F", None)]
+    #[case(Datatype::Bool, false, "true", None)]
+    #[trace]
+    fn test_canonical_value(
+        #[case] datatype: Datatype,
+        #[case] radix_prefixes: bool,
+        #[case] value: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let property = PropertySpecBuilder::new(datatype, V_0_1_0)
+            .radix_prefixes(radix_prefixes)
+            .build();
+
+        assert_eq!(canonical_value(&property, value).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case("^[0-9]+$", true)]
+    #[case(r"\A[0-9]+\z", true)]
+    #[case("(?i)^[a-z]+$", true)]
+    #[case("[0-9]+", false)]
+    #[case("^[0-9]+", false)]
+    #[case(r"^[0-9]+\$", false)]
+    #[trace]
+    fn test_is_anchored(#[case] pattern: &str, #[case] expected: bool) {
+        assert_eq!(is_anchored(pattern), expected);
+    }
+
+    #[test]
+    fn test_check_datatype_regex_compile_error() {
+        let property_name = get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE);
+
+        let result = check_datatype(&HashMap::new(), &property_name, "topic-(", &Datatype::Regex);
+
+        match result {
+            Err(Error::InvalidRegex { value, reason, .. }) => {
+                assert_eq!(value, "topic-(");
+                // the reason is the message of the regex engine
+                #[cfg(feature = "regex")]
+                assert!(reason.contains("unclosed group"), "{}", reason);
+                #[cfg(not(feature = "regex"))]
+                assert!(!reason.is_empty());
+            }
+            other => panic!("expected invalid regex error, got {:?}", other),
+        }
+    }
+
+    #[rstest]
+    #[case("correct-Horse-battery-9", vec![])]
+    #[case("Sh0rt!", vec![
+        "at least 12 characters required, got 6",
+        "estimated entropy of 39.4 bits is below 80 bits (use a longer password or more character classes)",
+    ])]
+    #[case("alllowercaseletters", vec!["at least one uppercase letter required", "at least one digit required"])]
+    #[case("aaaaaaaaaaaA1", vec!["estimated entropy of 77.4 bits is below 80 bits (use a longer password or more character classes)"])]
+    #[trace]
+    fn test_check_datatype_password(#[case] value: &str, #[case] expected: Vec<&str>) {
+        let property_name = get_conf_property_name(ENV_VAR_FLOAT, CONFIG_FILE);
+        let datatype = Datatype::Password {
+            min_length: Some(12),
+            required_classes: Some(vec![CharacterClass::Uppercase, CharacterClass::Digit]),
+            min_entropy: Some(80),
+        };
+
+        let result = check_datatype(&HashMap::new(), &property_name, value, &datatype);
+
+        if expected.is_empty() {
+            assert_eq!(result, Ok(()));
+        } else {
+            assert_eq!(
+                result,
+                Err(Error::PasswordTooWeak {
+                    property_name,
+                    reasons: expected.iter().map(|reason| reason.to_string()).collect(),
+                })
+            );
+        }
+    }
+
+    #[cfg(feature = "url")]
+    #[rstest]
+    #[case("https://stackable.tech/blog", Some(vec!["http", "https"]), true, None)]
+    #[case("HTTPS://stackable.tech", Some(vec!["https"]), true, None)]
+    #[case("file:///tmp/data", None, false, None)]
+    #[case("file:///tmp/data", None, true, Some("host is missing"))]
+    #[case("ftp://stackable.tech", Some(vec!["http", "https"]), false, Some("scheme 'ftp' is not one of [\"http\", \"https\"]"))]
+    #[case("stackable.tech", None, false, Some("relative URL without a base"))]
+    #[trace]
+    fn test_check_datatype_url(
+        #[case] value: &str,
+        #[case] allowed_schemes: Option<Vec<&str>>,
+        #[case] require_host: bool,
+        #[case] expected_reason: Option<&str>,
+    ) {
+        let property_name = get_conf_property_name("url", CONFIG_FILE);
+        let datatype = Datatype::Url {
+            allowed_schemes: allowed_schemes
+                .map(|schemes| schemes.iter().map(|scheme| scheme.to_string()).collect()),
+            require_host,
+        };
+
+        let result = check_datatype(&HashMap::new(), &property_name, value, &datatype);
+
+        assert_eq!(
+            result,
+            match expected_reason {
+                None => Ok(()),
+                Some(reason) => Err(Error::InvalidUrl {
+                    property_name: property_name.clone(),
+                    value: value.to_string(),
+                    reason: reason.to_string(),
+                }),
+            }
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[rstest]
+    #[case("2024-05-01T10:00:00Z", None, Some("2024-01-01T00:00:00+00:00"), None, Ok(()))]
+    #[case("2024-05-01T10:00:00+02:00", None, None, Some("2024-05-01T08:30:00Z"), Ok(()))]
+    #[case("2024-05-01T10:00:00+01:00", None, None, Some("2024-05-01T08:30:00Z"), Err(Error::PropertyValueOutOfBounds { property_name: get_conf_property_name("expiry", CONFIG_FILE), received: "2024-05-01T10:00:00+01:00".to_string(), expected: "2024-05-01T08:30:00Z".to_string() }))]
+    #[case("2024-05-01", Some("%Y-%m-%d"), Some("2024-06-01"), None, Err(Error::PropertyValueOutOfBounds { property_name: get_conf_property_name("expiry", CONFIG_FILE), received: "2024-05-01".to_string(), expected: "2024-06-01".to_string() }))]
+    #[case("01.05.2024 10:00", Some("%d.%m.%Y %H:%M"), None, None, Ok(()))]
+    #[case("2024-05-01", None, None, None, Err(Error::DatatypeNotMatching { property_name: get_conf_property_name("expiry", CONFIG_FILE), value: "2024-05-01".to_string(), datatype: "datetime (rfc3339)".to_string() }))]
+    #[trace]
+    fn test_check_datatype_date_time(
+        #[case] value: &str,
+        #[case] format: Option<&str>,
+        #[case] min: Option<&str>,
+        #[case] max: Option<&str>,
+        #[case] expected: Result<(), Error>,
+    ) {
+        let datatype = Datatype::DateTime {
+            format: format.map(str::to_string),
+            min: min.map(str::to_string),
+            max: max.map(str::to_string),
+        };
+
+        let result = check_datatype(
+            &HashMap::new(),
+            &get_conf_property_name("expiry", CONFIG_FILE),
+            value,
+            &datatype,
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[cfg(not(feature = "chrono"))]
+    #[test]
+    fn test_check_datatype_date_time_without_feature() {
+        let property_name = get_conf_property_name("expiry", CONFIG_FILE);
+        let datatype = Datatype::DateTime {
+            format: None,
+            min: None,
+            max: None,
+        };
+
+        let result = check_datatype(
+            &HashMap::new(),
+            &property_name,
+            "2024-05-01T10:00:00Z",
+            &datatype,
+        );
+
+        assert_eq!(
+            result,
+            Err(Error::DatatypeFeatureNotEnabled {
+                property_name,
+                datatype: "datetime".to_string(),
+                feature: "chrono".to_string(),
+            })
+        );
+    }
+
+    #[rstest]
+    #[case("abcd", None, Some("3"), Err(4))]
+    #[case("äöü", None, Some("3"), Err(6))]
+    #[case("äöü", Some(LengthUnit::Chars), Some("3"), Ok(()))]
+    #[case("äöü", Some(LengthUnit::Chars), Some("4"), Ok(()))]
+    #[case("ab", Some(LengthUnit::Chars), None, Err(2))]
+    #[trace]
+    fn test_check_datatype_string_length(
+        #[case] value: &str,
+        #[case] length_unit: Option<LengthUnit>,
+        #[case] max: Option<&str>,
+        #[case] expected: Result<(), usize>,
+    ) {
+        let property_name = get_conf_property_name("name", CONFIG_FILE);
+        let datatype = Datatype::String {
+            min: Some("3".to_string()),
+            max: max.map(str::to_string),
+            unit: Some("text".to_string()),
+            accepted_units: None,
+            default_unit: None,
+            length_unit,
+        };
+        let config_spec_units = get_product_config().config_spec.units;
+
+        let result = check_datatype(&config_spec_units, &property_name, value, &datatype);
+
+        assert_eq!(
+            result,
+            expected.map_err(|length| Error::StringLengthOutOfBounds {
+                property_name: property_name.clone(),
+                value: value.to_string(),
+                length,
+                length_unit: length_unit.unwrap_or(LengthUnit::Bytes),
+                expected: if length < 3 { "3" } else { max.unwrap() }.to_string(),
+            })
+        );
+    }
+
+    #[cfg(not(feature = "unicode-segmentation"))]
+    #[test]
+    fn test_check_datatype_string_length_graphemes_without_feature() {
+        let property_name = get_conf_property_name("name", CONFIG_FILE);
+        let datatype = Datatype::String {
+            min: None,
+            max: Some("2".to_string()),
+            unit: Some("text".to_string()),
+            accepted_units: None,
+            default_unit: None,
+            length_unit: Some(LengthUnit::Graphemes),
+        };
+        let config_spec_units = get_product_config().config_spec.units;
+
+        let result = check_datatype(&config_spec_units, &property_name, "ab", &datatype);
+
+        assert_eq!(
+            result,
+            Err(Error::DatatypeFeatureNotEnabled {
+                property_name,
+                datatype: "string (graphemes)".to_string(),
+                feature: "unicode-segmentation".to_string(),
+            })
+        );
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn test_check_datatype_string_length_graphemes() {
+        let property_name = get_conf_property_name("name", CONFIG_FILE);
+        let datatype = Datatype::String {
+            min: None,
+            max: Some("2".to_string()),
+            unit: Some("text".to_string()),
+            accepted_units: None,
+            default_unit: None,
+            length_unit: Some(LengthUnit::Graphemes),
+        };
+        let config_spec_units = get_product_config().config_spec.units;
+
+        // "e" with combining acute accent is a single grapheme but two chars
+        let result = check_datatype(&config_spec_units, &property_name, "e\u{301}e", &datatype);
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[rstest]
+    #[case("12.34", Some(4), Some(2), None, None, Ok(()))]
+    #[case("12.3400", Some(4), Some(2), None, None, Ok(()))]
+    #[case("12.345", Some(5), Some(2), None, None, Err(Error::DecimalPrecisionExceeded { property_name: get_conf_property_name("ratio", CONFIG_FILE), value: "12.345".to_string(), reason: "more than 2 digits after the decimal point".to_string() }))]
+    #[case("123.4", Some(4), Some(2), None, None, Err(Error::DecimalPrecisionExceeded { property_name: get_conf_property_name("ratio", CONFIG_FILE), value: "123.4".to_string(), reason: "more than 4 digits".to_string() }))]
+    #[case("0.3", None, None, Some("0.1"), Some("0.3"), Ok(()))]
+    #[case("0.30000000000000001", None, None, None, Some("0.3"), Err(Error::PropertyValueOutOfBounds { property_name: get_conf_property_name("ratio", CONFIG_FILE), received: "0.30000000000000001".to_string(), expected: "0.3".to_string() }))]
+    #[case("1,5", None, None, None, None, Err(Error::DatatypeNotMatching { property_name: get_conf_property_name("ratio", CONFIG_FILE), value: "1,5".to_string(), datatype: "decimal".to_string() }))]
+    #[trace]
+    fn test_check_datatype_decimal(
+        #[case] value: &str,
+        #[case] precision: Option<u32>,
+        #[case] scale: Option<u32>,
+        #[case] min: Option<&str>,
+        #[case] max: Option<&str>,
+        #[case] expected: Result<(), Error>,
+    ) {
+        let datatype = Datatype::Decimal {
+            precision,
+            scale,
+            min: min.map(str::to_string),
+            max: max.map(str::to_string),
+        };
+
+        let result = check_datatype(
+            &HashMap::new(),
+            &get_conf_property_name("ratio", CONFIG_FILE),
+            value,
+            &datatype,
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[cfg(not(feature = "rust_decimal"))]
+    #[test]
+    fn test_check_datatype_decimal_without_feature() {
+        let property_name = get_conf_property_name("ratio", CONFIG_FILE);
+        let datatype = Datatype::Decimal {
+            precision: None,
+            scale: None,
+            min: None,
+            max: None,
+        };
+
+        let result = check_datatype(&HashMap::new(), &property_name, "0.5", &datatype);
+
+        assert_eq!(
+            result,
+            Err(Error::DatatypeFeatureNotEnabled {
+                property_name,
+                datatype: "decimal".to_string(),
+                feature: "rust_decimal".to_string(),
+            })
+        );
+    }
+
+    #[cfg(not(feature = "url"))]
+    #[test]
+    fn test_check_datatype_url_without_feature() {
+        let property_name = get_conf_property_name("url", CONFIG_FILE);
+        let datatype = Datatype::Url {
+            allowed_schemes: None,
+            require_host: false,
+        };
+
+        let result = check_datatype(
+            &HashMap::new(),
+            &property_name,
+            "https://stackable.tech",
+            &datatype,
+        );
+
+        assert_eq!(
+            result,
+            Err(Error::DatatypeFeatureNotEnabled {
+                property_name,
+                datatype: "url".to_string(),
+                feature: "url".to_string(),
+            })
+        );
+    }
+
     const ALLOWED_VALUE_1: &str = "allowed_value_1";
     const ALLOWED_VALUE_2: &str = "allowed_value_2";
     const ALLOWED_VALUE_3: &str = "allowed_value_3";
@@ -884,8 +3696,118 @@ mod tests {
         #[case] allowed_values: Option<Vec<String>>,
         #[case] expected: Result<(), Error>,
     ) {
-        let result = check_allowed_values(property_name, property_value, &allowed_values);
+        let result = check_allowed_values(property_name, property_value, &allowed_values, &None);
+
+        assert_eq!(result, expected)
+    }
+
+    #[rstest]
+    #[case("rack-12", None, Ok(()))]
+    #[case("default", Some(vec!["default".to_string()]), Ok(()))]
+    #[case("rack-12a", None, Err(vec![]))]
+    #[case("my-rack-12", Some(vec!["default".to_string()]), Err(vec!["default".to_string()]))]
+    #[trace]
+    fn test_check_allowed_pattern(
+        #[case] property_value: &str,
+        #[case] allowed_values: Option<Vec<String>>,
+        #[case] expected: Result<(), Vec<String>>,
+    ) {
+        let property_name = get_conf_property_name(ENV_ALLOWED_VALUES, CONFIG_FILE);
+        let allowed_pattern = vec!["rack-\\d+".to_string(), "zone-[a-z]".to_string()];
+
+        let result = check_allowed_values(
+            &property_name,
+            property_value,
+            &allowed_values,
+            &Some(allowed_pattern.clone()),
+        );
+
+        assert_eq!(
+            result,
+            expected.map_err(
+                |allowed_values| Error::PropertyValueNotMatchingAllowedPattern {
+                    property_name: property_name.clone(),
+                    value: property_value.to_string(),
+                    allowed_values,
+                    allowed_pattern,
+                }
+            )
+        )
+    }
+
+    #[rstest]
+    #[case("127.0.0.1", Ok(()))]
+    #[case("0.0.0.0", Err(Error::PropertyValueForbidden {
+        property_name: get_conf_property_name(ENV_ALLOWED_VALUES, CONFIG_FILE),
+        value: "0.0.0.0".to_string(),
+    }))]
+    #[trace]
+    fn test_check_forbidden_values(
+        #[case] property_value: &str,
+        #[case] expected: Result<(), Error>,
+    ) {
+        let property_name = get_conf_property_name(ENV_ALLOWED_VALUES, CONFIG_FILE);
+
+        let result = check_forbidden_values(
+            &property_name,
+            property_value,
+            &Some(vec!["0.0.0.0".to_string(), "::".to_string()]),
+        );
+
+        assert_eq!(result, expected)
+    }
+
+    #[rstest]
+    #[case("16g", Ok(()))]
+    #[case("32768m", Ok(()))]
+    #[case("48g", Err(Error::PropertyValueNotRecommended {
+        property_name: get_conf_property_name(ENV_ALLOWED_VALUES, CONFIG_FILE),
+        value: "48g".to_string(),
+        recommended_range: "1g..32g".to_string(),
+        reason: "compressed oops are disabled above 32g".to_string(),
+    }))]
+    #[case("not a size", Ok(()))]
+    #[trace]
+    fn test_check_recommended_range(
+        #[case] property_value: &str,
+        #[case] expected: Result<(), Error>,
+    ) {
+        let property_name = get_conf_property_name(ENV_ALLOWED_VALUES, CONFIG_FILE);
+        let property = PropertySpecBuilder::new(
+            Datatype::MemorySize {
+                min: None,
+                max: Some("64g".to_string()),
+            },
+            "0.1.0",
+        )
+        .property_name(property_name.clone())
+        .recommended_range(RecommendedRange {
+            min: Some("1g".to_string()),
+            max: Some("32g".to_string()),
+            reason: Some("compressed oops are disabled above 32g".to_string()),
+        })
+        .build();
+
+        let result =
+            check_recommended_range(&HashMap::new(), &property_name, &property, property_value);
 
         assert_eq!(result, expected)
     }
+
+    #[test]
+    fn test_check_allowed_pattern_invalid() {
+        let property_name = get_conf_property_name(ENV_ALLOWED_VALUES, CONFIG_FILE);
+
+        let result = check_allowed_values(
+            &property_name,
+            "rack-1",
+            &None,
+            &Some(vec!["rack-(".to_string()]),
+        );
+
+        assert!(matches!(
+            result,
+            Err(Error::InvalidAllowedPattern { pattern, .. }) if pattern == "rack-("
+        ));
+    }
 }
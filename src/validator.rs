@@ -0,0 +1,321 @@
+//! Custom validators for value formats that cannot be captured by a single regex (e.g. kerberos
+//! principals or connection strings) and validation hooks.
+//!
+//! Properties reference a validator by name via `validator` in the property spec. The validator
+//! has to be registered via `ProductConfigSpec::register_validator` before validating user configs.
+//!
+//! # Example
+//!
+//! ```
+//! use product_config::reader::ConfigJsonReader;
+//! use product_config::types::PropertyName;
+//! use product_config::validator::CustomValidator;
+//! use product_config::ProductConfigSpec;
+//!
+//! struct KerberosPrincipal;
+//!
+//! impl CustomValidator for KerberosPrincipal {
+//!     fn validate(&self, _property_name: &PropertyName, value: &str) -> Result<(), String> {
+//!         match value.split_once('@') {
+//!             Some((primary, realm)) if !primary.is_empty() && !realm.is_empty() => Ok(()),
+//!             _ => Err("expected <primary>@<REALM>".to_string()),
+//!         }
+//!     }
+//! }
+//!
+//! let mut config = ProductConfigSpec::new(ConfigJsonReader::new(
+//!     "data/test_config_spec.json",
+//!     "data/test_property_spec.json",
+//! ))
+//! .unwrap();
+//!
+//! config.register_validator("kerberos-principal", Box::new(KerberosPrincipal));
+//! ```
+//!
+//! Hooks are closures attached to a property name via `ProductConfigSpec::add_hook`. They run
+//! after the built-in checks (for values that passed them) and may add errors or warnings.
+//!
+//! ```
+//! use product_config::reader::ConfigJsonReader;
+//! use product_config::validator::HookResult;
+//! use product_config::ProductConfigSpec;
+//!
+//! let mut config = ProductConfigSpec::new(ConfigJsonReader::new(
+//!     "data/test_config_spec.json",
+//!     "data/test_property_spec.json",
+//! ))
+//! .unwrap();
+//!
+//! config.add_hook("log.dirs", |value, _ctx| {
+//!     if value.split(',').any(|dir| !dir.starts_with('/')) {
+//!         HookResult::Error("log directories must be absolute".to_string())
+//!     } else {
+//!         HookResult::Ok
+//!     }
+//! });
+//! ```
+//!
+//! Secret resolvers registered via `ProductConfigSpec::register_secret_resolver` recognize
+//! references to credentials stored outside the config (e.g. `vault://path#key`). A resolved
+//! secret is validated instead of the reference, an opaque reference is accepted as it is.
+//! The reference (never the secret) is returned in the validation result.
+//!
+//! ```
+//! use product_config::reader::ConfigJsonReader;
+//! use product_config::types::PropertyName;
+//! use product_config::validator::{SecretResolver, SecretValue};
+//! use product_config::ProductConfigSpec;
+//!
+//! struct Vault;
+//!
+//! impl SecretResolver for Vault {
+//!     fn resolve(
+//!         &self,
+//!         _property_name: &PropertyName,
+//!         value: &str,
+//!     ) -> Option<Result<SecretValue, String>> {
+//!         let path = value.strip_prefix("vault://")?;
+//!         if path.contains('#') {
+//!             Some(Ok(SecretValue::Opaque))
+//!         } else {
+//!             Some(Err("expected vault://<path>#<key>".to_string()))
+//!         }
+//!     }
+//! }
+//!
+//! let mut config = ProductConfigSpec::new(ConfigJsonReader::new(
+//!     "data/test_config_spec.json",
+//!     "data/test_property_spec.json",
+//! ))
+//! .unwrap();
+//!
+//! config.register_secret_resolver(Box::new(Vault));
+//! ```
+use crate::error::Error;
+use crate::types::PropertyName;
+use crate::PropertyValidationResult;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// A validator for property values that is referenced by name in the property spec
+pub trait CustomValidator: Send + Sync {
+    /// Returns the reason if the value is not valid
+    ///
+    /// # Arguments
+    ///
+    /// * `property_name` - name of the property
+    /// * `value` - the value to be validated
+    ///
+    fn validate(&self, property_name: &PropertyName, value: &str) -> Result<(), String>;
+}
+
+/// The registered custom validators via name
+#[derive(Clone, Default)]
+pub(crate) struct CustomValidators {
+    validators: HashMap<String, Arc<dyn CustomValidator>>,
+}
+
+impl CustomValidators {
+    pub fn register(&mut self, name: &str, validator: Arc<dyn CustomValidator>) {
+        self.validators.insert(name.to_string(), validator);
+    }
+
+    /// Add all validators of other, replacing validators with the same name
+    pub fn extend(&mut self, other: CustomValidators) {
+        self.validators.extend(other.validators);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Arc<dyn CustomValidator>> {
+        self.validators.get(name)
+    }
+}
+
+impl fmt::Debug for CustomValidators {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut names: Vec<&String> = self.validators.keys().collect();
+        names.sort();
+        f.debug_list().entries(names).finish()
+    }
+}
+
+/// The outcome of a validation hook
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum HookResult {
+    Ok,
+    Warn(String),
+    Error(String),
+}
+
+/// Information about the validated property and user config that is passed to hooks
+#[derive(Clone, Debug)]
+pub struct HookContext<'a> {
+    pub property_name: &'a PropertyName,
+    pub product_version: &'a str,
+    pub role: Option<&'a str>,
+    // the merged user and property spec config (without prefix)
+    pub properties: &'a HashMap<String, String>,
+}
+
+type Hook = dyn Fn(&str, &HookContext) -> HookResult + Send + Sync;
+
+/// The registered validation hooks via property name
+#[derive(Clone, Default)]
+pub(crate) struct Hooks {
+    hooks: HashMap<String, Vec<Arc<Hook>>>,
+}
+
+impl Hooks {
+    pub fn add(&mut self, property_name: &str, hook: Arc<Hook>) {
+        self.hooks
+            .entry(property_name.to_string())
+            .or_default()
+            .push(hook);
+    }
+
+    /// Add all hooks of other (after the existing ones)
+    pub fn extend(&mut self, other: Hooks) {
+        for (property_name, hooks) in other.hooks {
+            self.hooks.entry(property_name).or_default().extend(hooks);
+        }
+    }
+
+    /// Run all hooks of the property in the order they were added. Errors of built-in checks are
+    /// kept. The first hook error replaces the result, hook warnings replace results that are not
+    /// warnings already.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the validated property value
+    /// * `context` - the property and config the value was validated for
+    /// * `result` - the result of the built-in checks
+    ///
+    pub fn run(
+        &self,
+        value: &str,
+        context: &HookContext,
+        result: PropertyValidationResult,
+    ) -> PropertyValidationResult {
+        let hooks = match self.hooks.get(&context.property_name.name) {
+            Some(hooks) => hooks,
+            None => return result,
+        };
+
+        if let PropertyValidationResult::Error(_) = result {
+            return result;
+        }
+
+        let mut result = result;
+        for hook in hooks {
+            match hook(value, context) {
+                HookResult::Ok => {}
+                HookResult::Warn(reason) => {
+                    if !matches!(result, PropertyValidationResult::Warn(..)) {
+                        result = PropertyValidationResult::Warn(
+                            value.to_string(),
+                            Error::HookWarning {
+                                property_name: context.property_name.clone(),
+                                value: value.to_string(),
+                                reason,
+                            },
+                        );
+                    }
+                }
+                HookResult::Error(reason) => {
+                    return PropertyValidationResult::Error(Error::HookFailed {
+                        property_name: context.property_name.clone(),
+                        value: value.to_string(),
+                        reason,
+                    });
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut names: Vec<(&String, usize)> = self
+            .hooks
+            .iter()
+            .map(|(name, hooks)| (name, hooks.len()))
+            .collect();
+        names.sort();
+        f.debug_map().entries(names).finish()
+    }
+}
+
+/// The outcome of a secret resolver for a secret reference
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SecretValue {
+    /// the secret the reference points to; validated instead of the reference
+    Resolved(String),
+    /// the reference is valid but deliberately not resolved; accepted without further checks
+    Opaque,
+}
+
+/// A resolver for values that reference secrets stored outside the config
+pub trait SecretResolver: Send + Sync {
+    /// Returns None if the value is no reference this resolver handles, otherwise the secret
+    /// value or the reason why the reference cannot be resolved
+    ///
+    /// # Arguments
+    ///
+    /// * `property_name` - name of the property
+    /// * `value` - the (possible) secret reference
+    ///
+    fn resolve(
+        &self,
+        property_name: &PropertyName,
+        value: &str,
+    ) -> Option<Result<SecretValue, String>>;
+}
+
+/// The registered secret resolvers in the order they were registered
+#[derive(Clone, Default)]
+pub(crate) struct SecretResolvers {
+    resolvers: Vec<Arc<dyn SecretResolver>>,
+}
+
+impl SecretResolvers {
+    pub fn register(&mut self, resolver: Arc<dyn SecretResolver>) {
+        self.resolvers.push(resolver);
+    }
+
+    /// Add all resolvers of other (after the existing ones)
+    pub fn extend(&mut self, other: SecretResolvers) {
+        self.resolvers.extend(other.resolvers);
+    }
+
+    /// Returns the outcome of the first resolver that handles the value or None if the value is
+    /// no secret reference
+    ///
+    /// # Arguments
+    ///
+    /// * `property_name` - name of the property
+    /// * `value` - the (possible) secret reference
+    ///
+    pub fn resolve(
+        &self,
+        property_name: &PropertyName,
+        value: &str,
+    ) -> Option<Result<SecretValue, Error>> {
+        self.resolvers.iter().find_map(|resolver| {
+            resolver.resolve(property_name, value).map(|resolved| {
+                resolved.map_err(|reason| Error::SecretNotResolved {
+                    property_name: property_name.clone(),
+                    reference: value.to_string(),
+                    reason,
+                })
+            })
+        })
+    }
+}
+
+impl fmt::Debug for SecretResolvers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} secret resolver(s)", self.resolvers.len())
+    }
+}
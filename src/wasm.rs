@@ -0,0 +1,157 @@
+//! Bindings for JavaScript via `wasm-bindgen` (`wasm` feature), e.g. to validate configuration
+//! forms of web frontends client-side with the same specs as the backend.
+//!
+//! The specs are loaded from their JSON texts (there is no file access in browsers), user configs
+//! are passed as JSON documents and the problems are returned as JSON array of objects with
+//! `name`, `pointer` and `message`:
+//!
+//! ```js
+//! import { ProductConfigSpec } from "./product_config.js";
+//!
+//! const spec = new ProductConfigSpec(configSpecJson, propertySpecJson);
+//! const problems = JSON.parse(spec.validate('{"log": {"dirs": ["/a"]}}', "server.properties", "1.0.0"));
+//! ```
+use crate::error::Error;
+use crate::reader::ConfigJsonStrReader;
+use crate::types::PropertyNameKind;
+use crate::validation::ValidationResult;
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+/// The product config spec for JavaScript
+#[wasm_bindgen(js_name = ProductConfigSpec)]
+pub struct WasmProductConfigSpec {
+    spec: crate::ProductConfigSpec,
+}
+
+#[wasm_bindgen(js_class = ProductConfigSpec)]
+impl WasmProductConfigSpec {
+    /// Loads the spec from the JSON texts of the config spec and the property spec
+    ///
+    /// # Arguments
+    ///
+    /// * `config_spec` - the config spec (units, prefixes, rules ...) as JSON
+    /// * `property_spec` - the property spec as JSON
+    ///
+    #[wasm_bindgen(constructor)]
+    pub fn new(config_spec: &str, property_spec: &str) -> Result<WasmProductConfigSpec, JsError> {
+        load(config_spec, property_spec).map_err(js_error)
+    }
+
+    /// Validates the user config document of the kind and returns the problems as JSON array
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - the user config document as JSON
+    /// * `kind` - `env`, `cli` or the name of the config file (e.g. `server.properties`)
+    /// * `product_version` - the product version
+    ///
+    pub fn validate(
+        &self,
+        document: &str,
+        kind: &str,
+        product_version: &str,
+    ) -> Result<String, JsError> {
+        validate(self, document, kind, product_version).map_err(js_error)
+    }
+
+    /// Returns the JSON Schema of the properties available in the product version, e.g. to
+    /// generate the configuration form
+    ///
+    /// # Arguments
+    ///
+    /// * `product_version` - the product version
+    ///
+    #[wasm_bindgen(js_name = toJsonSchema)]
+    pub fn to_json_schema(&self, product_version: &str) -> Result<String, JsError> {
+        self.spec
+            .to_json_schema(product_version)
+            .map(|schema| schema.to_string())
+            .map_err(js_error)
+    }
+}
+
+fn load(config_spec: &str, property_spec: &str) -> ValidationResult<WasmProductConfigSpec> {
+    Ok(WasmProductConfigSpec {
+        spec: crate::ProductConfigSpec::new(ConfigJsonStrReader::new(config_spec, property_spec))?,
+    })
+}
+
+/// Returns the problems of the user config document as JSON array
+fn validate(
+    spec: &WasmProductConfigSpec,
+    document: &str,
+    kind: &str,
+    product_version: &str,
+) -> ValidationResult<String> {
+    let document: Value = serde_json::from_str(document).map_err(|err| Error::FileNotParsable {
+        file_name: "user config".to_string(),
+        reason: err.to_string(),
+    })?;
+    let kind = match kind {
+        "env" => PropertyNameKind::Env,
+        "cli" => PropertyNameKind::Cli,
+        file => PropertyNameKind::Conf(file.to_string()),
+    };
+
+    let problems: Vec<Value> = spec
+        .spec
+        .validate_document(&document, &kind, product_version)?
+        .into_iter()
+        .map(|problem| {
+            json!({
+                "name": problem.name,
+                "pointer": problem.pointer,
+                "message": problem.error.to_string(),
+            })
+        })
+        .collect();
+    Ok(Value::Array(problems).to_string())
+}
+
+fn js_error(err: Error) -> JsError {
+    JsError::new(&err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::wasm::{load, validate};
+    use serde_json::{json, Value};
+
+    const CONFIG_SPEC: &str = include_str!("../data/test_config_spec.json");
+    const PROPERTY_SPEC: &str = include_str!("../data/test_property_spec.json");
+
+    #[test]
+    fn test_validate() {
+        let spec = load(CONFIG_SPEC, PROPERTY_SPEC).unwrap();
+
+        let problems: Value = serde_json::from_str(
+            &validate(
+                &spec,
+                r#"{"ENV_INTEGER_PORT_MIN_MAX": "0", "ENV_SSL_ENABLED": "true"}"#,
+                "env.sh",
+                "0.5.0",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let names: Vec<&Value> = problems
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|problem| &problem["name"])
+            .collect();
+        assert!(names.contains(&&json!("ENV_INTEGER_PORT_MIN_MAX")));
+        assert!(problems[0]["message"].is_string());
+    }
+
+    #[test]
+    fn test_load_invalid() {
+        assert!(matches!(
+            load(CONFIG_SPEC, "{").err(),
+            Some(Error::FileNotParsable { file_name, .. }) if file_name == "property spec"
+        ));
+    }
+}
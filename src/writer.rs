@@ -0,0 +1,736 @@
+//! A config writer implementation to persist a product config spec
+//!
+//! This is the counterpart to the config readers: a spec that was read (and possibly modified)
+//! can be written back without losing unknown fields like custom vendor annotations.
+//!
+//! For now only JSON as target is supported.
+//!
+//! Besides the spec, (validated) config values can be written as the product expects them,
+//! e.g. as Java `.properties` file via [`to_properties`] (or per Spring Boot profile via
+//! [`to_spring_profile_properties`]), as systemd env file or shell script via
+//! [`to_env_file`] and [`to_shell_exports`], as systemd unit drop-in via
+//! [`to_systemd_environment`] or as `environment:` section of a Docker Compose service via
+//! [`to_compose_environment`].
+use crate::error::Error;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use crate::reader::{JsonProductConfigSpecProperties, JsonPropertySpecs};
+use crate::types::{PropertyName, PropertyNameKind};
+use crate::ProductConfigSpec;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::fs::File;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::io::BufWriter;
+
+/// trait for different config writers for json or yaml
+pub trait ConfigWriter {
+    fn write(&self, product_config_spec: &ProductConfigSpec) -> Result<(), Error>;
+}
+
+/// specific json config writer struct
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub struct ConfigJsonWriter {
+    config_spec_path: String,
+    property_spec_path: String,
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl ConfigJsonWriter {
+    pub fn new(config_spec_path: &str, property_spec_path: &str) -> Self {
+        ConfigJsonWriter {
+            config_spec_path: config_spec_path.to_string(),
+            property_spec_path: property_spec_path.to_string(),
+        }
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl ConfigWriter for ConfigJsonWriter {
+    fn write(&self, product_config_spec: &ProductConfigSpec) -> Result<(), Error> {
+        let config_spec = JsonProductConfigSpecProperties {
+            units: product_config_spec.config_spec.unit_specs.clone(),
+            prefixes: product_config_spec.config_spec.prefixes.clone(),
+            rules: product_config_spec
+                .config_spec
+                .rules
+                .iter()
+                .map(|rule| rule.rule.clone())
+                .collect(),
+            normalization: product_config_spec.config_spec.normalization,
+            full_match: product_config_spec.config_spec.full_match,
+            types: product_config_spec.config_spec.types.clone(),
+            value_sets: product_config_spec.config_spec.value_sets.clone(),
+            groups: product_config_spec.config_spec.groups.clone(),
+            default_locale: product_config_spec.config_spec.default_locale.clone(),
+        };
+
+        write_file(&self.config_spec_path, &config_spec)?;
+
+        if product_config_spec.products.is_empty() {
+            write_file(
+                &self.property_spec_path,
+                &product_config_spec.property_spec_list,
+            )
+        } else {
+            let products = product_config_spec
+                .products
+                .iter()
+                .map(|(product, spec)| (product.clone(), spec.property_spec_list.clone()))
+                .collect();
+
+            write_file(
+                &self.property_spec_path,
+                &JsonPropertySpecs::Products { products },
+            )
+        }
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn write_file<T: Serialize>(path: &str, value: &T) -> Result<(), Error> {
+    let file = match File::create(path) {
+        Ok(file) => file,
+        Err(err) => {
+            return Err(Error::FileNotWritable {
+                file_name: path.to_string(),
+                reason: err.to_string(),
+            });
+        }
+    };
+
+    let writer = BufWriter::new(file);
+    match serde_json::to_writer_pretty(writer, value) {
+        Ok(()) => Ok(()),
+        Err(err) => Err(Error::FileNotWritable {
+            file_name: path.to_string(),
+            reason: err.to_string(),
+        }),
+    }
+}
+
+/// Returns the content of a Java `.properties` file with the values of the properties of kind
+/// `conf` of the provided file (one `key=value` line per property, sorted by key). Values may be
+/// keyed by any name of a property (e.g. its env name) and are written with the name of the
+/// config file; names unknown to the spec are kept as they are. Keys and values are escaped, so
+/// the file can be read via `java.util.Properties::load`.
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `file` - the name of the config file, e.g. `server.properties`
+/// * `values` - map with property name and (validated) value
+///
+pub fn to_properties(
+    spec: &ProductConfigSpec,
+    file: &str,
+    values: &HashMap<String, String>,
+) -> String {
+    kind_specific_values(spec, &PropertyNameKind::Conf(file.to_string()), values)
+        .iter()
+        .map(|(name, value)| {
+            format!(
+                "{}={}\n",
+                escape_properties(name, true),
+                escape_properties(value, false)
+            )
+        })
+        .collect()
+}
+
+/// Returns the file name and content of the Spring Boot config file of the profile, e.g.
+/// `application-prod.properties` for the profile `prod` and the file `application.properties`.
+/// The values of the profile are selected from the environment specific value sets and written
+/// like for [`to_properties`]. Returns None if there are no values for the profile.
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `file` - the name of the config file, e.g. `application.properties`
+/// * `profile_values` - map with profile and the (validated) values of the profile
+/// * `profile` - the profile to be written, e.g. `prod`
+///
+pub fn to_spring_profile_properties(
+    spec: &ProductConfigSpec,
+    file: &str,
+    profile_values: &HashMap<String, HashMap<String, String>>,
+    profile: &str,
+) -> Option<(String, String)> {
+    let values = profile_values.get(profile)?;
+    let file_name = match file.rsplit_once('.') {
+        Some((stem, extension)) => format!("{}-{}.{}", stem, profile, extension),
+        None => format!("{}-{}", file, profile),
+    };
+    Some((file_name, to_properties(spec, file, values)))
+}
+
+/// Returns the content of an env file with the values of the properties of kind `env` (one
+/// `KEY=value` line per property, sorted by key) as read by systemd via `EnvironmentFile=` (see
+/// [`to_systemd_environment_file`]). Values may be keyed by any name of a property and are
+/// written with its env name; names unknown to the spec are kept as they are and names that are
+/// no valid variable names are left out. Values that would be changed when read (e.g. with line
+/// breaks or surrounding spaces) are double quoted. `docker run --env-file` does not unquote
+/// values, use [`to_compose_environment`] for containers instead.
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `values` - map with property name and (validated) value
+///
+pub fn to_env_file(spec: &ProductConfigSpec, values: &HashMap<String, String>) -> String {
+    kind_specific_values(spec, &PropertyNameKind::Env, values)
+        .iter()
+        .filter(|(name, _)| is_shell_variable(name))
+        .map(|(name, value)| format!("{}={}\n", name, escape_env_file_value(value)))
+        .collect()
+}
+
+/// Returns a shell script exporting the values of the properties of kind `env` (one
+/// `export KEY='value'` line per property, sorted by key) to be sourced by POSIX shells. Values
+/// are single quoted, so they are never expanded. Like for [`to_env_file`], values may be keyed
+/// by any name of a property; names that are no valid variable names are left out.
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `values` - map with property name and (validated) value
+///
+pub fn to_shell_exports(spec: &ProductConfigSpec, values: &HashMap<String, String>) -> String {
+    kind_specific_values(spec, &PropertyNameKind::Env, values)
+        .iter()
+        .filter(|(name, _)| is_shell_variable(name))
+        .map(|(name, value)| format!("export {}={}\n", name, shell_quote(value)))
+        .collect()
+}
+
+/// Returns a systemd unit drop-in (`[Service]` section) setting the values of the properties of
+/// kind `env` via one `Environment=` directive per property (sorted by key), so services started
+/// by systemd get the same values as containers. Like for [`to_env_file`], values may be keyed by
+/// any name of a property; names that are no valid variable names are left out.
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `values` - map with property name and (validated) value
+///
+pub fn to_systemd_environment(
+    spec: &ProductConfigSpec,
+    values: &HashMap<String, String>,
+) -> String {
+    let mut unit = String::from("[Service]\n");
+    for (name, value) in kind_specific_values(spec, &PropertyNameKind::Env, values) {
+        if is_shell_variable(&name) {
+            unit.push_str(&format!(
+                "Environment=\"{}\"\n",
+                systemd_escape(&format!("{}={}", name, value))
+            ));
+        }
+    }
+    unit
+}
+
+/// Returns a systemd unit drop-in (`[Service]` section) reading the environment from the env
+/// file (e.g. written via [`to_env_file`]) via `EnvironmentFile=`, e.g. to keep sensitive values
+/// out of the unit files.
+///
+/// # Arguments
+///
+/// * `path` - the absolute path of the env file; specifiers like `%i` are kept
+///
+pub fn to_systemd_environment_file(path: &str) -> String {
+    format!("[Service]\nEnvironmentFile={}\n", path)
+}
+
+/// Returns the `environment:` section of a Docker Compose service with the values of the
+/// properties of kind `env` (sorted by key). Like for [`to_env_file`], values may be keyed by any
+/// name of a property; names that are no valid variable names are left out. Values are double
+/// quoted with `$` escaped as `$$`, so Compose does not interpolate them.
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `values` - map with property name and (validated) value
+/// * `sensitive_references` - if the values of sensitive properties are written as `${KEY}`
+///   references (to be set in the environment of `docker compose`) instead of literals
+///
+pub fn to_compose_environment(
+    spec: &ProductConfigSpec,
+    values: &HashMap<String, String>,
+    sensitive_references: bool,
+) -> String {
+    let mut environment = String::from("environment:\n");
+    for (name, value) in kind_specific_values(spec, &PropertyNameKind::Env, values) {
+        if !is_shell_variable(&name) {
+            continue;
+        }
+        let sensitive = spec.is_sensitive(&PropertyName {
+            name: name.clone(),
+            kind: PropertyNameKind::Env,
+        });
+        let value = if sensitive && sensitive_references {
+            format!("\"${{{}}}\"", name)
+        } else {
+            // JSON strings are valid double quoted YAML strings as well
+            serde_json::Value::String(value.replace('$', "$$")).to_string()
+        };
+        environment.push_str(&format!("  {}: {}\n", name, value));
+    }
+    environment
+}
+
+/// Returns the values keyed by the names of the properties for the kind (sorted by name).
+/// Values of properties without name for the kind are left out; if several names of a property
+/// are used, the value of the kind specific name (or else the first name) wins.
+///
+/// # Arguments
+///
+/// * `spec` - the product config spec
+/// * `kind` - the kind of the names to be used
+/// * `values` - map with property name (of any kind) and value
+///
+fn kind_specific_values<'a>(
+    spec: &ProductConfigSpec,
+    kind: &PropertyNameKind,
+    values: &'a HashMap<String, String>,
+) -> BTreeMap<String, &'a String> {
+    let mut result = BTreeMap::new();
+
+    for (name, value) in values.iter().collect::<BTreeMap<_, _>>() {
+        let mut properties = spec
+            .property_specs
+            .iter()
+            .filter(|(property_name, _)| &property_name.name == name)
+            .peekable();
+        if properties.peek().is_none() {
+            result.insert(name.clone(), value);
+            continue;
+        }
+
+        let kind_name = properties.find_map(|(_, property)| {
+            property
+                .property_names
+                .iter()
+                .find(|property_name| &property_name.kind == kind)
+        });
+        match kind_name {
+            Some(kind_name) if &kind_name.name == name => {
+                result.insert(name.clone(), value);
+            }
+            Some(kind_name) if !values.contains_key(&kind_name.name) => {
+                result.entry(kind_name.name.clone()).or_insert(value);
+            }
+            _ => {}
+        }
+    }
+
+    result
+}
+
+/// Returns the value as it is or, if it contains characters that would be changed when read by
+/// systemd (line breaks, quotes, backslashes, `#` or surrounding whitespace), double quoted with
+/// `"`, `\`, `` ` `` and `$` escaped by a backslash; line breaks are kept as they are (quoted
+/// values may span several lines)
+pub(crate) fn escape_env_file_value(value: &str) -> String {
+    let plain = !value.contains(['\n', '\r', '"', '\'', '#', '\\']) && value.trim() == value;
+    if plain {
+        return value.to_string();
+    }
+
+    let mut escaped = String::from('"');
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | '`' | '$') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Returns the value single quoted for POSIX shells (single quotes within the value are written
+/// as `'\''`), plain values like `/data` are kept as they are
+fn shell_quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "._-/:,@%+=".contains(c));
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Returns the text escaped to be double quoted in systemd unit files: `\`, `"` and line breaks
+/// are escaped and `%` (specifiers) is doubled
+fn systemd_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '%' => escaped.push_str("%%"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns if the name is a valid shell variable name (letters, digits and `_`, no leading digit)
+fn is_shell_variable(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Returns the text escaped for `.properties` files like `java.util.Properties::store` does:
+/// separators and spaces in keys, leading spaces and comment characters, backslashes, control
+/// characters and all characters beyond ASCII (as `\uXXXX`, the files are read as ISO 8859-1)
+///
+/// # Arguments
+///
+/// * `text` - the key or value
+/// * `key` - if the text is a key
+///
+pub(crate) fn escape_properties(text: &str, key: bool) -> String {
+    let mut escaped = String::new();
+    for (index, c) in text.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\x0c' => escaped.push_str("\\f"),
+            '=' | ':' | ' ' if key => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '#' | '!' | ' ' if index == 0 => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' '..='~' => escaped.push(c),
+            _ => {
+                let mut units = [0; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    escaped.push_str(&format!("\\u{:04X}", unit));
+                }
+            }
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{ProductConfigSpecBuilder, PropertySpecBuilder};
+    use crate::reader::ConfigJsonReader;
+    use crate::types::{Datatype, PropertyName, PropertyNameKind, Role};
+    use crate::writer::{
+        escape_env_file_value, escape_properties, shell_quote, to_compose_environment, to_env_file,
+        to_properties, to_shell_exports, to_spring_profile_properties, to_systemd_environment,
+        to_systemd_environment_file, ConfigJsonWriter, ConfigWriter,
+    };
+    use crate::ProductConfigSpec;
+    use rstest::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_write_preserves_unknown_fields() {
+        let config = ProductConfigSpec::new(ConfigJsonReader::new(
+            "data/test_config_spec.json",
+            "data/test_property_spec.json",
+        ))
+        .unwrap();
+
+        // unique per process and test, so parallel runs do not overwrite each other's files
+        let dir = std::env::temp_dir().join(format!(
+            "product_config_{}_write_preserves_unknown_fields",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_spec_path = dir.join("config_spec.json");
+        let property_spec_path = dir.join("property_spec.json");
+        let config_spec_path = config_spec_path.to_str().unwrap();
+        let property_spec_path = property_spec_path.to_str().unwrap();
+
+        ConfigJsonWriter::new(config_spec_path, property_spec_path)
+            .write(&config)
+            .unwrap();
+
+        let written =
+            ProductConfigSpec::new(ConfigJsonReader::new(config_spec_path, property_spec_path))
+                .unwrap();
+
+        assert_eq!(written.property_spec_list, config.property_spec_list);
+        assert_eq!(
+            written.config_spec.unit_specs,
+            config.config_spec.unit_specs
+        );
+        assert!(written
+            .property_spec_list
+            .iter()
+            .any(|spec| spec.extra.contains_key("x-ui-hidden")));
+        assert!(written
+            .config_spec
+            .unit_specs
+            .iter()
+            .any(|unit| unit.extra.contains_key("x-owner")));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn name(name: &str, kind: PropertyNameKind) -> PropertyName {
+        PropertyName {
+            name: name.to_string(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_to_properties() {
+        let conf = PropertyNameKind::Conf("server.properties".to_string());
+        let property = |names: Vec<PropertyName>| {
+            let mut builder = PropertySpecBuilder::new(Datatype::Bool, "1.0.0").role(Role {
+                name: "broker".to_string(),
+                required: false,
+            });
+            for name in names {
+                builder = builder.property_name(name);
+            }
+            builder.build()
+        };
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(property(vec![
+                name("log.dirs", conf.clone()),
+                name("LOG_DIRS", PropertyNameKind::Env),
+            ]))
+            .property_spec(property(vec![
+                name("ssl.enabled", conf.clone()),
+                name("SSL_ENABLED", PropertyNameKind::Env),
+            ]))
+            .property_spec(property(vec![name("JAVA_HOME", PropertyNameKind::Env)]))
+            .property_spec(property(vec![name("greeting", conf)]))
+            .build()
+            .unwrap();
+        let values: HashMap<String, String> = [
+            ("LOG_DIRS", "/data 1,/data:2"),
+            ("ssl.enabled", "true"),
+            ("SSL_ENABLED", "false"),
+            ("JAVA_HOME", "/usr/lib/jvm"),
+            ("greeting", "Grüße 😀"),
+            ("custom key", "#1"),
+        ]
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+
+        assert_eq!(
+            to_properties(&spec, "server.properties", &values),
+            "custom\\ key=\\#1\n\
+             greeting=Gr\\u00FC\\u00DFe \\uD83D\\uDE00\n\
+             log.dirs=/data 1,/data:2\n\
+             ssl.enabled=true\n"
+        );
+    }
+
+    #[rstest]
+    #[case("prod", "application.properties", Some(("application-prod.properties", "server.port=443\n")))]
+    #[case("dev", "application", Some(("application-dev", "server.port=8080\n")))]
+    #[case("test", "application.properties", None)]
+    #[trace]
+    fn test_to_spring_profile_properties(
+        #[case] profile: &str,
+        #[case] file: &str,
+        #[case] expected: Option<(&str, &str)>,
+    ) {
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "1.0.0")
+                    .property_name(name(
+                        "server.port",
+                        PropertyNameKind::Conf(file.to_string()),
+                    ))
+                    .property_name(name("SERVER_PORT", PropertyNameKind::Env))
+                    .role(Role {
+                        name: "server".to_string(),
+                        required: false,
+                    })
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        let profile_values: HashMap<String, HashMap<String, String>> = [
+            ("prod", "SERVER_PORT", "443"),
+            ("dev", "server.port", "8080"),
+        ]
+        .iter()
+        .map(|(profile, name, value)| {
+            (
+                profile.to_string(),
+                HashMap::from([(name.to_string(), value.to_string())]),
+            )
+        })
+        .collect();
+
+        assert_eq!(
+            to_spring_profile_properties(&spec, file, &profile_values, profile),
+            expected.map(|(name, content)| (name.to_string(), content.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_to_env_file_and_shell_exports() {
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(Datatype::Bool, "1.0.0")
+                    .property_name(name(
+                        "log.dirs",
+                        PropertyNameKind::Conf("server.properties".to_string()),
+                    ))
+                    .property_name(name("LOG_DIRS", PropertyNameKind::Env))
+                    .role(Role {
+                        name: "broker".to_string(),
+                        required: false,
+                    })
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        let values: HashMap<String, String> = [
+            ("log.dirs", "/data"),
+            ("OPTS", "-Xmx1g -Dname='x'"),
+            ("1INVALID", "x"),
+        ]
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+
+        assert_eq!(
+            to_env_file(&spec, &values),
+            "LOG_DIRS=/data\nOPTS=\"-Xmx1g -Dname='x'\"\n"
+        );
+        assert_eq!(
+            to_shell_exports(&spec, &values),
+            r"export LOG_DIRS=/data
+export OPTS='-Xmx1g -Dname='\''x'\'''
+"
+        );
+    }
+
+    #[test]
+    fn test_to_systemd_environment() {
+        let spec = ProductConfigSpecBuilder::new().build().unwrap();
+        let values: HashMap<String, String> = [
+            ("HEAP", "1g"),
+            ("OPTS", "-Dname=\"x\" -Dp=50%"),
+            ("in-valid", "x"),
+        ]
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+
+        assert_eq!(
+            to_systemd_environment(&spec, &values),
+            r#"[Service]
+Environment="HEAP=1g"
+Environment="OPTS=-Dname=\"x\" -Dp=50%%"
+"#
+        );
+        assert_eq!(
+            to_systemd_environment_file("/etc/kafka/%i.env"),
+            "[Service]\nEnvironmentFile=/etc/kafka/%i.env\n"
+        );
+    }
+
+    #[rstest]
+    #[case(
+        false,
+        r#"environment:
+  HEAP: "1g"
+  SSL_PASSWORD: "pa$$word"
+"#
+    )]
+    #[case(
+        true,
+        r#"environment:
+  HEAP: "1g"
+  SSL_PASSWORD: "${SSL_PASSWORD}"
+"#
+    )]
+    #[trace]
+    fn test_to_compose_environment(#[case] sensitive_references: bool, #[case] expected: &str) {
+        let spec = ProductConfigSpecBuilder::new()
+            .property_spec(
+                PropertySpecBuilder::new(
+                    Datatype::Password {
+                        min_length: None,
+                        required_classes: None,
+                        min_entropy: None,
+                    },
+                    "1.0.0",
+                )
+                .property_name(name(
+                    "ssl.password",
+                    PropertyNameKind::Conf("server.properties".to_string()),
+                ))
+                .property_name(name("SSL_PASSWORD", PropertyNameKind::Env))
+                .role(Role {
+                    name: "broker".to_string(),
+                    required: false,
+                })
+                .build(),
+            )
+            .build()
+            .unwrap();
+        let values: HashMap<String, String> = [
+            ("HEAP", "1g"),
+            ("ssl.password", "pa$word"),
+            ("in-valid", "x"),
+        ]
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+
+        assert_eq!(
+            to_compose_environment(&spec, &values, sensitive_references),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("/data", "/data")]
+    #[case("a b", "a b")]
+    #[case(" a", r#"" a""#)]
+    #[case("line\nbreak", "\"line\nbreak\"")]
+    #[case(r#"say "hi""#, r#""say \"hi\"""#)]
+    #[case("$HOME", "$HOME")]
+    #[case(r"C:\$x `y`", r#""C:\\\$x \`y\`""#)]
+    #[trace]
+    fn test_escape_env_file_value(#[case] value: &str, #[case] expected: &str) {
+        assert_eq!(escape_env_file_value(value), expected);
+    }
+
+    #[rstest]
+    #[case("/data", "/data")]
+    #[case("", "''")]
+    #[case("$HOME", "'$HOME'")]
+    #[case("it's", r"'it'\''s'")]
+    #[trace]
+    fn test_shell_quote(#[case] value: &str, #[case] expected: &str) {
+        assert_eq!(shell_quote(value), expected);
+    }
+
+    #[rstest]
+    #[case("a=b c", true, r"a\=b\ c")]
+    #[case(" x=y", false, r"\ x=y")]
+    #[case("#x\\y\n", false, r"\#x\\y\n")]
+    #[case("ä", false, r"\u00E4")]
+    #[trace]
+    fn test_escape_properties(#[case] text: &str, #[case] key: bool, #[case] expected: &str) {
+        assert_eq!(escape_properties(text, key), expected);
+    }
+}